@@ -6,11 +6,13 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, KindWithContent, Status, Task, TaskGroup, TaskId, TaskPriority,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 
 mod error;
 mod reader;
@@ -89,6 +91,29 @@ pub struct TaskDump {
         default
     )]
     pub finished_at: Option<OffsetDateTime>,
+    // The scheduled tasks were introduced in v1.15, everything prior to this version will be `None`.
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub run_at: Option<OffsetDateTime>,
+    // The recurring tasks were introduced in v1.15, everything prior to this version will be `None`.
+    #[serde(
+        serialize_with = "meilisearch_types::tasks::serialize_duration",
+        deserialize_with = "meilisearch_types::tasks::deserialize_duration",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub run_every: Option<Duration>,
+    // The task dependencies and groups were introduced in v1.15, everything prior to this version will be empty.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub depends_on: Vec<TaskId>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub group: Option<TaskGroup>,
+    // The task priorities were introduced in v1.15, everything prior to this version is `normal`.
+    #[serde(skip_serializing_if = "TaskPriority::is_normal", default)]
+    pub priority: TaskPriority,
 }
 
 // A `Kind` specific version made for the dump. If modified you may break the dump.
@@ -160,6 +185,11 @@ impl From<Task> for TaskDump {
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
+            run_at: task.run_at,
+            run_every: task.run_every,
+            depends_on: task.depends_on,
+            group: task.group,
+            priority: task.priority,
         }
     }
 }
@@ -298,6 +328,8 @@ pub(crate) mod test {
                 sort_facet_values_by: Setting::Set(
                     btreemap! { S("age") => FacetValuesSort::Count },
                 ),
+                hierarchical_facets: Setting::NotSet,
+                facet_stats: Setting::NotSet,
             }),
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
@@ -305,6 +337,9 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            analyzers: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -327,6 +362,7 @@ pub(crate) mod test {
                 progress_trace: Default::default(),
                 write_channel_congestion: None,
                 internal_database_sizes: Default::default(),
+                scheduling: None,
             },
             enqueued_at: Some(BatchEnqueuedAt {
                 earliest: datetime!(2022-11-11 0:00 UTC),
@@ -361,6 +397,11 @@ pub(crate) mod test {
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
                     started_at: Some(datetime!(2022-11-20 0:00 UTC)),
                     finished_at: Some(datetime!(2022-11-21 0:00 UTC)),
+                    run_at: None,
+                    run_every: None,
+                    depends_on: Vec::new(),
+                    group: None,
+                    priority: TaskPriority::Normal,
                 },
                 None,
             ),
@@ -385,6 +426,11 @@ pub(crate) mod test {
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
                     started_at: None,
                     finished_at: None,
+                    run_at: None,
+                    run_every: None,
+                    depends_on: Vec::new(),
+                    group: None,
+                    priority: TaskPriority::Normal,
                 },
                 Some(vec![
                     json!({ "id": 4, "race": "leonberg" }).as_object().unwrap().clone(),
@@ -404,6 +450,11 @@ pub(crate) mod test {
                     enqueued_at: datetime!(2022-11-15 0:00 UTC),
                     started_at: None,
                     finished_at: None,
+                    run_at: None,
+                    run_every: None,
+                    depends_on: Vec::new(),
+                    group: None,
+                    priority: TaskPriority::Normal,
                 },
                 None,
             ),
@@ -159,6 +159,11 @@ impl CompatV5ToV6 {
                     enqueued_at: task_view.enqueued_at,
                     started_at: task_view.started_at,
                     finished_at: task_view.finished_at,
+                    run_at: None,
+                    run_every: None,
+                    depends_on: Vec::new(),
+                    group: None,
+                    priority: v6::TaskPriority::Normal,
                 };
 
                 (task, content_file)
@@ -381,6 +386,8 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Set(faceting) => v6::Setting::Set(v6::FacetingSettings {
                     max_values_per_facet: faceting.max_values_per_facet.into(),
                     sort_facet_values_by: v6::Setting::NotSet,
+                    hierarchical_facets: v6::Setting::NotSet,
+                    facet_stats: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -397,6 +404,9 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            analyzers: v6::Setting::NotSet,
+            synonym_rules: v6::Setting::NotSet,
+            rerankers: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -1,14 +1,14 @@
 use nom::branch::alt;
-use nom::bytes::complete::{take_till, take_while, take_while1};
-use nom::character::complete::{char, multispace0};
-use nom::combinator::cut;
-use nom::sequence::{delimited, terminated};
+use nom::bytes::complete::{take_till, take_while, take_while1, take_while_m_n};
+use nom::character::complete::{char, digit1, multispace0, one_of};
+use nom::combinator::{cut, opt, recognize};
+use nom::sequence::{delimited, terminated, tuple};
 use nom::{InputIter, InputLength, InputTake, Slice};
 
 use crate::error::{ExpectedValueKind, NomErrorExt};
 use crate::{
-    parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point, parse_geo_radius,
-    Error, ErrorKind, IResult, Span, Token,
+    parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point, parse_geo_polygon,
+    parse_geo_radius, Error, ErrorKind, IResult, Span, Token,
 };
 
 /// This function goes through all characters in the [Span] if it finds any escaped character (`\`).
@@ -64,6 +64,47 @@ pub fn word_not_keyword<'a>(input: Span<'a>) -> IResult<'a, Token<'a>> {
     Ok((input, word))
 }
 
+// datetime       = digit{4} "-" digit{2} "-" digit{2} ("T" | "t") digit{2} ":" digit{2} ":" digit{2} ("." digit+)? ("Z" | "z" | ("+" | "-") digit{2} ":" digit{2})
+/// An RFC 3339 datetime, the only datetimes the filters can compare with the dates of the documents.
+pub fn datetime(input: Span) -> IResult<Token> {
+    let digits = |n| take_while_m_n(n, n, |c: char| c.is_ascii_digit());
+    let date = tuple((digits(4), char('-'), digits(2), char('-'), digits(2)));
+    let time = tuple((
+        one_of("Tt"),
+        digits(2),
+        char(':'),
+        digits(2),
+        char(':'),
+        digits(2),
+        opt(tuple((char('.'), digit1))),
+    ));
+    let offset = alt((
+        recognize(one_of("Zz")),
+        recognize(tuple((one_of("+-"), digits(2), char(':'), digits(2)))),
+    ));
+
+    // no word contains a `:`, so anything starting like a datetime must be a valid one
+    let looks_like_datetime: IResult<Span> = recognize(tuple((
+        digit1,
+        char('-'),
+        digit1,
+        char('-'),
+        digit1,
+        one_of("Tt"),
+        digit1,
+        char(':'),
+    )))(input);
+
+    match recognize(tuple((date, time, offset)))(input) {
+        Ok((input, datetime)) => Ok((input, datetime.into())),
+        Err(nom::Err::Error(_)) if looks_like_datetime.is_ok() => {
+            let (_, value) = take_till::<_, _, Error>(is_syntax_component)(input)?;
+            Err(nom::Err::Failure(Error::new_from_kind(value, ErrorKind::MalformedDatetime)))
+        }
+        Err(e) => Err(e),
+    }
+}
+
 // word           = {tag}
 pub fn word_exact<'a, 'b: 'a>(tag: &'b str) -> impl Fn(Span<'a>) -> IResult<'a, Token<'a>> {
     move |input| {
@@ -80,7 +121,7 @@ pub fn word_exact<'a, 'b: 'a>(tag: &'b str) -> impl Fn(Span<'a>) -> IResult<'a,
     }
 }
 
-/// value          = WS* ( word | singleQuoted | doubleQuoted) WS+
+/// value          = WS* ( datetime | word | singleQuoted | doubleQuoted) WS+
 pub fn parse_value(input: Span) -> IResult<Token> {
     // to get better diagnostic message we are going to strip the left whitespaces from the input right now
     let (input, _) = take_while(char::is_whitespace)(input)?;
@@ -128,6 +169,24 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         _ => (),
     }
 
+    match parse_geo_polygon(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _geoPolygon filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        _ => (),
+    }
+
     // this parser is only used when an error is encountered and it parse the
     // largest string possible that do not contain any “language” syntax.
     // If we try to parse `name = 🦀 AND language = rust` we want to return an
@@ -141,6 +200,7 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         alt((
             delimited(char('\''), cut(|input| quoted_by('\'', input)), cut(char('\''))),
             delimited(char('"'), cut(|input| quoted_by('"', input)), cut(char('"'))),
+            datetime,
             word_not_keyword,
         )),
         multispace0,
@@ -5,7 +5,7 @@ use dump::{KindDump, TaskDump, UpdateFile};
 use meilisearch_types::batches::{Batch, BatchId};
 use meilisearch_types::heed::RwTxn;
 use meilisearch_types::milli;
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task, TaskPriority};
 use roaring::RoaringBitmap;
 use uuid::Uuid;
 
@@ -20,6 +20,7 @@ pub struct Dump<'a> {
     indexes: HashMap<String, RoaringBitmap>,
     statuses: HashMap<Status, RoaringBitmap>,
     kinds: HashMap<Kind, RoaringBitmap>,
+    priorities: HashMap<TaskPriority, RoaringBitmap>,
 
     batch_indexes: HashMap<String, RoaringBitmap>,
     batch_statuses: HashMap<Status, RoaringBitmap>,
@@ -38,6 +39,7 @@ impl<'a> Dump<'a> {
             indexes: HashMap::new(),
             statuses: HashMap::new(),
             kinds: HashMap::new(),
+            priorities: HashMap::new(),
             batch_indexes: HashMap::new(),
             batch_statuses: HashMap::new(),
             batch_kinds: HashMap::new(),
@@ -142,6 +144,11 @@ impl<'a> Dump<'a> {
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
+            run_at: task.run_at,
+            run_every: task.run_every,
+            depends_on: task.depends_on,
+            group: task.group,
+            priority: task.priority,
             error: task.error,
             canceled_by: task.canceled_by,
             details: task.details,
@@ -239,6 +246,31 @@ impl<'a> Dump<'a> {
             task.enqueued_at,
             task.uid,
         )?;
+        if let Some(run_at) = task.run_at {
+            utils::insert_task_datetime(
+                &mut self.wtxn,
+                self.index_scheduler.queue.tasks.run_at,
+                run_at,
+                task.uid,
+            )?;
+        }
+        if !task.depends_on.is_empty() {
+            let dependencies = RoaringBitmap::from_iter(task.depends_on.iter().copied());
+            self.index_scheduler.queue.tasks.dependencies.put(
+                &mut self.wtxn,
+                &task.uid,
+                &dependencies,
+            )?;
+        }
+        if let Some(group) = &task.group {
+            self.index_scheduler.queue.tasks.update_group(
+                &mut self.wtxn,
+                &group.name,
+                |bitmap| {
+                    bitmap.insert(task.uid);
+                },
+            )?;
+        }
 
         // we can't override the started_at & finished_at, so we must only set it if the tasks is finished and won't change
         if matches!(task.status, Status::Succeeded | Status::Failed | Status::Canceled) {
@@ -262,6 +294,7 @@ impl<'a> Dump<'a> {
 
         self.statuses.entry(task.status).or_default().insert(task.uid);
         self.kinds.entry(task.kind.as_kind()).or_default().insert(task.uid);
+        self.priorities.entry(task.priority).or_default().insert(task.uid);
 
         Ok(task)
     }
@@ -285,6 +318,9 @@ impl<'a> Dump<'a> {
         for (kind, bitmap) in self.kinds {
             self.index_scheduler.queue.tasks.put_kind(&mut self.wtxn, kind, &bitmap)?;
         }
+        for (priority, bitmap) in self.priorities {
+            self.index_scheduler.queue.tasks.put_priority(&mut self.wtxn, priority, &bitmap)?;
+        }
 
         for (index, bitmap) in self.batch_indexes {
             self.index_scheduler.queue.batches.index_tasks.put(&mut self.wtxn, &index, &bitmap)?;
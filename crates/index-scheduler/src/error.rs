@@ -5,6 +5,7 @@ use meilisearch_types::error::{Code, ErrorCode};
 use meilisearch_types::tasks::{Kind, Status};
 use meilisearch_types::{heed, milli};
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::TaskId;
 
@@ -113,9 +114,31 @@ pub enum Error {
     TaskFileNotFound(TaskId),
     #[error("Batch `{0}` not found.")]
     BatchNotFound(BatchId),
-    #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
+    #[error("Webhook `{0}` not found.")]
+    WebhookNotFound(Uuid),
+    #[error("Task `{0}` cannot be used as a dependency because it does not exist.")]
+    TaskDependencyNotFound(TaskId),
+    #[error("Task `{task}` cannot be used as a dependency because its group `{group}` is not committed yet.")]
+    TaskDependencyInOpenGroup { task: TaskId, group: String },
+    #[error("Task `{dependency}` did not succeed.")]
+    TaskDependencyFailed { dependency: TaskId },
+    #[error("Tasks of type `{0}` cannot be part of a task group. Only the document operations and the settings updates can be grouped.")]
+    InvalidTaskGroupKind(Kind),
+    #[error("Task group `{0}` is already committed. Wait for its tasks to be processed before registering new tasks in it.")]
+    TaskGroupAlreadyCommitted(String),
+    #[error("Task `{task}` of the group `{group}` failed, none of the tasks of the group were committed: {error}")]
+    TaskGroupFailed { group: String, task: TaskId, error: String },
+    #[error("Task group `{group}` was interrupted while committing its indexes, it will be applied to the remaining ones: {error}")]
+    TaskGroupInterrupted { group: String, error: String },
+    #[error("Tasks of type `{0}` cannot be recurring. The document additions, task deletions, task cancelations and database upgrades can only run once.")]
+    InvalidTaskRunEveryKind(Kind),
+    #[error("The tasks of a group cannot be recurring.")]
+    RecurringTaskGroup,
+    #[error("The interval between two runs of a recurring task must be positive.")]
+    InvalidTaskRunEveryInterval,
+    #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.")]
     TaskDeletionWithEmptyQuery,
-    #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
+    #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.")]
     TaskCancelationWithEmptyQuery,
     #[error("Aborted task")]
     AbortedTask,
@@ -193,6 +216,16 @@ impl Error {
             | Error::TaskNotFound(_)
             | Error::TaskFileNotFound(_)
             | Error::BatchNotFound(_)
+            | Error::WebhookNotFound(_)
+            | Error::TaskDependencyNotFound(_)
+            | Error::TaskDependencyInOpenGroup { .. }
+            | Error::TaskDependencyFailed { .. }
+            | Error::InvalidTaskGroupKind(_)
+            | Error::TaskGroupAlreadyCommitted(_)
+            | Error::TaskGroupFailed { .. }
+            | Error::InvalidTaskRunEveryKind(_)
+            | Error::RecurringTaskGroup
+            | Error::InvalidTaskRunEveryInterval
             | Error::TaskDeletionWithEmptyQuery
             | Error::TaskCancelationWithEmptyQuery
             | Error::AbortedTask
@@ -209,6 +242,7 @@ impl Error {
             | Error::CorruptedTaskQueue
             | Error::DatabaseUpgrade(_)
             | Error::UnrecoverableError(_)
+            | Error::TaskGroupInterrupted { .. }
             | Error::HeedTransaction(_) => false,
             #[cfg(test)]
             Error::PlannedFailure => false,
@@ -255,6 +289,17 @@ impl ErrorCode for Error {
             Error::TaskNotFound(_) => Code::TaskNotFound,
             Error::TaskFileNotFound(_) => Code::TaskFileNotFound,
             Error::BatchNotFound(_) => Code::BatchNotFound,
+            Error::WebhookNotFound(_) => Code::WebhookNotFound,
+            Error::TaskDependencyNotFound(_) => Code::InvalidTaskDependsOn,
+            Error::TaskDependencyInOpenGroup { .. } => Code::InvalidTaskDependsOn,
+            Error::TaskDependencyFailed { .. } => Code::TaskDependencyFailed,
+            Error::InvalidTaskGroupKind(_) => Code::InvalidTaskGroup,
+            Error::TaskGroupAlreadyCommitted(_) => Code::InvalidTaskGroup,
+            Error::TaskGroupFailed { .. } => Code::TaskGroupFailed,
+            Error::TaskGroupInterrupted { .. } => Code::Internal,
+            Error::InvalidTaskRunEveryKind(_) => Code::InvalidTaskRunEvery,
+            Error::RecurringTaskGroup => Code::InvalidTaskRunEvery,
+            Error::InvalidTaskRunEveryInterval => Code::InvalidTaskRunEvery,
             Error::TaskDeletionWithEmptyQuery => Code::MissingTaskFilters,
             Error::TaskCancelationWithEmptyQuery => Code::MissingTaskFilters,
             // TODO: not sure of the Code to use
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
@@ -79,7 +80,9 @@ pub struct IndexMapper {
 
     /// A few types of long running batches of tasks that act on a single index set this field
     /// so that a handle to the index is available from other threads (search) in an optimized manner.
-    currently_updating_index: Arc<RwLock<Option<(String, Index)>>>,
+    ///
+    /// There can be one entry per batch processed concurrently.
+    currently_updating_index: Arc<RwLock<HashMap<String, Index>>>,
 }
 
 /// Whether the index is available for use or is forbidden to be inserted back in the index map
@@ -342,12 +345,8 @@ impl IndexMapper {
 
     /// Return an index, may open it if it wasn't already opened.
     pub fn index(&self, rtxn: &RoTxn, name: &str) -> Result<Index> {
-        if let Some((current_name, current_index)) =
-            self.currently_updating_index.read().unwrap().as_ref()
-        {
-            if current_name == name {
-                return Ok(current_index.clone());
-            }
+        if let Some(current_index) = self.currently_updating_index.read().unwrap().get(name) {
+            return Ok(current_index.clone());
         }
 
         let uuid = self
@@ -528,7 +527,12 @@ impl IndexMapper {
         &self.indexer_config
     }
 
-    pub fn set_currently_updating_index(&self, index: Option<(String, Index)>) {
-        *self.currently_updating_index.write().unwrap() = index;
+    pub fn set_currently_updating_index(&self, name: String, index: Index) {
+        self.currently_updating_index.write().unwrap().insert(name, index);
+    }
+
+    /// Releases the handle of an index that is not updated anymore.
+    pub fn unset_currently_updating_index(&self, name: &str) {
+        self.currently_updating_index.write().unwrap().remove(name);
     }
 }
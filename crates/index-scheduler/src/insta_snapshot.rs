@@ -5,7 +5,7 @@ use meilisearch_types::batches::{Batch, BatchEnqueuedAt, BatchStats};
 use meilisearch_types::heed::types::{SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{Database, RoTxn};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, RoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Details, Kind, Status, Task};
+use meilisearch_types::tasks::{Details, Kind, Status, Task, TaskGroup};
 use meilisearch_types::versioning;
 use roaring::RoaringBitmap;
 
@@ -28,12 +28,12 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
 
         index_mapper,
         features: _,
-        webhook_url: _,
-        webhook_authorization_header: _,
+        webhooks: _,
         test_breakpoint_sdr: _,
         planned_failures: _,
         run_loop_iteration: _,
         embedders: _,
+        rerankers: _,
     } = scheduler;
 
     let rtxn = env.read_txn().unwrap();
@@ -61,6 +61,12 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         snap.push('\n');
         snap.push_str(&snapshot_batch(&batch.to_batch()));
     }
+    for concurrent in processing.running() {
+        snap.push_str(&format!("\n### Concurrent batch {}:\n", concurrent.batch.uid));
+        snap.push_str(&snapshot_bitmap(&concurrent.tasks));
+        snap.push('\n');
+        snap.push_str(&snapshot_batch(&concurrent.batch.to_batch()));
+    }
     snap.push_str("\n----------------------------------------------------------------------\n");
 
     snap.push_str("### All Tasks:\n");
@@ -209,6 +215,11 @@ pub fn snapshot_task(task: &Task) -> String {
         enqueued_at: _,
         started_at: _,
         finished_at: _,
+        run_at: _,
+        run_every,
+        depends_on,
+        group,
+        priority,
         error,
         canceled_by,
         details,
@@ -224,6 +235,21 @@ pub fn snapshot_task(task: &Task) -> String {
     if let Some(canceled_by) = canceled_by {
         snap.push_str(&format!("canceled_by: {canceled_by}, "));
     }
+    if let Some(run_every) = run_every {
+        snap.push_str(&format!("run_every: {run_every}, "));
+    }
+    if !depends_on.is_empty() {
+        snap.push_str(&format!("depends_on: {depends_on:?}, "));
+    }
+    if let Some(TaskGroup { name, commit }) = group {
+        snap.push_str(&format!("group: {name:?}, "));
+        if *commit {
+            snap.push_str("commit, ");
+        }
+    }
+    if !priority.is_normal() {
+        snap.push_str(&format!("priority: {priority}, "));
+    }
     if let Some(error) = error {
         snap.push_str(&format!("error: {error:?}, "));
     }
@@ -34,12 +34,12 @@ pub mod upgrade;
 mod utils;
 pub mod uuid_codec;
 pub mod versioning;
+pub mod webhooks;
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub type TaskId = u32;
 
 use std::collections::{BTreeMap, HashMap};
-use std::io::{self, BufReader, Read};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
@@ -48,26 +48,28 @@ use std::time::Duration;
 use dump::Dump;
 pub use error::Error;
 pub use features::RoFeatures;
-use flate2::bufread::GzEncoder;
-use flate2::Compression;
 use meilisearch_types::batches::Batch;
 use meilisearch_types::features::{InstanceTogglableFeatures, Network, RuntimeTogglableFeatures};
 use meilisearch_types::heed::byteorder::BE;
 use meilisearch_types::heed::types::I128;
 use meilisearch_types::heed::{self, Env, RoTxn, WithoutTls};
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
+use meilisearch_types::milli::prompt::Prompt;
 use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::vector::rerank::{Reranker, RerankerConfig, RerankerOptions};
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
 use meilisearch_types::milli::{self, Index};
-use meilisearch_types::task_view::TaskView;
-use meilisearch_types::tasks::{KindWithContent, Task};
+use meilisearch_types::tasks::{KindWithContent, SchedulingPolicy, Task};
+use meilisearch_types::webhooks::Webhook;
 use processing::ProcessingTasks;
-pub use queue::Query;
 use queue::Queue;
+pub use queue::{Query, TaskOptions};
 use roaring::RoaringBitmap;
 use scheduler::Scheduler;
 use time::OffsetDateTime;
+use uuid::Uuid;
 use versioning::Versioning;
+use webhooks::WebhookData;
 
 use crate::index_mapper::IndexMapper;
 use crate::utils::clamp_to_page_size;
@@ -123,6 +125,9 @@ pub struct IndexSchedulerOptions {
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined maximum size (in bytes) of tasks at once.
     pub batched_tasks_size_limit: u64,
+    /// How the index of the next batch is picked among the indexes whose enqueued
+    /// tasks share the highest priority.
+    pub scheduling_policy: SchedulingPolicy,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
     /// The experimental features enabled for this instance.
@@ -157,10 +162,8 @@ pub struct IndexScheduler {
     /// Whether we should automatically cleanup the task queue or not.
     pub(crate) cleanup_enabled: bool,
 
-    /// The webhook url we should send tasks to after processing every batches.
-    pub(crate) webhook_url: Option<String>,
-    /// The Authorization header to send to the webhook URL.
-    pub(crate) webhook_authorization_header: Option<String>,
+    /// The webhooks we should send tasks to after processing every batches.
+    pub(crate) webhooks: WebhookData,
 
     /// A map to retrieve the runtime representation of an embedder depending on its configuration.
     ///
@@ -169,6 +172,11 @@ pub struct IndexScheduler {
     /// to the same embeddings for the same input text.
     embedders: Arc<RwLock<HashMap<EmbedderOptions, Arc<Embedder>>>>,
 
+    /// A map to retrieve the runtime representation of a reranker depending on its configuration.
+    ///
+    /// Like for the embedders, indexes with the same reranker configuration share the same reranker.
+    rerankers: Arc<RwLock<HashMap<RerankerOptions, Arc<Reranker>>>>,
+
     // ================= test
     // The next entry is dedicated to the tests.
     /// Provide a way to set a breakpoint in multiple part of the scheduler.
@@ -199,9 +207,9 @@ impl IndexScheduler {
 
             index_mapper: self.index_mapper.clone(),
             cleanup_enabled: self.cleanup_enabled,
-            webhook_url: self.webhook_url.clone(),
-            webhook_authorization_header: self.webhook_authorization_header.clone(),
+            webhooks: self.webhooks.clone(),
             embedders: self.embedders.clone(),
+            rerankers: self.rerankers.clone(),
             #[cfg(test)]
             test_breakpoint_sdr: self.test_breakpoint_sdr.clone(),
             #[cfg(test)]
@@ -213,7 +221,11 @@ impl IndexScheduler {
     }
 
     pub(crate) const fn nb_db() -> u32 {
-        Versioning::nb_db() + Queue::nb_db() + IndexMapper::nb_db() + features::FeatureData::nb_db()
+        Versioning::nb_db()
+            + Queue::nb_db()
+            + IndexMapper::nb_db()
+            + features::FeatureData::nb_db()
+            + WebhookData::nb_db()
     }
 
     /// Create an index scheduler and start its run loop.
@@ -267,6 +279,7 @@ impl IndexScheduler {
         let features = features::FeatureData::new(&env, &mut wtxn, options.instance_features)?;
         let queue = Queue::new(&env, &mut wtxn, &options)?;
         let index_mapper = IndexMapper::new(&env, &mut wtxn, &options, budget)?;
+        let webhooks = WebhookData::new(&env, &mut wtxn, &options)?;
         wtxn.commit()?;
 
         // allow unreachable_code to get rids of the warning in the case of a test build.
@@ -279,9 +292,9 @@ impl IndexScheduler {
             index_mapper,
             env,
             cleanup_enabled: options.cleanup_enabled,
-            webhook_url: options.webhook_url,
-            webhook_authorization_header: options.webhook_authorization_header,
+            webhooks,
             embedders: Default::default(),
+            rerankers: Default::default(),
 
             #[cfg(test)]
             test_breakpoint_sdr,
@@ -293,6 +306,7 @@ impl IndexScheduler {
         };
 
         this.run();
+        this.run_webhooks();
         Ok(this)
     }
 
@@ -395,6 +409,9 @@ impl IndexScheduler {
                     match ret {
                         Ok(Ok(TickOutcome::TickAgain(_))) => (),
                         Ok(Ok(TickOutcome::WaitForSignal)) => run.scheduler.wake_up.wait(),
+                        Ok(Ok(TickOutcome::WaitForSignalOrTimeout(timeout))) => {
+                            run.scheduler.wake_up.wait_timeout(timeout);
+                        }
                         Ok(Ok(TickOutcome::StopProcessingForever)) => break,
                         Ok(Err(e)) => {
                             tracing::error!("{e}");
@@ -413,6 +430,22 @@ impl IndexScheduler {
             .unwrap();
     }
 
+    /// Start the thread sending the deliveries to the webhooks.
+    ///
+    /// This function must be called only once per index scheduler.
+    fn run_webhooks(&self) {
+        let run = self.private_clone();
+        std::thread::Builder::new()
+            .name(String::from("webhooks"))
+            .spawn(move || loop {
+                if let Err(e) = run.webhooks.run(&run.env, &run.queue.tasks) {
+                    tracing::error!("While sending data to the webhooks: {e}");
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            })
+            .unwrap();
+    }
+
     pub fn indexer_config(&self) -> &IndexerConfig {
         &self.index_mapper.indexer_config
     }
@@ -637,6 +670,19 @@ impl IndexScheduler {
         kind: KindWithContent,
         task_id: Option<TaskId>,
         dry_run: bool,
+    ) -> Result<Task> {
+        self.register_with_options(kind, task_id, TaskOptions::default(), dry_run)
+    }
+
+    /// Register a new task in the scheduler, the options define when the task can be processed.
+    ///
+    /// If it fails and data was associated with the task, it tries to delete the associated data.
+    pub fn register_with_options(
+        &self,
+        kind: KindWithContent,
+        task_id: Option<TaskId>,
+        options: TaskOptions,
+        dry_run: bool,
     ) -> Result<Task> {
         // if the task doesn't delete or cancel anything and 40% of the task queue is full, we must refuse to enqueue the incoming task
         if !matches!(&kind, KindWithContent::TaskDeletion { tasks, .. } | KindWithContent::TaskCancelation { tasks, .. } if !tasks.is_empty())
@@ -647,16 +693,16 @@ impl IndexScheduler {
         }
 
         let mut wtxn = self.env.write_txn()?;
-        let task = self.queue.register(&mut wtxn, &kind, task_id, dry_run)?;
+        let task = self.queue.register(&mut wtxn, &kind, task_id, options, dry_run)?;
 
         // If the registered task is a task cancelation
         // we inform the processing tasks to stop (if necessary).
         if let KindWithContent::TaskCancelation { tasks, .. } = kind {
             let tasks_to_cancel = RoaringBitmap::from_iter(tasks);
-            if self.processing_tasks.read().unwrap().must_cancel_processing_tasks(&tasks_to_cancel)
-            {
-                self.scheduler.must_stop_processing.must_stop();
-            }
+            self.processing_tasks
+                .read()
+                .unwrap()
+                .cancel_processing_tasks(&tasks_to_cancel, &self.scheduler.must_stop_processing);
         }
 
         if let Err(e) = wtxn.commit() {
@@ -699,88 +745,6 @@ impl IndexScheduler {
         Ok(())
     }
 
-    /// Once the tasks changes have been committed we must send all the tasks that were updated to our webhook if there is one.
-    fn notify_webhook(&self, updated: &RoaringBitmap) -> Result<()> {
-        if let Some(ref url) = self.webhook_url {
-            struct TaskReader<'a, 'b> {
-                rtxn: &'a RoTxn<'a>,
-                index_scheduler: &'a IndexScheduler,
-                tasks: &'b mut roaring::bitmap::Iter<'b>,
-                buffer: Vec<u8>,
-                written: usize,
-            }
-
-            impl Read for TaskReader<'_, '_> {
-                fn read(&mut self, mut buf: &mut [u8]) -> std::io::Result<usize> {
-                    if self.buffer.is_empty() {
-                        match self.tasks.next() {
-                            None => return Ok(0),
-                            Some(task_id) => {
-                                let task = self
-                                    .index_scheduler
-                                    .queue
-                                    .tasks
-                                    .get_task(self.rtxn, task_id)
-                                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
-                                    .ok_or_else(|| {
-                                        io::Error::new(
-                                            io::ErrorKind::Other,
-                                            Error::CorruptedTaskQueue,
-                                        )
-                                    })?;
-
-                                serde_json::to_writer(
-                                    &mut self.buffer,
-                                    &TaskView::from_task(&task),
-                                )?;
-                                self.buffer.push(b'\n');
-                            }
-                        }
-                    }
-
-                    let mut to_write = &self.buffer[self.written..];
-                    let wrote = io::copy(&mut to_write, &mut buf)?;
-                    self.written += wrote as usize;
-
-                    // we wrote everything and must refresh our buffer on the next call
-                    if self.written == self.buffer.len() {
-                        self.written = 0;
-                        self.buffer.clear();
-                    }
-
-                    Ok(wrote as usize)
-                }
-            }
-
-            let rtxn = self.env.read_txn()?;
-
-            let task_reader = TaskReader {
-                rtxn: &rtxn,
-                index_scheduler: self,
-                tasks: &mut updated.into_iter(),
-                buffer: Vec::with_capacity(50), // on average a task is around ~100 bytes
-                written: 0,
-            };
-
-            // let reader = GzEncoder::new(BufReader::new(task_reader), Compression::default());
-            let reader = GzEncoder::new(BufReader::new(task_reader), Compression::default());
-            let request = ureq::post(url)
-                .timeout(Duration::from_secs(30))
-                .set("Content-Encoding", "gzip")
-                .set("Content-Type", "application/x-ndjson");
-            let request = match &self.webhook_authorization_header {
-                Some(header) => request.set("Authorization", header),
-                None => request,
-            };
-
-            if let Err(e) = request.send(reader) {
-                tracing::error!("While sending data to the webhook: {e}");
-            }
-        }
-
-        Ok(())
-    }
-
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
@@ -809,6 +773,25 @@ impl IndexScheduler {
         self.features.network()
     }
 
+    /// The webhooks managed through the API, the webhook configured at launch is not returned.
+    pub fn webhooks(&self) -> BTreeMap<Uuid, Webhook> {
+        self.webhooks.webhooks()
+    }
+
+    pub fn webhook(&self, uuid: Uuid) -> Result<Webhook> {
+        self.webhooks.webhook(uuid).ok_or(Error::WebhookNotFound(uuid))
+    }
+
+    pub fn put_webhook(&self, uuid: Uuid, webhook: Webhook) -> Result<()> {
+        let wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.webhooks.put_webhook(wtxn, uuid, webhook)
+    }
+
+    pub fn delete_webhook(&self, uuid: Uuid) -> Result<()> {
+        let wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.webhooks.delete_webhook(wtxn, uuid)
+    }
+
     pub fn embedders(
         &self,
         index_uid: String,
@@ -816,47 +799,80 @@ impl IndexScheduler {
     ) -> Result<EmbeddingConfigs> {
         let res: Result<_> = embedding_configs
             .into_iter()
-            .map(
-                |IndexEmbeddingConfig {
-                     name,
-                     config: milli::vector::EmbeddingConfig { embedder_options, prompt, quantized },
-                     ..
-                 }| {
-                    let prompt = Arc::new(
-                        prompt
-                            .try_into()
-                            .map_err(meilisearch_types::milli::Error::from)
-                            .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?,
-                    );
-                    // optimistically return existing embedder
-                    {
-                        let embedders = self.embedders.read().unwrap();
-                        if let Some(embedder) = embedders.get(&embedder_options) {
-                            return Ok((
-                                name,
-                                (embedder.clone(), prompt, quantized.unwrap_or_default()),
-                            ));
-                        }
+            .map(|IndexEmbeddingConfig { name, config, .. }| {
+                let quantization = config.quantization();
+                let milli::vector::EmbeddingConfig {
+                    embedder_options,
+                    prompt,
+                    quantized: _,
+                    quantization: _,
+                    distance,
+                } = config;
+                let prompt = Arc::new(
+                    prompt
+                        .try_into()
+                        .map_err(meilisearch_types::milli::Error::from)
+                        .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?,
+                );
+                // optimistically return existing embedder
+                {
+                    let embedders = self.embedders.read().unwrap();
+                    if let Some(embedder) = embedders.get(&embedder_options) {
+                        return Ok((
+                            name,
+                            (embedder.clone(), prompt, quantization, distance.unwrap_or_default()),
+                        ));
                     }
+                }
 
-                    // add missing embedder
-                    let embedder = Arc::new(
-                        Embedder::new(embedder_options.clone(), self.scheduler.embedding_cache_cap)
-                            .map_err(meilisearch_types::milli::vector::Error::from)
-                            .map_err(|err| {
-                                Error::from_milli(err.into(), Some(index_uid.clone()))
-                            })?,
-                    );
-                    {
-                        let mut embedders = self.embedders.write().unwrap();
-                        embedders.insert(embedder_options, embedder.clone());
-                    }
-                    Ok((name, (embedder, prompt, quantized.unwrap_or_default())))
-                },
-            )
+                // add missing embedder
+                let embedder = Arc::new(
+                    Embedder::new(embedder_options.clone(), self.scheduler.embedding_cache_cap)
+                        .map_err(meilisearch_types::milli::vector::Error::from)
+                        .map_err(|err| Error::from_milli(err.into(), Some(index_uid.clone())))?,
+                );
+                {
+                    let mut embedders = self.embedders.write().unwrap();
+                    embedders.insert(embedder_options, embedder.clone());
+                }
+                Ok((name, (embedder, prompt, quantization, distance.unwrap_or_default())))
+            })
             .collect();
         res.map(EmbeddingConfigs::new)
     }
+
+    /// Returns the runtime representation of a reranker of an index, and the prompt rendering the documents it scores.
+    pub fn reranker(
+        &self,
+        index_uid: String,
+        RerankerConfig { options, prompt }: RerankerConfig,
+    ) -> Result<(Arc<Reranker>, Arc<Prompt>)> {
+        let prompt = Arc::new(
+            prompt
+                .try_into()
+                .map_err(meilisearch_types::milli::Error::from)
+                .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?,
+        );
+        // optimistically return existing reranker
+        {
+            let rerankers = self.rerankers.read().unwrap();
+            if let Some(reranker) = rerankers.get(&options) {
+                return Ok((reranker.clone(), prompt));
+            }
+        }
+
+        // add missing reranker
+        let reranker = Arc::new(
+            Reranker::new(options.clone())
+                .map_err(meilisearch_types::milli::vector::Error::from)
+                .map_err(|err| Error::from_milli(err.into(), Some(index_uid.clone())))?,
+        );
+        {
+            let mut rerankers = self.rerankers.write().unwrap();
+            rerankers.insert(options, reranker.clone());
+        }
+        Ok((reranker, prompt))
+    }
 }
 
 /// The outcome of calling the [`IndexScheduler::tick`] function.
@@ -867,6 +883,9 @@ pub enum TickOutcome {
     TickAgain(u64),
     /// The scheduler should wait for an external signal before attempting another `tick`.
     WaitForSignal,
+    /// The scheduler should wait for an external signal or for the given duration before
+    /// attempting another `tick`, a scheduled task can be processed once the duration has elapsed.
+    WaitForSignalOrTimeout(Duration),
     /// The scheduler exits the run-loop and will never process tasks again
     StopProcessingForever,
 }
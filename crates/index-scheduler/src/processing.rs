@@ -1,18 +1,39 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 
-use meilisearch_types::milli::progress::{AtomicSubStep, NamedStep, Progress, ProgressView};
+use meilisearch_types::batches::{BatchId, BatchScheduling};
+use meilisearch_types::milli::progress::{AtomicSubStep, NamedStep, Progress, ProgressView, Step};
 use meilisearch_types::milli::{make_atomic_progress, make_enum_progress};
 use roaring::RoaringBitmap;
 
+use crate::scheduler::MustStopProcessing;
 use crate::utils::ProcessingBatch;
 
 #[derive(Clone, Default)]
 pub struct ProcessingTasks {
     pub batch: Option<Arc<ProcessingBatch>>,
-    /// The list of tasks ids that are currently running.
+    /// The list of tasks ids that are currently running, in any of the processing batches.
     pub processing: Arc<RoaringBitmap>,
     /// The progress on processing tasks
     pub progress: Option<Progress>,
+    /// The batches processed in parallel on independent indexes.
+    ///
+    /// Only used when more than one batch can be processed at a time, `batch` is then always empty.
+    /// The aborted batches are kept until the cancelation of their tasks is processed.
+    pub concurrent: Vec<ConcurrentBatch>,
+}
+
+/// A batch processed on a worker thread along with other batches of independent indexes.
+#[derive(Clone)]
+pub struct ConcurrentBatch {
+    pub batch: Arc<ProcessingBatch>,
+    /// The list of tasks ids processed by this batch.
+    pub tasks: Arc<RoaringBitmap>,
+    pub progress: Progress,
+    /// Set to stop this batch only, the other batches keep going.
+    pub must_stop_processing: MustStopProcessing,
+    /// Whether the batch was stopped and is waiting for its tasks to be canceled.
+    pub aborted: bool,
 }
 
 impl ProcessingTasks {
@@ -25,16 +46,94 @@ impl ProcessingTasks {
         Some(self.progress.as_ref()?.as_progress_view())
     }
 
+    /// Returns every processing batch along with its tasks and progress.
+    pub fn batches(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<ProcessingBatch>, &RoaringBitmap, Option<&Progress>)> {
+        let main =
+            self.batch.iter().map(|batch| (batch, &*self.processing, self.progress.as_ref()));
+        let concurrent = self.running().map(|c| (&c.batch, &*c.tasks, Some(&c.progress)));
+        main.chain(concurrent)
+    }
+
+    /// Returns the batches processed concurrently that were not aborted.
+    pub fn running(&self) -> impl Iterator<Item = &ConcurrentBatch> {
+        self.concurrent.iter().filter(|c| !c.aborted)
+    }
+
+    /// Returns the processing batch with the given uid along with its tasks and progress.
+    pub fn get_batch(
+        &self,
+        batch_id: BatchId,
+    ) -> Option<(&Arc<ProcessingBatch>, &RoaringBitmap, Option<&Progress>)> {
+        self.batches().find(|(batch, _, _)| batch.uid == batch_id)
+    }
+
+    /// Returns the uids of all the processing batches.
+    pub fn batch_uids(&self) -> RoaringBitmap {
+        self.batches().map(|(batch, _, _)| batch.uid).collect()
+    }
+
+    /// Stores a batch processed concurrently with the other ones, and the date time at which it started.
+    pub fn start_concurrent_processing(
+        &mut self,
+        processing_batch: ProcessingBatch,
+        tasks: RoaringBitmap,
+        must_stop_processing: MustStopProcessing,
+    ) -> Progress {
+        let progress = batch_progress(&processing_batch);
+        // The batches processed alone never run along with the concurrent ones,
+        // if there is one it was aborted and is not processing anymore.
+        self.batch = None;
+        self.progress = None;
+        self.concurrent.push(ConcurrentBatch {
+            batch: Arc::new(processing_batch),
+            tasks: Arc::new(tasks),
+            progress: progress.clone(),
+            must_stop_processing,
+            aborted: false,
+        });
+        self.update_concurrent_processing();
+
+        progress
+    }
+
+    /// Removes a batch processed concurrently from the processing ones.
+    pub fn stop_concurrent_processing(&mut self, batch_id: BatchId) -> Option<ConcurrentBatch> {
+        let position = self.concurrent.iter().position(|c| c.batch.uid == batch_id)?;
+        let concurrent = self.concurrent.remove(position);
+        self.update_concurrent_processing();
+        Some(concurrent)
+    }
+
+    /// Marks a batch processed concurrently as aborted, its tasks are not processing anymore
+    /// but the batch is kept, along with its `started_at` date, until its tasks are canceled.
+    pub fn abort_concurrent_processing(&mut self, batch_id: BatchId) {
+        if let Some(concurrent) = self.concurrent.iter_mut().find(|c| c.batch.uid == batch_id) {
+            concurrent.aborted = true;
+            self.update_concurrent_processing();
+        }
+    }
+
+    /// Removes the aborted batches whose tasks were canceled.
+    pub fn remove_canceled_batches(&mut self, canceled_tasks: &RoaringBitmap) {
+        self.concurrent.retain(|c| !c.aborted || c.tasks.is_disjoint(canceled_tasks));
+    }
+
+    fn update_concurrent_processing(&mut self) {
+        self.processing =
+            Arc::new(self.running().fold(RoaringBitmap::new(), |acc, c| acc | &*c.tasks));
+    }
+
     /// Stores the currently processing tasks, and the date time at which it started.
     pub fn start_processing(
         &mut self,
         processing_batch: ProcessingBatch,
         processing: RoaringBitmap,
     ) -> Progress {
+        let progress = batch_progress(&processing_batch);
         self.batch = Some(Arc::new(processing_batch));
         self.processing = Arc::new(processing);
-        let progress = Progress::default();
-        progress.update_progress(BatchProgress::ProcessingTasks);
         self.progress = Some(progress.clone());
 
         progress
@@ -48,6 +147,7 @@ impl ProcessingTasks {
             batch: std::mem::take(&mut self.batch),
             processing: std::mem::take(&mut self.processing),
             progress: None,
+            concurrent: Vec::new(),
         }
     }
 
@@ -55,6 +155,55 @@ impl ProcessingTasks {
     pub fn must_cancel_processing_tasks(&self, canceled_tasks: &RoaringBitmap) -> bool {
         !self.processing.is_disjoint(canceled_tasks)
     }
+
+    /// Stops the processing batches containing one of the canceled tasks. The batch processed
+    /// alone is stopped with the `must_stop_processing` flag of the scheduler, while the batches
+    /// processed concurrently are stopped with their own flag, so that the other ones keep going.
+    pub fn cancel_processing_tasks(
+        &self,
+        canceled_tasks: &RoaringBitmap,
+        must_stop_processing: &MustStopProcessing,
+    ) {
+        if self.batch.is_some() && self.must_cancel_processing_tasks(canceled_tasks) {
+            must_stop_processing.must_stop();
+        }
+        for concurrent in self.running().filter(|c| !c.tasks.is_disjoint(canceled_tasks)) {
+            concurrent.must_stop_processing.must_stop();
+        }
+    }
+}
+
+/// Creates the progress of a batch, it starts with the scheduling choice of the batch if any.
+fn batch_progress(processing_batch: &ProcessingBatch) -> Progress {
+    let progress = Progress::default();
+    if let Some(scheduling) = &processing_batch.stats.scheduling {
+        progress.update_progress(SchedulingStep(scheduling.clone()));
+    }
+    progress.update_progress(BatchProgress::ProcessingTasks);
+    progress
+}
+
+/// Explains why the batch was picked among the enqueued tasks.
+///
+/// It never advances, so it doesn't change the percentage of the batch progress.
+pub struct SchedulingStep(pub BatchScheduling);
+
+impl Step for SchedulingStep {
+    fn name(&self) -> Cow<'static, str> {
+        let BatchScheduling { priority, policy, skipped_tasks } = &self.0;
+        format!(
+            "scheduled by the {policy} policy among the {priority} priority tasks, skipping {skipped_tasks} tasks"
+        )
+        .into()
+    }
+
+    fn current(&self) -> u32 {
+        0
+    }
+
+    fn total(&self) -> u32 {
+        1
+    }
 }
 
 make_enum_progress! {
@@ -186,6 +335,7 @@ mod test {
     use meili_snap::{json_string, snapshot};
 
     use super::*;
+    use crate::insta_snapshot::snapshot_bitmap;
 
     #[test]
     fn one_level() {
@@ -218,6 +368,39 @@ mod test {
         "#);
     }
 
+    #[test]
+    fn concurrent_batches() {
+        let mut processing = ProcessingTasks::new();
+        processing
+            .start_concurrent_processing(ProcessingBatch::new(0), RoaringBitmap::from_iter([0, 1]));
+        let progress = processing
+            .start_concurrent_processing(ProcessingBatch::new(1), RoaringBitmap::from_iter([2]));
+        progress.update_progress(BatchProgress::WritingTasksToDisk);
+        snapshot!(snapshot_bitmap(&processing.processing), @"[0,1,2,]");
+        snapshot!(snapshot_bitmap(&processing.batch_uids()), @"[0,1,]");
+
+        let (batch, tasks, progress) = processing.get_batch(1).unwrap();
+        snapshot!(batch.uid, @"1");
+        snapshot!(snapshot_bitmap(tasks), @"[2,]");
+        snapshot!(json_string!(progress.unwrap().as_progress_view()), @r#"
+        {
+          "steps": [
+            {
+              "currentStep": "writing tasks to disk",
+              "finished": 1,
+              "total": 2
+            }
+          ],
+          "percentage": 50.0
+        }
+        "#);
+
+        assert!(processing.stop_concurrent_processing(0).is_some());
+        assert!(processing.stop_concurrent_processing(0).is_none());
+        snapshot!(snapshot_bitmap(&processing.processing), @"[2,]");
+        snapshot!(snapshot_bitmap(&processing.batch_uids()), @"[1,]");
+    }
+
     #[test]
     fn task_progress() {
         let mut processing = ProcessingTasks::new();
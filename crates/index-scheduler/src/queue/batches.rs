@@ -280,9 +280,9 @@ impl BatchQueue {
         tasks
             .into_iter()
             .map(|batch_id| {
-                if Some(batch_id) == processing.batch.as_ref().map(|batch| batch.uid) {
-                    let mut batch = processing.batch.as_ref().unwrap().to_batch();
-                    batch.progress = processing.get_progress_view();
+                if let Some((batch, _, progress)) = processing.get_batch(batch_id) {
+                    let mut batch = batch.to_batch();
+                    batch.progress = progress.map(|progress| progress.as_progress_view());
                     Ok(batch)
                 } else {
                     self.get_batch(rtxn, batch_id)
@@ -317,12 +317,13 @@ impl Queue {
             after_started_at,
             before_finished_at,
             after_finished_at,
+            before_run_at,
+            after_run_at,
         } = query;
 
+        let processing_batches = processing.batch_uids();
         let mut batches = self.batches.all_batch_ids(rtxn)?;
-        if let Some(batch_id) = processing.batch.as_ref().map(|batch| batch.uid) {
-            batches.insert(batch_id);
-        }
+        batches |= &processing_batches;
 
         if let Some(from) = from {
             let range = if reverse.unwrap_or_default() {
@@ -343,20 +344,14 @@ impl Queue {
             for status in status {
                 match status {
                     // special case for Processing batches
-                    Status::Processing => {
-                        if let Some(batch_id) = processing.batch.as_ref().map(|batch| batch.uid) {
-                            status_batches.insert(batch_id);
-                        }
-                    }
+                    Status::Processing => status_batches |= &processing_batches,
                     // Enqueued tasks are not stored in batches
                     Status::Enqueued => (),
                     status => status_batches |= &self.batches.get_status(rtxn, *status)?,
                 };
             }
             if !status.contains(&Status::Processing) {
-                if let Some(ref batch) = processing.batch {
-                    batches.remove(batch.uid);
-                }
+                batches -= &processing_batches;
             }
             batches &= status_batches;
         }
@@ -397,17 +392,40 @@ impl Queue {
             }
         }
 
+        // The run at dates are only stored for the tasks, we keep the batches containing the matching tasks
+        if after_run_at.is_some() || before_run_at.is_some() {
+            let mut scheduled_tasks = self.tasks.all_task_ids(rtxn)?;
+            keep_ids_within_datetimes(
+                rtxn,
+                &mut scheduled_tasks,
+                self.tasks.run_at,
+                *after_run_at,
+                *before_run_at,
+            )?;
+            let mut scheduled_batches = RoaringBitmap::new();
+            for task in self.tasks.get_existing_tasks(rtxn, &scheduled_tasks)? {
+                if let Some(batch_uid) = task.batch_uid {
+                    scheduled_batches.insert(batch_uid);
+                }
+            }
+            for (batch, tasks, _) in processing.batches() {
+                if !scheduled_tasks.is_disjoint(tasks) {
+                    scheduled_batches.insert(batch.uid);
+                }
+            }
+            batches &= scheduled_batches;
+        }
+
         if let Some(kind) = &types {
             let mut kind_batches = RoaringBitmap::new();
             for kind in kind {
                 kind_batches |= self.batches.get_kind(rtxn, *kind)?;
-                if let Some(uid) = processing
-                    .batch
-                    .as_ref()
-                    .and_then(|batch| batch.kinds.contains(kind).then_some(batch.uid))
-                {
-                    kind_batches.insert(uid);
-                }
+                kind_batches.extend(
+                    processing
+                        .batches()
+                        .filter(|(batch, _, _)| batch.kinds.contains(kind))
+                        .map(|(batch, _, _)| batch.uid),
+                );
             }
             batches &= &kind_batches;
         }
@@ -416,13 +434,12 @@ impl Queue {
             let mut index_batches = RoaringBitmap::new();
             for index in index {
                 index_batches |= self.batches.index_batches(rtxn, index)?;
-                if let Some(uid) = processing
-                    .batch
-                    .as_ref()
-                    .and_then(|batch| batch.indexes.contains(index).then_some(batch.uid))
-                {
-                    index_batches.insert(uid);
-                }
+                index_batches.extend(
+                    processing
+                        .batches()
+                        .filter(|(batch, _, _)| batch.indexes.contains(index))
+                        .map(|(batch, _, _)| batch.uid),
+                );
             }
             batches &= &index_batches;
         }
@@ -433,24 +450,22 @@ impl Queue {
         // Once we have filtered the two subsets, we put them back together and assign it back to `batches`.
         batches = {
             let (mut filtered_non_processing_batches, mut filtered_processing_batches) =
-                (&batches - &*processing.processing, &batches & &*processing.processing);
+                (&batches - &processing_batches, &batches & &processing_batches);
 
             // special case for Processing batches
-            // A closure that clears the filtered_processing_batches if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_batches whose started_at date falls outside the given bounds
             let mut clear_filtered_processing_batches =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &processing
-                            .batch
-                            .as_ref()
-                            .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at)
-                            .unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_batches.clear();
+                    for (batch, _, _) in processing.batches() {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_batches.remove(batch.uid);
+                        }
                     }
                 };
             match (after_started_at, before_started_at) {
@@ -536,7 +551,7 @@ impl Queue {
         if query.index_uids.is_some() || !filters.all_indexes_authorized() {
             for kind in enum_iterator::all::<Kind>().filter(|kind| !kind.related_to_one_index()) {
                 batches -= self.tasks.get_kind(rtxn, kind)?;
-                if let Some(batch) = processing.batch.as_ref() {
+                for (batch, _, _) in processing.batches() {
                     if batch.kinds.contains(&kind) {
                         batches.remove(batch.uid);
                     }
@@ -559,7 +574,7 @@ impl Queue {
                     forbidden_indexes |= index_tasks;
                 }
             }
-            if let Some(batch) = processing.batch.as_ref() {
+            for (batch, _, _) in processing.batches() {
                 for index in &batch.indexes {
                     if filters.is_index_authorized(index) {
                         valid_indexes.insert(batch.uid);
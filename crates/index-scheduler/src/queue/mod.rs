@@ -15,14 +15,14 @@ use file_store::FileStore;
 use meilisearch_types::batches::BatchId;
 use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task, TaskGroup, TaskPriority};
 use roaring::RoaringBitmap;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 pub(crate) use self::batches::BatchQueue;
-pub(crate) use self::tasks::TaskQueue;
+pub(crate) use self::tasks::{TaskGroupJournal, TaskQueue};
 use crate::processing::ProcessingTasks;
 use crate::utils::{
     check_index_swap_validity, filter_out_references_to_newer_tasks, ProcessingBatch,
@@ -81,6 +81,30 @@ pub struct Query {
     pub before_finished_at: Option<OffsetDateTime>,
     /// Exclusive lower bound of the matched tasks' [`finished_at`](meilisearch_types::tasks::Task::finished_at) field.
     pub after_finished_at: Option<OffsetDateTime>,
+    /// Exclusive upper bound of the matched tasks' [`run_at`](meilisearch_types::tasks::Task::run_at) field.
+    pub before_run_at: Option<OffsetDateTime>,
+    /// Exclusive lower bound of the matched tasks' [`run_at`](meilisearch_types::tasks::Task::run_at) field.
+    pub after_run_at: Option<OffsetDateTime>,
+}
+
+/// Defines how a task registered in the [`IndexScheduler`] must be scheduled.
+///
+/// The default options make the task eligible to be processed immediately.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct TaskOptions {
+    /// The task stays enqueued and is not processed before this date.
+    pub run_at: Option<OffsetDateTime>,
+    /// Once finished, a copy of the task is enqueued to run after this interval, counted
+    /// from the previous `run_at` date.
+    pub run_every: Option<time::Duration>,
+    /// The task stays enqueued until all these tasks are finished, and fails
+    /// if one of them did not succeed.
+    pub depends_on: Vec<TaskId>,
+    /// The tasks of a group stay enqueued until the group is committed, and are then
+    /// processed together: either all of them succeed or none of them are applied.
+    pub group: Option<TaskGroup>,
+    /// The indexes with the highest priority enqueued tasks are processed first.
+    pub priority: TaskPriority,
 }
 
 impl Query {
@@ -105,6 +129,8 @@ impl Query {
                 after_started_at: None,
                 before_finished_at: None,
                 after_finished_at: None,
+                before_run_at: None,
+                after_run_at: None,
             }
         )
     }
@@ -257,6 +283,7 @@ impl Queue {
         wtxn: &mut RwTxn,
         kind: &KindWithContent,
         task_id: Option<TaskId>,
+        options: TaskOptions,
         dry_run: bool,
     ) -> Result<Task> {
         let next_task_id = self.tasks.next_task_id(wtxn)?;
@@ -274,6 +301,11 @@ impl Queue {
             enqueued_at: OffsetDateTime::now_utc(),
             started_at: None,
             finished_at: None,
+            run_at: options.run_at,
+            run_every: options.run_every,
+            depends_on: options.depends_on,
+            group: options.group,
+            priority: options.priority,
             error: None,
             canceled_by: None,
             details: kind.default_details(),
@@ -286,6 +318,11 @@ impl Queue {
         // If the register task is an index swap task, verify that it is well-formed
         // (that it does not contain duplicate indexes).
         check_index_swap_validity(&task)?;
+        // If the task depends on other tasks or is part of a group, verify that it
+        // cannot wait for something that will never be processed.
+        task.depends_on.sort_unstable();
+        task.depends_on.dedup();
+        self.check_task_scheduling(wtxn, &task)?;
 
         // At this point the task is going to be registered and no further checks will be done
         if dry_run {
@@ -297,6 +334,45 @@ impl Queue {
         Ok(task)
     }
 
+    fn check_task_scheduling(&self, rtxn: &RoTxn, task: &Task) -> Result<()> {
+        for &uid in &task.depends_on {
+            let dependency =
+                self.tasks.get_task(rtxn, uid)?.ok_or(Error::TaskDependencyNotFound(uid))?;
+            // The tasks of an open group can't be processed, depending on them could
+            // make the group wait for itself.
+            if let Some(group) = dependency.group {
+                if dependency.status == Status::Enqueued
+                    && !self.tasks.enqueued_group(rtxn, &group.name)?.1
+                {
+                    return Err(Error::TaskDependencyInOpenGroup { task: uid, group: group.name });
+                }
+            }
+        }
+
+        if let Some(group) = &task.group {
+            if !task.kind.can_be_grouped() {
+                return Err(Error::InvalidTaskGroupKind(task.kind.as_kind()));
+            }
+            if self.tasks.enqueued_group(rtxn, &group.name)?.1 {
+                return Err(Error::TaskGroupAlreadyCommitted(group.name.clone()));
+            }
+        }
+
+        if let Some(run_every) = task.run_every {
+            if !task.kind.can_recur() {
+                return Err(Error::InvalidTaskRunEveryKind(task.kind.as_kind()));
+            }
+            if task.group.is_some() {
+                return Err(Error::RecurringTaskGroup);
+            }
+            if !run_every.is_positive() {
+                return Err(Error::InvalidTaskRunEveryInterval);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Register a task to cleanup the task queue if needed
     pub fn cleanup_task_queue(&self, wtxn: &mut RwTxn) -> Result<()> {
         let nb_tasks = self.tasks.all_task_ids(wtxn)?.len();
@@ -342,6 +418,7 @@ impl Queue {
                 tasks: to_delete,
             },
             None,
+            TaskOptions::default(),
             false,
         )?;
 
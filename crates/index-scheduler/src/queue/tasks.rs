@@ -3,8 +3,9 @@ use std::ops::{Bound, RangeBounds};
 use meilisearch_types::heed::types::{DecodeIgnore, SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, RoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Kind, Status, Task};
+use meilisearch_types::tasks::{Kind, Status, Task, TaskPriority};
 use roaring::{MultiOps, RoaringBitmap};
+use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::{Query, Queue};
@@ -15,7 +16,7 @@ use crate::utils::{
 use crate::{Error, Result, TaskId, BEI128};
 
 /// The number of database used by the task queue
-const NUMBER_OF_DATABASES: u32 = 8;
+const NUMBER_OF_DATABASES: u32 = 14;
 /// Database const names for the `IndexScheduler`.
 mod db_name {
     pub const ALL_TASKS: &str = "all-tasks";
@@ -27,6 +28,30 @@ mod db_name {
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
+    pub const RUN_AT: &str = "run-at";
+    pub const DEPENDENCIES: &str = "dependencies";
+    pub const GROUP_TASKS: &str = "group-tasks";
+    pub const TASK_GROUP_JOURNAL: &str = "task-group-journal";
+    pub const PRIORITY: &str = "priority";
+    pub const ROUND_ROBIN: &str = "round-robin";
+}
+
+/// The key of the index of the last batch started by the round-robin scheduling policy.
+mod round_robin_key {
+    pub const LAST_INDEX: &str = "last-index";
+}
+
+/// The progress of a task group being applied, written in the task queue before any of its
+/// indexes is modified.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TaskGroupJournal {
+    /// The last task of the group, it identifies the group once its name is reused.
+    pub last_task: TaskId,
+    /// The indexes created for the group, deleted if it fails.
+    pub created_indexes: Vec<String>,
+    /// The processed tasks of the group, once all of them succeeded and its indexes
+    /// started being committed.
+    pub committing: Option<Vec<Task>>,
 }
 
 pub struct TaskQueue {
@@ -48,6 +73,18 @@ pub struct TaskQueue {
     pub(crate) started_at: Database<BEI128, CboRoaringBitmapCodec>,
     /// Store the task ids of tasks which finished at a specific date
     pub(crate) finished_at: Database<BEI128, CboRoaringBitmapCodec>,
+    /// Store the task ids of scheduled tasks which must not be processed before a specific date
+    pub(crate) run_at: Database<BEI128, CboRoaringBitmapCodec>,
+    /// Store the tasks that must be finished before a task uid is processed
+    pub(crate) dependencies: Database<BEU32, RoaringBitmapCodec>,
+    /// Store the tasks associated to a task group.
+    pub(crate) group_tasks: Database<Str, RoaringBitmapCodec>,
+    /// Store the progress of the task groups being applied, to recover them after a crash.
+    pub(crate) task_group_journal: Database<Str, SerdeJson<TaskGroupJournal>>,
+    /// All the tasks ids grouped by their priority.
+    pub(crate) priority: Database<SerdeBincode<TaskPriority>, RoaringBitmapCodec>,
+    /// The cursor of the round-robin scheduling policy.
+    pub(crate) round_robin: Database<Str, Str>,
 }
 
 impl TaskQueue {
@@ -61,6 +98,12 @@ impl TaskQueue {
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
+            run_at: self.run_at,
+            dependencies: self.dependencies,
+            group_tasks: self.group_tasks,
+            task_group_journal: self.task_group_journal,
+            priority: self.priority,
+            round_robin: self.round_robin,
         }
     }
 
@@ -78,6 +121,12 @@ impl TaskQueue {
             enqueued_at: env.create_database(wtxn, Some(db_name::ENQUEUED_AT))?,
             started_at: env.create_database(wtxn, Some(db_name::STARTED_AT))?,
             finished_at: env.create_database(wtxn, Some(db_name::FINISHED_AT))?,
+            run_at: env.create_database(wtxn, Some(db_name::RUN_AT))?,
+            dependencies: env.create_database(wtxn, Some(db_name::DEPENDENCIES))?,
+            group_tasks: env.create_database(wtxn, Some(db_name::GROUP_TASKS))?,
+            task_group_journal: env.create_database(wtxn, Some(db_name::TASK_GROUP_JOURNAL))?,
+            priority: env.create_database(wtxn, Some(db_name::PRIORITY))?,
+            round_robin: env.create_database(wtxn, Some(db_name::ROUND_ROBIN))?,
         })
     }
 
@@ -132,6 +181,11 @@ impl TaskQueue {
             old_task.enqueued_at, task.enqueued_at,
             "Cannot update a task's enqueued_at time"
         );
+        assert_eq!(old_task.run_at, task.run_at, "Cannot update a task's run_at time");
+        assert_eq!(old_task.run_every, task.run_every, "Cannot update a task's run_every interval");
+        assert_eq!(old_task.depends_on, task.depends_on, "Cannot update a task's dependencies");
+        assert_eq!(old_task.group, task.group, "Cannot update a task's group");
+        assert_eq!(old_task.priority, task.priority, "Cannot update a task's priority");
         if old_task.started_at != task.started_at {
             assert!(
                 reprocessing || old_task.started_at.is_none(),
@@ -235,6 +289,36 @@ impl TaskQueue {
         Ok(())
     }
 
+    pub(crate) fn get_priority(
+        &self,
+        rtxn: &RoTxn,
+        priority: TaskPriority,
+    ) -> Result<RoaringBitmap> {
+        Ok(self.priority.get(rtxn, &priority)?.unwrap_or_default())
+    }
+
+    pub(crate) fn put_priority(
+        &self,
+        wtxn: &mut RwTxn,
+        priority: TaskPriority,
+        bitmap: &RoaringBitmap,
+    ) -> Result<()> {
+        Ok(self.priority.put(wtxn, &priority, bitmap)?)
+    }
+
+    pub(crate) fn update_priority(
+        &self,
+        wtxn: &mut RwTxn,
+        priority: TaskPriority,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.get_priority(wtxn, priority)?;
+        f(&mut tasks);
+        self.put_priority(wtxn, priority, &tasks)?;
+
+        Ok(())
+    }
+
     /// Convert an iterator to a `Vec` of tasks. The tasks MUST exist or a
     /// `CorruptedTaskQueue` error will be thrown.
     pub(crate) fn get_existing_tasks(
@@ -267,10 +351,187 @@ impl TaskQueue {
             bitmap.insert(task.uid);
         })?;
 
+        self.update_priority(wtxn, task.priority, |bitmap| {
+            bitmap.insert(task.uid);
+        })?;
+
         utils::insert_task_datetime(wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
+        if let Some(run_at) = task.run_at {
+            utils::insert_task_datetime(wtxn, self.run_at, run_at, task.uid)?;
+        }
+        if !task.depends_on.is_empty() {
+            let dependencies = RoaringBitmap::from_iter(task.depends_on.iter().copied());
+            self.dependencies.put(wtxn, &task.uid, &dependencies)?;
+        }
+        if let Some(group) = &task.group {
+            self.update_group(wtxn, &group.name, |bitmap| {
+                bitmap.insert(task.uid);
+            })?;
+        }
 
         Ok(())
     }
+
+    /// Returns the whole set of tasks that belongs to this task group.
+    pub(crate) fn group_tasks(&self, rtxn: &RoTxn, group: &str) -> Result<RoaringBitmap> {
+        Ok(self.group_tasks.get(rtxn, group)?.unwrap_or_default())
+    }
+
+    pub(crate) fn update_group(
+        &self,
+        wtxn: &mut RwTxn,
+        group: &str,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.group_tasks(wtxn, group)?;
+        f(&mut tasks);
+        if tasks.is_empty() {
+            self.group_tasks.delete(wtxn, group)?;
+        } else {
+            self.group_tasks.put(wtxn, group, &tasks)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the last batch started by the round-robin scheduling policy.
+    pub(crate) fn round_robin_last_index(&self, rtxn: &RoTxn) -> Result<Option<String>> {
+        Ok(self.round_robin.get(rtxn, round_robin_key::LAST_INDEX)?.map(ToOwned::to_owned))
+    }
+
+    /// Stores the index of the batch the round-robin scheduling policy just started.
+    pub(crate) fn put_round_robin_last_index(&self, wtxn: &mut RwTxn, index: &str) -> Result<()> {
+        Ok(self.round_robin.put(wtxn, round_robin_key::LAST_INDEX, index)?)
+    }
+
+    /// Returns the journal of the group, if the group whose last task is `last_task` started
+    /// being applied.
+    pub(crate) fn task_group_journal(
+        &self,
+        rtxn: &RoTxn,
+        group: &str,
+        last_task: TaskId,
+    ) -> Result<Option<TaskGroupJournal>> {
+        let journal = self.task_group_journal.get(rtxn, group)?;
+        Ok(journal.filter(|journal| journal.last_task == last_task))
+    }
+
+    /// Returns the name and the enqueued tasks of the groups that were interrupted while
+    /// their indexes were being committed.
+    pub(crate) fn interrupted_groups(&self, rtxn: &RoTxn) -> Result<Vec<(String, RoaringBitmap)>> {
+        let mut groups = Vec::new();
+        for (group, tasks, _) in self.enqueued_groups(rtxn)? {
+            let Some(last_task) = tasks.max() else { continue };
+            let journal = self.task_group_journal(rtxn, &group, last_task)?;
+            if journal.is_some_and(|journal| journal.committing.is_some()) {
+                groups.push((group, tasks));
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Returns the enqueued tasks of the group and whether the group is committed.
+    ///
+    /// A group is committed once its last task is registered, only the enqueued tasks
+    /// are considered since a group name can be reused once its tasks are processed.
+    pub(crate) fn enqueued_group(
+        &self,
+        rtxn: &RoTxn,
+        group: &str,
+    ) -> Result<(RoaringBitmap, bool)> {
+        let tasks = self.group_tasks(rtxn, group)? & self.get_status(rtxn, Status::Enqueued)?;
+        let committed = self.contains_group_commit(rtxn, &tasks)?;
+        Ok((tasks, committed))
+    }
+
+    /// Returns the name, the enqueued tasks and whether it is committed of every group
+    /// that still contains enqueued tasks.
+    pub(crate) fn enqueued_groups(
+        &self,
+        rtxn: &RoTxn,
+    ) -> Result<Vec<(String, RoaringBitmap, bool)>> {
+        let enqueued = self.get_status(rtxn, Status::Enqueued)?;
+        let mut groups = Vec::new();
+        for entry in self.group_tasks.iter(rtxn)? {
+            let (group, tasks) = entry?;
+            let tasks = tasks & &enqueued;
+            if tasks.is_empty() {
+                continue;
+            }
+            let committed = self.contains_group_commit(rtxn, &tasks)?;
+            groups.push((group.to_string(), tasks, committed));
+        }
+        Ok(groups)
+    }
+
+    fn contains_group_commit(&self, rtxn: &RoTxn, tasks: &RoaringBitmap) -> Result<bool> {
+        for task in self.get_existing_tasks(rtxn, tasks)? {
+            if task.group.is_some_and(|group| group.commit) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns the enqueued tasks that must wait for some of their dependencies to finish,
+    /// and the enqueued tasks that have a dependency that did not succeed.
+    pub(crate) fn unsatisfied_dependencies(
+        &self,
+        rtxn: &RoTxn,
+    ) -> Result<(RoaringBitmap, RoaringBitmap)> {
+        let enqueued = self.get_status(rtxn, Status::Enqueued)?;
+        let unsuccessful =
+            self.get_status(rtxn, Status::Failed)? | self.get_status(rtxn, Status::Canceled)?;
+
+        let mut blocked = RoaringBitmap::new();
+        let mut failed = RoaringBitmap::new();
+        for entry in self.dependencies.iter(rtxn)? {
+            let (task_id, dependencies) = entry?;
+            if !enqueued.contains(task_id) {
+                continue;
+            }
+            if !dependencies.is_disjoint(&unsuccessful) {
+                failed.insert(task_id);
+            } else if !dependencies.is_disjoint(&enqueued) {
+                blocked.insert(task_id);
+            }
+        }
+        Ok((blocked, failed))
+    }
+
+    /// Returns the enqueued tasks that must not be processed before a date later than `now`.
+    pub(crate) fn scheduled_tasks(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<RoaringBitmap> {
+        let mut scheduled = RoaringBitmap::new();
+        let range = (Bound::Excluded(now.unix_timestamp_nanos()), Bound::Unbounded);
+        for entry in self.run_at.range(rtxn, &range)? {
+            let (_, task_ids) = entry?;
+            scheduled |= task_ids;
+        }
+        Ok(scheduled & self.get_status(rtxn, Status::Enqueued)?)
+    }
+
+    /// Returns the earliest date later than `now` at which a scheduled task can be processed.
+    pub(crate) fn next_run_at(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<Option<OffsetDateTime>> {
+        let enqueued = self.get_status(rtxn, Status::Enqueued)?;
+        let range = (Bound::Excluded(now.unix_timestamp_nanos()), Bound::Unbounded);
+        for entry in self.run_at.range(rtxn, &range)? {
+            let (timestamp, task_ids) = entry?;
+            if !task_ids.is_disjoint(&enqueued) {
+                let run_at = OffsetDateTime::from_unix_timestamp_nanos(timestamp)
+                    .map_err(|_| Error::CorruptedTaskQueue)?;
+                return Ok(Some(run_at));
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl Queue {
@@ -281,8 +542,8 @@ impl Queue {
         query: &Query,
         processing_tasks: &ProcessingTasks,
     ) -> Result<RoaringBitmap> {
-        let ProcessingTasks { batch: processing_batch, processing: processing_tasks, progress: _ } =
-            processing_tasks;
+        let processing = processing_tasks;
+        let processing_tasks = &processing.processing;
         let Query {
             limit,
             from,
@@ -299,6 +560,8 @@ impl Queue {
             after_started_at,
             before_finished_at,
             after_finished_at,
+            before_run_at,
+            after_run_at,
         } = query;
 
         let mut tasks = self.tasks.all_task_ids(rtxn)?;
@@ -315,8 +578,8 @@ impl Queue {
         if let Some(batch_uids) = batch_uids {
             let mut batch_tasks = RoaringBitmap::new();
             for batch_uid in batch_uids {
-                if processing_batch.as_ref().is_some_and(|batch| batch.uid == *batch_uid) {
-                    batch_tasks |= &**processing_tasks;
+                if let Some((_, tasks, _)) = processing.get_batch(*batch_uid) {
+                    batch_tasks |= tasks;
                 } else {
                     batch_tasks |= self.tasks_in_batch(rtxn, *batch_uid)?;
                 }
@@ -388,20 +651,19 @@ impl Queue {
                 (&tasks - &**processing_tasks, &tasks & &**processing_tasks);
 
             // special case for Processing tasks
-            // A closure that clears the filtered_processing_tasks if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_tasks whose batch started_at date falls outside the given bounds
             let mut clear_filtered_processing_tasks =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &processing_batch
-                            .as_ref()
-                            .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at)
-                            .unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_tasks.clear();
+                    for (batch, tasks, _) in processing.batches() {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_tasks -= tasks;
+                        }
                     }
                 };
             match (after_started_at, before_started_at) {
@@ -444,6 +706,14 @@ impl Queue {
             *before_finished_at,
         )?;
 
+        keep_ids_within_datetimes(
+            rtxn,
+            &mut tasks,
+            self.tasks.run_at,
+            *after_run_at,
+            *before_run_at,
+        )?;
+
         if let Some(limit) = limit {
             tasks = if query.reverse.unwrap_or_default() {
                 tasks.into_iter().take(*limit as usize).collect()
@@ -512,25 +782,24 @@ impl Queue {
             .tasks
             .get_existing_tasks(rtxn, tasks.take(query.limit.unwrap_or(u32::MAX) as usize))?;
 
-        let ProcessingTasks { batch, processing, progress: _ } = processing_tasks;
-
         let ret = tasks.into_iter();
-        if processing.is_empty() || batch.is_none() {
+        if processing_tasks.processing.is_empty() {
             Ok((ret.collect(), total))
         } else {
-            // Safe because we ensured there was a batch in the previous branch
-            let batch = batch.as_ref().unwrap();
             Ok((
                 ret.map(|task| {
-                    if processing.contains(task.uid) {
-                        Task {
+                    let batch = processing_tasks
+                        .batches()
+                        .find(|(_, tasks, _)| tasks.contains(task.uid))
+                        .map(|(batch, _, _)| batch);
+                    match batch {
+                        Some(batch) => Task {
                             status: Status::Processing,
                             batch_uid: Some(batch.uid),
                             started_at: Some(batch.started_at),
                             ..task
-                        }
-                    } else {
-                        task
+                        },
+                        None => task,
                     }
                 })
                 .collect(),
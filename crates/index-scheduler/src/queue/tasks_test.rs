@@ -7,7 +7,7 @@ use time::{Duration, OffsetDateTime};
 use crate::insta_snapshot::{snapshot_bitmap, snapshot_index_scheduler};
 use crate::test_utils::Breakpoint::*;
 use crate::test_utils::{index_creation_task, FailureLocation};
-use crate::{IndexScheduler, Query};
+use crate::{IndexScheduler, Query, TaskOptions};
 
 #[test]
 fn query_tasks_from_and_limit() {
@@ -439,3 +439,39 @@ fn query_tasks_canceled_by() {
     // Return only 1 because the user is not authorized to see task 2
     snapshot!(snapshot_bitmap(&tasks), @"[1,]");
 }
+
+#[test]
+fn query_tasks_run_at() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    let now = OffsetDateTime::now_utc();
+    let kind = index_creation_task("catto", "mouse");
+    let _task = index_scheduler.register(kind, None, false).unwrap();
+    let kind = index_creation_task("doggo", "sheep");
+    let options = TaskOptions { run_at: Some(now + Duration::hours(1)), ..Default::default() };
+    let _task = index_scheduler.register_with_options(kind, None, options, false).unwrap();
+    let kind = index_creation_task("whalo", "fish");
+    let options = TaskOptions { run_at: Some(now + Duration::days(2)), ..Default::default() };
+    let _task = index_scheduler.register_with_options(kind, None, options, false).unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    let query = Query { after_run_at: Some(now), ..Default::default() };
+    let (tasks, _) = index_scheduler
+        .get_task_ids_from_authorized_indexes(&query, &AuthFilter::default())
+        .unwrap();
+    // the tasks without a run at date are never matched
+    snapshot!(snapshot_bitmap(&tasks), @"[1,2,]");
+
+    let query = Query { before_run_at: Some(now + Duration::days(1)), ..Default::default() };
+    let (tasks, _) = index_scheduler
+        .get_task_ids_from_authorized_indexes(&query, &AuthFilter::default())
+        .unwrap();
+    snapshot!(snapshot_bitmap(&tasks), @"[1,]");
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let scheduled = index_scheduler.queue.tasks.scheduled_tasks(&rtxn, now).unwrap();
+    snapshot!(snapshot_bitmap(&scheduled), @"[1,2,]");
+    let scheduled =
+        index_scheduler.queue.tasks.scheduled_tasks(&rtxn, now + Duration::days(1)).unwrap();
+    snapshot!(snapshot_bitmap(&scheduled), @"[2,]");
+}
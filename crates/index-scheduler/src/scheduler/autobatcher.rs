@@ -120,6 +120,12 @@ pub enum BatchKind {
 }
 
 impl BatchKind {
+    /// Whether the batch created from this kind only modifies its own index and can be
+    /// processed at the same time as the batches of the other indexes.
+    pub fn can_be_processed_concurrently(&self) -> bool {
+        !matches!(self, BatchKind::IndexDeletion { .. } | BatchKind::IndexSwap { .. })
+    }
+
     #[rustfmt::skip]
     fn allow_index_creation(&self) -> Option<bool> {
         match self {
@@ -1,15 +1,21 @@
+use std::collections::BTreeSet;
 use std::fmt;
+use std::ops::Bound;
 
+use meilisearch_types::batches::BatchScheduling;
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::{Settings, Unchecked};
-use meilisearch_types::tasks::{BatchStopReason, Kind, KindWithContent, Status, Task};
-use roaring::RoaringBitmap;
+use meilisearch_types::tasks::{
+    BatchStopReason, Kind, KindWithContent, SchedulingPolicy, Status, Task, TaskPriority,
+};
+use roaring::{MultiOps, RoaringBitmap};
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use super::autobatcher::{self, BatchKind};
 use crate::utils::ProcessingBatch;
-use crate::{Error, IndexScheduler, Result};
+use crate::{Error, IndexScheduler, Result, TaskId};
 
 /// Represents a combination of tasks that can all be processed at the same time.
 ///
@@ -50,6 +56,15 @@ pub(crate) enum Batch {
     UpgradeDatabase {
         tasks: Vec<Task>,
     },
+    /// The tasks that cannot be processed because one of their dependencies did not succeed.
+    UnsatisfiedDependencies(Vec<Task>),
+    /// The operations of a committed task group, they're applied on their respective indexes
+    /// and committed only if all of them succeed.
+    TaskGroup {
+        name: String,
+        operations: Vec<IndexOperation>,
+        indexes_to_create: BTreeSet<String>,
+    },
 }
 
 #[derive(Debug)]
@@ -109,25 +124,12 @@ impl Batch {
             Batch::SnapshotCreation(tasks)
             | Batch::TaskDeletions(tasks)
             | Batch::UpgradeDatabase { tasks }
+            | Batch::UnsatisfiedDependencies(tasks)
             | Batch::IndexDeletion { tasks, .. } => {
                 RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
             }
-            Batch::IndexOperation { op, .. } => match op {
-                IndexOperation::DocumentOperation { tasks, .. }
-                | IndexOperation::Settings { tasks, .. }
-                | IndexOperation::DocumentDeletion { tasks, .. }
-                | IndexOperation::DocumentClear { tasks, .. } => {
-                    RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
-                }
-                IndexOperation::DocumentEdition { task, .. } => {
-                    RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
-                }
-                IndexOperation::DocumentClearAndSetting {
-                    cleared_tasks: tasks,
-                    settings_tasks: other,
-                    ..
-                } => RoaringBitmap::from_iter(tasks.iter().chain(other).map(|task| task.uid)),
-            },
+            Batch::IndexOperation { op, .. } => op.ids(),
+            Batch::TaskGroup { operations, .. } => operations.iter().map(|op| op.ids()).union(),
             Batch::IndexSwap { task } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
@@ -143,6 +145,8 @@ impl Batch {
             | SnapshotCreation(_)
             | Dump(_)
             | UpgradeDatabase { .. }
+            | UnsatisfiedDependencies(_)
+            | TaskGroup { .. }
             | IndexSwap { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
@@ -150,6 +154,15 @@ impl Batch {
             | IndexDeletion { index_uid, .. } => Some(index_uid),
         }
     }
+
+    /// Whether the batch only modifies its own index and can be processed
+    /// at the same time as the batches of the other indexes.
+    pub fn can_be_processed_concurrently(&self) -> bool {
+        matches!(
+            self,
+            Batch::IndexOperation { .. } | Batch::IndexCreation { .. } | Batch::IndexUpdate { .. }
+        )
+    }
 }
 
 impl fmt::Display for Batch {
@@ -168,6 +181,8 @@ impl fmt::Display for Batch {
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
             Batch::UpgradeDatabase { .. } => f.write_str("UpgradeDatabase")?,
+            Batch::UnsatisfiedDependencies(_) => f.write_str("UnsatisfiedDependencies")?,
+            Batch::TaskGroup { name, .. } => write!(f, "TaskGroup {name:?}")?,
         };
         match index_uid {
             Some(name) => f.write_fmt(format_args!(" on {name:?} from tasks: {tasks:?}")),
@@ -177,6 +192,26 @@ impl fmt::Display for Batch {
 }
 
 impl IndexOperation {
+    /// Return the task ids associated with this operation.
+    pub fn ids(&self) -> RoaringBitmap {
+        match self {
+            IndexOperation::DocumentOperation { tasks, .. }
+            | IndexOperation::Settings { tasks, .. }
+            | IndexOperation::DocumentDeletion { tasks, .. }
+            | IndexOperation::DocumentClear { tasks, .. } => {
+                RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
+            }
+            IndexOperation::DocumentEdition { task, .. } => {
+                RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
+            }
+            IndexOperation::DocumentClearAndSetting {
+                cleared_tasks: tasks,
+                settings_tasks: other,
+                ..
+            } => RoaringBitmap::from_iter(tasks.iter().chain(other).map(|task| task.uid)),
+        }
+    }
+
     pub fn index_uid(&self) -> &str {
         match self {
             IndexOperation::DocumentOperation { index_uid, .. }
@@ -422,24 +457,201 @@ impl IndexScheduler {
         }
     }
 
+    /// Convert the enqueued tasks of a committed group into a [`Batch::TaskGroup`].
+    ///
+    /// The tasks are autobatched index by index, in the order of their registration,
+    /// until all of them are part of an operation.
+    fn create_next_batch_group(
+        &self,
+        rtxn: &RoTxn,
+        name: String,
+        tasks: &RoaringBitmap,
+        current_batch: &mut ProcessingBatch,
+    ) -> Result<Batch> {
+        let mut tasks_per_index: Vec<(String, Vec<(TaskId, KindWithContent)>)> = Vec::new();
+        for task in self.queue.tasks.get_existing_tasks(rtxn, tasks)? {
+            // the grouped tasks are always associated with exactly one index
+            let index_uid = task.index_uid().ok_or(Error::CorruptedTaskQueue)?.to_string();
+            match tasks_per_index.iter_mut().find(|(uid, _)| *uid == index_uid) {
+                Some((_, tasks)) => tasks.push((task.uid, task.kind)),
+                None => tasks_per_index.push((index_uid, vec![(task.uid, task.kind)])),
+            }
+        }
+
+        let mut operations = Vec::new();
+        let mut indexes_to_create = BTreeSet::new();
+        for (index_uid, mut tasks) in tasks_per_index {
+            let mut index_exists = self.index_mapper.exists(rtxn, &index_uid)?;
+            let mut primary_key = None;
+            if index_exists {
+                let index = self.index_mapper.index(rtxn, &index_uid)?;
+                let rtxn = index.read_txn()?;
+                primary_key = index.primary_key(&rtxn)?.map(|pk| pk.to_string());
+            }
+
+            while let Some((batchkind, create_index, _)) =
+                autobatcher::autobatch(tasks.clone(), index_exists, primary_key.as_deref())
+            {
+                let batch = self.create_next_batch_index(
+                    rtxn,
+                    index_uid.clone(),
+                    batchkind,
+                    current_batch,
+                    create_index,
+                )?;
+                let Some(Batch::IndexOperation { op, must_create_index }) = batch else {
+                    // only the tasks that can be grouped are registered in a group
+                    return Err(Error::CorruptedTaskQueue);
+                };
+                if must_create_index && !index_exists {
+                    indexes_to_create.insert(index_uid.clone());
+                }
+                index_exists |= must_create_index;
+
+                let ids = op.ids();
+                tasks.retain(|(id, _)| !ids.contains(*id));
+                operations.push(op);
+            }
+        }
+
+        current_batch.reason(BatchStopReason::TaskGroup { name: name.clone() });
+        Ok(Batch::TaskGroup { name, operations, indexes_to_create })
+    }
+
+    /// Returns the first task of the next batch and records why it was picked in the batch stats.
+    ///
+    /// The index of the batch is picked among the indexes that contain enqueued tasks of the
+    /// highest priority, according to the [`SchedulingPolicy`]. Since the tasks of an index must
+    /// be processed in their registration order, the batch always starts with the oldest enqueued
+    /// task of this index, whatever its priority.
+    fn next_task_to_batch(
+        &self,
+        rtxn: &RoTxn,
+        enqueued: &RoaringBitmap,
+        current_batch: &mut ProcessingBatch,
+    ) -> Result<Option<TaskId>> {
+        let high = self.queue.tasks.get_priority(rtxn, TaskPriority::High)? & enqueued;
+        let low = self.queue.tasks.get_priority(rtxn, TaskPriority::Low)? & enqueued;
+        // The tasks registered before the priorities were introduced are not part of the
+        // priority database, every task that is neither high nor low is considered normal.
+        let (priority, candidates) = if !high.is_empty() {
+            (TaskPriority::High, high)
+        } else if enqueued.len() > low.len() {
+            (TaskPriority::Normal, enqueued - low)
+        } else {
+            (TaskPriority::Low, low)
+        };
+
+        let policy = self.scheduler.scheduling_policy;
+        let candidate = match policy {
+            SchedulingPolicy::Fifo => candidates.min(),
+            SchedulingPolicy::RoundRobin => self.next_round_robin_task(rtxn, &candidates)?,
+        };
+        let candidate = if let Some(candidate) = candidate { candidate } else { return Ok(None) };
+
+        let task = self.queue.tasks.get_task(rtxn, candidate)?.ok_or(Error::CorruptedTaskQueue)?;
+        let task_id = match task.indexes().first() {
+            Some(index) => {
+                (self.queue.tasks.index_tasks(rtxn, index)? & enqueued).min().unwrap_or(candidate)
+            }
+            None => candidate,
+        };
+
+        // We only explain the choice when the batch may not start with the oldest enqueued task.
+        if policy != SchedulingPolicy::Fifo || priority != TaskPriority::Normal {
+            current_batch.stats.scheduling = Some(BatchScheduling {
+                priority,
+                policy,
+                skipped_tasks: enqueued.rank(task_id).saturating_sub(1),
+            });
+        }
+
+        Ok(Some(task_id))
+    }
+
+    /// Returns the oldest candidate task of the first index, in the lexicographic order, that
+    /// follows the index of the previous batch and contains candidate tasks.
+    ///
+    /// The index of the previous batch is the index of the last started batch associated
+    /// with a single index, it is written in the task queue when the batch starts.
+    fn next_round_robin_task(
+        &self,
+        rtxn: &RoTxn,
+        candidates: &RoaringBitmap,
+    ) -> Result<Option<TaskId>> {
+        let previous = self.queue.tasks.round_robin_last_index(rtxn)?;
+
+        let previous = previous.as_deref();
+        let after = (previous.map_or(Bound::Unbounded, Bound::Excluded), Bound::Unbounded);
+        let until = (Bound::Unbounded, previous.map_or(Bound::Unbounded, Bound::Included));
+        for range in [after, until] {
+            for entry in self.queue.tasks.index_tasks.range(rtxn, &range)? {
+                let (_, tasks) = entry?;
+                if let Some(task_id) = (tasks & candidates).min() {
+                    return Ok(Some(task_id));
+                }
+            }
+        }
+
+        // Only the candidates that are not associated with any index are left
+        Ok(candidates.min())
+    }
+
     /// Create the next batch to be processed;
     /// 1. We get the *last* task to cancel.
     /// 2. We get the *next* task to delete.
     /// 3. We get the *next* snapshot to process.
     /// 4. We get the *next* dump to process.
-    /// 5. We get the *next* tasks to process for a specific index.
+    /// 5. We get the tasks whose dependencies did not succeed.
+    /// 6. We get the *next* committed task group, if it was registered before the next task.
+    /// 7. We get the *next* tasks to process for the index with the highest priority tasks.
+    ///
+    /// Only the database upgrade comes before a task group interrupted while committing its
+    /// indexes, which is resumed before anything else.
+    ///
+    /// When `concurrent_only` is set, other batches are running and only a batch that can be
+    /// processed along with them is created. If the next batch must be processed alone,
+    /// nothing is created and it waits for the running batches to finish.
     #[tracing::instrument(level = "trace", skip(self, rtxn), target = "indexing::scheduler")]
     pub(crate) fn create_next_batch(
         &self,
         rtxn: &RoTxn,
+        concurrent_only: bool,
     ) -> Result<Option<(Batch, ProcessingBatch)>> {
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::InsideCreateBatch)?;
 
-        let batch_id = self.queue.batches.next_batch_id(rtxn)?;
+        // The batches processed concurrently are not written yet, their ids must not be reused.
+        let processing = self.processing_tasks.read().unwrap().clone();
+        let mut batch_id = self.queue.batches.next_batch_id(rtxn)?;
+        if let Some(last) = processing.concurrent.iter().map(|c| c.batch.uid).max() {
+            batch_id = batch_id.max(last + 1);
+        }
         let mut current_batch = ProcessingBatch::new(batch_id);
 
-        let enqueued = &self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
+        // The tasks of the indexes that are being processed on other threads must wait for
+        // their batch to finish, they're still enqueued on disk.
+        let mut busy = RoaringBitmap::new();
+        for concurrent in processing.running() {
+            busy |= &*concurrent.tasks;
+            for index in &concurrent.batch.indexes {
+                busy |= self.queue.tasks.index_tasks(rtxn, index)?;
+            }
+        }
+
+        // The scheduled tasks stay enqueued until their run at date is reached, the tasks
+        // that depend on other tasks until their dependencies are finished, and the tasks
+        // of a group are only processed along with the rest of their group.
+        let scheduled = self.queue.tasks.scheduled_tasks(rtxn, OffsetDateTime::now_utc())?;
+        let (blocked, mut unsatisfied) = self.queue.tasks.unsatisfied_dependencies(rtxn)?;
+        let groups = self.queue.tasks.enqueued_groups(rtxn)?;
+        let grouped = groups.iter().map(|(_, tasks, _)| tasks).union();
+        let enqueued = &(self.queue.tasks.get_status(rtxn, Status::Enqueued)?
+            - &scheduled
+            - &blocked
+            - &unsatisfied
+            - grouped
+            - &busy);
         let count_total_enqueued = enqueued.len();
         let failed = &self.queue.tasks.get_status(rtxn, Status::Failed)?;
 
@@ -447,6 +659,9 @@ impl IndexScheduler {
         // There shouldn't be multiple upgrade tasks but just in case we're going to batch all of them at the same time
         let upgrade = self.queue.tasks.get_kind(rtxn, Kind::UpgradeDatabase)? & (enqueued | failed);
         if !upgrade.is_empty() {
+            if concurrent_only {
+                return Ok(None);
+            }
             let mut tasks = self.queue.tasks.get_existing_tasks(rtxn, upgrade)?;
             // In the case of an upgrade database batch, we want to find back the original batch that tried processing it
             // and re-use its id
@@ -459,9 +674,22 @@ impl IndexScheduler {
             return Ok(Some((Batch::UpgradeDatabase { tasks }, current_batch)));
         }
 
+        // Then, a task group interrupted while committing its indexes must be applied to the
+        // remaining ones before any other task can modify them.
+        if let Some((name, tasks)) = self.queue.tasks.interrupted_groups(rtxn)?.into_iter().next() {
+            if concurrent_only {
+                return Ok(None);
+            }
+            let batch = self.create_next_batch_group(rtxn, name, &tasks, &mut current_batch)?;
+            return Ok(Some((batch, current_batch)));
+        }
+
         // 1. we get the last task to cancel.
         let to_cancel = self.queue.tasks.get_kind(rtxn, Kind::TaskCancelation)? & enqueued;
         if let Some(task_id) = to_cancel.max() {
+            if concurrent_only {
+                return Ok(None);
+            }
             let mut task =
                 self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
             current_batch.processing(Some(&mut task));
@@ -475,6 +703,9 @@ impl IndexScheduler {
         // 2. we get the next task to delete
         let to_delete = self.queue.tasks.get_kind(rtxn, Kind::TaskDeletion)? & enqueued;
         if !to_delete.is_empty() {
+            if concurrent_only {
+                return Ok(None);
+            }
             let mut tasks = self.queue.tasks.get_existing_tasks(rtxn, to_delete)?;
             current_batch.processing(&mut tasks);
             current_batch
@@ -485,6 +716,9 @@ impl IndexScheduler {
         // 3. we batch the snapshot.
         let to_snapshot = self.queue.tasks.get_kind(rtxn, Kind::SnapshotCreation)? & enqueued;
         if !to_snapshot.is_empty() {
+            if concurrent_only {
+                return Ok(None);
+            }
             let mut tasks = self.queue.tasks.get_existing_tasks(rtxn, to_snapshot)?;
             current_batch.processing(&mut tasks);
             current_batch
@@ -495,6 +729,9 @@ impl IndexScheduler {
         // 4. we batch the dumps.
         let to_dump = self.queue.tasks.get_kind(rtxn, Kind::DumpCreation)? & enqueued;
         if let Some(to_dump) = to_dump.min() {
+            if concurrent_only {
+                return Ok(None);
+            }
             let mut task =
                 self.queue.tasks.get_task(rtxn, to_dump)?.ok_or(Error::CorruptedTaskQueue)?;
             current_batch.processing(Some(&mut task));
@@ -505,8 +742,55 @@ impl IndexScheduler {
             return Ok(Some((Batch::Dump(task), current_batch)));
         }
 
-        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
-        let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
+        // 5. we fail the tasks whose dependencies did not succeed, along with the rest of
+        // their group if they're part of a committed one. The open groups may still receive
+        // new tasks and are only failed once committed.
+        for (_, tasks, committed) in &groups {
+            if tasks.is_disjoint(&unsatisfied) {
+                continue;
+            }
+            if *committed {
+                unsatisfied |= tasks;
+            } else {
+                unsatisfied -= tasks;
+            }
+        }
+        if !unsatisfied.is_empty() {
+            if concurrent_only {
+                return Ok(None);
+            }
+            let mut tasks = self.queue.tasks.get_existing_tasks(rtxn, &unsatisfied)?;
+            current_batch.processing(&mut tasks);
+            current_batch.reason(BatchStopReason::UnsatisfiedDependencies);
+            return Ok(Some((Batch::UnsatisfiedDependencies(tasks), current_batch)));
+        }
+
+        // 6. we batch the next committed group whose tasks are all ready to be processed.
+        let next_group = groups
+            .iter()
+            .filter(|(_, tasks, committed)| {
+                *committed && tasks.is_disjoint(&scheduled) && tasks.is_disjoint(&blocked)
+            })
+            .min_by_key(|(_, tasks, _)| tasks.min());
+        if let Some((name, tasks, _)) = next_group {
+            if enqueued.min().is_none_or(|next| tasks.min() < Some(next)) {
+                if concurrent_only {
+                    return Ok(None);
+                }
+                let batch =
+                    self.create_next_batch_group(rtxn, name.clone(), tasks, &mut current_batch)?;
+                return Ok(Some((batch, current_batch)));
+            }
+        }
+
+        // 7. We make a batch from the unprioritised tasks. Start by picking the next task
+        // according to the priorities and the scheduling policy.
+        let task_id =
+            if let Some(task_id) = self.next_task_to_batch(rtxn, enqueued, &mut current_batch)? {
+                task_id
+            } else {
+                return Ok(None);
+            };
         let mut task =
             self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
 
@@ -518,6 +802,9 @@ impl IndexScheduler {
             index_name
         } else {
             assert!(matches!(&task.kind, KindWithContent::IndexSwap { swaps } if swaps.is_empty()));
+            if concurrent_only {
+                return Ok(None);
+            }
             current_batch.processing(Some(&mut task));
             current_batch.reason(BatchStopReason::TaskCannotBeBatched {
                 kind: Kind::IndexSwap,
@@ -583,6 +870,9 @@ impl IndexScheduler {
         if let Some((batchkind, create_index, autobatch_stop_reason)) =
             autobatcher::autobatch(enqueued, index_already_exists, primary_key.as_deref())
         {
+            if concurrent_only && !batchkind.can_be_processed_concurrently() {
+                return Ok(None);
+            }
             current_batch.reason(autobatch_stop_reason.unwrap_or(stop_reason));
             return Ok(self
                 .create_next_batch_index(
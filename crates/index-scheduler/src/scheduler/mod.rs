@@ -6,6 +6,7 @@ mod process_batch;
 mod process_dump_creation;
 mod process_index_operation;
 mod process_snapshot_creation;
+mod process_task_group;
 mod process_upgrade;
 #[cfg(test)]
 mod test;
@@ -16,23 +17,29 @@ mod test_embedders;
 #[cfg(test)]
 mod test_failure;
 
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 
 use convert_case::{Case, Casing as _};
+use create_batch::Batch;
+use crossbeam_channel::{Receiver, Sender};
 use meilisearch_types::error::ResponseError;
-use meilisearch_types::heed::{Env, WithoutTls};
+use meilisearch_types::heed::{Env, RwTxn, WithoutTls};
 use meilisearch_types::milli;
-use meilisearch_types::tasks::Status;
+use meilisearch_types::milli::progress::Progress;
+use meilisearch_types::tasks::{KindWithContent, SchedulingPolicy, Status, Task};
 use process_batch::ProcessBatchInfo;
 use rayon::current_num_threads;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
+use time::OffsetDateTime;
 
 use crate::processing::{AtomicTaskStep, BatchProgress};
-use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, TickOutcome};
+use crate::utils::ProcessingBatch;
+use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, TaskOptions, TickOutcome};
 
 #[derive(Default, Clone, Debug)]
 pub struct MustStopProcessing(Arc<AtomicBool>);
@@ -67,6 +74,16 @@ pub struct Scheduler {
     /// The maximum size, in bytes, of tasks in a batch.
     pub(crate) batched_tasks_size_limit: u64,
 
+    /// How the index of the next batch is picked among the indexes with the highest priority.
+    pub(crate) scheduling_policy: SchedulingPolicy,
+
+    /// The maximum number of batches of different indexes processed at the same time.
+    pub(crate) max_concurrent_batches: usize,
+
+    /// The batches processed on other threads are sent back through this channel once
+    /// finished, so that their tasks are written to disk by the scheduler thread.
+    pub(crate) finished_batches: (Sender<FinishedBatch>, Receiver<FinishedBatch>),
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
@@ -93,6 +110,9 @@ impl Scheduler {
             autobatching_enabled: self.autobatching_enabled,
             max_number_of_batched_tasks: self.max_number_of_batched_tasks,
             batched_tasks_size_limit: self.batched_tasks_size_limit,
+            scheduling_policy: self.scheduling_policy,
+            max_concurrent_batches: self.max_concurrent_batches,
+            finished_batches: self.finished_batches.clone(),
             dumps_path: self.dumps_path.clone(),
             snapshots_path: self.snapshots_path.clone(),
             auth_env: self.auth_env.clone(),
@@ -109,6 +129,9 @@ impl Scheduler {
             autobatching_enabled: options.autobatching_enabled,
             max_number_of_batched_tasks: options.max_number_of_batched_tasks,
             batched_tasks_size_limit: options.batched_tasks_size_limit,
+            scheduling_policy: options.scheduling_policy,
+            max_concurrent_batches: options.indexer_config.max_concurrent_batches.max(1),
+            finished_batches: crossbeam_channel::unbounded(),
             dumps_path: options.dumps_path.clone(),
             snapshots_path: options.snapshots_path.clone(),
             auth_env,
@@ -118,11 +141,53 @@ impl Scheduler {
     }
 }
 
+/// A batch processed on another thread, sent back to the scheduler thread once finished.
+pub(crate) struct FinishedBatch {
+    res: Result<(Vec<Task>, ProcessBatchInfo)>,
+    processing_batch: ProcessingBatch,
+    progress: Progress,
+    ids: RoaringBitmap,
+    index_uid: Option<String>,
+}
+
+/// Logs what a failed batch was doing and converts its panic, if any, into an error.
+fn batch_result(
+    res: std::thread::Result<Result<(Vec<Task>, ProcessBatchInfo)>>,
+    progress: &Progress,
+) -> Result<(Vec<Task>, ProcessBatchInfo)> {
+    match res {
+        Ok(ret) => {
+            if ret.is_err() {
+                if let Ok(progress_view) = serde_json::to_string(&progress.as_progress_view()) {
+                    tracing::warn!("Batch failed while doing: {progress_view}")
+                }
+            }
+            ret
+        }
+        Err(panic) => {
+            if let Ok(progress_view) = serde_json::to_string(&progress.as_progress_view()) {
+                tracing::warn!("Batch failed while doing: {progress_view}")
+            }
+            let msg = match panic.downcast_ref::<&'static str>() {
+                Some(s) => *s,
+                None => match panic.downcast_ref::<String>() {
+                    Some(s) => &s[..],
+                    None => "Box<dyn Any>",
+                },
+            };
+            Err(Error::ProcessBatchPanicked(msg.to_string()))
+        }
+    }
+}
+
 impl IndexScheduler {
     /// Perform one iteration of the run loop.
     ///
-    /// 1. See if we need to cleanup the task queue
-    /// 2. Find the next batch of tasks to be processed.
+    /// 1. Write to disk the tasks of the batches processed concurrently that are finished,
+    ///    and see if we need to cleanup the task queue
+    /// 2. Find the next batch of tasks to be processed. When several batches can be processed
+    ///    at the same time, the batches of different indexes are processed on their own thread
+    ///    and the next tick starts right away.
     /// 3. Update the information of these tasks following the start of their processing.
     /// 4. Update the in-memory list of processed tasks accordingly.
     /// 5. Process the batch:
@@ -139,24 +204,91 @@ impl IndexScheduler {
             self.breakpoint(crate::test_utils::Breakpoint::Start);
         }
 
+        // The tasks of the batches processed on other threads are only written by the scheduler thread.
+        // All the finished batches are written before returning, whatever happened to one of them.
+        let mut finished_tasks = 0;
+        let mut stop_processing_forever = false;
+        let mut error = None;
+        while let Ok(finished) = self.scheduler.finished_batches.1.try_recv() {
+            let FinishedBatch { res, processing_batch, progress, ids, index_uid } = finished;
+            let batch_id = processing_batch.uid;
+            match self.finish_batch(res, processing_batch, progress, ids, index_uid) {
+                Ok(TickOutcome::TickAgain(processed)) => finished_tasks += processed,
+                Ok(TickOutcome::StopProcessingForever) => stop_processing_forever = true,
+                Ok(TickOutcome::WaitForSignal | TickOutcome::WaitForSignalOrTimeout(_)) => (),
+                Err(e) => {
+                    // The batch is not processing anymore and its tasks can be batched again
+                    self.processing_tasks.write().unwrap().stop_concurrent_processing(batch_id);
+                    if let Some(previous) = error.replace(e) {
+                        tracing::error!("{previous}");
+                    }
+                }
+            }
+        }
+        match error {
+            Some(e) if !stop_processing_forever => return Err(e),
+            Some(e) => tracing::error!("{e}"),
+            None => (),
+        }
+        if stop_processing_forever {
+            return Ok(TickOutcome::StopProcessingForever);
+        }
+
+        // We wait for one of the batches to finish before creating a new one
+        let running_batches = self.processing_tasks.read().unwrap().running().count();
+        if running_batches >= self.scheduler.max_concurrent_batches {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
         if self.cleanup_enabled {
             let mut wtxn = self.env.write_txn()?;
             self.queue.cleanup_task_queue(&mut wtxn)?;
             wtxn.commit()?;
         }
 
+        // The batches that are not related to a single index are processed alone, while other
+        // batches are running only the batches of the other indexes can be created.
+        let concurrent_only = running_batches > 0;
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
-        let (batch, mut processing_batch) =
-            match self.create_next_batch(&rtxn).map_err(|e| Error::CreateBatch(Box::new(e)))? {
-                Some(batch) => batch,
-                None => return Ok(TickOutcome::WaitForSignal),
-            };
+        let (batch, mut processing_batch) = match self
+            .create_next_batch(&rtxn, concurrent_only)
+            .map_err(|e| Error::CreateBatch(Box::new(e)))?
+        {
+            Some(batch) => batch,
+            None => {
+                // wake up in time to process the next scheduled task
+                let now = OffsetDateTime::now_utc();
+                return Ok(match self.queue.tasks.next_run_at(&rtxn, now)? {
+                    Some(run_at) => {
+                        TickOutcome::WaitForSignalOrTimeout((run_at - now).unsigned_abs())
+                    }
+                    None => TickOutcome::WaitForSignal,
+                });
+            }
+        };
         let index_uid = batch.index_uid().map(ToOwned::to_owned);
+        let concurrently =
+            self.scheduler.max_concurrent_batches > 1 && batch.can_be_processed_concurrently();
         drop(rtxn);
+        debug_assert!(!concurrent_only || concurrently);
+
+        // The next round-robin batch follows the index of the last started batch, even when the
+        // batches finish in another order, and the batches without index don't restart the round.
+        if let (SchedulingPolicy::RoundRobin, Some(index_uid)) =
+            (self.scheduler.scheduling_policy, &index_uid)
+        {
+            let mut wtxn = self.env.write_txn()?;
+            self.queue.tasks.put_round_robin_last_index(&mut wtxn, index_uid)?;
+            wtxn.commit()?;
+        }
 
         // 1. store the starting date with the bitmap of processing tasks.
-        let mut ids = batch.ids();
-        let processed_tasks = ids.len();
+        let ids = batch.ids();
+
+        if concurrently {
+            self.process_batch_concurrently(batch, processing_batch, ids, index_uid)?;
+            return Ok(TickOutcome::TickAgain(finished_tasks));
+        }
 
         // We reset the must_stop flag to be sure that we don't stop processing tasks
         self.scheduler.must_stop_processing.reset();
@@ -184,38 +316,120 @@ impl IndexScheduler {
                     })
                     .unwrap();
 
-                match handle.join() {
-                    Ok(ret) => {
-                        if ret.is_err() {
-                            if let Ok(progress_view) =
-                                serde_json::to_string(&progress.as_progress_view())
-                            {
-                                tracing::warn!("Batch failed while doing: {progress_view}")
-                            }
-                        }
-                        ret
-                    }
-                    Err(panic) => {
-                        if let Ok(progress_view) =
-                            serde_json::to_string(&progress.as_progress_view())
-                        {
-                            tracing::warn!("Batch failed while doing: {progress_view}")
-                        }
-                        let msg = match panic.downcast_ref::<&'static str>() {
-                            Some(s) => *s,
-                            None => match panic.downcast_ref::<String>() {
-                                Some(s) => &s[..],
-                                None => "Box<dyn Any>",
-                            },
-                        };
-                        Err(Error::ProcessBatchPanicked(msg.to_string()))
-                    }
-                }
+                batch_result(handle.join(), &progress)
             })
         };
 
-        // Reset the currently updating index to relinquish the index handle
-        self.index_mapper.set_currently_updating_index(None);
+        self.finish_batch(res, processing_batch, progress, ids, index_uid)
+    }
+
+    /// Process a batch on its own thread, along with the batches of the other indexes.
+    ///
+    /// The batch is sent back to the scheduler thread once finished, so that its tasks
+    /// are written to disk by the next tick.
+    fn process_batch_concurrently(
+        &self,
+        batch: Batch,
+        mut processing_batch: ProcessingBatch,
+        ids: RoaringBitmap,
+        index_uid: Option<String>,
+    ) -> Result<()> {
+        let batch_id = processing_batch.uid;
+        // The batch has its own flag, so that canceling its tasks doesn't stop the other batches
+        let must_stop_processing = MustStopProcessing::default();
+        let progress = self
+            .processing_tasks
+            .write()
+            .unwrap()
+            // We can clone the processing batch here because we don't want its modification to affect the view of the processing batches
+            .start_concurrent_processing(
+                processing_batch.clone(),
+                ids.clone(),
+                must_stop_processing.clone(),
+            );
+
+        #[cfg(test)]
+        self.breakpoint(crate::test_utils::Breakpoint::BatchCreated);
+
+        let mut cloned_index_scheduler = self.private_clone();
+        cloned_index_scheduler.scheduler.must_stop_processing = must_stop_processing;
+        let spawned = std::thread::Builder::new()
+            .name(format!("batch-operation-{batch_id}"))
+            .spawn(move || {
+                let index_scheduler = cloned_index_scheduler;
+                let res = catch_unwind(AssertUnwindSafe(|| {
+                    index_scheduler.process_batch(batch, &mut processing_batch, progress.clone())
+                }));
+                let res = batch_result(res, &progress);
+                let finished = FinishedBatch { res, processing_batch, progress, ids, index_uid };
+                // The scheduler owns both ends of the channel, sending cannot fail.
+                let _ = index_scheduler.scheduler.finished_batches.0.send(finished);
+                index_scheduler.scheduler.wake_up.signal();
+            });
+
+        if let Err(e) = spawned {
+            self.processing_tasks.write().unwrap().stop_concurrent_processing(batch_id);
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+
+    /// Enqueue a copy of the recurring tasks that succeeded or failed, to run once their
+    /// interval elapsed after their previous `run_at` date. The runs missed while
+    /// Meilisearch was down are skipped.
+    fn enqueue_recurring_tasks(&self, wtxn: &mut RwTxn, ids: &RoaringBitmap) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        for id in ids {
+            let task = self.queue.tasks.get_task(wtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+            let Some(run_every) = task.run_every else { continue };
+            if task.status == Status::Canceled {
+                continue;
+            }
+
+            let mut run_at = task.run_at.unwrap_or(task.enqueued_at) + run_every;
+            if run_at <= now {
+                let missed = ((now - run_at) / run_every) as u32;
+                run_at += run_every * missed.saturating_add(1);
+            }
+
+            let mut kind = task.kind;
+            // The dump must contain the keys of the day it is created
+            if let KindWithContent::DumpCreation { keys, .. } = &mut kind {
+                *keys = meilisearch_auth::list_api_keys(self.scheduler.auth_env.clone())
+                    .map_err(|e| Error::Anyhow(e.into()))?;
+            }
+            let options = TaskOptions {
+                run_at: Some(run_at),
+                run_every: Some(run_every),
+                priority: task.priority,
+                ..Default::default()
+            };
+            let next = self.queue.register(wtxn, &kind, None, options, false)?;
+            tracing::info!(
+                "Enqueued the task {} to run again the task {id} at {run_at}.",
+                next.uid
+            );
+        }
+        Ok(())
+    }
+
+    /// Write the result of a processed batch to disk and delete the content of its tasks.
+    fn finish_batch(
+        &self,
+        res: Result<(Vec<Task>, ProcessBatchInfo)>,
+        mut processing_batch: ProcessingBatch,
+        progress: Progress,
+        mut ids: RoaringBitmap,
+        index_uid: Option<String>,
+    ) -> Result<TickOutcome> {
+        let batch_id = processing_batch.uid;
+        let processed_tasks = ids.len();
+
+        // Reset the currently updating indexes to relinquish the index handles
+        for index_uid in &processing_batch.indexes {
+            self.index_mapper.unset_currently_updating_index(index_uid);
+        }
 
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::AcquiringWtxn)?;
@@ -264,6 +478,10 @@ impl IndexScheduler {
                         .tasks
                         .update_task(&mut wtxn, &task)
                         .map_err(|e| Error::UnrecoverableError(Box::new(e)))?;
+                    // The journal of a task group is only needed until its tasks are written
+                    if let Some(group) = &task.group {
+                        self.queue.tasks.task_group_journal.delete(&mut wtxn, &group.name)?;
+                    }
                 }
                 if let Some(canceled_by) = canceled_by {
                     self.queue.tasks.canceled_by.put(&mut wtxn, &canceled_by, &canceled)?;
@@ -281,6 +499,9 @@ impl IndexScheduler {
                 wtxn.abort();
 
                 tracing::info!("A batch of tasks was aborted.");
+                // A batch processed concurrently must free its place for the next ones, but is
+                // kept until the cancelation of its tasks is processed.
+                self.processing_tasks.write().unwrap().abort_concurrent_processing(batch_id);
                 // We make sure that we don't call `stop_processing` on the `processing_tasks`,
                 // this is because we want to let the next tick call `create_next_batch` and keep
                 // the `started_at` date times and `processings` of the current processing tasks.
@@ -288,6 +509,12 @@ impl IndexScheduler {
                 // date in the task on disk.
                 return Ok(TickOutcome::TickAgain(0));
             }
+            // A task group interrupted while committing its indexes keeps its tasks enqueued,
+            // the next batch applies it to the remaining indexes.
+            Err(e @ Error::TaskGroupInterrupted { .. }) => {
+                wtxn.abort();
+                return Err(e);
+            }
             // If an index said it was full, we need to:
             // 1. identify which index is full
             // 2. close the associated environment
@@ -302,6 +529,7 @@ impl IndexScheduler {
                 // fixme: handle error more gracefully? not sure when this could happen
                 self.index_mapper.resize_index(&wtxn, &index_uid)?;
                 wtxn.abort();
+                self.processing_tasks.write().unwrap().stop_concurrent_processing(batch_id);
 
                 tracing::info!("The max database size was reached. Resizing the index.");
 
@@ -350,7 +578,9 @@ impl IndexScheduler {
         }
 
         // We must re-add the canceled task so they're part of the same batch.
-        ids |= canceled;
+        ids |= &canceled;
+
+        self.enqueue_recurring_tasks(&mut wtxn, &ids)?;
 
         let ProcessBatchInfo { congestion, pre_commit_dabases_sizes, post_commit_dabases_sizes } =
             process_batch_info;
@@ -405,6 +635,11 @@ impl IndexScheduler {
 
         self.queue.write_batch(&mut wtxn, processing_batch, &ids)?;
 
+        // We shouldn't crash the tick function if we can't record the deliveries to the webhooks.
+        if let Err(e) = self.webhooks.record_deliveries(&mut wtxn, &self.queue.tasks, &ids) {
+            tracing::error!("While recording the deliveries to the webhooks: {e}");
+        }
+
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::CommittingWtxn)?;
 
@@ -412,7 +647,14 @@ impl IndexScheduler {
 
         // We should stop processing AFTER everything is processed and written to disk otherwise, a batch (which only lives in RAM) may appear in the processing task
         // and then become « not found » for some time until the commit everything is written and the final commit is made.
-        self.processing_tasks.write().unwrap().stop_processing();
+        {
+            let mut processing_tasks = self.processing_tasks.write().unwrap();
+            if processing_tasks.stop_concurrent_processing(batch_id).is_none() {
+                processing_tasks.stop_processing();
+            }
+            // The batches aborted by the cancelation are not needed anymore
+            processing_tasks.remove_canceled_batches(&canceled);
+        }
 
         // Once the tasks are committed, we should delete all the update files associated ASAP to avoid leaking files in case of a restart
         tracing::debug!("Deleting the update files");
@@ -438,8 +680,8 @@ impl IndexScheduler {
             Ok(())
         })?;
 
-        // We shouldn't crash the tick function if we can't send data to the webhook.
-        let _ = self.notify_webhook(&ids);
+        // The deliveries are sent by the webhooks thread.
+        self.webhooks.wake_up();
 
         #[cfg(test)]
         self.breakpoint(crate::test_utils::Breakpoint::AfterProcessing);
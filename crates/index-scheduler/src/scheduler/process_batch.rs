@@ -145,8 +145,7 @@ impl IndexScheduler {
                 };
 
                 // the index operation can take a long time, so save this handle to make it available to the search for the duration of the tick
-                self.index_mapper
-                    .set_currently_updating_index(Some((index_uid.clone(), index.clone())));
+                self.index_mapper.set_currently_updating_index(index_uid.clone(), index.clone());
 
                 let mut index_wtxn = index.write_txn()?;
                 let pre_commit_dabases_sizes = index.database_sizes(&index_wtxn)?;
@@ -349,6 +348,40 @@ impl IndexScheduler {
                 task.status = Status::Succeeded;
                 Ok((vec![task], ProcessBatchInfo::default()))
             }
+            Batch::UnsatisfiedDependencies(mut tasks) => {
+                let rtxn = self.env.read_txn()?;
+                let unsuccessful = self.queue.tasks.get_status(&rtxn, Status::Failed)?
+                    | self.queue.tasks.get_status(&rtxn, Status::Canceled)?;
+                let failed_dependency = |task: &Task| {
+                    task.depends_on.iter().copied().find(|uid| unsuccessful.contains(*uid))
+                };
+
+                // The tasks of a group fail along with the group member whose dependency failed
+                let mut group_dependencies = HashMap::new();
+                for task in &tasks {
+                    if let (Some(group), Some(dependency)) = (&task.group, failed_dependency(task))
+                    {
+                        group_dependencies.entry(group.name.clone()).or_insert(dependency);
+                    }
+                }
+
+                for task in &mut tasks {
+                    let dependency = failed_dependency(task)
+                        .or_else(|| {
+                            let group = task.group.as_ref()?;
+                            group_dependencies.get(&group.name).copied()
+                        })
+                        .ok_or(Error::CorruptedTaskQueue)?;
+                    task.status = Status::Failed;
+                    task.error = Some(Error::TaskDependencyFailed { dependency }.into());
+                    task.details = task.details.take().map(|d| d.to_failed());
+                }
+
+                Ok((tasks, ProcessBatchInfo::default()))
+            }
+            Batch::TaskGroup { name, operations, indexes_to_create } => {
+                self.process_task_group(name, operations, indexes_to_create, progress)
+            }
             Batch::UpgradeDatabase { mut tasks } => {
                 let KindWithContent::UpgradeDatabase { from } = tasks.last().unwrap().kind else {
                     unreachable!();
@@ -464,7 +497,9 @@ impl IndexScheduler {
         let mut affected_indexes = HashSet::new();
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
+        let mut affected_priorities = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut affected_groups = HashSet::new();
         // The tasks that have been removed *per batches*.
         let mut affected_batches: HashMap<BatchId, RoaringBitmap> = HashMap::new();
 
@@ -477,6 +512,7 @@ impl IndexScheduler {
             affected_indexes.extend(task.indexes().into_iter().map(|x| x.to_owned()));
             affected_statuses.insert(task.status);
             affected_kinds.insert(task.kind.as_kind());
+            affected_priorities.insert(task.priority);
             // Note: don't delete the persisted task data since
             // we can only delete succeeded, failed, and canceled tasks.
             // In each of those cases, the persisted data is supposed to
@@ -487,6 +523,9 @@ impl IndexScheduler {
                 task.enqueued_at,
                 task.uid,
             )?;
+            if let Some(run_at) = task.run_at {
+                utils::remove_task_datetime(wtxn, self.queue.tasks.run_at, run_at, task.uid)?;
+            }
             if let Some(started_at) = task.started_at {
                 utils::remove_task_datetime(
                     wtxn,
@@ -506,6 +545,12 @@ impl IndexScheduler {
             if let Some(canceled_by) = task.canceled_by {
                 affected_canceled_by.insert(canceled_by);
             }
+            if !task.depends_on.is_empty() {
+                self.queue.tasks.dependencies.delete(wtxn, &task.uid)?;
+            }
+            if let Some(group) = task.group {
+                affected_groups.insert(group.name);
+            }
             if let Some(batch_uid) = task.batch_uid {
                 affected_batches.entry(batch_uid).or_default().insert(task_id);
             }
@@ -532,6 +577,16 @@ impl IndexScheduler {
             atomic_progress.fetch_add(1, Ordering::Relaxed);
         }
 
+        for priority in affected_priorities.iter() {
+            self.queue
+                .tasks
+                .update_priority(wtxn, *priority, |bitmap| *bitmap -= &to_delete_tasks)?;
+        }
+
+        for group in affected_groups.iter() {
+            self.queue.tasks.update_group(wtxn, group, |bitmap| *bitmap -= &to_delete_tasks)?;
+        }
+
         progress.update_progress(TaskDeletionProgress::DeletingTasks);
         let (atomic_progress, task_progress) = AtomicTaskStep::new(to_delete_tasks.len() as u32);
         progress.update_progress(task_progress);
@@ -656,8 +711,13 @@ impl IndexScheduler {
 
         // 1. Remove from this list the tasks that we are not allowed to cancel
         //    Notice that only the _enqueued_ ones are cancelable and we should
-        //    have already aborted the indexation of the _processing_ ones
-        let cancelable_tasks = self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
+        //    have already aborted the indexation of the _processing_ ones.
+        //    The task groups interrupted while committing are partially applied
+        //    and cannot be canceled anymore.
+        let mut cancelable_tasks = self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
+        for (_, tasks) in self.queue.tasks.interrupted_groups(rtxn)? {
+            cancelable_tasks -= tasks;
+        }
         let tasks_to_cancel = cancelable_tasks & matched_tasks;
 
         let (task_progress, progress_obj) = AtomicTaskStep::new(tasks_to_cancel.len() as u32);
@@ -0,0 +1,203 @@
+use std::collections::BTreeSet;
+
+use meilisearch_types::milli::progress::Progress;
+use meilisearch_types::milli::ChannelCongestion;
+use meilisearch_types::tasks::{Status, Task};
+use meilisearch_types::Index;
+use roaring::MultiOps;
+
+use super::create_batch::IndexOperation;
+use super::process_batch::ProcessBatchInfo;
+use crate::processing::FinalizingIndexStep;
+use crate::queue::TaskGroupJournal;
+use crate::{Error, IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Apply the operations of a committed task group and commit them only if every task
+    /// of the group succeeded.
+    ///
+    /// The write transactions of all the indexes of the group are kept open until every
+    /// operation is applied so that a single failure rolls back all the indexes. The indexes
+    /// created for the group are deleted if it fails.
+    ///
+    /// Since the indexes can't be committed atomically, the group is journaled in the task
+    /// queue before they are committed, and each index records the last task of the group in
+    /// the same transaction as its changes. A group interrupted while committing, by a crash
+    /// or an error, keeps its tasks enqueued and is processed again before any other batch,
+    /// only applying its operations to the indexes it was not committed to yet.
+    pub(super) fn process_task_group(
+        &self,
+        name: String,
+        operations: Vec<IndexOperation>,
+        indexes_to_create: BTreeSet<String>,
+        progress: Progress,
+    ) -> Result<(Vec<Task>, ProcessBatchInfo)> {
+        let last_task =
+            operations.iter().map(|op| op.ids()).union().max().ok_or(Error::CorruptedTaskQueue)?;
+        let rtxn = self.env.read_txn()?;
+        let mut journal = self.queue.tasks.task_group_journal(&rtxn, &name, last_task)?.unwrap_or(
+            TaskGroupJournal { last_task, created_indexes: Vec::new(), committing: None },
+        );
+        drop(rtxn);
+        if journal.committing.is_some() {
+            tracing::warn!(
+                "Resuming the task group `{name}` interrupted while committing its indexes."
+            );
+        }
+
+        // 1. Retrieve, or create, the indexes of the group in the order of the operations
+        let mut indexes: Vec<(String, Index)> = Vec::new();
+        for op in &operations {
+            let index_uid = op.index_uid();
+            if indexes.iter().any(|(uid, _)| uid == index_uid) {
+                continue;
+            }
+            let index = if indexes_to_create.contains(index_uid) {
+                let mut wtxn = self.env.write_txn()?;
+                if !self.index_mapper.exists(&wtxn, index_uid)? {
+                    // the index is journaled along with its creation to be deleted if the group fails
+                    journal.created_indexes.push(index_uid.to_string());
+                    self.queue.tasks.task_group_journal.put(&mut wtxn, &name, &journal)?;
+                }
+                self.index_mapper.create_index(wtxn, index_uid, None)?
+            } else {
+                let rtxn = self.env.read_txn()?;
+                self.index_mapper.index(&rtxn, index_uid)?
+            };
+            indexes.push((index_uid.to_string(), index));
+        }
+
+        // 2. Apply all the operations and commit the indexes together
+        let (tasks, congestion) = match self.apply_task_group(
+            &name,
+            &indexes,
+            operations,
+            &mut journal,
+            &progress,
+        ) {
+            Ok(ret) => ret,
+            // Some indexes may already be committed, the group can't be rolled back anymore
+            // and must be applied to the remaining ones.
+            Err(e) if journal.committing.is_some() => {
+                return Err(Error::TaskGroupInterrupted { group: name, error: e.to_string() });
+            }
+            Err(e) => {
+                // The index handles must be released before deleting the indexes we created
+                for (index_uid, _) in &indexes {
+                    self.index_mapper.unset_currently_updating_index(index_uid);
+                }
+                drop(indexes);
+                for index_uid in &journal.created_indexes {
+                    let res = self
+                        .env
+                        .write_txn()
+                        .map_err(Error::from)
+                        .and_then(|wtxn| self.index_mapper.delete_index(wtxn, index_uid));
+                    if let Err(e) = res {
+                        tracing::error!(
+                            error = &e as &dyn std::error::Error,
+                            "Could not delete the index `{index_uid}` created by the task group `{name}`"
+                        );
+                    }
+                }
+                let mut wtxn = self.env.write_txn()?;
+                self.queue.tasks.task_group_journal.delete(&mut wtxn, &name)?;
+                wtxn.commit()?;
+                return Err(e);
+            }
+        };
+
+        // 3. If the group processed successfully, we're going to store the new stats of its
+        // indexes. Since the tasks have already been processed and this is a non-critical
+        // operation, if it fails we should not fail the entire batch.
+        progress.update_progress(FinalizingIndexStep::ComputingStats);
+        for (index_uid, index) in &indexes {
+            let res = || -> Result<()> {
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(index, &index_rtxn)
+                    .map_err(|e| Error::from_milli(e, Some(index_uid.to_string())))?;
+                let mut wtxn = self.env.write_txn()?;
+                self.index_mapper.store_stats_of(&mut wtxn, index_uid, &stats)?;
+                wtxn.commit()?;
+                Ok(())
+            }();
+
+            if let Err(e) = res {
+                tracing::error!(
+                    error = &e as &dyn std::error::Error,
+                    "Could not write the stats of the index"
+                );
+            }
+        }
+
+        Ok((tasks, ProcessBatchInfo { congestion, ..Default::default() }))
+    }
+
+    fn apply_task_group(
+        &self,
+        name: &str,
+        indexes: &[(String, Index)],
+        operations: Vec<IndexOperation>,
+        journal: &mut TaskGroupJournal,
+        progress: &Progress,
+    ) -> Result<(Vec<Task>, Option<ChannelCongestion>)> {
+        // The indexes the group was committed to before being interrupted are left untouched
+        let mut wtxns = Vec::new();
+        for (index_uid, index) in indexes {
+            let rtxn = index.read_txn()?;
+            let applied = index
+                .applied_task_group(&rtxn)
+                .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+            drop(rtxn);
+            let committed = journal.committing.is_some() && applied == Some(journal.last_task);
+            wtxns.push(if committed { None } else { Some(index.write_txn()?) });
+        }
+
+        let mut tasks = Vec::new();
+        let mut congestion = None;
+        for op in operations {
+            let position = indexes
+                .iter()
+                .position(|(uid, _)| uid == op.index_uid())
+                .ok_or(Error::CorruptedTaskQueue)?;
+            let (index_uid, index) = &indexes[position];
+            let Some(wtxn) = &mut wtxns[position] else { continue };
+            // the operations can take a long time, so save this handle to make it available to the search
+            self.index_mapper.set_currently_updating_index(index_uid.clone(), index.clone());
+
+            let (op_tasks, op_congestion) =
+                self.apply_index_operation(wtxn, index, op, progress)?;
+            congestion = op_congestion.or(congestion);
+            tasks.extend(op_tasks);
+        }
+
+        // A single failed task is enough to roll back the whole group,
+        // the write transactions are aborted when dropped.
+        if let Some(task) = tasks.iter().find(|task| task.status == Status::Failed) {
+            let error = task.error.as_ref().map(|e| e.message.clone()).unwrap_or_default();
+            return Err(Error::TaskGroupFailed { group: name.to_string(), task: task.uid, error });
+        }
+
+        // The processed tasks are journaled before the first index is committed, a resumed
+        // group keeps the results of its first run.
+        let tasks = journal.committing.take().unwrap_or(tasks);
+        journal.committing = Some(tasks.clone());
+        let mut wtxn = self.env.write_txn()?;
+        self.queue.tasks.task_group_journal.put(&mut wtxn, name, journal)?;
+        wtxn.commit()?;
+
+        progress.update_progress(FinalizingIndexStep::Committing);
+        let span = tracing::trace_span!(target: "indexing::scheduler", "commit");
+        let _entered = span.enter();
+        for ((_, index), wtxn) in indexes.iter().zip(wtxns) {
+            let Some(mut wtxn) = wtxn else { continue };
+            index.put_applied_task_group(&mut wtxn, journal.last_task)?;
+            wtxn.commit()?;
+
+            #[cfg(test)]
+            self.maybe_fail(crate::test_utils::FailureLocation::CommittingTaskGroup)?;
+        }
+
+        Ok((tasks, congestion))
+    }
+}
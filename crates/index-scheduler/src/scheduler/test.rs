@@ -7,15 +7,19 @@ use meilisearch_types::milli::index::IndexEmbeddingConfig;
 use meilisearch_types::milli::update::IndexDocumentsMethod::*;
 use meilisearch_types::milli::{self};
 use meilisearch_types::settings::SettingEmbeddingSettings;
-use meilisearch_types::tasks::{IndexSwap, KindWithContent};
+use meilisearch_types::tasks::{
+    IndexSwap, KindWithContent, SchedulingPolicy, TaskGroup, TaskPriority,
+};
 use roaring::RoaringBitmap;
+use time::{Duration, OffsetDateTime};
 
-use crate::insta_snapshot::snapshot_index_scheduler;
+use crate::insta_snapshot::{snapshot_bitmap, snapshot_index_scheduler, snapshot_task};
+use crate::scheduler::MustStopProcessing;
 use crate::test_utils::Breakpoint::*;
 use crate::test_utils::{
     index_creation_task, read_json, replace_document_import_task, sample_documents,
 };
-use crate::IndexScheduler;
+use crate::{IndexScheduler, TaskOptions};
 
 #[test]
 fn insert_task_while_another_task_is_processing() {
@@ -929,3 +933,480 @@ fn create_and_list_index() {
     ]
     "###);
 }
+
+#[test]
+fn scheduled_tasks_wait_for_their_run_at_date() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let tomorrow = OffsetDateTime::now_utc() + Duration::days(1);
+    let options = TaskOptions { run_at: Some(tomorrow), ..Default::default() };
+    index_scheduler
+        .register_with_options(index_creation_task("index_a", "id"), None, options, false)
+        .unwrap();
+    index_scheduler.register(index_creation_task("index_b", "id"), None, false).unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    // the scheduled task is skipped
+    handle.advance_one_successful_batch();
+
+    // a run at date in the past doesn't delay the task
+    let yesterday = OffsetDateTime::now_utc() - Duration::days(1);
+    let options = TaskOptions { run_at: Some(yesterday), ..Default::default() };
+    index_scheduler
+        .register_with_options(index_creation_task("index_c", "id"), None, options, false)
+        .unwrap();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let statuses: Vec<_> = (0..3)
+        .map(|id| index_scheduler.queue.tasks.get_task(&rtxn, id).unwrap().unwrap().status)
+        .collect();
+    snapshot!(format!("{statuses:?}"), @"[Enqueued, Succeeded, Succeeded]");
+
+    let next_run_at =
+        index_scheduler.queue.tasks.next_run_at(&rtxn, OffsetDateTime::now_utc()).unwrap();
+    assert_eq!(next_run_at, Some(tomorrow));
+}
+
+#[test]
+fn recurring_tasks_are_enqueued_again_once_finished() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    // the document additions can't be enqueued again once their content is deleted
+    let options = TaskOptions { run_every: Some(Duration::DAY), ..Default::default() };
+    let err = index_scheduler
+        .register_with_options(
+            replace_document_import_task("doggo", None, 0, 1),
+            None,
+            options,
+            false,
+        )
+        .unwrap_err();
+    snapshot!(err, @"Tasks of type `documentAdditionOrUpdate` cannot be recurring. The document additions, task deletions, task cancelations and database upgrades can only run once.");
+
+    let yesterday = OffsetDateTime::now_utc() - Duration::days(1);
+    let options = TaskOptions {
+        run_at: Some(yesterday),
+        run_every: Some(Duration::hours(10)),
+        ..Default::default()
+    };
+    index_scheduler
+        .register_with_options(index_creation_task("index_a", "id"), None, options, false)
+        .unwrap();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    // the copy skips the runs missed since yesterday
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let copy = index_scheduler.queue.tasks.get_task(&rtxn, 1).unwrap().unwrap();
+    assert_eq!(copy.run_at, Some(yesterday + Duration::hours(30)));
+    snapshot!(snapshot_task(&copy), @r###"{uid: 1, status: enqueued, run_every: 10h, details: { primary_key: Some("id") }, kind: IndexCreation { index_uid: "index_a", primary_key: Some("id") }}"###);
+    drop(rtxn);
+
+    // canceling the copy ends the recurrence
+    index_scheduler
+        .register(
+            KindWithContent::TaskCancelation {
+                query: "test_query".to_owned(),
+                tasks: RoaringBitmap::from_iter([1]),
+            },
+            None,
+            false,
+        )
+        .unwrap();
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let all_tasks = index_scheduler.queue.tasks.all_task_ids(&rtxn).unwrap();
+    snapshot!(snapshot_bitmap(&all_tasks), @"[0,1,2,]");
+}
+
+#[test]
+fn tasks_wait_for_their_dependencies() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let tomorrow = OffsetDateTime::now_utc() + Duration::days(1);
+    let options = TaskOptions { run_at: Some(tomorrow), ..Default::default() };
+    index_scheduler
+        .register_with_options(index_creation_task("index_a", "id"), None, options, false)
+        .unwrap();
+    let options = TaskOptions { depends_on: vec![0], ..Default::default() };
+    index_scheduler
+        .register_with_options(index_creation_task("index_b", "id"), None, options, false)
+        .unwrap();
+    index_scheduler.register(index_creation_task("index_c", "id"), None, false).unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    // the task depending on the scheduled task is skipped
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let statuses: Vec<_> = (0..3)
+        .map(|id| index_scheduler.queue.tasks.get_task(&rtxn, id).unwrap().unwrap().status)
+        .collect();
+    snapshot!(format!("{statuses:?}"), @"[Enqueued, Enqueued, Succeeded]");
+    drop(rtxn);
+
+    let options = TaskOptions { depends_on: vec![12], ..Default::default() };
+    let error = index_scheduler
+        .register_with_options(index_creation_task("index_d", "id"), None, options, false)
+        .unwrap_err();
+    snapshot!(error, @"Task `12` cannot be used as a dependency because it does not exist.");
+}
+
+#[test]
+fn tasks_fail_when_their_dependencies_fail() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    let options = TaskOptions { depends_on: vec![1], ..Default::default() };
+    index_scheduler
+        .register_with_options(index_creation_task("index_b", "id"), None, options, false)
+        .unwrap();
+
+    handle.advance_one_successful_batch();
+    // the index already exists
+    handle.advance_one_failed_batch();
+    // the dependency failed
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 2).unwrap().unwrap();
+    snapshot!(task.status, @"failed");
+    snapshot!(task.error.unwrap().message, @"Task `1` did not succeed.");
+    assert!(!index_scheduler.index_exists("index_b").unwrap());
+}
+
+#[test]
+fn task_group_is_committed_as_a_whole() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler.register(index_creation_task("products", "id"), None, false).unwrap();
+    handle.advance_one_successful_batch();
+
+    let group = |commit| TaskGroup { name: S("migration"), commit };
+
+    // the second document addition is missing its primary key and makes the whole group fail
+    let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+    file.persist().unwrap();
+    let options = TaskOptions { group: Some(group(false)), ..Default::default() };
+    index_scheduler
+        .register_with_options(
+            replace_document_import_task("products", Some("id"), 0, documents_count),
+            None,
+            options,
+            false,
+        )
+        .unwrap();
+
+    // the tasks of an open group are not processed
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    handle.advance_one_successful_batch();
+
+    let content = r#"{ "name": "kefir" }"#;
+    let (_uuid, mut file) = index_scheduler.queue.create_update_file_with_uuid(1).unwrap();
+    let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+    file.persist().unwrap();
+    let options = TaskOptions { group: Some(group(true)), ..Default::default() };
+    index_scheduler
+        .register_with_options(
+            replace_document_import_task("products_v2", Some("id"), 1, documents_count),
+            None,
+            options,
+            false,
+        )
+        .unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    // a committed group cannot receive new tasks
+    let options = TaskOptions { group: Some(group(false)), ..Default::default() };
+    let error = index_scheduler
+        .register_with_options(
+            KindWithContent::DocumentClear { index_uid: S("products") },
+            None,
+            options,
+            false,
+        )
+        .unwrap_err();
+    snapshot!(error, @"Task group `migration` is already committed. Wait for its tasks to be processed before registering new tasks in it.");
+
+    handle.advance_one_failed_batch();
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let statuses: Vec<_> = (1..4)
+        .map(|id| index_scheduler.queue.tasks.get_task(&rtxn, id).unwrap().unwrap().status)
+        .collect();
+    snapshot!(format!("{statuses:?}"), @"[Failed, Succeeded, Failed]");
+    drop(rtxn);
+
+    // nothing was committed in the existing index and the new index was removed
+    let index = index_scheduler.index("products").unwrap();
+    let rtxn = index.read_txn().unwrap();
+    assert_eq!(index.number_of_documents(&rtxn).unwrap(), 0);
+    assert!(!index_scheduler.index_exists("products_v2").unwrap());
+
+    // once processed, the name of the group can be reused
+    let (file, documents_count) = sample_documents(&index_scheduler, 2, 2);
+    file.persist().unwrap();
+    let options = TaskOptions { group: Some(group(false)), ..Default::default() };
+    index_scheduler
+        .register_with_options(
+            replace_document_import_task("products", Some("id"), 2, documents_count),
+            None,
+            options,
+            false,
+        )
+        .unwrap();
+    let (file, documents_count) = sample_documents(&index_scheduler, 3, 3);
+    file.persist().unwrap();
+    let options = TaskOptions { group: Some(group(true)), ..Default::default() };
+    index_scheduler
+        .register_with_options(
+            replace_document_import_task("products_v2", Some("id"), 3, documents_count),
+            None,
+            options,
+            false,
+        )
+        .unwrap();
+
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+
+    for index_uid in ["products", "products_v2"] {
+        let index = index_scheduler.index(index_uid).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+    }
+}
+
+#[test]
+fn invalid_task_groups() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    let group = TaskGroup { name: S("migration"), commit: false };
+    let options = TaskOptions { group: Some(group), ..Default::default() };
+    let error = index_scheduler
+        .register_with_options(index_creation_task("index_a", "id"), None, options.clone(), false)
+        .unwrap_err();
+    snapshot!(error, @"Tasks of type `indexCreation` cannot be part of a task group. Only the document operations and the settings updates can be grouped.");
+
+    index_scheduler
+        .register_with_options(
+            KindWithContent::DocumentClear { index_uid: S("index_a") },
+            None,
+            options,
+            false,
+        )
+        .unwrap();
+    let options = TaskOptions { depends_on: vec![0], ..Default::default() };
+    let error = index_scheduler
+        .register_with_options(index_creation_task("index_b", "id"), None, options, false)
+        .unwrap_err();
+    snapshot!(error, @"Task `0` cannot be used as a dependency because its group `migration` is not committed yet.");
+}
+
+#[test]
+fn high_priority_tasks_are_processed_first() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    let options = TaskOptions { priority: TaskPriority::Low, ..Default::default() };
+    index_scheduler
+        .register_with_options(index_creation_task("index_b", "id"), None, options, false)
+        .unwrap();
+    let options = TaskOptions { priority: TaskPriority::High, ..Default::default() };
+    index_scheduler
+        .register_with_options(index_creation_task("index_c", "id"), None, options, false)
+        .unwrap();
+    // a high priority task can't skip the older tasks of its own index
+    index_scheduler.register(index_creation_task("index_d", "id"), None, false).unwrap();
+    let options = TaskOptions { priority: TaskPriority::High, ..Default::default() };
+    index_scheduler
+        .register_with_options(
+            KindWithContent::IndexUpdate { index_uid: S("index_d"), primary_key: None },
+            None,
+            options,
+            false,
+        )
+        .unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    handle.advance_n_successful_batches(5);
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let batches: Vec<_> = (0..5)
+        .map(|id| index_scheduler.queue.tasks.get_task(&rtxn, id).unwrap().unwrap().batch_uid)
+        .map(Option::unwrap)
+        .collect();
+    snapshot!(format!("{batches:?}"), @"[3, 4, 0, 1, 2]");
+
+    let scheduling: Vec<_> = (0..5)
+        .map(|id| index_scheduler.queue.batches.get_batch(&rtxn, id).unwrap().unwrap())
+        .map(|batch| serde_json::to_string(&batch.stats.scheduling).unwrap())
+        .collect();
+    snapshot!(scheduling.join("\n"), @r###"
+    {"priority":"high","policy":"fifo","skippedTasks":2}
+    {"priority":"high","policy":"fifo","skippedTasks":2}
+    {"priority":"high","policy":"fifo","skippedTasks":2}
+    null
+    {"priority":"low","policy":"fifo","skippedTasks":0}
+    "###);
+}
+
+#[test]
+fn round_robin_scheduling_alternates_between_indexes() {
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.scheduling_policy = SchedulingPolicy::RoundRobin;
+        None
+    });
+
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    for _ in 0..2 {
+        let kind = KindWithContent::IndexUpdate { index_uid: S("index_a"), primary_key: None };
+        index_scheduler.register(kind, None, false).unwrap();
+    }
+    index_scheduler.register(index_creation_task("index_b", "id"), None, false).unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    handle.advance_n_successful_batches(4);
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let batches: Vec<_> = (0..4)
+        .map(|id| index_scheduler.queue.tasks.get_task(&rtxn, id).unwrap().unwrap().batch_uid)
+        .map(Option::unwrap)
+        .collect();
+    snapshot!(format!("{batches:?}"), @"[0, 2, 3, 1]");
+
+    let batch = index_scheduler.queue.batches.get_batch(&rtxn, 1).unwrap().unwrap();
+    snapshot!(serde_json::to_string(&batch.stats.scheduling).unwrap(), @r###"{"priority":"normal","policy":"roundRobin","skippedTasks":2}"###);
+}
+
+#[test]
+fn round_robin_scheduling_ignores_the_batches_without_index() {
+    let (index_scheduler, mut handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.scheduling_policy = SchedulingPolicy::RoundRobin;
+        None
+    });
+
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    let kind = KindWithContent::IndexUpdate { index_uid: S("index_a"), primary_key: None };
+    index_scheduler.register(kind, None, false).unwrap();
+    index_scheduler.register(index_creation_task("index_b", "id"), None, false).unwrap();
+    handle.advance_one_successful_batch();
+
+    // The task deletion is batched alone and must not restart the round from the first index
+    let kind = KindWithContent::TaskDeletion {
+        query: "test_query".to_owned(),
+        tasks: RoaringBitmap::from_iter([100]),
+    };
+    index_scheduler.register(kind, None, false).unwrap();
+    handle.advance_n_successful_batches(3);
+    index_scheduler.assert_internally_consistent();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let batches: Vec<_> = (0..4)
+        .map(|id| index_scheduler.queue.tasks.get_task(&rtxn, id).unwrap().unwrap().batch_uid)
+        .map(Option::unwrap)
+        .collect();
+    snapshot!(format!("{batches:?}"), @"[0, 3, 2, 1]");
+
+    let last_index = index_scheduler.queue.tasks.round_robin_last_index(&rtxn).unwrap();
+    snapshot!(format!("{last_index:?}"), @r###"Some("index_a")"###);
+
+    // The scheduling choice is also part of the progress of the batch
+    let batch = index_scheduler.queue.batches.get_batch(&rtxn, 2).unwrap().unwrap();
+    let scheduling =
+        "scheduled by the roundRobin policy among the normal priority tasks, skipping 1 tasks";
+    assert!(batch.stats.progress_trace.keys().any(|step| step.starts_with(scheduling)));
+}
+
+#[test]
+fn concurrent_batches_skip_the_indexes_being_processed() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("index_b", "id"), None, false).unwrap();
+    let kind = KindWithContent::IndexUpdate { index_uid: S("index_a"), primary_key: None };
+    index_scheduler.register(kind, None, false).unwrap();
+
+    // The first batch is processing on another thread and its tasks are still enqueued on disk
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let (batch, processing_batch) =
+        index_scheduler.create_next_batch(&rtxn, false).unwrap().unwrap();
+    snapshot!(snapshot_bitmap(&batch.ids()), @"[0,]");
+    index_scheduler.processing_tasks.write().unwrap().start_concurrent_processing(
+        processing_batch,
+        batch.ids(),
+        MustStopProcessing::default(),
+    );
+
+    // The next batch must neither reuse its id nor contain a task of the same index
+    let (batch, processing_batch) =
+        index_scheduler.create_next_batch(&rtxn, true).unwrap().unwrap();
+    snapshot!(snapshot_bitmap(&batch.ids()), @"[1,]");
+    snapshot!(processing_batch.uid, @"1");
+    index_scheduler.processing_tasks.write().unwrap().start_concurrent_processing(
+        processing_batch,
+        batch.ids(),
+        MustStopProcessing::default(),
+    );
+
+    assert!(index_scheduler.create_next_batch(&rtxn, true).unwrap().is_none());
+
+    // Once the first batch is aborted, its tasks can be batched again
+    index_scheduler.processing_tasks.write().unwrap().abort_concurrent_processing(0);
+    let (batch, processing_batch) =
+        index_scheduler.create_next_batch(&rtxn, true).unwrap().unwrap();
+    snapshot!(snapshot_bitmap(&batch.ids()), @"[0,]");
+    snapshot!(processing_batch.uid, @"2");
+}
+
+#[test]
+fn cancelation_only_stops_the_targeted_concurrent_batch() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler.register(index_creation_task("index_a", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("index_b", "id"), None, false).unwrap();
+
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let mut flags = Vec::new();
+    for concurrent_only in [false, true] {
+        let (batch, processing_batch) =
+            index_scheduler.create_next_batch(&rtxn, concurrent_only).unwrap().unwrap();
+        let must_stop_processing = MustStopProcessing::default();
+        index_scheduler.processing_tasks.write().unwrap().start_concurrent_processing(
+            processing_batch,
+            batch.ids(),
+            must_stop_processing.clone(),
+        );
+        flags.push(must_stop_processing);
+    }
+    drop(rtxn);
+
+    let kind = KindWithContent::TaskCancelation {
+        query: "test_query".to_owned(),
+        tasks: RoaringBitmap::from_iter([1]),
+    };
+    index_scheduler.register(kind, None, false).unwrap();
+    assert!(!flags[0].get());
+    assert!(flags[1].get());
+    assert!(!index_scheduler.scheduler.must_stop_processing.get());
+
+    // The aborted batch is not processing anymore but is kept until its tasks are canceled
+    let mut processing = index_scheduler.processing_tasks.write().unwrap();
+    processing.abort_concurrent_processing(1);
+    snapshot!(snapshot_bitmap(&processing.batch_uids()), @"[0,]");
+    snapshot!(snapshot_bitmap(&processing.processing), @"[0,]");
+    assert_eq!(processing.concurrent.len(), 2);
+
+    processing.remove_canceled_batches(&RoaringBitmap::from_iter([1]));
+    assert_eq!(processing.concurrent.len(), 1);
+}
@@ -423,8 +423,11 @@ fn import_vectors_first_and_embedder_later() {
                     max_bytes: Some(
                         400,
                     ),
+                    chunking: None,
                 },
                 quantized: None,
+                quantization: None,
+                distance: None,
             },
             user_provided: RoaringBitmap<[1, 2]>,
         },
@@ -613,6 +616,8 @@ fn delete_document_containing_vector() {
                         EmbedderOptions {
                             dimensions: 3,
                             distribution: None,
+                            multi_vector: None,
+                            sparse: None,
                         },
                     ),
                     prompt: PromptData {
@@ -620,8 +625,11 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
                     quantized: None,
+                    quantization: None,
+                    distance: None,
                 },
                 user_provided: RoaringBitmap<[0]>,
             },
@@ -657,6 +665,8 @@ fn delete_document_containing_vector() {
                         EmbedderOptions {
                             dimensions: 3,
                             distribution: None,
+                            multi_vector: None,
+                            sparse: None,
                         },
                     ),
                     prompt: PromptData {
@@ -664,8 +674,11 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
                     quantized: None,
+                    quantization: None,
+                    distance: None,
                 },
                 user_provided: RoaringBitmap<[]>,
             },
@@ -6,12 +6,14 @@ use meilisearch_types::milli::obkv_to_json;
 use meilisearch_types::milli::update::IndexDocumentsMethod::*;
 use meilisearch_types::milli::update::Setting;
 use meilisearch_types::milli::FilterableAttributesRule;
-use meilisearch_types::tasks::{Kind, KindWithContent};
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, TaskGroup};
 
 use crate::insta_snapshot::snapshot_index_scheduler;
 use crate::test_utils::Breakpoint::*;
-use crate::test_utils::{index_creation_task, read_json, FailureLocation};
-use crate::IndexScheduler;
+use crate::test_utils::{
+    index_creation_task, read_json, replace_document_import_task, sample_documents, FailureLocation,
+};
+use crate::{IndexScheduler, TaskOptions};
 
 #[test]
 fn fail_in_process_batch_for_index_creation() {
@@ -325,3 +327,60 @@ fn upgrade_failure() {
     handle.advance_one_successful_batch();
     snapshot!(snapshot_index_scheduler(&index_scheduler), name: "after_removing_the_upgrade_tasks");
 }
+
+#[test]
+fn interrupted_task_group_is_resumed() {
+    let (index_scheduler, mut handle) =
+        IndexScheduler::test(true, vec![(1, FailureLocation::CommittingTaskGroup)]);
+
+    for (id, index_uid) in ["products", "products_v2"].into_iter().enumerate() {
+        let (file, documents_count) = sample_documents(&index_scheduler, id as u128, id);
+        file.persist().unwrap();
+        let group = TaskGroup { name: S("migration"), commit: id == 1 };
+        let options = TaskOptions { group: Some(group), ..Default::default() };
+        index_scheduler
+            .register_with_options(
+                replace_document_import_task(index_uid, Some("id"), id as u128, documents_count),
+                None,
+                options,
+                false,
+            )
+            .unwrap();
+    }
+
+    let statuses = |index_scheduler: &IndexScheduler| {
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let statuses: Vec<Status> = (0..2)
+            .map(|id| index_scheduler.queue.tasks.get_task(&rtxn, id).unwrap().unwrap().status)
+            .collect();
+        format!("{statuses:?}")
+    };
+    let documents = |index_scheduler: &IndexScheduler| {
+        let documents: Vec<u64> = ["products", "products_v2"]
+            .into_iter()
+            .map(|index_uid| {
+                let index = index_scheduler.index(index_uid).unwrap();
+                let rtxn = index.read_txn().unwrap();
+                index.number_of_documents(&rtxn).unwrap()
+            })
+            .collect();
+        format!("{documents:?}")
+    };
+
+    // the group is interrupted once committed to its first index and its tasks stay enqueued
+    handle.advance_till([Start, BatchCreated]);
+    while handle.advance() != Start {}
+    snapshot!(statuses(&index_scheduler), @"[Enqueued, Enqueued]");
+    snapshot!(documents(&index_scheduler), @"[1, 0]");
+
+    // the next batch only applies the group to the index it was not committed to
+    handle.advance_till([BatchCreated]);
+    while handle.advance() != ProcessBatchSucceeded {}
+    handle.advance_till([AfterProcessing]);
+    index_scheduler.assert_internally_consistent();
+
+    snapshot!(statuses(&index_scheduler), @"[Succeeded, Succeeded]");
+    snapshot!(documents(&index_scheduler), @"[1, 1]");
+    let rtxn = index_scheduler.read_txn().unwrap();
+    assert!(index_scheduler.queue.tasks.task_group_journal.is_empty(&rtxn).unwrap());
+}
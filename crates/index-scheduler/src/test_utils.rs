@@ -9,7 +9,7 @@ use meilisearch_auth::open_auth_store_env;
 use meilisearch_types::document_formats::DocumentFormatError;
 use meilisearch_types::milli::update::IndexDocumentsMethod::ReplaceDocuments;
 use meilisearch_types::milli::update::IndexerConfig;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::tasks::{KindWithContent, SchedulingPolicy};
 use meilisearch_types::{versioning, VERSION_FILE_NAME};
 use tempfile::{NamedTempFile, TempDir};
 use uuid::Uuid;
@@ -42,6 +42,7 @@ pub(crate) enum FailureLocation {
     UpdatingTaskAfterProcessBatchSuccess { task_uid: u32 },
     UpdatingTaskAfterProcessBatchFailure,
     CommittingWtxn,
+    CommittingTaskGroup,
 }
 
 impl IndexScheduler {
@@ -110,6 +111,7 @@ impl IndexScheduler {
             max_number_of_tasks: 1_000_000,
             max_number_of_batched_tasks: usize::MAX,
             batched_tasks_size_limit: u64::MAX,
+            scheduling_policy: SchedulingPolicy::Fifo,
             instance_features: Default::default(),
             auto_upgrade: true, // Don't cost much and will ensure the happy path works
             embedding_cache_cap: 10,
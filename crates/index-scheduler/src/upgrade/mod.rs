@@ -78,6 +78,11 @@ pub fn upgrade_index_scheduler(
             enqueued_at: OffsetDateTime::now_utc(),
             started_at: None,
             finished_at: None,
+            run_at: None,
+            run_every: None,
+            depends_on: Vec::new(),
+            group: None,
+            priority: Default::default(),
             error: None,
             canceled_by: None,
             details: Some(Details::UpgradeDatabase { from, to }),
@@ -361,6 +361,11 @@ impl crate::IndexScheduler {
                 enqueued_at,
                 started_at,
                 finished_at,
+                run_at,
+                run_every,
+                depends_on,
+                group,
+                priority,
                 error: _,
                 canceled_by,
                 details,
@@ -428,6 +433,34 @@ impl crate::IndexScheduler {
                     .unwrap();
                 assert!(db_finished_at.contains(task_id));
             }
+            if let Some(run_at) = run_at {
+                let db_run_at = self
+                    .queue
+                    .tasks
+                    .run_at
+                    .get(&rtxn, &run_at.unix_timestamp_nanos())
+                    .unwrap()
+                    .unwrap();
+                assert!(db_run_at.contains(task_id));
+            }
+            if run_every.is_some() {
+                assert!(kind.can_recur());
+            }
+            if !depends_on.is_empty() {
+                let db_dependencies =
+                    self.queue.tasks.dependencies.get(&rtxn, &task_id).unwrap().unwrap();
+                assert_eq!(db_dependencies, RoaringBitmap::from_iter(depends_on));
+            }
+            if let Some(group) = group {
+                let db_group_tasks = self.queue.tasks.group_tasks(&rtxn, &group.name).unwrap();
+                assert!(db_group_tasks.contains(task_id));
+            }
+            // The tasks registered before the priorities were introduced are not in the
+            // database and are considered as `normal`.
+            if !priority.is_normal() {
+                let db_priority = self.queue.tasks.get_priority(&rtxn, priority).unwrap();
+                assert!(db_priority.contains(task_id));
+            }
             if let Some(canceled_by) = canceled_by {
                 let db_canceled_tasks =
                     self.queue.tasks.get_status(&rtxn, Status::Canceled).unwrap();
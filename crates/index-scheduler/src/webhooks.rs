@@ -0,0 +1,438 @@
+//! The webhooks notified of the processed tasks.
+//!
+//! The deliveries are recorded in the same transaction as the tasks they notify about,
+//! and are then sent by a dedicated thread. A delivery that fails is retried with an exponential
+//! backoff, so that neither a restart nor an unavailable endpoint silently drops a notification.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use hmac::{Hmac, Mac};
+use meilisearch_types::heed::types::SerdeJson;
+use meilisearch_types::heed::{self, Database, Env, RoTxn, RwTxn, WithoutTls};
+use meilisearch_types::milli::BEU64;
+use meilisearch_types::task_view::TaskView;
+use meilisearch_types::webhooks::Webhook;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use synchronoise::SignalEvent;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::queue::TaskQueue;
+use crate::uuid_codec::UuidCodec;
+use crate::{Error, IndexSchedulerOptions, Result, TaskId};
+
+/// The number of database used by the webhooks
+const NUMBER_OF_DATABASES: u32 = 2;
+/// Database const names for the `WebhookData`.
+mod db_name {
+    pub const WEBHOOKS: &str = "webhooks";
+    pub const WEBHOOK_DELIVERIES: &str = "webhook-deliveries";
+}
+
+/// The uuid of the webhook configured when launching the instance, which cannot be managed through the API.
+const LAUNCH_WEBHOOK: Uuid = Uuid::nil();
+
+/// A delivery failing this many times is dropped.
+const MAX_DELIVERY_ATTEMPTS: u32 = 10;
+/// The delay before the first retry of a failed delivery, doubled after every failure.
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The header containing the signature of the deliveries of the webhooks with a secret.
+///
+/// Its value is `t=<timestamp>,v1=<signature>`, where the signature is the hex encoded
+/// HMAC-SHA256 of `<timestamp>.<uncompressed body>` keyed with the secret of the webhook.
+pub const SIGNATURE_HEADER: &str = "X-Meilisearch-Signature";
+/// The header containing the identifier of a delivery, identical for all the attempts of a delivery.
+pub const DELIVERY_HEADER: &str = "X-Meilisearch-Delivery";
+
+/// The tasks waiting to be sent to a webhook.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Delivery {
+    /// Identifies the delivery for the webhook, so that the retries can be deduplicated.
+    uuid: Uuid,
+    webhook: Uuid,
+    tasks: Vec<TaskId>,
+    attempts: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    next_attempt_at: OffsetDateTime,
+}
+
+#[derive(Clone)]
+pub(crate) struct WebhookData {
+    persisted: Database<UuidCodec, SerdeJson<Webhook>>,
+    deliveries: Database<BEU64, SerdeJson<Delivery>>,
+    webhooks: Arc<RwLock<BTreeMap<Uuid, Webhook>>>,
+    /// The webhook configured when launching the instance.
+    launch_webhook: Option<Arc<Webhook>>,
+    /// Wakes the delivery thread up when new deliveries are recorded.
+    wake_up: Arc<SignalEvent>,
+}
+
+impl WebhookData {
+    pub(crate) const fn nb_db() -> u32 {
+        NUMBER_OF_DATABASES
+    }
+
+    pub fn new(
+        env: &Env<WithoutTls>,
+        wtxn: &mut RwTxn,
+        options: &IndexSchedulerOptions,
+    ) -> Result<Self> {
+        let persisted: Database<UuidCodec, SerdeJson<Webhook>> =
+            env.create_database(wtxn, Some(db_name::WEBHOOKS))?;
+        let deliveries = env.create_database(wtxn, Some(db_name::WEBHOOK_DELIVERIES))?;
+        let webhooks = persisted.iter(wtxn)?.collect::<heed::Result<_>>()?;
+
+        let launch_webhook = options.webhook_url.as_ref().map(|url| {
+            let headers = options
+                .webhook_authorization_header
+                .iter()
+                .map(|header| ("Authorization".to_string(), header.clone()))
+                .collect();
+            Arc::new(Webhook {
+                url: url.clone(),
+                headers,
+                secret: None,
+                index_uids: None,
+                types: None,
+                statuses: None,
+            })
+        });
+
+        Ok(Self {
+            persisted,
+            deliveries,
+            webhooks: Arc::new(RwLock::new(webhooks)),
+            launch_webhook,
+            wake_up: Arc::new(SignalEvent::auto(true)),
+        })
+    }
+
+    pub fn webhooks(&self) -> BTreeMap<Uuid, Webhook> {
+        self.webhooks.read().unwrap().clone()
+    }
+
+    pub fn webhook(&self, uuid: Uuid) -> Option<Webhook> {
+        self.webhooks.read().unwrap().get(&uuid).cloned()
+    }
+
+    pub fn put_webhook(&self, mut wtxn: RwTxn, uuid: Uuid, webhook: Webhook) -> Result<()> {
+        self.persisted.put(&mut wtxn, &uuid, &webhook)?;
+        wtxn.commit()?;
+
+        self.webhooks.write().unwrap().insert(uuid, webhook);
+        Ok(())
+    }
+
+    /// Deletes a webhook, its pending deliveries are dropped when they are due.
+    pub fn delete_webhook(&self, mut wtxn: RwTxn, uuid: Uuid) -> Result<()> {
+        if !self.persisted.delete(&mut wtxn, &uuid)? {
+            return Err(Error::WebhookNotFound(uuid));
+        }
+        wtxn.commit()?;
+
+        self.webhooks.write().unwrap().remove(&uuid);
+        Ok(())
+    }
+
+    fn target(&self, uuid: Uuid) -> Option<Webhook> {
+        match &self.launch_webhook {
+            Some(webhook) if uuid == LAUNCH_WEBHOOK => Some(Webhook::clone(webhook)),
+            _ => self.webhook(uuid),
+        }
+    }
+
+    /// Records a delivery of the updated tasks for every webhook they match.
+    ///
+    /// Must be called with the transaction in which the tasks are updated, and followed by
+    /// a call to [`Self::wake_up`] once the transaction is committed.
+    pub fn record_deliveries(
+        &self,
+        wtxn: &mut RwTxn,
+        tasks: &TaskQueue,
+        updated: &RoaringBitmap,
+    ) -> Result<()> {
+        let mut webhooks: Vec<_> = self.webhooks().into_iter().collect();
+        if let Some(webhook) = &self.launch_webhook {
+            webhooks.push((LAUNCH_WEBHOOK, Webhook::clone(webhook)));
+        }
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let mut matching: Vec<Vec<TaskId>> = vec![Vec::new(); webhooks.len()];
+        for task_id in updated {
+            let task = tasks.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            for ((_, webhook), matching) in webhooks.iter().zip(&mut matching) {
+                if webhook.matches(&task) {
+                    matching.push(task_id);
+                }
+            }
+        }
+
+        let mut delivery_id = self.deliveries.last(wtxn)?.map_or(0, |(id, _)| id + 1);
+        let now = OffsetDateTime::now_utc();
+        for ((webhook, _), tasks) in webhooks.into_iter().zip(matching) {
+            if tasks.is_empty() {
+                continue;
+            }
+            let delivery = Delivery {
+                uuid: Uuid::new_v4(),
+                webhook,
+                tasks,
+                attempts: 0,
+                next_attempt_at: now,
+            };
+            self.deliveries.put(wtxn, &delivery_id, &delivery)?;
+            delivery_id += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn wake_up(&self) {
+        self.wake_up.signal();
+    }
+
+    /// Sends the due deliveries, then waits until the next delivery is due or new deliveries are recorded.
+    ///
+    /// This function must only be called from the webhooks thread.
+    pub fn run(&self, env: &Env<WithoutTls>, tasks: &TaskQueue) -> Result<()> {
+        match self.send_due_deliveries(env, tasks)? {
+            Some(next) => {
+                self.wake_up.wait_timeout(next);
+            }
+            None => self.wake_up.wait(),
+        }
+        Ok(())
+    }
+
+    /// Sends the due deliveries and returns the delay before the next delivery is due.
+    ///
+    /// The webhooks are sent their deliveries concurrently, so that an unavailable endpoint
+    /// doesn't hold back the deliveries to the other webhooks.
+    fn send_due_deliveries(
+        &self,
+        env: &Env<WithoutTls>,
+        tasks: &TaskQueue,
+    ) -> Result<Option<Duration>> {
+        let now = OffsetDateTime::now_utc();
+        let mut next_attempt_at: Option<OffsetDateTime> = None;
+
+        let pending: Vec<(u64, Delivery)> = {
+            let rtxn = env.read_txn()?;
+            self.deliveries.iter(&rtxn)?.collect::<heed::Result<_>>()?
+        };
+
+        // the due deliveries of every webhook, in the order they were recorded
+        let mut due: BTreeMap<Uuid, Vec<(u64, Delivery)>> = BTreeMap::new();
+        for (delivery_id, delivery) in pending {
+            if delivery.next_attempt_at > now {
+                next_attempt_at = Some(earliest(next_attempt_at, delivery.next_attempt_at));
+                continue;
+            }
+            due.entry(delivery.webhook).or_default().push((delivery_id, delivery));
+        }
+        if due.is_empty() {
+            return Ok(next_attempt_at.map(delay_until));
+        }
+
+        let sent: Vec<Vec<(u64, Delivery, Outcome)>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = due
+                .into_iter()
+                .map(|(webhook, deliveries)| {
+                    scope.spawn(move || {
+                        self.send_webhook_deliveries(env, tasks, webhook, deliveries)
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Result<_>>()
+        })?;
+
+        let mut wtxn = env.write_txn()?;
+        for (delivery_id, delivery, outcome) in sent.into_iter().flatten() {
+            match outcome {
+                Outcome::Done => {
+                    self.deliveries.delete(&mut wtxn, &delivery_id)?;
+                }
+                Outcome::Failed(error) if delivery.attempts >= MAX_DELIVERY_ATTEMPTS => {
+                    tracing::error!(
+                        webhook = %delivery.webhook,
+                        "Dropping a delivery after {} failed attempts: {error}",
+                        delivery.attempts,
+                    );
+                    self.deliveries.delete(&mut wtxn, &delivery_id)?;
+                }
+                Outcome::Failed(error) => {
+                    tracing::warn!(
+                        webhook = %delivery.webhook,
+                        "Failed to send a delivery, attempt {}/{MAX_DELIVERY_ATTEMPTS}: {error}",
+                        delivery.attempts,
+                    );
+                    next_attempt_at = Some(earliest(next_attempt_at, delivery.next_attempt_at));
+                    self.deliveries.put(&mut wtxn, &delivery_id, &delivery)?;
+                }
+                Outcome::Postponed => {
+                    next_attempt_at = Some(earliest(next_attempt_at, delivery.next_attempt_at));
+                    self.deliveries.put(&mut wtxn, &delivery_id, &delivery)?;
+                }
+            }
+        }
+        wtxn.commit()?;
+
+        Ok(next_attempt_at.map(delay_until))
+    }
+
+    /// Sends the due deliveries of a webhook in order.
+    ///
+    /// Once a delivery fails, the following deliveries are postponed to its next attempt
+    /// rather than waiting for the timeout of the unavailable endpoint one after the other.
+    fn send_webhook_deliveries(
+        &self,
+        env: &Env<WithoutTls>,
+        tasks: &TaskQueue,
+        webhook: Uuid,
+        deliveries: Vec<(u64, Delivery)>,
+    ) -> Result<Vec<(u64, Delivery, Outcome)>> {
+        let target = self.target(webhook);
+        let mut retry_at = None;
+        let mut sent = Vec::with_capacity(deliveries.len());
+
+        for (delivery_id, mut delivery) in deliveries {
+            let outcome = match (&target, retry_at) {
+                (_, Some(retry_at)) => {
+                    delivery.next_attempt_at = retry_at;
+                    Outcome::Postponed
+                }
+                (None, None) => {
+                    tracing::debug!(%webhook, "Dropping a delivery to a deleted webhook");
+                    Outcome::Done
+                }
+                (Some(target), None) => {
+                    let rtxn = env.read_txn()?;
+                    match send(&rtxn, tasks, &delivery, target) {
+                        Ok(()) => Outcome::Done,
+                        Err(error) => {
+                            delivery.attempts += 1;
+                            delivery.next_attempt_at =
+                                OffsetDateTime::now_utc() + retry_delay(delivery.attempts);
+                            retry_at = Some(delivery.next_attempt_at);
+                            Outcome::Failed(error)
+                        }
+                    }
+                }
+            };
+            sent.push((delivery_id, delivery, outcome));
+        }
+
+        Ok(sent)
+    }
+}
+
+/// What happened to a due delivery during a pass of the delivery thread.
+enum Outcome {
+    /// The delivery was sent, or its webhook was deleted.
+    Done,
+    /// The delivery failed with this error.
+    Failed(String),
+    /// The delivery was not attempted, as a previous delivery to the same webhook failed.
+    Postponed,
+}
+
+fn delay_until(date: OffsetDateTime) -> Duration {
+    let delay = date - OffsetDateTime::now_utc();
+    delay.try_into().unwrap_or(Duration::ZERO)
+}
+
+fn earliest(current: Option<OffsetDateTime>, date: OffsetDateTime) -> OffsetDateTime {
+    current.map_or(date, |current| current.min(date))
+}
+
+/// The delay before the next attempt of a delivery that failed `attempts` times.
+fn retry_delay(attempts: u32) -> Duration {
+    FIRST_RETRY_DELAY.saturating_mul(2u32.saturating_pow(attempts - 1)).min(MAX_RETRY_DELAY)
+}
+
+/// Sends the tasks to the webhook as gzipped ndjson, returning an error message on failure.
+fn send(
+    rtxn: &RoTxn,
+    tasks: &TaskQueue,
+    delivery: &Delivery,
+    webhook: &Webhook,
+) -> std::result::Result<(), String> {
+    let mut payload = Vec::new();
+    for &task_id in &delivery.tasks {
+        // the task may have been deleted since the delivery was recorded
+        let Some(task) = tasks.get_task(rtxn, task_id).map_err(|e| e.to_string())? else {
+            continue;
+        };
+        serde_json::to_writer(&mut payload, &TaskView::from_task(&task))
+            .map_err(|e| e.to_string())?;
+        payload.push(b'\n');
+    }
+    if payload.is_empty() {
+        return Ok(());
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&payload).map_err(|e| e.to_string())?;
+    let body = encoder.finish().map_err(|e| e.to_string())?;
+
+    let mut request = ureq::post(&webhook.url)
+        .timeout(DELIVERY_TIMEOUT)
+        .set("Content-Encoding", "gzip")
+        .set("Content-Type", "application/x-ndjson")
+        .set(DELIVERY_HEADER, &delivery.uuid.to_string());
+    for (name, value) in &webhook.headers {
+        request = request.set(name, value);
+    }
+    if let Some(secret) = &webhook.secret {
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        request = request.set(SIGNATURE_HEADER, &signature(secret, timestamp, &payload));
+    }
+
+    request.send(body.as_slice()).map(drop).map_err(|e| e.to_string())
+}
+
+fn signature(secret: &str, timestamp: i64, payload: &[u8]) -> String {
+    // HMAC accepts keys of any size
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+    let signature: String =
+        mac.finalize().into_bytes().iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("t={timestamp},v1={signature}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retry_delays() {
+        let delays: Vec<_> =
+            (1..MAX_DELIVERY_ATTEMPTS).map(|attempts| retry_delay(attempts).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert_eq!(retry_delay(100), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn signatures() {
+        let signature = signature("secret", 1700000000, b"{\"uid\":0}\n");
+        assert_eq!(
+            signature,
+            "t=1700000000,v1=ac3203ec18bdf4841fa2e4d655c413951bc52753e83608b36e267c5e41602cf0"
+        );
+    }
+}
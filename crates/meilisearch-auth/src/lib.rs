@@ -16,6 +16,11 @@ use store::{generate_key_as_hexa, HeedAuthStore};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+/// Lists the API keys of the auth env, unlike [`AuthController::new`] it never generates the default keys.
+pub fn list_api_keys(auth_env: Env<WithoutTls>) -> Result<Vec<Key>> {
+    HeedAuthStore::new(auth_env)?.list_api_keys()
+}
+
 #[derive(Clone)]
 pub struct AuthController {
     store: HeedAuthStore,
@@ -0,0 +1,167 @@
+use std::convert::Infallible;
+
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
+use milli::{AnalyzerRule, AnalyzerTokenizer, AttributePatterns, MAX_GRAM_LENGTH, MIN_GRAM_LENGTH};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::InvalidSettingsAnalyzers;
+
+/// The gram lengths used by the n-gram tokenizers when they are not specified.
+pub const DEFAULT_MIN_GRAM: u8 = 2;
+pub const DEFAULT_MAX_GRAM: u8 = 3;
+
+fn validate_analyzer<E: DeserializeError>(
+    analyzer: AnalyzerRuleView,
+    location: ValuePointerRef,
+) -> Result<AnalyzerRuleView, E> {
+    let msg = match analyzer.tokenizer {
+        AnalyzerTokenizerView::Standard | AnalyzerTokenizerView::Keyword
+            if analyzer.min_gram.is_some() || analyzer.max_gram.is_some() =>
+        {
+            format!(
+                "`minGram` and `maxGram` can only be specified with the `ngram` and `edgeNgram` tokenizers, but the tokenizer is `{}`",
+                analyzer.tokenizer
+            )
+        }
+        AnalyzerTokenizerView::Ngram | AnalyzerTokenizerView::EdgeNgram => {
+            let (min_gram, max_gram) = analyzer.gram_bounds();
+            if !(MIN_GRAM_LENGTH..=MAX_GRAM_LENGTH).contains(&min_gram)
+                || !(MIN_GRAM_LENGTH..=MAX_GRAM_LENGTH).contains(&max_gram)
+            {
+                format!(
+                    "`minGram` and `maxGram` must be between {MIN_GRAM_LENGTH} and {MAX_GRAM_LENGTH}, but found `{min_gram}` and `{max_gram}`"
+                )
+            } else if min_gram > max_gram {
+                format!(
+                    "`minGram` must be lower than or equal to `maxGram`, but found `{min_gram}` and `{max_gram}`"
+                )
+            } else {
+                return Ok(analyzer);
+            }
+        }
+        _ => return Ok(analyzer),
+    };
+    Err(deserr::take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected { msg },
+        location,
+    )))
+}
+
+/// The tokenization and normalization applied to the attributes matching the patterns
+/// instead of the default ones, both when indexing documents and when searching.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[deserr(rename_all = camelCase, deny_unknown_fields, validate = validate_analyzer -> DeserrJsonError<InvalidSettingsAnalyzers>)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AnalyzerRuleView {
+    pub attribute_patterns: AttributePatterns,
+    #[serde(default)]
+    #[deserr(default)]
+    pub tokenizer: AnalyzerTokenizerView,
+    /// The length of the shortest grams, only for the `ngram` and `edgeNgram` tokenizers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub min_gram: Option<u8>,
+    /// The length of the longest grams, only for the `ngram` and `edgeNgram` tokenizers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub max_gram: Option<u8>,
+    #[serde(default = "default_true")]
+    #[deserr(default = true)]
+    pub lowercase: bool,
+    #[serde(default = "default_true")]
+    #[deserr(default = true)]
+    pub ascii_folding: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl AnalyzerRuleView {
+    fn gram_bounds(&self) -> (u8, u8) {
+        (self.min_gram.unwrap_or(DEFAULT_MIN_GRAM), self.max_gram.unwrap_or(DEFAULT_MAX_GRAM))
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Deserr,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum AnalyzerTokenizerView {
+    /// Splits the text into words on every character that is neither a letter nor a digit.
+    #[default]
+    Standard,
+    /// Keeps the whole text as a single word, made of its letters and digits.
+    Keyword,
+    /// Also indexes all the substrings of the words.
+    Ngram,
+    /// Also indexes all the prefixes of the words.
+    EdgeNgram,
+}
+
+impl std::fmt::Display for AnalyzerTokenizerView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalyzerTokenizerView::Standard => f.write_str("standard"),
+            AnalyzerTokenizerView::Keyword => f.write_str("keyword"),
+            AnalyzerTokenizerView::Ngram => f.write_str("ngram"),
+            AnalyzerTokenizerView::EdgeNgram => f.write_str("edgeNgram"),
+        }
+    }
+}
+
+impl From<AnalyzerRule> for AnalyzerRuleView {
+    fn from(rule: AnalyzerRule) -> Self {
+        let (tokenizer, min_gram, max_gram) = match rule.tokenizer {
+            AnalyzerTokenizer::Standard => (AnalyzerTokenizerView::Standard, None, None),
+            AnalyzerTokenizer::Keyword => (AnalyzerTokenizerView::Keyword, None, None),
+            AnalyzerTokenizer::Ngram { min_gram, max_gram } => {
+                (AnalyzerTokenizerView::Ngram, Some(min_gram), Some(max_gram))
+            }
+            AnalyzerTokenizer::EdgeNgram { min_gram, max_gram } => {
+                (AnalyzerTokenizerView::EdgeNgram, Some(min_gram), Some(max_gram))
+            }
+        };
+        Self {
+            attribute_patterns: rule.attribute_patterns,
+            tokenizer,
+            min_gram,
+            max_gram,
+            lowercase: rule.lowercase,
+            ascii_folding: rule.ascii_folding,
+        }
+    }
+}
+
+impl From<AnalyzerRuleView> for AnalyzerRule {
+    fn from(view: AnalyzerRuleView) -> Self {
+        let (min_gram, max_gram) = view.gram_bounds();
+        let tokenizer = match view.tokenizer {
+            AnalyzerTokenizerView::Standard => AnalyzerTokenizer::Standard,
+            AnalyzerTokenizerView::Keyword => AnalyzerTokenizer::Keyword,
+            AnalyzerTokenizerView::Ngram => AnalyzerTokenizer::Ngram { min_gram, max_gram },
+            AnalyzerTokenizerView::EdgeNgram => AnalyzerTokenizer::EdgeNgram { min_gram, max_gram },
+        };
+        Self {
+            attribute_patterns: view.attribute_patterns,
+            tokenizer,
+            lowercase: view.lowercase,
+            ascii_folding: view.ascii_folding,
+        }
+    }
+}
@@ -6,7 +6,7 @@ use time::OffsetDateTime;
 use utoipa::ToSchema;
 
 use crate::task_view::DetailsView;
-use crate::tasks::{BatchStopReason, Kind, Status};
+use crate::tasks::{BatchStopReason, Kind, SchedulingPolicy, Status, TaskPriority};
 
 pub type BatchId = u32;
 
@@ -82,4 +82,21 @@ pub struct BatchStats {
     pub write_channel_congestion: Option<serde_json::Map<String, serde_json::Value>>,
     #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
     pub internal_database_sizes: serde_json::Map<String, serde_json::Value>,
+    /// Why the batch was picked, only present when the batch was not simply made
+    /// from the oldest enqueued task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduling: Option<BatchScheduling>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct BatchScheduling {
+    /// The highest priority among the enqueued tasks when the batch was created.
+    pub priority: TaskPriority,
+    /// The policy used to pick the index of the batch among the indexes with this priority.
+    pub policy: SchedulingPolicy,
+    /// The number of enqueued tasks that were registered before the batch's tasks
+    /// but left for a later batch.
+    pub skipped_tasks: u64,
 }
@@ -247,6 +247,11 @@ InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchEmbedder                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarEmbedder                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHybridQuery              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFusion                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRrfK                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRerank                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchReranker                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRerankTopK               , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
@@ -256,6 +261,7 @@ InvalidMultiSearchFacetsByIndex       , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFacetOrder          , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFederated           , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFederationOptions   , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchFusion              , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchMaxValuesPerFacet   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchMergeFacets         , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryFacets         , InvalidRequest       , BAD_REQUEST ;
@@ -263,6 +269,7 @@ InvalidMultiSearchQueryPagination     , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryRankingRules   , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchQueryPosition       , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchRemote              , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchRrfK                , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchWeight              , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkRemotes                 , InvalidRequest       , BAD_REQUEST ;
 InvalidNetworkSelf                    , InvalidRequest       , BAD_REQUEST ;
@@ -280,6 +287,9 @@ InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetRanges              , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchHierarchicalFacets       , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchExhaustiveFacetCount, InvalidRequest       , BAD_REQUEST ;
@@ -326,26 +336,43 @@ InvalidSettingsNonSeparatorTokens     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSeparatorTokens        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDictionary             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsSynonymRules           , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsRerankers              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsLocalizedAttributes    , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsAnalyzers              , InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterEnqueuedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterFinishedAt            , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskAfterRunAt                 , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterStartedAt             , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeEnqueuedAt           , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeFinishedAt           , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskBeforeRunAt                , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeStartedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskCanceledBy                 , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskDependsOn                  , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskFrom                       , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskGroup                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskLimit                      , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskPriority                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskReverse                    , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskRunAt                      , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskRunEvery                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskStatuses                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskTypes                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskUids                       , InvalidRequest       , BAD_REQUEST  ;
 InvalidBatchUids                      , InvalidRequest       , BAD_REQUEST  ;
+InvalidWebhookHeaders                 , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookIndexUids               , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookSecret                  , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookStatuses                , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookTypes                   , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookUrl                     , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookUuid                    , InvalidRequest       , BAD_REQUEST ;
 IoError                               , System               , UNPROCESSABLE_ENTITY;
 FeatureNotEnabled                     , InvalidRequest       , BAD_REQUEST ;
 MalformedPayload                      , InvalidRequest       , BAD_REQUEST ;
@@ -364,6 +391,7 @@ MissingPayload                        , InvalidRequest       , BAD_REQUEST ;
 MissingSearchHybrid                   , InvalidRequest       , BAD_REQUEST ;
 MissingSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 MissingTaskFilters                    , InvalidRequest       , BAD_REQUEST ;
+MissingWebhookUrl                     , InvalidRequest       , BAD_REQUEST ;
 NoSpaceLeftOnDevice                   , System               , UNPROCESSABLE_ENTITY;
 PayloadTooLarge                       , InvalidRequest       , PAYLOAD_TOO_LARGE ;
 RemoteBadResponse                     , System               , BAD_GATEWAY ;
@@ -373,6 +401,8 @@ RemoteInvalidApiKey                   , Auth                 , FORBIDDEN ;
 RemoteRemoteError                     , System               , BAD_GATEWAY ;
 RemoteTimeout                         , System               , BAD_GATEWAY ;
 TooManySearchRequests                 , System               , SERVICE_UNAVAILABLE ;
+TaskDependencyFailed                  , InvalidRequest       , BAD_REQUEST ;
+TaskGroupFailed                       , InvalidRequest       , BAD_REQUEST ;
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
 TaskFileNotFound                      , InvalidRequest       , NOT_FOUND ;
 BatchNotFound                         , InvalidRequest       , NOT_FOUND ;
@@ -381,6 +411,7 @@ TooManyVectors                        , InvalidRequest       , BAD_REQUEST ;
 UnretrievableDocument                 , Internal             , BAD_REQUEST ;
 UnretrievableErrorCode                , InvalidRequest       , BAD_REQUEST ;
 UnsupportedMediaType                  , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
+WebhookNotFound                       , InvalidRequest       , NOT_FOUND ;
 
 // Experimental features
 VectorEmbeddingError                  , InvalidRequest       , BAD_REQUEST ;
@@ -428,10 +459,15 @@ impl ErrorCode for milli::Error {
                     | UserError::InvalidOpenAiModelDimensions { .. }
                     | UserError::InvalidOpenAiModelDimensionsMax { .. }
                     | UserError::InvalidSettingsDimensions { .. }
+                    | UserError::InvalidSettingsThreads { .. }
                     | UserError::InvalidUrl { .. }
                     | UserError::InvalidSettingsDocumentTemplateMaxBytes { .. }
+                    | UserError::InvalidSettingsChunking { .. }
+                    | UserError::InvalidSettingsSparse { .. }
+                    | UserError::InvalidSettingsQuantization { .. }
                     | UserError::InvalidPrompt(_)
                     | UserError::InvalidDisableBinaryQuantization { .. }
+                    | UserError::InvalidDistanceForBinaryQuantization { .. }
                     | UserError::InvalidSourceForNested { .. }
                     | UserError::MissingSourceForNested { .. }
                     | UserError::InvalidSettingsEmbedder { .. } => Code::InvalidSettingsEmbedders,
@@ -445,6 +481,12 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
+                    UserError::InvalidFacetRangesAttribute { .. }
+                    | UserError::TooManyFacetRangeBuckets { .. } => Code::InvalidSearchFacetRanges,
+                    UserError::InvalidHierarchicalFacetName { .. }
+                    | UserError::HierarchicalFacetAttributeNotFilterable { .. } => {
+                        Code::InvalidSearchHierarchicalFacets
+                    }
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
@@ -455,11 +497,14 @@ impl ErrorCode for milli::Error {
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
                     UserError::InvalidVectorDimensions { .. }
+                    | UserError::InvalidMultiVectorDimensions { .. }
                     | UserError::InvalidIndexingVectorDimensions { .. } => {
                         Code::InvalidVectorDimensions
                     }
                     UserError::InvalidVectorsMapType { .. }
-                    | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
+                    | UserError::InvalidVectorsEmbedderConf { .. }
+                    | UserError::InvalidIndexingSparseVector { .. } => Code::InvalidVectorsType,
+                    UserError::InvalidSparseVector { .. } => Code::InvalidSearchVector,
                     UserError::TooManyVectors(_, _) => Code::TooManyVectors,
                     UserError::SortError(_) => Code::InvalidSearchSort,
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
@@ -467,6 +512,7 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::InvalidSearchEmbedder(_) => Code::InvalidSearchEmbedder,
                     UserError::InvalidSimilarEmbedder(_) => Code::InvalidSimilarEmbedder,
+                    UserError::InvalidSearchReranker(_) => Code::InvalidSearchReranker,
                     UserError::VectorEmbeddingError(_) | UserError::DocumentEmbeddingError(_) => {
                         Code::VectorEmbeddingError
                     }
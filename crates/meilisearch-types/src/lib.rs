@@ -1,3 +1,4 @@
+pub mod analyzers;
 pub mod batch_view;
 pub mod batches;
 pub mod compression;
@@ -10,11 +11,14 @@ pub mod index_uid;
 pub mod index_uid_pattern;
 pub mod keys;
 pub mod locales;
+pub mod rerankers;
 pub mod settings;
 pub mod star_or;
+pub mod synonym_rules;
 pub mod task_view;
 pub mod tasks;
 pub mod versioning;
+pub mod webhooks;
 pub use milli::{heed, Index};
 use uuid::Uuid;
 pub use versioning::VERSION_FILE_NAME;
@@ -1,14 +1,51 @@
-use deserr::Deserr;
-use milli::{AttributePatterns, LocalizedAttributesRule};
+use std::convert::Infallible;
+
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
+use milli::{supports_stemming, AttributePatterns, LocalizedAttributesRule};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::InvalidSettingsLocalizedAttributes;
+
+/// The locales whose words can be stemmed.
+const STEMMING_LOCALES: &[&str] = &[
+    "ara", "dan", "deu", "ell", "eng", "fin", "fra", "hun", "ita", "nld", "nob", "por", "ron",
+    "rus", "spa", "swe", "tam", "tur",
+];
+
+fn validate_localized_attributes_rule<E: DeserializeError>(
+    rule: LocalizedAttributesRuleView,
+    location: ValuePointerRef,
+) -> Result<LocalizedAttributesRuleView, E> {
+    if rule.stemming && !rule.locales.iter().any(|&locale| supports_stemming(locale.into())) {
+        let msg = format!(
+            "`stemming` requires at least one locale supporting stemming, expected one of {}",
+            STEMMING_LOCALES.join(", ")
+        );
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg },
+            location,
+        )));
+    }
+    Ok(rule)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
-#[deserr(rename_all = camelCase)]
+#[deserr(rename_all = camelCase, validate = validate_localized_attributes_rule -> DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
 #[serde(rename_all = "camelCase")]
 pub struct LocalizedAttributesRuleView {
     pub attribute_patterns: AttributePatterns,
     pub locales: Vec<Locale>,
+    /// Also index and search the stems of the words, in the locales supporting stemming.
+    #[serde(default, skip_serializing_if = "is_false")]
+    #[deserr(default)]
+    pub stemming: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
 }
 
 impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
@@ -16,6 +53,7 @@ impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
         Self {
             attribute_patterns: rule.attribute_patterns,
             locales: rule.locales.into_iter().map(|l| l.into()).collect(),
+            stemming: rule.stemming,
         }
     }
 }
@@ -25,6 +63,7 @@ impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
         Self {
             attribute_patterns: view.attribute_patterns,
             locales: view.locales.into_iter().map(|l| l.into()).collect(),
+            stemming: view.stemming,
         }
     }
 }
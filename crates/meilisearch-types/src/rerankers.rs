@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::num::NonZeroUsize;
+
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
+use milli::prompt::{Prompt, PromptData};
+use milli::vector::rerank::{
+    HfRerankerOptions, RerankerConfig, RerankerOptions, RestRerankerOptions,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::InvalidSettingsRerankers;
+
+fn validate_reranker<E: DeserializeError>(
+    reranker: RerankerSettings,
+    location: ValuePointerRef,
+) -> Result<RerankerSettings, E> {
+    let msg = match reranker.check() {
+        Ok(()) => return Ok(reranker),
+        Err(msg) => msg,
+    };
+    Err(deserr::take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected { msg },
+        location,
+    )))
+}
+
+/// A reranker reordering the top hits of the searches requesting it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[deserr(rename_all = camelCase, deny_unknown_fields, validate = validate_reranker -> DeserrJsonError<InvalidSettingsRerankers>)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RerankerSettings {
+    pub source: RerankerSource,
+    /// The model of the Hugging Face Hub, only for the `huggingFace` source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub model: Option<String>,
+    /// The revision of the model, only for the `huggingFace` source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub revision: Option<String>,
+    /// Directory containing the files of the model, only for the `huggingFace` source.
+    ///
+    /// It must be inside the models directory set with `--models-dir`, relative paths are resolved from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub model_path: Option<String>,
+    /// The URL of the reranker, mandatory for the `rest` source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub url: Option<String>,
+    /// The key sent as a bearer token to the reranker, only for the `rest` source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub api_key: Option<String>,
+    /// Template request containing the `{{query}}` and an array of `{{document}}`s, mandatory for the `rest` source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<serde_json::Value>)]
+    pub request: Option<serde_json::Value>,
+    /// Template response containing an array of `{{score}}`s, mandatory for the `rest` source.
+    ///
+    /// When the scores are not in the order of the documents, each score must come with the `{{index}}` of its document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<serde_json::Value>)]
+    pub response: Option<serde_json::Value>,
+    /// Additional headers sent to the reranker, only for the `rest` source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub headers: Option<BTreeMap<String, String>>,
+    /// The template rendering the documents sent to the reranker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub document_template: Option<String>,
+    /// The maximum size of a rendered document, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    pub document_template_max_bytes: Option<NonZeroUsize>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserr, Serialize, Deserialize, ToSchema,
+)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum RerankerSource {
+    /// A reranker queried through a REST API.
+    Rest,
+    /// A cross-encoder model of the Hugging Face Hub, running locally.
+    HuggingFace,
+}
+
+impl RerankerSettings {
+    fn check(&self) -> Result<(), String> {
+        let forbidden: &[(&str, bool)] = match self.source {
+            RerankerSource::Rest => &[
+                ("model", self.model.is_some()),
+                ("revision", self.revision.is_some()),
+                ("modelPath", self.model_path.is_some()),
+            ],
+            RerankerSource::HuggingFace => &[
+                ("url", self.url.is_some()),
+                ("apiKey", self.api_key.is_some()),
+                ("request", self.request.is_some()),
+                ("response", self.response.is_some()),
+                ("headers", self.headers.is_some()),
+            ],
+        };
+        if let Some((field, _)) = forbidden.iter().find(|(_, present)| *present) {
+            return Err(format!("`{field}` is unavailable for source `{}`", self.source));
+        }
+
+        match self.source {
+            RerankerSource::Rest => {
+                let (Some(url), Some(request), Some(response)) =
+                    (&self.url, &self.request, &self.response)
+                else {
+                    return Err("`url`, `request` and `response` are mandatory for source `rest`"
+                        .to_string());
+                };
+                RestRerankerOptions {
+                    api_key: None,
+                    url: url.clone(),
+                    request: request.clone(),
+                    response: response.clone(),
+                    headers: Default::default(),
+                }
+                .check_templates()
+                .map_err(|error| error.to_string())?;
+            }
+            RerankerSource::HuggingFace => {
+                if self.model.is_none() && self.model_path.is_none() {
+                    return Err(
+                        "`model` or `modelPath` is mandatory for source `huggingFace`".to_string()
+                    );
+                }
+            }
+        }
+
+        if let Some(template) = &self.document_template {
+            Prompt::new(template.clone(), self.document_template_max_bytes)
+                .map_err(|error| format!("`documentTemplate`: {error}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for RerankerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RerankerSource::Rest => f.write_str("rest"),
+            RerankerSource::HuggingFace => f.write_str("huggingFace"),
+        }
+    }
+}
+
+impl From<RerankerConfig> for RerankerSettings {
+    fn from(RerankerConfig { options, prompt }: RerankerConfig) -> Self {
+        let mut settings = Self {
+            source: RerankerSource::Rest,
+            model: None,
+            revision: None,
+            model_path: None,
+            url: None,
+            api_key: None,
+            request: None,
+            response: None,
+            headers: None,
+            document_template: Some(prompt.template),
+            document_template_max_bytes: prompt.max_bytes,
+        };
+        match options {
+            RerankerOptions::Rest(RestRerankerOptions {
+                api_key,
+                url,
+                request,
+                response,
+                headers,
+            }) => {
+                settings.url = Some(url);
+                settings.api_key = api_key;
+                settings.request = Some(request);
+                settings.response = Some(response);
+                settings.headers = Some(headers);
+            }
+            RerankerOptions::HuggingFace(HfRerankerOptions { model, revision, model_path }) => {
+                settings.source = RerankerSource::HuggingFace;
+                settings.model = Some(model);
+                settings.revision = revision;
+                settings.model_path = model_path;
+            }
+        }
+        settings
+    }
+}
+
+impl From<RerankerSettings> for RerankerConfig {
+    /// Panics: if the settings were not checked
+    fn from(settings: RerankerSettings) -> Self {
+        let options = match settings.source {
+            RerankerSource::Rest => RerankerOptions::Rest(RestRerankerOptions {
+                api_key: settings.api_key,
+                url: settings.url.expect("checked reranker settings"),
+                request: settings.request.expect("checked reranker settings"),
+                response: settings.response.expect("checked reranker settings"),
+                headers: settings.headers.unwrap_or_default(),
+            }),
+            RerankerSource::HuggingFace => RerankerOptions::HuggingFace(HfRerankerOptions {
+                model: settings.model.or_else(|| settings.model_path.clone()).unwrap_or_default(),
+                revision: settings.revision,
+                model_path: settings.model_path,
+            }),
+        };
+        let prompt = PromptData {
+            template: settings.document_template.unwrap_or_else(|| PromptData::default().template),
+            max_bytes: settings.document_template_max_bytes,
+            chunking: None,
+        };
+        Self { options, prompt }
+    }
+}
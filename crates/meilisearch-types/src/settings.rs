@@ -11,14 +11,19 @@ use fst::IntoStreamer;
 use milli::index::{IndexEmbeddingConfig, PrefixSearch};
 use milli::proximity::ProximityPrecision;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, FilterableAttributesRule, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    Criterion, CriterionError, FacetStat, FilterableAttributesRule, Index, DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 use utoipa::ToSchema;
 
+use crate::analyzers::AnalyzerRuleView;
 use crate::deserr::DeserrJsonError;
 use crate::error::deserr_codes::*;
 use crate::facet_values_sort::FacetValuesSort;
 use crate::locales::LocalizedAttributesRuleView;
+use crate::rerankers::RerankerSettings;
+use crate::synonym_rules::SynonymRuleView;
 
 /// The maximum number of results that the engine
 /// will be able to return in one search call.
@@ -108,7 +113,7 @@ pub struct TypoSettings {
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
-#[deserr(rename_all = camelCase, deny_unknown_fields)]
+#[deserr(rename_all = camelCase, deny_unknown_fields, where_predicate = __Deserr_E: deserr::MergeWithError<DeserrJsonError<InvalidSettingsFaceting>>)]
 pub struct FacetingSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -118,6 +123,62 @@ pub struct FacetingSettings {
     #[deserr(default)]
     #[schema(value_type = Option<BTreeMap<String, FacetValuesSort>>, example = json!({ "genre": FacetValuesSort::Count }))]
     pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<BTreeMap<String, HierarchicalFacetSettings>>, example = json!({ "categories": { "attributes": ["categories.lvl0", "categories.lvl1"], "separator": " > " }}))]
+    pub hierarchical_facets: Setting<BTreeMap<String, HierarchicalFacetSettings>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<BTreeMap<String, Vec<FacetStat>>>, example = json!({ "price": ["sum", "avg", "p50"] }))]
+    pub facet_stats: Setting<BTreeMap<String, BTreeSet<FacetStat>>>,
+}
+
+fn validate_hierarchical_facet<E: DeserializeError>(
+    facet: HierarchicalFacetSettings,
+    location: ValuePointerRef,
+) -> Result<HierarchicalFacetSettings, E> {
+    let msg = if facet.attributes.is_empty() {
+        "`attributes` must contain at least one attribute".to_string()
+    } else if facet.separator.trim().is_empty() {
+        format!(
+            "`separator` must contain a non-whitespace character, but found `{:?}`",
+            facet.separator
+        )
+    } else {
+        return Ok(facet);
+    };
+    Err(deserr::take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected { msg },
+        location,
+    )))
+}
+
+/// A category tree whose levels are stored in distinct attributes, each containing
+/// the full path of the category with the names of the ancestors joined by the separator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase, validate = validate_hierarchical_facet -> DeserrJsonError<InvalidSettingsFaceting>)]
+pub struct HierarchicalFacetSettings {
+    /// The attributes of the levels, from the root to the leaves.
+    #[schema(example = json!(["categories.lvl0", "categories.lvl1"]))]
+    pub attributes: Vec<String>,
+    #[schema(example = json!(" > "))]
+    pub separator: String,
+}
+
+impl From<HierarchicalFacetSettings> for milli::HierarchicalFacet {
+    fn from(
+        HierarchicalFacetSettings { attributes, separator }: HierarchicalFacetSettings,
+    ) -> Self {
+        milli::HierarchicalFacet { attributes, separator }
+    }
+}
+
+impl From<milli::HierarchicalFacet> for HierarchicalFacetSettings {
+    fn from(milli::HierarchicalFacet { attributes, separator }: milli::HierarchicalFacet) -> Self {
+        HierarchicalFacetSettings { attributes, separator }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
@@ -288,6 +349,21 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Custom tokenization and normalization of the attributes matching the patterns.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsAnalyzers>)]
+    #[schema(value_type = Option<Vec<AnalyzerRuleView>>, example = json!([{ "attributePatterns": ["sku"], "tokenizer": "keyword" }]))]
+    pub analyzers: Setting<Vec<AnalyzerRuleView>>,
+    /// Synonyms applying in one or both directions, with an optional ranking penalty.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonymRules>)]
+    #[schema(value_type = Option<Vec<SynonymRuleView>>, example = json!([{ "type": "oneWay", "input": "tv", "synonyms": ["television"], "penalty": 1 }]))]
+    pub synonym_rules: Setting<Vec<SynonymRuleView>>,
+    /// Rerankers that searches can use to reorder their top hits, by name.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsRerankers>)]
+    #[schema(value_type = Option<BTreeMap<String, RerankerSettings>>, example = json!({ "crossEncoder": { "source": "huggingFace", "model": "cross-encoder/ms-marco-MiniLM-L-6-v2" } }))]
+    pub rerankers: Setting<BTreeMap<String, RerankerSettings>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -296,6 +372,12 @@ pub struct Settings<T> {
 
 impl<T> Settings<T> {
     pub fn hide_secrets(&mut self) {
+        if let Setting::Set(rerankers) = &mut self.rerankers {
+            for api_key in rerankers.values_mut().filter_map(|reranker| reranker.api_key.as_mut()) {
+                Self::hide_secret(api_key);
+            }
+        }
+
         let Setting::Set(embedders) = &mut self.embedders else {
             return;
         };
@@ -313,7 +395,7 @@ impl<T> Settings<T> {
         }
     }
 
-    fn hide_secret(secret: &mut String) {
+    pub(crate) fn hide_secret(secret: &mut String) {
         match secret.len() {
             x if x < 10 => {
                 secret.replace_range(.., "XXX...");
@@ -354,6 +436,9 @@ impl Settings<Checked> {
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            analyzers: Setting::Reset,
+            synonym_rules: Setting::Reset,
+            rerankers: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -380,6 +465,9 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            analyzers,
+            synonym_rules,
+            rerankers,
             _kind,
         } = self;
 
@@ -404,6 +492,9 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            analyzers,
+            synonym_rules,
+            rerankers,
             _kind: PhantomData,
         }
     }
@@ -454,6 +545,9 @@ impl Settings<Unchecked> {
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
             prefix_search: self.prefix_search,
+            analyzers: self.analyzers,
+            synonym_rules: self.synonym_rules,
+            rerankers: self.rerankers,
             _kind: PhantomData,
         }
     }
@@ -530,6 +624,9 @@ impl Settings<Unchecked> {
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
             facet_search: other.facet_search.or(self.facet_search),
+            analyzers: other.analyzers.or(self.analyzers.clone()),
+            synonym_rules: other.synonym_rules.or(self.synonym_rules.clone()),
+            rerankers: other.rerankers.or(self.rerankers.clone()),
             _kind: PhantomData,
         }
     }
@@ -568,6 +665,9 @@ pub fn apply_settings_to_builder(
         localized_attributes: localized_attributes_rules,
         facet_search,
         prefix_search,
+        analyzers,
+        synonym_rules,
+        rerankers,
         _kind,
     } = settings;
 
@@ -658,6 +758,33 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match analyzers {
+        Setting::Set(ref analyzers) => {
+            builder.set_analyzers(analyzers.iter().cloned().map(|a| a.into()).collect())
+        }
+        Setting::Reset => builder.reset_analyzers(),
+        Setting::NotSet => (),
+    }
+
+    match synonym_rules {
+        Setting::Set(ref rules) => {
+            builder.set_synonym_rules(rules.iter().cloned().map(|r| r.into()).collect())
+        }
+        Setting::Reset => builder.reset_synonym_rules(),
+        Setting::NotSet => (),
+    }
+
+    match rerankers {
+        Setting::Set(ref rerankers) => builder.set_rerankers(
+            rerankers
+                .iter()
+                .map(|(name, reranker)| (name.clone(), reranker.clone().into()))
+                .collect(),
+        ),
+        Setting::Reset => builder.reset_rerankers(),
+        Setting::NotSet => (),
+    }
+
     match typo_tolerance {
         Setting::Set(ref value) => {
             match value.enabled {
@@ -714,7 +841,12 @@ pub fn apply_settings_to_builder(
     }
 
     match faceting {
-        Setting::Set(FacetingSettings { max_values_per_facet, sort_facet_values_by }) => {
+        Setting::Set(FacetingSettings {
+            max_values_per_facet,
+            sort_facet_values_by,
+            hierarchical_facets,
+            facet_stats,
+        }) => {
             match max_values_per_facet {
                 Setting::Set(val) => builder.set_max_values_per_facet(*val),
                 Setting::Reset => builder.reset_max_values_per_facet(),
@@ -727,10 +859,24 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_sort_facet_values_by(),
                 Setting::NotSet => (),
             }
+            match hierarchical_facets {
+                Setting::Set(val) => builder.set_hierarchical_facets(
+                    val.iter().map(|(name, facet)| (name.clone(), facet.clone().into())).collect(),
+                ),
+                Setting::Reset => builder.reset_hierarchical_facets(),
+                Setting::NotSet => (),
+            }
+            match facet_stats {
+                Setting::Set(val) => builder.set_facet_stats(val.clone()),
+                Setting::Reset => builder.reset_facet_stats(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             builder.reset_max_values_per_facet();
             builder.reset_sort_facet_values_by();
+            builder.reset_hierarchical_facets();
+            builder.reset_facet_stats();
         }
         Setting::NotSet => (),
     }
@@ -848,6 +994,14 @@ pub fn settings(
                 .map(|(name, sort)| (name, sort.into()))
                 .collect(),
         ),
+        hierarchical_facets: Setting::Set(
+            index
+                .hierarchical_facets(rtxn)?
+                .into_iter()
+                .map(|(name, facet)| (name, facet.into()))
+                .collect(),
+        ),
+        facet_stats: Setting::Set(index.facet_stats(rtxn)?),
     };
 
     let pagination = PaginationSettings {
@@ -876,6 +1030,12 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let analyzers = index.analyzers(rtxn)?;
+
+    let synonym_rules = index.user_defined_synonym_rules(rtxn)?;
+
+    let rerankers = index.rerankers(rtxn)?;
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -914,6 +1074,11 @@ pub fn settings(
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
         facet_search: Setting::Set(facet_search),
+        analyzers: Setting::Set(analyzers.into_iter().map(|a| a.into()).collect()),
+        synonym_rules: Setting::Set(synonym_rules.into_iter().map(|r| r.into()).collect()),
+        rerankers: Setting::Set(
+            rerankers.into_iter().map(|(name, reranker)| (name, reranker.into())).collect(),
+        ),
         _kind: PhantomData,
     };
 
@@ -1141,6 +1306,9 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            analyzers: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1172,6 +1340,9 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            analyzers: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -0,0 +1,133 @@
+use std::convert::Infallible;
+
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
+use milli::{SynonymRule, MAX_SYNONYM_PENALTY};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::deserr::DeserrJsonError;
+use crate::error::deserr_codes::InvalidSettingsSynonymRules;
+
+fn validate_synonym_rule<E: DeserializeError>(
+    rule: SynonymRuleView,
+    location: ValuePointerRef,
+) -> Result<SynonymRuleView, E> {
+    let msg = match &rule {
+        SynonymRuleView::MultiWay { synonyms, .. } if synonyms.len() < 2 => {
+            format!(
+                "`synonyms` must contain at least two expressions, but found {}",
+                synonyms.len()
+            )
+        }
+        SynonymRuleView::MultiWay { penalty, .. } | SynonymRuleView::OneWay { penalty, .. }
+            if *penalty > MAX_SYNONYM_PENALTY =>
+        {
+            format!("`penalty` must be between 0 and {MAX_SYNONYM_PENALTY}, but found `{penalty}`")
+        }
+        SynonymRuleView::OneWay { synonyms, .. } if synonyms.is_empty() => {
+            "`synonyms` must contain at least one expression".to_string()
+        }
+        SynonymRuleView::AltCorrection { typos, .. }
+            if !(1..=MAX_SYNONYM_PENALTY).contains(typos) =>
+        {
+            format!("`typos` must be between 1 and {MAX_SYNONYM_PENALTY}, but found `{typos}`")
+        }
+        SynonymRuleView::AltCorrection { corrections, .. } if corrections.is_empty() => {
+            "`corrections` must contain at least one expression".to_string()
+        }
+        SynonymRuleView::Placeholder { replacements, .. } if replacements.is_empty() => {
+            "`replacements` must contain at least one expression".to_string()
+        }
+        _ => return Ok(rule),
+    };
+    Err(deserr::take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected { msg },
+        location,
+    )))
+}
+
+/// A rule defining which expressions of a search query also match other expressions.
+///
+/// The synonyms with a `penalty` are ranked by the `typo` ranking rule as if they
+/// were that many typos away from the query.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize, ToSchema)]
+#[deserr(tag = "type", rename_all = camelCase, deny_unknown_fields, validate = validate_synonym_rule -> DeserrJsonError<InvalidSettingsSynonymRules>)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+pub enum SynonymRuleView {
+    /// All the expressions are synonyms of each other.
+    MultiWay {
+        synonyms: Vec<String>,
+        #[serde(default)]
+        #[deserr(default)]
+        penalty: u8,
+    },
+    /// The synonyms match when the input is in the query, but not the other way around.
+    OneWay {
+        input: String,
+        synonyms: Vec<String>,
+        #[serde(default)]
+        #[deserr(default)]
+        penalty: u8,
+    },
+    /// The corrections of the word are ranked as if they were `typos` typos away from it.
+    AltCorrection {
+        word: String,
+        corrections: Vec<String>,
+        #[serde(default = "default_typos")]
+        #[deserr(default = 1)]
+        typos: u8,
+    },
+    /// The placeholder written in the documents matches any of its replacements in the query.
+    Placeholder { placeholder: String, replacements: Vec<String> },
+}
+
+fn default_typos() -> u8 {
+    1
+}
+
+impl SynonymRuleView {
+    /// The name of the type of the rule, as written in the settings.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SynonymRuleView::MultiWay { .. } => "multiWay",
+            SynonymRuleView::OneWay { .. } => "oneWay",
+            SynonymRuleView::AltCorrection { .. } => "altCorrection",
+            SynonymRuleView::Placeholder { .. } => "placeholder",
+        }
+    }
+}
+
+impl From<SynonymRule> for SynonymRuleView {
+    fn from(rule: SynonymRule) -> Self {
+        match rule {
+            SynonymRule::MultiWay { synonyms, penalty } => Self::MultiWay { synonyms, penalty },
+            SynonymRule::OneWay { input, synonyms, penalty } => {
+                Self::OneWay { input, synonyms, penalty }
+            }
+            SynonymRule::AltCorrection { word, corrections, typos } => {
+                Self::AltCorrection { word, corrections, typos }
+            }
+            SynonymRule::Placeholder { placeholder, replacements } => {
+                Self::Placeholder { placeholder, replacements }
+            }
+        }
+    }
+}
+
+impl From<SynonymRuleView> for SynonymRule {
+    fn from(view: SynonymRuleView) -> Self {
+        match view {
+            SynonymRuleView::MultiWay { synonyms, penalty } => Self::MultiWay { synonyms, penalty },
+            SynonymRuleView::OneWay { input, synonyms, penalty } => {
+                Self::OneWay { input, synonyms, penalty }
+            }
+            SynonymRuleView::AltCorrection { word, corrections, typos } => {
+                Self::AltCorrection { word, corrections, typos }
+            }
+            SynonymRuleView::Placeholder { placeholder, replacements } => {
+                Self::Placeholder { placeholder, replacements }
+            }
+        }
+    }
+}
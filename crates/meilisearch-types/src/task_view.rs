@@ -6,7 +6,9 @@ use utoipa::ToSchema;
 use crate::batches::BatchId;
 use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
-use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
+use crate::tasks::{
+    serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskGroup, TaskId, TaskPriority,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -46,6 +48,31 @@ pub struct TaskView {
     #[schema(value_type = String, example = json!("2024-08-08_14:12:09.393Z"))]
     #[serde(with = "time::serde::rfc3339::option", default)]
     pub finished_at: Option<OffsetDateTime>,
+    /// The date before which a scheduled task is not processed, in the `RFC 3339` format.
+    #[schema(value_type = Option<String>, example = json!("2024-08-09_00:00:00Z"))]
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub run_at: Option<OffsetDateTime>,
+    /// The interval after which a copy of the task is enqueued once it is finished, expressed as a `ISO-8601` duration format.
+    #[schema(value_type = Option<String>, example = json!("P1D"))]
+    #[serde(serialize_with = "serialize_duration", skip_serializing_if = "Option::is_none")]
+    pub run_every: Option<Duration>,
+    /// Whether the task is enqueued and waits for its `runAt` date, only present when `true`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub scheduled: bool,
+    /// The uids of the tasks that must be finished before this one is processed.
+    #[schema(value_type = Vec<u32>, example = json!([4310, 4311]))]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub depends_on: Vec<TaskId>,
+    /// The group of tasks that are committed, or not, together with this one.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub group: Option<TaskGroup>,
+    /// The priority of the task, only present when it is not `normal`.
+    #[serde(skip_serializing_if = "TaskPriority::is_normal", default)]
+    pub priority: TaskPriority,
 }
 
 impl TaskView {
@@ -63,6 +90,13 @@ impl TaskView {
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
+            run_at: task.run_at,
+            run_every: task.run_every,
+            scheduled: task.status == Status::Enqueued
+                && task.run_at.is_some_and(|run_at| run_at > OffsetDateTime::now_utc()),
+            depends_on: task.depends_on.clone(),
+            group: task.group.clone(),
+            priority: task.priority,
         }
     }
 }
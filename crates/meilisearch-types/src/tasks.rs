@@ -7,7 +7,7 @@ use enum_iterator::Sequence;
 use milli::update::IndexDocumentsMethod;
 use milli::Object;
 use roaring::RoaringBitmap;
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -32,6 +32,30 @@ pub struct Task {
     pub started_at: Option<OffsetDateTime>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub finished_at: Option<OffsetDateTime>,
+    /// The task stays enqueued and is not processed before this date.
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub run_at: Option<OffsetDateTime>,
+    /// Once finished, a copy of the task is enqueued to run after this interval.
+    #[serde(
+        serialize_with = "serialize_duration",
+        deserialize_with = "deserialize_duration",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub run_every: Option<Duration>,
+    /// The task stays enqueued until all these tasks are finished.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub depends_on: Vec<TaskId>,
+    /// The group of tasks that are committed, or not, together with this one.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub group: Option<TaskGroup>,
+    /// The tasks of the indexes with the highest priority tasks are processed first.
+    #[serde(skip_serializing_if = "TaskPriority::is_normal", default)]
+    pub priority: TaskPriority,
 
     pub error: Option<ResponseError>,
     pub canceled_by: Option<TaskId>,
@@ -157,6 +181,54 @@ pub enum KindWithContent {
     },
 }
 
+/// A group of tasks applied together: when one of them fails, none of them is applied.
+///
+/// A group interrupted while committing its indexes, e.g. by a crash, is resumed before
+/// any other task and applied to the indexes it was not committed to yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskGroup {
+    /// The name shared by all the tasks of the group.
+    pub name: String,
+    /// Whether this task is the last one of the group. Once committed, the group
+    /// cannot receive new tasks and is processed as a whole.
+    #[serde(default)]
+    pub commit: bool,
+}
+
+impl KindWithContent {
+    /// Whether a task of this kind can be part of a [`TaskGroup`].
+    ///
+    /// Only the operations applied within the write transaction of an existing index
+    /// can be rolled back together.
+    pub fn can_be_grouped(&self) -> bool {
+        matches!(
+            self,
+            KindWithContent::DocumentAdditionOrUpdate { .. }
+                | KindWithContent::DocumentEdition { .. }
+                | KindWithContent::DocumentDeletion { .. }
+                | KindWithContent::DocumentDeletionByFilter { .. }
+                | KindWithContent::DocumentClear { .. }
+                | KindWithContent::SettingsUpdate { .. }
+        )
+    }
+
+    /// Whether a task of this kind can be enqueued again once finished, see [`Task::run_every`].
+    ///
+    /// The document additions are excluded because their content file is deleted once
+    /// processed, and the task deletions and cancelations because they match a fixed
+    /// set of tasks.
+    pub fn can_recur(&self) -> bool {
+        !matches!(
+            self,
+            KindWithContent::DocumentAdditionOrUpdate { .. }
+                | KindWithContent::TaskCancelation { .. }
+                | KindWithContent::TaskDeletion { .. }
+                | KindWithContent::UpgradeDatabase { .. }
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IndexSwap {
@@ -469,6 +541,121 @@ impl fmt::Display for ParseTaskStatusError {
 }
 impl std::error::Error for ParseTaskStatusError {}
 
+/// The priority of a task.
+///
+/// The tasks of an index are always processed in their registration order, the priority
+/// only decides which index is processed first.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Sequence,
+    PartialOrd,
+    Ord,
+    ToSchema,
+)]
+#[schema(example = json!(TaskPriority::High))]
+#[serde(rename_all = "camelCase")]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl TaskPriority {
+    pub fn is_normal(&self) -> bool {
+        *self == TaskPriority::Normal
+    }
+}
+
+impl Display for TaskPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskPriority::Low => write!(f, "low"),
+            TaskPriority::Normal => write!(f, "normal"),
+            TaskPriority::High => write!(f, "high"),
+        }
+    }
+}
+
+impl FromStr for TaskPriority {
+    type Err = ParseTaskPriorityError;
+
+    fn from_str(priority: &str) -> Result<Self, Self::Err> {
+        if priority.eq_ignore_ascii_case("low") {
+            Ok(TaskPriority::Low)
+        } else if priority.eq_ignore_ascii_case("normal") {
+            Ok(TaskPriority::Normal)
+        } else if priority.eq_ignore_ascii_case("high") {
+            Ok(TaskPriority::High)
+        } else {
+            Err(ParseTaskPriorityError(priority.to_owned()))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseTaskPriorityError(pub String);
+impl fmt::Display for ParseTaskPriorityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid task priority. Available priorities are {}.",
+            self.0,
+            enum_iterator::all::<TaskPriority>()
+                .map(|s| format!("`{s}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+impl std::error::Error for ParseTaskPriorityError {}
+
+/// How the scheduler picks the index of the next batch among the indexes whose
+/// enqueued tasks share the highest priority.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SchedulingPolicy {
+    /// The index of the oldest enqueued task is processed first.
+    #[default]
+    Fifo,
+    /// The indexes are processed one after the other, in the lexicographic order
+    /// of their names, so that a busy index cannot starve the other ones.
+    RoundRobin,
+}
+
+impl Display for SchedulingPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulingPolicy::Fifo => write!(f, "fifo"),
+            SchedulingPolicy::RoundRobin => write!(f, "roundRobin"),
+        }
+    }
+}
+
+impl FromStr for SchedulingPolicy {
+    type Err = ParseSchedulingPolicyError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.trim().to_lowercase().as_str() {
+            "fifo" => Ok(SchedulingPolicy::Fifo),
+            "roundrobin" | "round-robin" | "round_robin" => Ok(SchedulingPolicy::RoundRobin),
+            _ => Err(ParseSchedulingPolicyError(policy.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("`{0}` is not a valid scheduling policy. Available policies are `fifo` and `roundRobin`.")]
+pub struct ParseSchedulingPolicyError(pub String);
+
 /// The type of the task.
 #[derive(
     Debug,
@@ -724,6 +911,10 @@ pub enum BatchStopReason {
     SettingsWithDocumentOperation {
         id: TaskId,
     },
+    TaskGroup {
+        name: String,
+    },
+    UnsatisfiedDependencies,
 }
 
 impl BatchStopReason {
@@ -812,6 +1003,12 @@ impl Display for BatchStopReason {
                     "task with id {id} is a document operation in a batch of settings changes"
                 )
             }
+            BatchStopReason::TaskGroup { name } => {
+                write!(f, "the tasks of the group `{name}` are committed together")
+            }
+            BatchStopReason::UnsatisfiedDependencies => {
+                f.write_str("batched all the tasks whose dependencies did not succeed")
+            }
         }
     }
 }
@@ -867,11 +1064,70 @@ pub fn serialize_duration<S: Serializer>(
     }
 }
 
+/// Deserialize a `time::Duration` written by [`serialize_duration`].
+pub fn deserialize_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(s) => parse_duration(&s).map(Some).ok_or_else(|| {
+            serde::de::Error::custom(format!("`{s}` is not a valid ISO 8601 duration"))
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Parse the ISO 8601 durations made of weeks, days, hours, minutes and seconds,
+/// e.g. `P1D`, `PT12H` or `PT1.5S`. The years and months are rejected since their
+/// length varies.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    if !s.chars().all(|c| c.is_ascii_digit() || c == '.' || c.is_ascii_uppercase()) {
+        return None;
+    }
+    let s = s.strip_prefix('P')?;
+    let (date, time) = match s.split_once('T') {
+        Some((_, "")) => return None,
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return None;
+    }
+
+    let mut duration = Duration::ZERO;
+    let mut date = date;
+    for (unit, length) in [('W', Duration::WEEK), ('D', Duration::DAY)] {
+        if let Some((value, rest)) = date.split_once(unit) {
+            duration += length.checked_mul(value.parse().ok()?)?;
+            date = rest;
+        }
+    }
+    if !date.is_empty() {
+        return None;
+    }
+
+    let mut time = time.unwrap_or_default();
+    for (unit, length) in [('H', Duration::HOUR), ('M', Duration::MINUTE)] {
+        if let Some((value, rest)) = time.split_once(unit) {
+            duration += length.checked_mul(value.parse().ok()?)?;
+            time = rest;
+        }
+    }
+    if let Some(value) = time.strip_suffix('S') {
+        duration += Duration::checked_seconds_f64(value.parse().ok()?)?;
+    } else if !time.is_empty() {
+        return None;
+    }
+
+    Some(duration)
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use super::{Details, Kind};
+    use time::Duration;
+
+    use super::{parse_duration, serialize_duration, Details, Kind};
     use crate::heed::types::SerdeJson;
     use crate::heed::{BytesDecode, BytesEncode};
 
@@ -896,4 +1152,24 @@ mod tests {
             assert_eq!(kind, k, "{kind}.to_string() returned {s} which was parsed as {k}");
         }
     }
+
+    #[test]
+    fn parse_iso8601_durations() {
+        assert_eq!(parse_duration("P1D"), Some(Duration::DAY));
+        assert_eq!(parse_duration("P2W"), Some(Duration::WEEK * 2));
+        assert_eq!(parse_duration("PT12H"), Some(Duration::HOUR * 12));
+        assert_eq!(parse_duration("P1DT1H30M"), Some(Duration::minutes(25 * 60 + 30)));
+        assert_eq!(parse_duration("PT1.500S"), Some(Duration::milliseconds(1500)));
+
+        for invalid in ["", "P", "PT", "1D", "P1Y", "P1M", "PT1D", "P1H", "PT-1S", "PT1H2"] {
+            assert_eq!(parse_duration(invalid), None, "{invalid:?} should not be parsed");
+        }
+
+        // the durations written by `serialize_duration` can be read back
+        for duration in [Duration::DAY + Duration::SECOND, Duration::microseconds(1_000_001)] {
+            let value = serialize_duration(&Some(duration), serde_json::value::Serializer).unwrap();
+            let parsed = parse_duration(value.as_str().unwrap());
+            assert_eq!(parsed, Some(duration));
+        }
+    }
 }
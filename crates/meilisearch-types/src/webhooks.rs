@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::index_uid_pattern::IndexUidPattern;
+use crate::settings::{Settings, Unchecked};
+use crate::tasks::{Kind, Status, Task};
+
+/// An endpoint notified of the tasks processed by the instance.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub url: String,
+    /// The additional headers sent with every delivery, such as an `Authorization` header.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// The secret used to sign the deliveries, when present.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// The index patterns of the notified tasks, all the tasks when `None`.
+    #[serde(default)]
+    pub index_uids: Option<Vec<IndexUidPattern>>,
+    /// The types of the notified tasks, all the types when `None`.
+    #[serde(default)]
+    pub types: Option<Vec<Kind>>,
+    /// The statuses of the notified tasks, all the statuses when `None`.
+    #[serde(default)]
+    pub statuses: Option<Vec<Status>>,
+}
+
+impl Webhook {
+    /// Hides the values of the headers, as they usually contain credentials.
+    pub fn hide_secrets(&mut self) {
+        for value in self.headers.values_mut() {
+            Settings::<Unchecked>::hide_secret(value);
+        }
+    }
+
+    /// Whether the task must be sent to this webhook.
+    ///
+    /// Tasks that are not related to an index, such as dumps or task deletions,
+    /// are only sent when all the indexes are notified.
+    pub fn matches(&self, task: &Task) -> bool {
+        let index_matches = match &self.index_uids {
+            None => true,
+            Some(patterns) => {
+                let indexes = task.indexes();
+                if indexes.is_empty() {
+                    patterns.iter().any(IndexUidPattern::matches_all)
+                } else {
+                    indexes.iter().any(|index| patterns.iter().any(|p| p.matches_str(index)))
+                }
+            }
+        };
+        let type_matches =
+            self.types.as_ref().map_or(true, |types| types.contains(&task.kind.as_kind()));
+        let status_matches =
+            self.statuses.as_ref().map_or(true, |statuses| statuses.contains(&task.status));
+
+        index_matches && type_matches && status_matches
+    }
+}
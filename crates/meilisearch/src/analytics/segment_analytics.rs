@@ -11,6 +11,7 @@ use byte_unit::Byte;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::{AuthController, AuthFilter};
 use meilisearch_types::features::RuntimeTogglableFeatures;
+use meilisearch_types::tasks::SchedulingPolicy;
 use meilisearch_types::InstanceUid;
 use once_cell::sync::Lazy;
 use segment::message::{Identify, Track, User};
@@ -196,6 +197,7 @@ struct Infos {
     experimental_reduce_indexing_memory_usage: bool,
     experimental_max_number_of_batched_tasks: usize,
     experimental_limit_batched_tasks_total_size: u64,
+    experimental_scheduling_policy: SchedulingPolicy,
     experimental_network: bool,
     experimental_get_task_documents_route: bool,
     experimental_composite_embedders: bool,
@@ -204,6 +206,7 @@ struct Infos {
     db_path: bool,
     import_dump: bool,
     dump_dir: bool,
+    models_dir: bool,
     ignore_missing_dump: bool,
     ignore_dump_if_db_exists: bool,
     import_snapshot: bool,
@@ -218,6 +221,7 @@ struct Infos {
     log_level: String,
     max_indexing_memory: MaxMemory,
     max_indexing_threads: MaxThreads,
+    experimental_max_concurrent_batches: usize,
     with_configuration_file: bool,
     ssl_auth_path: bool,
     ssl_cert_path: bool,
@@ -247,6 +251,7 @@ impl Infos {
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
+            experimental_scheduling_policy,
             experimental_embedding_cache_entries,
             http_addr,
             master_key: _,
@@ -272,6 +277,7 @@ impl Infos {
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             dump_dir,
+            models_dir,
             log_level,
             indexer_options,
             config_file_path,
@@ -283,8 +289,12 @@ impl Infos {
             ScheduleSnapshot::Enabled(interval) => Some(interval),
         };
 
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } =
-            indexer_options;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            experimental_max_concurrent_batches,
+            skip_index_budget: _,
+        } = indexer_options;
 
         let RuntimeTogglableFeatures {
             metrics,
@@ -319,6 +329,7 @@ impl Infos {
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
             dump_dir: dump_dir != PathBuf::from("dumps/"),
+            models_dir: models_dir.is_some(),
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             import_snapshot: import_snapshot.is_some(),
@@ -331,11 +342,13 @@ impl Infos {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size:
                 experimental_limit_batched_tasks_total_size.into(),
+            experimental_scheduling_policy,
             task_queue_webhook: task_webhook_url.is_some(),
             task_webhook_authorization_header: task_webhook_authorization_header.is_some(),
             log_level: log_level.to_string(),
             max_indexing_memory,
             max_indexing_threads,
+            experimental_max_concurrent_batches,
             with_configuration_file: config_file_path.is_some(),
             ssl_auth_path: ssl_auth_path.is_some(),
             ssl_cert_path: ssl_cert_path.is_some(),
@@ -35,6 +35,12 @@ pub enum MeilisearchHttpError {
     PaginationInFederatedQuery(usize, &'static str),
     #[error("Inside `.queries[{0}]`: Using facet options is not allowed in federated queries.\n - Hint: remove `facets` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.facetsByIndex.{1}: {2:?}` for facets in federated search")]
     FacetsInFederatedQuery(usize, String, Vec<String>),
+    #[error("Inside `.queries[{0}]`: Using facet ranges is not allowed in federated queries.\n - Hint: remove `facetRanges` from query #{0} or remove `federation` from the request")]
+    FacetRangesInFederatedQuery(usize),
+    #[error("Inside `.queries[{0}]`: Using hierarchical facets is not allowed in federated queries.\n - Hint: remove `hierarchicalFacets` from query #{0} or remove `federation` from the request")]
+    HierarchicalFacetsInFederatedQuery(usize),
+    #[error("Inside `.queries[{0}]`: Using disjunctive facets is not allowed in federated queries.\n - Hint: remove `disjunctiveFacets` from query #{0} or remove `federation` from the request")]
+    DisjunctiveFacetsInFederatedQuery(usize),
     #[error("Inconsistent order for values in facet `{facet}`: index `{previous_uid}` orders {previous_facet_order}, but index `{current_uid}` orders {index_facet_order}.\n - Hint: Remove `federation.mergeFacets` or change `faceting.sortFacetValuesBy` to be consistent in settings.")]
     InconsistentFacetOrder {
         facet: String,
@@ -78,6 +84,8 @@ pub enum MeilisearchHttpError {
     Join(#[from] JoinError),
     #[error("Invalid request: missing `hybrid` parameter when `vector` is present.")]
     MissingSearchHybrid,
+    #[error("Invalid value in parameter `rerank.topK`: `{top_k}` is greater than the `pagination.maxTotalHits` of the index (`{max_total_hits}`).\n - Hint: lower `rerank.topK` or raise `pagination.maxTotalHits` in the settings of the index.")]
+    RerankTopKTooLarge { top_k: usize, max_total_hits: usize },
 }
 
 impl MeilisearchHttpError {
@@ -111,6 +119,7 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::RerankTopKTooLarge { .. } => Code::InvalidSearchRerankTopK,
             MeilisearchHttpError::FederationOptionsInNonFederatedRequest(_) => {
                 Code::InvalidMultiSearchFederationOptions
             }
@@ -118,6 +127,11 @@ impl ErrorCode for MeilisearchHttpError {
                 Code::InvalidMultiSearchQueryPagination
             }
             MeilisearchHttpError::FacetsInFederatedQuery(..) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::FacetRangesInFederatedQuery(_)
+            | MeilisearchHttpError::HierarchicalFacetsInFederatedQuery(_)
+            | MeilisearchHttpError::DisjunctiveFacetsInFederatedQuery(_) => {
+                Code::InvalidMultiSearchQueryFacets
+            }
             MeilisearchHttpError::InconsistentFacetOrder { .. } => {
                 Code::InvalidMultiSearchFacetOrder
             }
@@ -14,6 +14,7 @@ use clap::Parser;
 use meilisearch_types::features::InstanceTogglableFeatures;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::ThreadPoolNoAbortBuilder;
+use meilisearch_types::tasks::SchedulingPolicy;
 use rustls::server::{ServerSessionMemoryCache, WebPkiClientVerifier};
 use rustls::RootCertStore;
 use rustls_pemfile::{certs, ec_private_keys, rsa_private_keys};
@@ -47,6 +48,7 @@ const MEILI_IMPORT_DUMP: &str = "MEILI_IMPORT_DUMP";
 const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
 const MEILI_DUMP_DIR: &str = "MEILI_DUMP_DIR";
+const MEILI_MODELS_DIR: &str = "MEILI_MODELS_DIR";
 const MEILI_LOG_LEVEL: &str = "MEILI_LOG_LEVEL";
 const MEILI_EXPERIMENTAL_LOGS_MODE: &str = "MEILI_EXPERIMENTAL_LOGS_MODE";
 const MEILI_EXPERIMENTAL_DUMPLESS_UPGRADE: &str = "MEILI_EXPERIMENTAL_DUMPLESS_UPGRADE";
@@ -63,6 +65,7 @@ const MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS: &str =
     "MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS";
 const MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE: &str =
     "MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_SIZE";
+const MEILI_EXPERIMENTAL_SCHEDULING_POLICY: &str = "MEILI_EXPERIMENTAL_SCHEDULING_POLICY";
 const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES: &str =
     "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES";
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
@@ -77,6 +80,7 @@ const DEFAULT_DUMP_DIR: &str = "dumps/";
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
+const MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES: &str = "MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES";
 const DEFAULT_LOG_EVERY_N: usize = 100_000;
 
 // Each environment (index and task-db) is taking space in the virtual address space.
@@ -338,6 +342,14 @@ pub struct Opt {
     #[serde(default = "default_dump_dir")]
     pub dump_dir: PathBuf,
 
+    /// Sets the directory of the local models used by the `onnx` and `huggingFace` embedders
+    /// and rerankers.
+    ///
+    /// The `modelPath` of these embedders and rerankers must lead inside this directory,
+    /// relative paths are resolved from it. Local models are disabled when it is not defined.
+    #[clap(long, env = MEILI_MODELS_DIR)]
+    pub models_dir: Option<PathBuf>,
+
     /// Defines how much detail should be present in Meilisearch's logs.
     ///
     /// Meilisearch currently supports six log levels, listed in order of
@@ -447,6 +459,14 @@ pub struct Opt {
     #[serde(default = "default_limit_batched_tasks_total_size")]
     pub experimental_limit_batched_tasks_total_size: Byte,
 
+    /// Experimentally picks the index of the next batch in turn among the indexes whose enqueued tasks
+    /// share the highest priority, so that a busy index cannot starve the other ones.
+    ///
+    /// Supported values are `fifo`, which processes the index of the oldest enqueued task first, and `roundRobin`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SCHEDULING_POLICY, default_value_t)]
+    #[serde(default)]
+    pub experimental_scheduling_policy: SchedulingPolicy,
+
     /// Enables experimental caching of search query embeddings. The value represents the maximal number of entries in the cache of each
     /// distinct embedder.
     ///
@@ -536,6 +556,7 @@ impl Opt {
             snapshot_dir,
             schedule_snapshot,
             dump_dir,
+            models_dir,
             log_level,
             indexer_options,
             import_snapshot: _,
@@ -558,6 +579,7 @@ impl Opt {
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
+            experimental_scheduling_policy,
             experimental_embedding_cache_entries,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
@@ -602,6 +624,9 @@ impl Opt {
         }
 
         export_to_env_if_not_present(MEILI_DUMP_DIR, dump_dir);
+        if let Some(models_dir) = models_dir {
+            export_to_env_if_not_present(MEILI_MODELS_DIR, models_dir);
+        }
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level.to_string());
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_CONTAINS_FILTER,
@@ -651,6 +676,10 @@ impl Opt {
             MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE,
             experimental_limit_batched_tasks_total_size.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_SCHEDULING_POLICY,
+            experimental_scheduling_policy.to_string(),
+        );
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES,
             experimental_embedding_cache_entries.to_string(),
@@ -726,6 +755,14 @@ pub struct IndexerOpts {
     #[serde(default)]
     pub max_indexing_threads: MaxThreads,
 
+    /// Experimentally processes the batches of different indexes at the same time. The value is the
+    /// maximum number of batches processed concurrently, they share the indexing memory and threads.
+    ///
+    /// The tasks that are not related to a single index, such as dumps or index swaps, are still processed alone.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES, default_value_t = default_max_concurrent_batches())]
+    #[serde(default = "default_max_concurrent_batches")]
+    pub experimental_max_concurrent_batches: usize,
+
     /// Whether or not we want to determine the budget of virtual memory address space we have available dynamically
     /// (the default), or statically.
     ///
@@ -739,7 +776,12 @@ pub struct IndexerOpts {
 impl IndexerOpts {
     /// Exports the values to their corresponding env vars if they are not set.
     pub fn export_to_env(self) {
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } = self;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            experimental_max_concurrent_batches,
+            skip_index_budget: _,
+        } = self;
         if let Some(max_indexing_memory) = max_indexing_memory.0 {
             export_to_env_if_not_present(
                 MEILI_MAX_INDEXING_MEMORY,
@@ -750,6 +792,10 @@ impl IndexerOpts {
             MEILI_MAX_INDEXING_THREADS,
             max_indexing_threads.0.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES,
+            experimental_max_concurrent_batches.to_string(),
+        );
     }
 }
 
@@ -768,6 +814,7 @@ impl TryFrom<&IndexerOpts> for IndexerConfig {
             thread_pool: Some(thread_pool),
             max_positions_per_attributes: None,
             skip_index_budget: other.skip_index_budget,
+            max_concurrent_batches: other.experimental_max_concurrent_batches,
             ..Default::default()
         })
     }
@@ -976,6 +1023,10 @@ fn default_embedding_cache_entries() -> usize {
     0
 }
 
+fn default_max_concurrent_batches() -> usize {
+    1
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
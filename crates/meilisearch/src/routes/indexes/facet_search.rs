@@ -9,6 +9,7 @@ use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::vector::sparse::QueryVector;
 use serde_json::Value;
 use tracing::debug;
 use utoipa::{OpenApi, ToSchema};
@@ -54,7 +55,7 @@ pub struct FacetSearchQuery {
     pub facet_name: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
-    #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
+    #[deserr(default, error = DeserrJsonError<InvalidSearchVector>, from(Option<QueryVector>) = QueryVector::into_embedding)]
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
@@ -328,6 +329,9 @@ impl From<FacetSearchQuery> for SearchQuery {
             sort: None,
             distinct: None,
             facets: None,
+            facet_ranges: None,
+            hierarchical_facets: None,
+            disjunctive_facets: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
@@ -335,6 +339,7 @@ impl From<FacetSearchQuery> for SearchQuery {
             vector,
             attributes_to_search_on,
             hybrid,
+            rerank: None,
             ranking_score_threshold,
             locales,
         }
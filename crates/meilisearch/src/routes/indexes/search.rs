@@ -10,6 +10,7 @@ use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
 use meilisearch_types::serde_cs::vec::CS;
+use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use serde_json::Value;
 use tracing::debug;
 use utoipa::{IntoParams, OpenApi};
@@ -22,10 +23,11 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, SearchPOST};
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, perform_search, Fusion, HybridQuery, MatchingStrategy, RankingScoreThreshold,
+    RerankQuery, RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio,
+    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_RERANK_TOP_K, DEFAULT_RRF_K, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -107,6 +109,9 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     #[param(value_type = Vec<String>, explode = false)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    #[param(value_type = Vec<String>, explode = false)]
+    disjunctive_facets: Option<CS<String>>,
     #[deserr(default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     #[param(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     highlight_pre_tag: String,
@@ -126,6 +131,17 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSemanticRatio>)]
     #[param(value_type = f32)]
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFusion>)]
+    #[param(value_type = Fusion)]
+    pub hybrid_fusion: Option<Fusion>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRrfK>)]
+    #[param(value_type = u32)]
+    pub hybrid_rrf_k: Option<Param<u32>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchReranker>)]
+    pub rerank_reranker: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRerankTopK>)]
+    #[param(value_type = usize)]
+    pub rerank_top_k: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     #[param(value_type = f32)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
@@ -181,19 +197,42 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
         };
 
         let hybrid = match (other.hybrid_embedder, other.hybrid_semantic_ratio) {
-            (None, None) => None,
+            (None, None) => {
+                if other.hybrid_fusion.is_some() || other.hybrid_rrf_k.is_some() {
+                    return Err(ResponseError::from_msg(
+                        "`hybridEmbedder` is mandatory when `hybridFusion` or `hybridRrfK` is present"
+                            .into(),
+                        meilisearch_types::error::Code::InvalidSearchHybridQuery,
+                    ));
+                }
+                None
+            }
             (None, Some(_)) => {
                 return Err(ResponseError::from_msg(
                     "`hybridEmbedder` is mandatory when `hybridSemanticRatio` is present".into(),
                     meilisearch_types::error::Code::InvalidSearchHybridQuery,
                 ));
             }
-            (Some(embedder), None) => {
-                Some(HybridQuery { semantic_ratio: DEFAULT_SEMANTIC_RATIO(), embedder })
-            }
-            (Some(embedder), Some(semantic_ratio)) => {
-                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder })
+            (Some(embedder), semantic_ratio) => Some(HybridQuery {
+                semantic_ratio: semantic_ratio.map_or_else(DEFAULT_SEMANTIC_RATIO, |ratio| *ratio),
+                embedder,
+                fusion: other.hybrid_fusion.unwrap_or_default(),
+                rrf_k: other.hybrid_rrf_k.map_or_else(DEFAULT_RRF_K, |k| k.0),
+            }),
+        };
+
+        let rerank = match (other.rerank_reranker, other.rerank_top_k) {
+            (None, None) => None,
+            (None, Some(_)) => {
+                return Err(ResponseError::from_msg(
+                    "`rerankReranker` is mandatory when `rerankTopK` is present".into(),
+                    meilisearch_types::error::Code::InvalidSearchRerank,
+                ));
             }
+            (Some(reranker), top_k) => Some(RerankQuery {
+                reranker,
+                top_k: top_k.map_or_else(DEFAULT_RERANK_TOP_K, |k| k.0),
+            }),
         };
 
         if other.vector.is_some() && hybrid.is_none() {
@@ -222,12 +261,17 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            // nested ranges and paths can't be expressed as query parameters
+            facet_ranges: None,
+            hierarchical_facets: None,
+            disjunctive_facets: other.disjunctive_facets.map(|o| o.into_iter().collect()),
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
+            rerank,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
             locales: other.locales.map(|o| o.into_iter().collect()),
         })
@@ -339,6 +383,12 @@ pub async fn search_with_url_query(
 
     let search_kind =
         search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
+    let rerank = search_reranker(
+        query.rerank.as_ref(),
+        index_scheduler.get_ref(),
+        index_uid.to_string(),
+        &index,
+    )?;
     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
     let permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
@@ -347,6 +397,7 @@ pub async fn search_with_url_query(
             &index,
             query,
             search_kind,
+            rerank,
             retrieve_vector,
             index_scheduler.features(),
         )
@@ -445,6 +496,12 @@ pub async fn search_with_post(
 
     let search_kind =
         search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
+    let rerank = search_reranker(
+        query.rerank.as_ref(),
+        index_scheduler.get_ref(),
+        index_uid.to_string(),
+        &index,
+    )?;
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors);
 
     let permit = search_queue.try_get_search_permit().await?;
@@ -454,6 +511,7 @@ pub async fn search_with_post(
             &index,
             query,
             search_kind,
+            rerank,
             retrieve_vectors,
             index_scheduler.features(),
         )
@@ -488,29 +546,64 @@ pub fn search_kind(
         // no query, no vector => placeholder search
         (None, _, None) => Ok(SearchKind::KeywordOnly),
         // hybrid.semantic_ratio == 1.0 => vector
-        (_, Some(HybridQuery { semantic_ratio, embedder }), v) if **semantic_ratio == 1.0 => {
-            SearchKind::semantic(index_scheduler, index_uid, index, embedder, v.map(|v| v.len()))
+        (_, Some(HybridQuery { semantic_ratio, embedder, .. }), v) if **semantic_ratio == 1.0 => {
+            SearchKind::semantic(index_scheduler, index_uid, index, embedder, v)
         }
         // hybrid.semantic_ratio == 0.0 => keyword
-        (_, Some(HybridQuery { semantic_ratio, embedder: _ }), _) if **semantic_ratio == 0.0 => {
+        (_, Some(HybridQuery { semantic_ratio, .. }), _) if **semantic_ratio == 0.0 => {
             Ok(SearchKind::KeywordOnly)
         }
         // no query, hybrid, vector => semantic
-        (None, Some(HybridQuery { semantic_ratio: _, embedder }), Some(v)) => {
-            SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v.len()))
+        (None, Some(HybridQuery { embedder, .. }), Some(v)) => {
+            SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v))
         }
         // query, no hybrid, no vector => keyword
         (Some(_), None, None) => Ok(SearchKind::KeywordOnly),
         // query, hybrid, maybe vector => hybrid
-        (Some(_), Some(HybridQuery { semantic_ratio, embedder }), v) => SearchKind::hybrid(
+        (Some(_), Some(hybrid), v) => SearchKind::hybrid(
             index_scheduler,
             index_uid,
             index,
-            embedder,
-            **semantic_ratio,
-            v.map(|v| v.len()),
+            &hybrid.embedder,
+            *hybrid.semantic_ratio,
+            hybrid.milli_fusion(),
+            v,
         ),
 
         (_, None, Some(_)) => Err(MeilisearchHttpError::MissingSearchHybrid.into()),
     }
 }
+
+pub fn search_reranker(
+    rerank: Option<&RerankQuery>,
+    index_scheduler: &IndexScheduler,
+    index_uid: String,
+    index: &milli::Index,
+) -> Result<Option<milli::RerankSearch>, ResponseError> {
+    let Some(RerankQuery { reranker: reranker_name, top_k }) = rerank else {
+        return Ok(None);
+    };
+    let config = {
+        let rtxn = index.read_txn()?;
+        // the reranker can't be sent more hits than the search can return
+        let max_total_hits = index
+            .pagination_max_total_hits(&rtxn)?
+            .map(|x| x as usize)
+            .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+        if *top_k > max_total_hits {
+            return Err(
+                MeilisearchHttpError::RerankTopKTooLarge { top_k: *top_k, max_total_hits }.into()
+            );
+        }
+        index.rerankers(&rtxn)?.remove(reranker_name).ok_or_else(|| {
+            milli::Error::from(milli::UserError::InvalidSearchReranker(reranker_name.to_owned()))
+        })?
+    };
+    let (reranker, prompt) = index_scheduler.reranker(index_uid, config)?;
+    Ok(Some(milli::RerankSearch {
+        reranker_name: reranker_name.to_owned(),
+        reranker,
+        prompt,
+        top_k: *top_k,
+    }))
+}
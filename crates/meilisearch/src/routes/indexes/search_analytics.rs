@@ -8,7 +8,7 @@ use serde_json::{json, Value};
 use crate::aggregate_methods;
 use crate::analytics::{Aggregate, AggregateMethod};
 use crate::search::{
-    SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    Fusion, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
     DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
     DEFAULT_SEMANTIC_RATIO,
 };
@@ -40,6 +40,7 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -60,8 +61,15 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // Whether the semantic ratio passed to a hybrid search equals the default ratio.
     semantic_ratio: bool,
     hybrid: bool,
+    // Whether the hybrid search fused its results by reciprocal rank.
+    rrf_fusion: bool,
     retrieve_vectors: bool,
 
+    // rerank
+    rerank: bool,
+    // The maximum number of hits sent to a reranker
+    max_rerank_top_k: usize,
+
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
 
@@ -86,6 +94,9 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // facets
     facets_sum_of_terms: usize,
     facets_total_number_of_facets: usize,
+    facet_ranges: bool,
+    hierarchical_facets: bool,
+    disjunctive_facets: bool,
 
     // scoring
     show_ranking_score: bool,
@@ -117,12 +128,16 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             sort,
             distinct,
             facets: _,
+            facet_ranges,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
             hybrid,
+            rerank,
             ranking_score_threshold,
             locales,
         } = query;
@@ -139,6 +154,10 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
 
         ret.distinct = distinct.is_some();
 
+        ret.facet_ranges = facet_ranges.is_some();
+        ret.hierarchical_facets = hierarchical_facets.is_some();
+        ret.disjunctive_facets = disjunctive_facets.is_some();
+
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
             ret.filter_total_number_of_criteria = 1;
@@ -160,6 +179,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -207,6 +227,12 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
         if let Some(hybrid) = hybrid {
             ret.semantic_ratio = hybrid.semantic_ratio != DEFAULT_SEMANTIC_RATIO();
             ret.hybrid = true;
+            ret.rrf_fusion = hybrid.fusion == Fusion::Rrf;
+        }
+
+        if let Some(rerank) = rerank {
+            ret.rerank = true;
+            ret.max_rerank_top_k = rerank.top_k;
         }
 
         ret
@@ -221,6 +247,8 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            facet_ranges: _,
+            hierarchical_facet_distribution: _,
             degraded,
             used_negative_operator,
         } = result;
@@ -252,6 +280,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             distinct,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -273,10 +302,16 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facet_ranges,
+            hierarchical_facets,
+            disjunctive_facets,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
             hybrid,
+            rrf_fusion,
+            rerank,
+            max_rerank_top_k,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -305,6 +340,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -327,6 +363,11 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.retrieve_vectors |= retrieve_vectors;
         self.semantic_ratio |= semantic_ratio;
         self.hybrid |= hybrid;
+        self.rrf_fusion |= rrf_fusion;
+
+        // rerank
+        self.rerank |= rerank;
+        self.max_rerank_top_k = self.max_rerank_top_k.max(max_rerank_top_k);
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
@@ -349,6 +390,9 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.facets_sum_of_terms = self.facets_sum_of_terms.saturating_add(facets_sum_of_terms);
         self.facets_total_number_of_facets =
             self.facets_total_number_of_facets.saturating_add(facets_total_number_of_facets);
+        self.facet_ranges |= facet_ranges;
+        self.hierarchical_facets |= hierarchical_facets;
+        self.disjunctive_facets |= disjunctive_facets;
 
         // matching strategy
         for (key, value) in matching_strategy.into_iter() {
@@ -378,6 +422,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             distinct,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -399,10 +444,16 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facet_ranges,
+            hierarchical_facets,
+            disjunctive_facets,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
             hybrid,
+            rrf_fusion,
+            rerank,
+            max_rerank_top_k,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -434,6 +485,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             "filter": {
                "with_geoRadius": filter_with_geo_radius,
                "with_geoBoundingBox": filter_with_geo_bounding_box,
+               "with_geoPolygon": filter_with_geo_polygon,
                "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
             },
@@ -450,6 +502,11 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             "hybrid": {
                 "enabled": hybrid,
                 "semantic_ratio": semantic_ratio,
+                "rrf_fusion": rrf_fusion,
+            },
+            "rerank": {
+                "enabled": rerank,
+                "max_top_k": max_rerank_top_k,
             },
             "pagination": {
                "max_limit": max_limit,
@@ -468,6 +525,9 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             },
             "facets": {
                 "avg_facets_number": format!("{:.2}", facets_sum_of_terms as f64 / facets_total_number_of_facets as f64),
+                "with_ranges": facet_ranges,
+                "with_hierarchical_facets": hierarchical_facets,
+                "with_disjunctive_facets": disjunctive_facets,
             },
             "matching_strategy": {
                 "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
@@ -1,9 +1,11 @@
+use std::path::Path;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::settings::{
     settings, SecretPolicy, SettingEmbeddingSettings, Settings, Unchecked,
@@ -16,7 +18,7 @@ use super::settings_analytics::*;
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
-use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::routes::{get_task_id, is_dry_run, task_options, SummarizedTaskView};
 use crate::Opt;
 
 /// This macro generates the routes for the settings.
@@ -82,7 +84,7 @@ macro_rules! make_setting_route {
             use $crate::extractors::authentication::GuardedData;
             use $crate::extractors::sequential_extractor::SeqHandler;
             use $crate::Opt;
-            use $crate::routes::{is_dry_run, get_task_id, SummarizedTaskView};
+            use $crate::routes::{is_dry_run, task_options, get_task_id, SummarizedTaskView};
             #[allow(unused_imports)]
             use super::*;
 
@@ -140,10 +142,12 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let options = task_options(&req)?;
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_options(task, uid, options, dry_run)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Delete settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -213,6 +217,7 @@ macro_rules! make_setting_route {
                 let new_settings = $crate::routes::indexes::settings::validate_settings(
                     new_settings,
                     &index_scheduler,
+                    &opt,
                 )?;
 
                 let allow_index_creation =
@@ -226,10 +231,12 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let options = task_options(&req)?;
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_options(task, uid, options, dry_run)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Update settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -508,6 +515,39 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/analyzers",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::analyzers::AnalyzerRuleView>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsAnalyzers,
+        >,
+        attr: analyzers,
+        camelcase_attr: "analyzers",
+        analytics: AnalyzersAnalytics
+    },
+    {
+        route: "/synonym-rules",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::synonym_rules::SynonymRuleView>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsSynonymRules,
+        >,
+        attr: synonym_rules,
+        camelcase_attr: "synonymRules",
+        analytics: SynonymRulesAnalytics
+    },
+    {
+        route: "/rerankers",
+        update_verb: put,
+        value_type: std::collections::BTreeMap<String, meilisearch_types::rerankers::RerankerSettings>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsRerankers,
+        >,
+        attr: rerankers,
+        camelcase_attr: "rerankers",
+        analytics: RerankersAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -555,7 +595,7 @@ pub async fn update_all(
 
     let new_settings = body.into_inner();
     debug!(parameters = ?new_settings, "Update all settings");
-    let new_settings = validate_settings(new_settings, &index_scheduler)?;
+    let new_settings = validate_settings(new_settings, &index_scheduler, &opt)?;
 
     analytics.publish(
         SettingsAnalytics {
@@ -597,6 +637,9 @@ pub async fn update_all(
             ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            analyzers: AnalyzersAnalytics::new(new_settings.analyzers.as_ref().set()),
+            synonym_rules: SynonymRulesAnalytics::new(new_settings.synonym_rules.as_ref().set()),
+            rerankers: RerankersAnalytics::new(new_settings.rerankers.as_ref().set()),
         },
         &req,
     );
@@ -611,10 +654,12 @@ pub async fn update_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let options = task_options(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task, uid, options, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Update all settings");
     Ok(HttpResponse::Accepted().json(task))
@@ -705,10 +750,12 @@ pub async fn delete_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let options = task_options(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task, uid, options, dry_run)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete all settings");
     Ok(HttpResponse::Accepted().json(task))
@@ -717,9 +764,10 @@ pub async fn delete_all(
 fn validate_settings(
     settings: Settings<Unchecked>,
     index_scheduler: &IndexScheduler,
+    opt: &Opt,
 ) -> Result<Settings<Unchecked>, ResponseError> {
     use meilisearch_types::milli::update::Setting;
-    use meilisearch_types::milli::vector::settings::EmbedderSource;
+    use meilisearch_types::milli::vector::settings::{EmbedderSource, SubEmbeddingSettings};
 
     let features = index_scheduler.features();
     if let Setting::Set(embedders) = &settings.embedders {
@@ -741,5 +789,88 @@ fn validate_settings(
         }
     }
 
-    Ok(settings.validate()?)
+    let mut settings = settings.validate()?;
+
+    let models_dir = opt.models_dir.as_deref();
+    if let Setting::Set(embedders) = &mut settings.embedders {
+        for (name, SettingEmbeddingSettings { inner: embedder }) in embedders.iter_mut() {
+            let Setting::Set(embedder) = embedder else {
+                continue;
+            };
+            if let Setting::Set(model_path) = &mut embedder.model_path {
+                resolve_model_path(
+                    model_path,
+                    models_dir,
+                    &format!(".embedders.{name}.modelPath"),
+                    Code::InvalidSettingsEmbedders,
+                )?;
+            }
+            for (field, sub_embedder) in [
+                ("searchEmbedder", &mut embedder.search_embedder),
+                ("indexingEmbedder", &mut embedder.indexing_embedder),
+            ] {
+                if let Setting::Set(SubEmbeddingSettings {
+                    model_path: Setting::Set(model_path),
+                    ..
+                }) = sub_embedder
+                {
+                    resolve_model_path(
+                        model_path,
+                        models_dir,
+                        &format!(".embedders.{name}.{field}.modelPath"),
+                        Code::InvalidSettingsEmbedders,
+                    )?;
+                }
+            }
+        }
+    }
+
+    if let Setting::Set(rerankers) = &mut settings.rerankers {
+        for (name, reranker) in rerankers.iter_mut() {
+            if let Some(model_path) = &mut reranker.model_path {
+                resolve_model_path(
+                    model_path,
+                    models_dir,
+                    &format!(".rerankers.{name}.modelPath"),
+                    Code::InvalidSettingsRerankers,
+                )?;
+            }
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Replaces the `modelPath` of a local model by its canonical path inside the models directory,
+/// so that the settings can't make Meilisearch open any other file of the server.
+fn resolve_model_path(
+    model_path: &mut String,
+    models_dir: Option<&Path>,
+    location: &str,
+    code: Code,
+) -> Result<(), ResponseError> {
+    let Some(models_dir) = models_dir else {
+        return Err(ResponseError::from_msg(
+            format!("`{location}`: local models are disabled.\n - Hint: launch Meilisearch with `--models-dir` to load the models of a directory."),
+            code,
+        ));
+    };
+
+    let resolved = models_dir.canonicalize().ok().and_then(|models_dir| {
+        let path = models_dir.join(&*model_path).canonicalize().ok()?;
+        path.starts_with(&models_dir).then_some(path)
+    });
+
+    match resolved {
+        Some(path) => {
+            *model_path = path.to_string_lossy().into_owned();
+            Ok(())
+        }
+        None => Err(ResponseError::from_msg(
+            format!(
+                "`{location}`: `{model_path}` is not an existing path inside the models directory."
+            ),
+            code,
+        )),
+    }
 }
@@ -5,14 +5,17 @@
 
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 
+use meilisearch_types::analyzers::{AnalyzerRuleView, AnalyzerTokenizerView};
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
 use meilisearch_types::milli::update::Setting;
 use meilisearch_types::milli::FilterableAttributesRule;
+use meilisearch_types::rerankers::{RerankerSettings, RerankerSource};
 use meilisearch_types::settings::{
     FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
     RankingRuleView, SettingEmbeddingSettings, TypoSettings,
 };
+use meilisearch_types::synonym_rules::SynonymRuleView;
 use serde::Serialize;
 
 use crate::analytics::Aggregate;
@@ -39,6 +42,9 @@ pub struct SettingsAnalytics {
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
     pub facet_search: FacetSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub analyzers: AnalyzersAnalytics,
+    pub synonym_rules: SynonymRulesAnalytics,
+    pub rerankers: RerankersAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -134,6 +140,14 @@ impl Aggregate for SettingsAnalytics {
                     .faceting
                     .sort_facet_values_by_total
                     .or(self.faceting.sort_facet_values_by_total),
+                hierarchical_facets_total: new
+                    .faceting
+                    .hierarchical_facets_total
+                    .or(self.faceting.hierarchical_facets_total),
+                facet_stats_total: new
+                    .faceting
+                    .facet_stats_total
+                    .or(self.faceting.facet_stats_total),
             },
             pagination: PaginationAnalytics {
                 max_total_hits: new.pagination.max_total_hits.or(self.pagination.max_total_hits),
@@ -173,6 +187,36 @@ impl Aggregate for SettingsAnalytics {
                     (Some(bq), None) | (None, Some(bq)) => Some(bq),
                     (Some(this), Some(other)) => Some(this | other),
                 },
+                distances: match (self.embedders.distances, new.embedders.distances) {
+                    (None, None) => None,
+                    (Some(distances), None) | (None, Some(distances)) => Some(distances),
+                    (Some(this), Some(other)) => Some(this.union(&other).cloned().collect()),
+                },
+                quantizations: match (self.embedders.quantizations, new.embedders.quantizations) {
+                    (None, None) => None,
+                    (Some(quantizations), None) | (None, Some(quantizations)) => {
+                        Some(quantizations)
+                    }
+                    (Some(this), Some(other)) => Some(this.union(&other).cloned().collect()),
+                },
+                multi_vector_used: match (
+                    self.embedders.multi_vector_used,
+                    new.embedders.multi_vector_used,
+                ) {
+                    (None, None) => None,
+                    (Some(mv), None) | (None, Some(mv)) => Some(mv),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
+                chunking_used: match (self.embedders.chunking_used, new.embedders.chunking_used) {
+                    (None, None) => None,
+                    (Some(chunking), None) | (None, Some(chunking)) => Some(chunking),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
+                sparse_used: match (self.embedders.sparse_used, new.embedders.sparse_used) {
+                    (None, None) => None,
+                    (Some(sparse), None) | (None, Some(sparse)) => Some(sparse),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
             },
             search_cutoff_ms: SearchCutoffMsAnalytics {
                 search_cutoff_ms: new
@@ -198,6 +242,18 @@ impl Aggregate for SettingsAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            analyzers: AnalyzersAnalytics {
+                total: new.analyzers.total.or(self.analyzers.total),
+                tokenizers: new.analyzers.tokenizers.or(self.analyzers.tokenizers),
+            },
+            synonym_rules: SynonymRulesAnalytics {
+                total: new.synonym_rules.total.or(self.synonym_rules.total),
+                types: new.synonym_rules.types.or(self.synonym_rules.types),
+            },
+            rerankers: RerankersAnalytics {
+                total: new.rerankers.total.or(self.rerankers.total),
+                sources: new.rerankers.sources.or(self.rerankers.sources),
+            },
         })
     }
 
@@ -424,6 +480,8 @@ pub struct FacetingAnalytics {
     pub max_values_per_facet: Option<usize>,
     pub sort_facet_values_by_star_count: Option<bool>,
     pub sort_facet_values_by_total: Option<usize>,
+    pub hierarchical_facets_total: Option<usize>,
+    pub facet_stats_total: Option<usize>,
 }
 
 impl FacetingAnalytics {
@@ -439,6 +497,12 @@ impl FacetingAnalytics {
             sort_facet_values_by_total: setting
                 .as_ref()
                 .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+            hierarchical_facets_total: setting
+                .as_ref()
+                .and_then(|s| s.hierarchical_facets.as_ref().set().map(|s| s.len())),
+            facet_stats_total: setting
+                .as_ref()
+                .and_then(|s| s.facet_stats.as_ref().set().map(|s| s.len())),
         }
     }
 
@@ -504,6 +568,16 @@ pub struct EmbeddersAnalytics {
     pub document_template_max_bytes: Option<usize>,
     // |=
     pub binary_quantization_used: Option<bool>,
+    // Merge the distances
+    pub distances: Option<HashSet<String>>,
+    // Merge the quantizations
+    pub quantizations: Option<HashSet<String>>,
+    // |=
+    pub multi_vector_used: Option<bool>,
+    // |=
+    pub chunking_used: Option<bool>,
+    // |=
+    pub sparse_used: Option<bool>,
 }
 
 impl EmbeddersAnalytics {
@@ -524,10 +598,27 @@ impl EmbeddersAnalytics {
                     EmbedderSource::Ollama => sources.insert("ollama".to_string()),
                     EmbedderSource::Rest => sources.insert("rest".to_string()),
                     EmbedderSource::Composite => sources.insert("composite".to_string()),
+                    EmbedderSource::Onnx => sources.insert("onnx".to_string()),
                 };
             }
         };
 
+        let distances = setting.as_ref().map(|map| {
+            map.values()
+                .filter_map(|config| config.inner.clone().set())
+                .filter_map(|config| config.distance.set())
+                .map(|distance| distance.to_string())
+                .collect()
+        });
+
+        let quantizations = setting.as_ref().map(|map| {
+            map.values()
+                .filter_map(|config| config.inner.clone().set())
+                .filter_map(|config| config.quantization.set())
+                .map(|quantization| quantization.to_string())
+                .collect()
+        });
+
         Self {
             total: setting.as_ref().map(|s| s.len()),
             sources: Some(sources),
@@ -547,6 +638,23 @@ impl EmbeddersAnalytics {
                     .filter_map(|config| config.inner.clone().set())
                     .any(|config| config.binary_quantized.set().is_some())
             }),
+            distances,
+            quantizations,
+            multi_vector_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.multi_vector.set().unwrap_or_default())
+            }),
+            chunking_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.chunking.set().is_some())
+            }),
+            sparse_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.sparse.set().unwrap_or_default())
+            }),
         }
     }
 
@@ -674,3 +782,62 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct AnalyzersAnalytics {
+    pub total: Option<usize>,
+    pub tokenizers: Option<BTreeSet<AnalyzerTokenizerView>>,
+}
+
+impl AnalyzersAnalytics {
+    pub fn new(analyzers: Option<&Vec<AnalyzerRuleView>>) -> Self {
+        Self {
+            total: analyzers.map(|analyzers| analyzers.len()),
+            tokenizers: analyzers
+                .map(|analyzers| analyzers.iter().map(|analyzer| analyzer.tokenizer).collect()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { analyzers: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct SynonymRulesAnalytics {
+    pub total: Option<usize>,
+    pub types: Option<BTreeSet<&'static str>>,
+}
+
+impl SynonymRulesAnalytics {
+    pub fn new(rules: Option<&Vec<SynonymRuleView>>) -> Self {
+        Self {
+            total: rules.map(|rules| rules.len()),
+            types: rules.map(|rules| rules.iter().map(|rule| rule.kind()).collect()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { synonym_rules: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct RerankersAnalytics {
+    pub total: Option<usize>,
+    pub sources: Option<BTreeSet<RerankerSource>>,
+}
+
+impl RerankersAnalytics {
+    pub fn new(rerankers: Option<&BTreeMap<String, RerankerSettings>>) -> Self {
+        Self {
+            total: rerankers.map(|rerankers| rerankers.len()),
+            sources: rerankers
+                .map(|rerankers| rerankers.values().map(|reranker| reranker.source).collect()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { rerankers: self, ..Default::default() }
+    }
+}
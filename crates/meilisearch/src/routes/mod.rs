@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
-use index_scheduler::IndexScheduler;
+use index_scheduler::{IndexScheduler, TaskOptions};
 use meilisearch_auth::AuthController;
 use meilisearch_types::batch_view::BatchView;
 use meilisearch_types::batches::BatchStats;
@@ -18,9 +18,12 @@ use meilisearch_types::settings::{
     Unchecked,
 };
 use meilisearch_types::task_view::{DetailsView, TaskView};
-use meilisearch_types::tasks::{Kind, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    parse_duration, serialize_duration, Kind, Status, Task, TaskGroup, TaskId, TaskPriority,
+};
 use serde::{Deserialize, Serialize};
-use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
 use tracing::debug;
 use utoipa::{OpenApi, ToSchema};
 
@@ -66,6 +69,7 @@ mod swap_indexes;
 pub mod tasks;
 #[cfg(test)]
 mod tasks_test;
+pub mod webhooks;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -84,6 +88,7 @@ mod tasks_test;
         (path = "/swap-indexes", api = swap_indexes::SwapIndexesApi),
         (path = "/experimental-features", api = features::ExperimentalFeaturesApi),
         (path = "/network", api = network::NetworkApi),
+        (path = "/webhooks", api = webhooks::WebhooksApi),
     ),
     paths(get_health, get_version, get_stats),
     tags(
@@ -113,7 +118,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
         .service(web::scope("/metrics").configure(metrics::configure))
         .service(web::scope("/experimental-features").configure(features::configure))
-        .service(web::scope("/network").configure(network::configure));
+        .service(web::scope("/network").configure(network::configure))
+        .service(web::scope("/webhooks").configure(webhooks::configure));
 
     #[cfg(feature = "swagger")]
     {
@@ -173,6 +179,108 @@ pub fn is_dry_run(req: &HttpRequest, opt: &Opt) -> Result<bool, ResponseError> {
         .is_some_and(|s| s.to_lowercase() == "true"))
 }
 
+/// Extracts how the task must be scheduled from the headers of the request.
+///
+/// - The `RunAt` header contains the `RFC 3339` date before which the task must not be processed.
+/// - The `RunEvery` header contains the `ISO 8601` interval, e.g. `P1D`, after which a copy of
+///   the task is enqueued once it succeeded or failed. Canceling the task ends the recurrence.
+/// - The `DependsOn` header contains the comma-separated uids of the tasks that must be finished
+///   before the task is processed.
+/// - The `TaskGroup` header contains the name of the group the task belongs to, and the
+///   `TaskGroupCommit` header marks the last task of the group. The tasks of a group are
+///   rolled back together when one of them fails, and a group interrupted while committing
+///   its indexes is resumed once Meilisearch restarts.
+/// - The `TaskPriority` header contains the priority of the task, `low`, `normal` or `high`.
+pub fn task_options(req: &HttpRequest) -> Result<TaskOptions, ResponseError> {
+    let run_at = header_str(req, "RunAt", Code::InvalidTaskRunAt)?
+        .map(|s| {
+            OffsetDateTime::parse(s, &Rfc3339).map_err(|e| {
+                ResponseError::from_msg(
+                    format!("Could not parse the RunAt `{s}` as an RFC 3339 date-time: {e}"),
+                    Code::InvalidTaskRunAt,
+                )
+            })
+        })
+        .transpose()?;
+
+    let run_every = header_str(req, "RunEvery", Code::InvalidTaskRunEvery)?
+        .map(|s| {
+            parse_duration(s).filter(|duration| duration.is_positive()).ok_or_else(|| {
+                ResponseError::from_msg(
+                    format!("Could not parse the RunEvery `{s}` as a positive ISO 8601 duration made of weeks, days, hours, minutes and seconds, e.g. `P1D` or `PT12H`."),
+                    Code::InvalidTaskRunEvery,
+                )
+            })
+        })
+        .transpose()?;
+
+    let depends_on = match header_str(req, "DependsOn", Code::InvalidTaskDependsOn)? {
+        Some(s) => s
+            .split(',')
+            .map(|uid| {
+                uid.trim().parse::<TaskId>().map_err(|e| {
+                    ResponseError::from_msg(
+                        format!("Could not parse the DependsOn `{s}` as a list of task uids: {e}"),
+                        Code::InvalidTaskDependsOn,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    let commit = header_str(req, "TaskGroupCommit", Code::InvalidTaskGroup)?
+        .is_some_and(|s| s.to_lowercase() == "true");
+    let group = match header_str(req, "TaskGroup", Code::InvalidTaskGroup)? {
+        Some(name) => {
+            if name.is_empty()
+                || name.len() > 400
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(ResponseError::from_msg(
+                    format!("`{name}` is not a valid task group name. A task group name can be an integer or a string containing only alphanumeric characters, hyphens (-) and underscores (_), and can not be more than 400 bytes."),
+                    Code::InvalidTaskGroup,
+                ));
+            }
+            Some(TaskGroup { name: name.to_string(), commit })
+        }
+        None if commit => {
+            return Err(ResponseError::from_msg(
+                "The TaskGroupCommit header can only be used along with the TaskGroup header."
+                    .to_string(),
+                Code::InvalidTaskGroup,
+            ))
+        }
+        None => None,
+    };
+
+    let priority = header_str(req, "TaskPriority", Code::InvalidTaskPriority)?
+        .map(|s| {
+            s.trim()
+                .parse::<TaskPriority>()
+                .map_err(|e| ResponseError::from_msg(e.to_string(), Code::InvalidTaskPriority))
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(TaskOptions { run_at, run_every, depends_on, group, priority })
+}
+
+fn header_str<'a>(
+    req: &'a HttpRequest,
+    name: &str,
+    code: Code,
+) -> Result<Option<&'a str>, ResponseError> {
+    req.headers()
+        .get(name)
+        .map(|header| {
+            header.to_str().map_err(|e| {
+                ResponseError::from_msg(format!("{name} is not a valid utf-8 string: {e}"), code)
+            })
+        })
+        .transpose()
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SummarizedTaskView {
@@ -189,6 +297,27 @@ pub struct SummarizedTaskView {
     /// The date on which the task was enqueued.
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     enqueued_at: OffsetDateTime,
+    /// The date before which the task is not processed, only present for the scheduled tasks.
+    #[schema(value_type = Option<String>)]
+    #[serde(
+        serialize_with = "time::serde::rfc3339::option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    run_at: Option<OffsetDateTime>,
+    /// The interval after which a copy of the task is enqueued, only present for the recurring tasks.
+    #[schema(value_type = Option<String>)]
+    #[serde(serialize_with = "serialize_duration", skip_serializing_if = "Option::is_none")]
+    run_every: Option<Duration>,
+    /// The tasks that must be finished before this one is processed.
+    #[schema(value_type = Vec<u32>)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<TaskId>,
+    /// The group of tasks that are committed, or not, together with this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<TaskGroup>,
+    /// The priority of the task, only present when it is not `normal`.
+    #[serde(skip_serializing_if = "TaskPriority::is_normal")]
+    priority: TaskPriority,
 }
 
 impl From<Task> for SummarizedTaskView {
@@ -199,6 +328,11 @@ impl From<Task> for SummarizedTaskView {
             status: task.status,
             kind: task.kind.as_kind(),
             enqueued_at: task.enqueued_at,
+            run_at: task.run_at,
+            run_every: task.run_every,
+            depends_on: task.depends_on,
+            group: task.group,
+            priority: task.priority,
         }
     }
 }
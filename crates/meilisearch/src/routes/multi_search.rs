@@ -16,7 +16,7 @@ use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
-use crate::routes::indexes::search::search_kind;
+use crate::routes::indexes::search::{search_kind, search_reranker};
 use crate::search::{
     add_search_rules, perform_federated_search, perform_search, FederatedSearch,
     FederatedSearchResult, RetrieveVectors, SearchQueryWithIndex, SearchResultWithIndex,
@@ -248,6 +248,13 @@ pub async fn multi_search_with_post(
                         &index,
                     )
                     .with_index(query_index)?;
+                    let rerank = search_reranker(
+                        query.rerank.as_ref(),
+                        index_scheduler.get_ref(),
+                        index_uid_str.clone(),
+                        &index,
+                    )
+                    .with_index(query_index)?;
                     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
 
                     let search_result = tokio::task::spawn_blocking(move || {
@@ -256,6 +263,7 @@ pub async fn multi_search_with_post(
                             &index,
                             query,
                             search_kind,
+                            rerank,
                             retrieve_vector,
                             features,
                         )
@@ -58,12 +58,16 @@ impl MultiSearchAggregator {
             sort: _,
             distinct: _,
             facets: _,
+            facet_ranges: _,
+            hierarchical_facets: _,
+            disjunctive_facets: _,
             highlight_pre_tag: _,
             highlight_post_tag: _,
             crop_marker: _,
             matching_strategy: _,
             attributes_to_search_on: _,
             hybrid: _,
+            rerank: _,
             ranking_score_threshold: _,
             locales: _,
         } in &federated_search.queries
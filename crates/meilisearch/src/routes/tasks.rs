@@ -22,7 +22,7 @@ use tokio::io::AsyncReadExt;
 use tokio::task;
 use utoipa::{IntoParams, OpenApi, ToSchema};
 
-use super::{get_task_id, is_dry_run, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
+use super::{get_task_id, is_dry_run, task_options, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT};
 use crate::analytics::{Aggregate, AggregateMethod, Analytics};
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
@@ -121,6 +121,14 @@ pub struct TasksFilterQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeFinishedAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
     #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
     pub before_finished_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter the scheduled tasks based on their runAt time. Matches tasks scheduled after the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterRunAt>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub after_run_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter the scheduled tasks based on their runAt time. Matches tasks scheduled before the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeRunAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub before_run_at: OptionStarOr<OffsetDateTime>,
 }
 
 impl TasksFilterQuery {
@@ -141,6 +149,8 @@ impl TasksFilterQuery {
             after_started_at: self.after_started_at.merge_star_and_none(),
             before_finished_at: self.before_finished_at.merge_star_and_none(),
             after_finished_at: self.after_finished_at.merge_star_and_none(),
+            before_run_at: self.before_run_at.merge_star_and_none(),
+            after_run_at: self.after_run_at.merge_star_and_none(),
         }
     }
 }
@@ -161,7 +171,9 @@ impl TaskDeletionOrCancelationQuery {
                 after_started_at: OptionStarOr::None,
                 before_started_at: OptionStarOr::None,
                 after_finished_at: OptionStarOr::None,
-                before_finished_at: OptionStarOr::None
+                before_finished_at: OptionStarOr::None,
+                after_run_at: OptionStarOr::None,
+                before_run_at: OptionStarOr::None
             }
         )
     }
@@ -220,6 +232,14 @@ pub struct TaskDeletionOrCancelationQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeFinishedAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
     #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
     pub before_finished_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter the scheduled tasks based on their runAt time. Matches tasks scheduled after the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterRunAt>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub after_run_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter the scheduled tasks based on their runAt time. Matches tasks scheduled before the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeRunAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub before_run_at: OptionStarOr<OffsetDateTime>,
 }
 
 impl TaskDeletionOrCancelationQuery {
@@ -240,6 +260,8 @@ impl TaskDeletionOrCancelationQuery {
             after_started_at: self.after_started_at.merge_star_and_none(),
             before_finished_at: self.before_finished_at.merge_star_and_none(),
             after_finished_at: self.after_finished_at.merge_star_and_none(),
+            before_run_at: self.before_run_at.merge_star_and_none(),
+            after_run_at: self.after_run_at.merge_star_and_none(),
         }
     }
 }
@@ -262,6 +284,8 @@ struct TaskFilterAnalytics<Method: AggregateMethod> {
     filtered_by_after_started_at: bool,
     filtered_by_before_finished_at: bool,
     filtered_by_after_finished_at: bool,
+    filtered_by_before_run_at: bool,
+    filtered_by_after_run_at: bool,
 
     #[serde(skip)]
     marker: std::marker::PhantomData<Method>,
@@ -291,6 +315,9 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
                 | new.filtered_by_before_finished_at,
             filtered_by_after_finished_at: self.filtered_by_after_finished_at
                 | new.filtered_by_after_finished_at,
+            filtered_by_before_run_at: self.filtered_by_before_run_at
+                | new.filtered_by_before_run_at,
+            filtered_by_after_run_at: self.filtered_by_after_run_at | new.filtered_by_after_run_at,
 
             marker: std::marker::PhantomData,
         })
@@ -322,7 +349,7 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
         )),
         (status = 400, description = "A filter is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
-                "message": "Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
+                "message": "Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.",
                 "code": "missing_task_filters",
                 "type": "invalid_request",
                 "link": "https://docs.meilisearch.com/errors#missing_task_filters"
@@ -372,6 +399,8 @@ async fn cancel_tasks(
             filtered_by_after_started_at: params.after_started_at.is_some(),
             filtered_by_before_finished_at: params.before_finished_at.is_some(),
             filtered_by_after_finished_at: params.after_finished_at.is_some(),
+            filtered_by_before_run_at: params.before_run_at.is_some(),
+            filtered_by_after_run_at: params.after_run_at.is_some(),
 
             marker: std::marker::PhantomData,
         },
@@ -387,9 +416,11 @@ async fn cancel_tasks(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task =
-        task::spawn_blocking(move || index_scheduler.register(task_cancelation, uid, dry_run))
-            .await??;
+    let options = task_options(&req)?;
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task_cancelation, uid, options, dry_run)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     Ok(HttpResponse::Ok().json(task))
@@ -416,7 +447,7 @@ async fn cancel_tasks(
         )),
         (status = 400, description = "A filter is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
-                "message": "Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
+                "message": "Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.",
                 "code": "missing_task_filters",
                 "type": "invalid_request",
                 "link": "https://docs.meilisearch.com/errors#missing_task_filters"
@@ -466,6 +497,8 @@ async fn delete_tasks(
             filtered_by_after_started_at: params.after_started_at.is_some(),
             filtered_by_before_finished_at: params.before_finished_at.is_some(),
             filtered_by_after_finished_at: params.after_finished_at.is_some(),
+            filtered_by_before_run_at: params.before_run_at.is_some(),
+            filtered_by_after_run_at: params.after_run_at.is_some(),
 
             marker: std::marker::PhantomData,
         },
@@ -481,8 +514,11 @@ async fn delete_tasks(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task = task::spawn_blocking(move || index_scheduler.register(task_deletion, uid, dry_run))
-        .await??;
+    let options = task_options(&req)?;
+    let task = task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task_deletion, uid, options, dry_run)
+    })
+    .await??;
     let task: SummarizedTaskView = task.into();
 
     Ok(HttpResponse::Ok().json(task))
@@ -0,0 +1,453 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::{
+    InvalidWebhookHeaders, InvalidWebhookIndexUids, InvalidWebhookSecret, InvalidWebhookStatuses,
+    InvalidWebhookTypes, InvalidWebhookUrl,
+};
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::update::Setting;
+use meilisearch_types::tasks::{Kind, Status};
+use meilisearch_types::webhooks::Webhook;
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::ActionPolicy;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_webhooks, create_webhook, get_webhook, patch_webhook, delete_webhook),
+    tags((
+        name = "Webhooks",
+        description = "The `/webhooks` route allows you to manage the endpoints notified whenever tasks are processed.
+
+Each webhook receives the tasks matching its `indexUids`, `types` and `statuses` as gzipped ndjson. When a `secret` is set, the deliveries are signed in the `X-Meilisearch-Signature` header. Failed deliveries are retried with an exponential backoff.
+
+This route is **synchronous**. This means that no task object will be returned, and any change to the webhooks will be made available immediately.",
+    )),
+)]
+pub struct WebhooksApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(list_webhooks))
+            .route(web::post().to(SeqHandler(create_webhook))),
+    )
+    .service(
+        web::resource("/{uuid}")
+            .route(web::get().to(get_webhook))
+            .route(web::patch().to(SeqHandler(patch_webhook)))
+            .route(web::delete().to(SeqHandler(delete_webhook))),
+    );
+}
+
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookSettings {
+    /// The URL receiving the tasks.
+    #[schema(value_type = Option<String>, example = json!("https://example.com/meilisearch-tasks"))]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookUrl>)]
+    pub url: Setting<String>,
+    /// Additional headers sent with every delivery. Their values are hidden in the responses.
+    #[schema(value_type = Option<BTreeMap<String, String>>, example = json!({ "Authorization": "Bearer 0123456789" }))]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookHeaders>)]
+    pub headers: Setting<BTreeMap<String, String>>,
+    /// The secret used to sign the deliveries. It is never returned.
+    #[schema(value_type = Option<String>)]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookSecret>)]
+    pub secret: Setting<String>,
+    /// Only the tasks of the indexes matching these patterns are sent. All the tasks are sent when `null`.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["movies", "products_*"]))]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookIndexUids>)]
+    pub index_uids: Setting<Vec<IndexUidPattern>>,
+    /// Only the tasks of these types are sent. All the tasks are sent when `null`.
+    #[schema(value_type = Option<Vec<Kind>>, example = json!(["documentAdditionOrUpdate"]))]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookTypes>)]
+    pub types: Setting<Vec<String>>,
+    /// Only the tasks with these statuses are sent. All the tasks are sent when `null`.
+    #[schema(value_type = Option<Vec<Status>>, example = json!(["failed"]))]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookStatuses>)]
+    pub statuses: Setting<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookView {
+    pub uuid: Uuid,
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+    #[schema(value_type = Option<Vec<String>>)]
+    pub index_uids: Option<Vec<IndexUidPattern>>,
+    pub types: Option<Vec<Kind>>,
+    pub statuses: Option<Vec<Status>>,
+}
+
+impl WebhookView {
+    fn new(uuid: Uuid, mut webhook: Webhook) -> Self {
+        webhook.hide_secrets();
+        let Webhook { url, headers, secret: _, index_uids, types, statuses } = webhook;
+        Self { uuid, url, headers, index_uids, types, statuses }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookResults {
+    pub results: Vec<WebhookView>,
+}
+
+impl WebhookSettings {
+    /// Applies the settings to the existing webhook, or creates a new webhook.
+    fn apply(self, old: Option<Webhook>) -> Result<Webhook, ResponseError> {
+        let WebhookSettings { url, headers, secret, index_uids, types, statuses } = self;
+
+        let url = match (url, old.as_ref()) {
+            (Setting::Set(url), _) => {
+                if let Err(error) = url::Url::parse(&url) {
+                    return Err(ResponseError::from_msg(
+                        format!("Invalid `.url` (`{url}`): {error}"),
+                        Code::InvalidWebhookUrl,
+                    ));
+                }
+                url
+            }
+            (Setting::Reset, _) => {
+                return Err(ResponseError::from_msg(
+                    "Field `.url` cannot be set to `null`".to_string(),
+                    Code::InvalidWebhookUrl,
+                ))
+            }
+            (Setting::NotSet, Some(old)) => old.url.clone(),
+            (Setting::NotSet, None) => {
+                return Err(ResponseError::from_msg(
+                    "Missing field `.url`".to_string(),
+                    Code::MissingWebhookUrl,
+                ))
+            }
+        };
+
+        let types = parse_all::<Kind>(types, "types", Code::InvalidWebhookTypes)?;
+        let statuses = parse_all::<Status>(statuses, "statuses", Code::InvalidWebhookStatuses)?;
+
+        let old = old.unwrap_or(Webhook {
+            url: String::new(),
+            headers: BTreeMap::new(),
+            secret: None,
+            index_uids: None,
+            types: None,
+            statuses: None,
+        });
+
+        Ok(Webhook {
+            url,
+            headers: match headers {
+                Setting::Set(headers) => headers,
+                Setting::Reset => BTreeMap::new(),
+                Setting::NotSet => old.headers,
+            },
+            secret: merge(secret, old.secret),
+            index_uids: merge(index_uids, old.index_uids),
+            types: merge(types, old.types),
+            statuses: merge(statuses, old.statuses),
+        })
+    }
+}
+
+fn merge<T>(new: Setting<T>, old: Option<T>) -> Option<T> {
+    match new {
+        Setting::Set(new) => Some(new),
+        Setting::Reset => None,
+        Setting::NotSet => old,
+    }
+}
+
+fn parse_all<T>(
+    values: Setting<Vec<String>>,
+    field: &str,
+    code: Code,
+) -> Result<Setting<Vec<T>>, ResponseError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    match values {
+        Setting::Set(values) => values
+            .iter()
+            .map(|value| T::from_str(value))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Setting::Set)
+            .map_err(|error| ResponseError::from_msg(format!("Invalid `.{field}`: {error}"), code)),
+        Setting::Reset => Ok(Setting::Reset),
+        Setting::NotSet => Ok(Setting::NotSet),
+    }
+}
+
+fn parse_uuid(uuid: &str) -> Result<Uuid, ResponseError> {
+    Uuid::from_str(uuid).map_err(|error| {
+        ResponseError::from_msg(
+            format!("Invalid webhook uuid `{uuid}`: {error}"),
+            Code::InvalidWebhookUuid,
+        )
+    })
+}
+
+#[derive(Serialize)]
+pub struct WebhooksAnalytics {
+    total_webhooks: usize,
+    signed_webhooks: usize,
+    filtered_webhooks: usize,
+}
+
+impl WebhooksAnalytics {
+    fn new(index_scheduler: &IndexScheduler) -> Self {
+        let webhooks = index_scheduler.webhooks();
+        Self {
+            total_webhooks: webhooks.len(),
+            signed_webhooks: webhooks.values().filter(|webhook| webhook.secret.is_some()).count(),
+            filtered_webhooks: webhooks
+                .values()
+                .filter(|webhook| {
+                    webhook.index_uids.is_some()
+                        || webhook.types.is_some()
+                        || webhook.statuses.is_some()
+                })
+                .count(),
+        }
+    }
+}
+
+impl Aggregate for WebhooksAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Webhooks Updated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        new
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// List webhooks
+///
+/// List the webhooks notified of the processed tasks.
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "Webhooks",
+    security(("Bearer" = ["webhooks.get", "*"])),
+    responses(
+        (status = OK, description = "The webhooks are returned", body = WebhookResults, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "uuid": "627ea538-733d-4545-8d2d-03526eb381ce",
+                        "url": "https://example.com/meilisearch-tasks",
+                        "headers": { "Authorization": "BeXXXX..." },
+                        "indexUids": ["movies"],
+                        "types": null,
+                        "statuses": ["failed"]
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn list_webhooks(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let results = index_scheduler
+        .webhooks()
+        .into_iter()
+        .map(|(uuid, webhook)| WebhookView::new(uuid, webhook))
+        .collect();
+    let results = WebhookResults { results };
+    debug!(returns = ?results, "List webhooks");
+    Ok(HttpResponse::Ok().json(results))
+}
+
+/// Create a webhook
+///
+/// Register a new endpoint notified of the processed tasks.
+#[utoipa::path(
+    post,
+    path = "",
+    tag = "Webhooks",
+    request_body = WebhookSettings,
+    security(("Bearer" = ["webhooks.update", "*"])),
+    responses(
+        (status = CREATED, description = "The webhook has been created", body = WebhookView, content_type = "application/json", example = json!(
+            {
+                "uuid": "627ea538-733d-4545-8d2d-03526eb381ce",
+                "url": "https://example.com/meilisearch-tasks",
+                "headers": {},
+                "indexUids": null,
+                "types": null,
+                "statuses": null
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn create_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<IndexScheduler>>,
+    settings: AwebJson<WebhookSettings, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let settings = settings.into_inner();
+    debug!(parameters = ?settings, "Create webhook");
+
+    let webhook = settings.apply(None)?;
+    let uuid = Uuid::new_v4();
+    index_scheduler.put_webhook(uuid, webhook.clone())?;
+
+    analytics.publish(WebhooksAnalytics::new(&index_scheduler), &req);
+
+    let view = WebhookView::new(uuid, webhook);
+    debug!(returns = ?view, "Create webhook");
+    Ok(HttpResponse::Created().json(view))
+}
+
+/// Get a webhook
+#[utoipa::path(
+    get,
+    path = "/{uuid}",
+    tag = "Webhooks",
+    security(("Bearer" = ["webhooks.get", "*"])),
+    params(("uuid" = String, Path, example = "627ea538-733d-4545-8d2d-03526eb381ce", description = "The uuid of the webhook", nullable = false)),
+    responses(
+        (status = OK, description = "The webhook is returned", body = WebhookView, content_type = "application/json"),
+        (status = 404, description = "The webhook does not exist", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Webhook `627ea538-733d-4545-8d2d-03526eb381ce` not found.",
+                "code": "webhook_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+            }
+        )),
+    )
+)]
+async fn get_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_GET }>, Data<IndexScheduler>>,
+    uuid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = parse_uuid(&uuid)?;
+    let webhook = index_scheduler.webhook(uuid)?;
+
+    let view = WebhookView::new(uuid, webhook);
+    debug!(returns = ?view, "Get webhook");
+    Ok(HttpResponse::Ok().json(view))
+}
+
+/// Update a webhook
+///
+/// The fields that are not specified are left unchanged, and the fields set to `null` are reset.
+#[utoipa::path(
+    patch,
+    path = "/{uuid}",
+    tag = "Webhooks",
+    request_body = WebhookSettings,
+    security(("Bearer" = ["webhooks.update", "*"])),
+    params(("uuid" = String, Path, example = "627ea538-733d-4545-8d2d-03526eb381ce", description = "The uuid of the webhook", nullable = false)),
+    responses(
+        (status = OK, description = "The updated webhook is returned", body = WebhookView, content_type = "application/json"),
+        (status = 404, description = "The webhook does not exist", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Webhook `627ea538-733d-4545-8d2d-03526eb381ce` not found.",
+                "code": "webhook_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+            }
+        )),
+    )
+)]
+async fn patch_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<IndexScheduler>>,
+    uuid: web::Path<String>,
+    settings: AwebJson<WebhookSettings, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = parse_uuid(&uuid)?;
+    let settings = settings.into_inner();
+    debug!(parameters = ?settings, "Patch webhook");
+
+    let old = index_scheduler.webhook(uuid)?;
+    let webhook = settings.apply(Some(old))?;
+    index_scheduler.put_webhook(uuid, webhook.clone())?;
+
+    analytics.publish(WebhooksAnalytics::new(&index_scheduler), &req);
+
+    let view = WebhookView::new(uuid, webhook);
+    debug!(returns = ?view, "Patch webhook");
+    Ok(HttpResponse::Ok().json(view))
+}
+
+/// Delete a webhook
+///
+/// The pending deliveries to the webhook are dropped.
+#[utoipa::path(
+    delete,
+    path = "/{uuid}",
+    tag = "Webhooks",
+    security(("Bearer" = ["webhooks.update", "*"])),
+    params(("uuid" = String, Path, example = "627ea538-733d-4545-8d2d-03526eb381ce", description = "The uuid of the webhook", nullable = false)),
+    responses(
+        (status = NO_CONTENT, description = "The webhook has been deleted"),
+        (status = 404, description = "The webhook does not exist", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Webhook `627ea538-733d-4545-8d2d-03526eb381ce` not found.",
+                "code": "webhook_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+            }
+        )),
+    )
+)]
+async fn delete_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<IndexScheduler>>,
+    uuid: web::Path<String>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = parse_uuid(&uuid)?;
+    index_scheduler.delete_webhook(uuid)?;
+
+    analytics.publish(WebhooksAnalytics::new(&index_scheduler), &req);
+
+    debug!("Delete webhook");
+    Ok(HttpResponse::NoContent().finish())
+}
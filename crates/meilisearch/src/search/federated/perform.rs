@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::iter::Zip;
 use std::rc::Rc;
 use std::str::FromStr as _;
@@ -19,8 +20,8 @@ use tokio::task::JoinHandle;
 
 use super::super::ranking_rules::{self, RankingRules};
 use super::super::{
-    compute_facet_distribution_stats, prepare_search, AttributesFormat, ComputedFacets, HitMaker,
-    HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
+    compute_facet_distribution_stats, prepare_search, AttributesFormat, ComputedFacets, Fusion,
+    HitMaker, HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
 };
 use super::proxy::{proxy_search, ProxySearchError, ProxySearchParams};
 use super::types::{
@@ -29,7 +30,7 @@ use super::types::{
 };
 use super::weighted_scores;
 use crate::error::MeilisearchHttpError;
-use crate::routes::indexes::search::search_kind;
+use crate::routes::indexes::search::{search_kind, search_reranker};
 use crate::search::federated::types::{INDEX_UID, QUERIES_POSITION, WEIGHTED_RANKING_SCORE};
 
 pub async fn perform_federated_search(
@@ -108,7 +109,9 @@ pub async fn perform_federated_search(
         merge_metadata(&mut results_by_index, &remote_results);
 
     // 3.2. merge hits
-    let merged_hits: Vec<_> = merge_index_global_results(results_by_index, &mut remote_results)
+    let global_results =
+        merge_index_global_results(results_by_index, &mut remote_results, federation.fusion);
+    let merged_hits: Vec<_> = global_results
         .skip(federation.offset)
         .take(federation.limit)
         .inspect(|hit| {
@@ -216,9 +219,47 @@ fn merge_index_local_results(
     )
 }
 
+/// Merges the results of the queries to the same index by reciprocal rank.
+///
+/// A document returned by several queries sums its contributions, and keeps the scores of the query
+/// that contributed the most.
+fn fuse_index_local_results(
+    results_by_query: Vec<SearchResultByQuery<'_>>,
+    k: u32,
+) -> Vec<(SearchResultByQueryIterItem<'_>, f64)> {
+    let mut fused: Vec<(SearchResultByQueryIterItem, f64, f64)> = Vec::new();
+    let mut positions = HashMap::new();
+    for results in results_by_query {
+        for (rank, item) in SearchResultByQueryIter::new(results).enumerate() {
+            let contribution = weighted_scores::reciprocal_rank(rank, k, *item.weight);
+            match positions.entry(item.docid) {
+                Entry::Occupied(entry) => {
+                    let (best_item, fused_score, best_contribution) = &mut fused[*entry.get()];
+                    *fused_score += contribution;
+                    if contribution > *best_contribution {
+                        *best_contribution = contribution;
+                        *best_item = item;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(fused.len());
+                    fused.push((item, contribution, contribution));
+                }
+            }
+        }
+    }
+    fused.sort_by(|(left, left_score, _), (right, right_score, _)| {
+        // the biggest score goes first, ties are broken using query index
+        weighted_scores::compare_fused(*right_score, *left_score)
+            .then(left.query_index.cmp(&right.query_index))
+    });
+    fused.into_iter().map(|(item, fused_score, _)| (item, fused_score)).collect()
+}
+
 fn merge_index_global_results(
     results_by_index: Vec<SearchResultByIndex>,
     remote_results: &mut [FederatedSearchResult],
+    fusion: Fusion,
 ) -> impl Iterator<Item = MergedSearchHit> + '_ {
     itertools::kmerge_by(
         // local results
@@ -233,12 +274,20 @@ fn merge_index_global_results(
             let (left_it, left_weighted_global_score, left_query_index) = left.to_score();
             let (right_it, right_weighted_global_score, right_query_index) = right.to_score();
 
-            match weighted_scores::compare(
-                left_it,
-                left_weighted_global_score,
-                right_it,
-                right_weighted_global_score,
-            ) {
+            let ordering = match fusion {
+                Fusion::Score => weighted_scores::compare(
+                    left_it,
+                    left_weighted_global_score,
+                    right_it,
+                    right_weighted_global_score,
+                ),
+                // with reciprocal rank fusion, the global score is the fused score
+                Fusion::Rrf => weighted_scores::compare_fused(
+                    left_weighted_global_score,
+                    right_weighted_global_score,
+                ),
+            };
+            match ordering {
                 // the biggest score goes first
                 Ordering::Greater => true,
                 // break ties using query index
@@ -320,8 +369,10 @@ impl MergedSearchHit {
                     search_hit_by_index.score.iter(),
                     *search_hit_by_index.weight,
                 )),
-                ScoreDetails::global_score(search_hit_by_index.score.iter())
-                    * *search_hit_by_index.weight,
+                search_hit_by_index.fused_score.unwrap_or_else(|| {
+                    ScoreDetails::global_score(search_hit_by_index.score.iter())
+                        * *search_hit_by_index.weight
+                }),
                 search_hit_by_index.query_index,
             ),
             MergedSearchHit::Remote { hit: _, score, global_weighted_score, query_index } => {
@@ -366,6 +417,8 @@ struct SearchHitByIndex {
     score: Vec<ScoreDetails>,
     weight: Weight,
     query_index: usize,
+    /// The reciprocal rank fusion score of the hit, if the hits are merged by rank.
+    fused_score: Option<f64>,
 }
 
 struct SearchResultByIndex {
@@ -474,6 +527,20 @@ impl PartitionedQueries {
             .into());
         }
 
+        if federated_query.facet_ranges.is_some() {
+            return Err(MeilisearchHttpError::FacetRangesInFederatedQuery(query_index).into());
+        }
+
+        if federated_query.hierarchical_facets.is_some() {
+            return Err(
+                MeilisearchHttpError::HierarchicalFacetsInFederatedQuery(query_index).into()
+            );
+        }
+
+        if federated_query.disjunctive_facets.is_some() {
+            return Err(MeilisearchHttpError::DisjunctiveFacetsInFederatedQuery(query_index).into());
+        }
+
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
         let federation_options = federation_options.unwrap_or_default();
@@ -731,6 +798,12 @@ impl SearchByIndex {
             let res: Result<(), ResponseError> = (|| {
                 let search_kind =
                     search_kind(&query, params.index_scheduler, index_uid.to_string(), &index)?;
+                let rerank = search_reranker(
+                    query.rerank.as_ref(),
+                    params.index_scheduler,
+                    index_uid.to_string(),
+                    &index,
+                )?;
 
                 let canonicalization_kind = match (&search_kind, &query.q) {
                     (SearchKind::SemanticOnly { .. }, _) => {
@@ -805,6 +878,7 @@ impl SearchByIndex {
                     &rtxn,
                     &query,
                     &search_kind,
+                    rerank,
                     time_budget,
                     params.features,
                 )?;
@@ -829,6 +903,7 @@ impl SearchByIndex {
                     show_ranking_score: query.show_ranking_score,
                     show_ranking_score_details: query.show_ranking_score_details,
                     locales: query.locales.map(|l| l.iter().copied().map(Into::into).collect()),
+                    semantic_embedder: query.hybrid.map(|hybrid| hybrid.embedder),
                 };
 
                 let milli::SearchResult {
@@ -868,52 +943,59 @@ impl SearchByIndex {
                 return Err(error);
             }
         }
+        let merged_results = match self.federation.fusion {
+            Fusion::Score => {
+                either::Left(merge_index_local_results(results_by_query).map(|item| (item, None)))
+            }
+            Fusion::Rrf => either::Right(
+                fuse_index_local_results(results_by_query, self.federation.rrf_k)
+                    .into_iter()
+                    .map(|(item, fused_score)| (item, Some(fused_score))),
+            ),
+        };
         let mut documents_seen = RoaringBitmap::new();
-        let merged_result: Result<Vec<_>, ResponseError> =
-            merge_index_local_results(results_by_query)
-                // skip documents we've already seen & mark that we saw the current document
-                .filter(|SearchResultByQueryIterItem { docid, .. }| documents_seen.insert(*docid))
-                .take(params.required_hit_count)
-                // 2.3 make hits
-                .map(
-                    |SearchResultByQueryIterItem {
-                         docid,
-                         score,
-                         weight,
-                         hit_maker,
-                         query_index,
-                     }| {
-                        let mut hit = hit_maker.make_hit(docid, &score)?;
-                        let weighted_score = ScoreDetails::global_score(score.iter()) * (*weight);
-
-                        let mut _federation = serde_json::json!(
-                            {
-                                INDEX_UID: index_uid,
-                                QUERIES_POSITION: query_index,
-                                WEIGHTED_RANKING_SCORE: weighted_score,
-                            }
-                        );
-                        if params.has_remote && !params.is_proxy {
-                            _federation.as_object_mut().unwrap().insert(
-                                FEDERATION_REMOTE.to_string(),
-                                params.network.local.clone().into(),
-                            );
+        let merged_result: Result<Vec<_>, ResponseError> = merged_results
+            // skip documents we've already seen & mark that we saw the current document
+            .filter(|(SearchResultByQueryIterItem { docid, .. }, _)| documents_seen.insert(*docid))
+            .take(params.required_hit_count)
+            // 2.3 make hits
+            .map(
+                |(
+                    SearchResultByQueryIterItem { docid, score, weight, hit_maker, query_index },
+                    fused_score,
+                )| {
+                    let mut hit = hit_maker.make_hit(docid, &score)?;
+                    let weighted_score = fused_score
+                        .unwrap_or_else(|| ScoreDetails::global_score(score.iter()) * (*weight));
+
+                    let mut _federation = serde_json::json!(
+                        {
+                            INDEX_UID: index_uid,
+                            QUERIES_POSITION: query_index,
+                            WEIGHTED_RANKING_SCORE: weighted_score,
                         }
-                        if params.is_proxy {
-                            _federation.as_object_mut().unwrap().insert(
-                                WEIGHTED_SCORE_VALUES.to_string(),
-                                serde_json::json!(ScoreDetails::weighted_score_values(
-                                    score.iter(),
-                                    *weight
-                                )
-                                .collect_vec()),
-                            );
-                        }
-                        hit.document.insert(FEDERATION_HIT.to_string(), _federation);
-                        Ok(SearchHitByIndex { hit, score, weight, query_index })
-                    },
-                )
-                .collect();
+                    );
+                    if params.has_remote && !params.is_proxy {
+                        _federation.as_object_mut().unwrap().insert(
+                            FEDERATION_REMOTE.to_string(),
+                            params.network.local.clone().into(),
+                        );
+                    }
+                    if params.is_proxy {
+                        _federation.as_object_mut().unwrap().insert(
+                            WEIGHTED_SCORE_VALUES.to_string(),
+                            serde_json::json!(ScoreDetails::weighted_score_values(
+                                score.iter(),
+                                *weight
+                            )
+                            .collect_vec()),
+                        );
+                    }
+                    hit.document.insert(FEDERATION_HIT.to_string(), _federation);
+                    Ok(SearchHitByIndex { hit, score, weight, query_index, fused_score })
+                },
+            )
+            .collect();
         let merged_result = merged_result?;
         let estimated_total_hits = candidates.len() as usize;
         let facets = facets_by_index
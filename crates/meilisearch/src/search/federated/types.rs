@@ -6,9 +6,9 @@ use std::vec::Vec;
 use indexmap::IndexMap;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::{
-    InvalidMultiSearchFacetsByIndex, InvalidMultiSearchMaxValuesPerFacet,
+    InvalidMultiSearchFacetsByIndex, InvalidMultiSearchFusion, InvalidMultiSearchMaxValuesPerFacet,
     InvalidMultiSearchMergeFacets, InvalidMultiSearchQueryPosition, InvalidMultiSearchRemote,
-    InvalidMultiSearchWeight, InvalidSearchLimit, InvalidSearchOffset,
+    InvalidMultiSearchRrfK, InvalidMultiSearchWeight, InvalidSearchLimit, InvalidSearchOffset,
 };
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
@@ -17,7 +17,9 @@ use meilisearch_types::milli::OrderBy;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::super::{ComputedFacets, FacetStats, HitsInfo, SearchHit, SearchQueryWithIndex};
+use super::super::{
+    ComputedFacets, FacetStats, Fusion, HitsInfo, SearchHit, SearchQueryWithIndex, DEFAULT_RRF_K,
+};
 
 pub const DEFAULT_FEDERATED_WEIGHT: f64 = 1.0;
 
@@ -58,10 +60,11 @@ impl std::convert::TryFrom<f64> for Weight {
     type Error = InvalidMultiSearchWeight;
 
     fn try_from(f: f64) -> Result<Self, Self::Error> {
-        if f < 0.0 {
-            Err(InvalidMultiSearchWeight)
-        } else {
+        // also rejects NaN, which would make the scores impossible to order
+        if f.is_finite() && f >= 0.0 {
             Ok(Weight(f))
+        } else {
+            Err(InvalidMultiSearchWeight)
         }
     }
 }
@@ -87,6 +90,16 @@ pub struct Federation {
     pub facets_by_index: BTreeMap<IndexUid, Option<Vec<String>>>,
     #[deserr(default, error = DeserrJsonError<InvalidMultiSearchMergeFacets>)]
     pub merge_facets: Option<MergeFacets>,
+    /// How the hits of the queries are merged.
+    ///
+    /// `rrf` ranks each hit by the sum of `weight / (rrfK + rank)` over the queries returning it,
+    /// where `rank` is its 1-based position in the results of the query.
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchFusion>)]
+    #[schema(default)]
+    pub fusion: Fusion,
+    #[deserr(default = DEFAULT_RRF_K(), error = DeserrJsonError<InvalidMultiSearchRrfK>)]
+    #[schema(default = DEFAULT_RRF_K)]
+    pub rrf_k: u32,
 }
 
 #[derive(Copy, Clone, Debug, deserr::Deserr, Serialize, Default, ToSchema)]
@@ -237,8 +250,23 @@ impl FederatedFacets {
                     Entry::Occupied(mut entry) => {
                         let stats = entry.get_mut();
 
-                        stats.min = f64::min(stats.min, index_stats.min);
-                        stats.max = f64::max(stats.max, index_stats.max);
+                        stats.merge_min_max(&index_stats);
+                        // the averages are weighted by the counts, the percentiles can't be merged
+                        stats.avg =
+                            match (stats.avg, stats.count, index_stats.avg, index_stats.count) {
+                                (Some(avg), Some(count), Some(index_avg), Some(index_count)) => {
+                                    Some(
+                                        (avg * count as f64 + index_avg * index_count as f64)
+                                            / (count + index_count) as f64,
+                                    )
+                                }
+                                _ => None,
+                            };
+                        stats.count = stats.count.zip(index_stats.count).map(|(l, r)| l + r);
+                        stats.sum = stats.sum.zip(index_stats.sum).map(|(l, r)| l + r);
+                        stats.p50 = None;
+                        stats.p90 = None;
+                        stats.p99 = None;
                     }
                 }
             }
@@ -280,8 +308,7 @@ impl FederatedFacets {
                     }
                     Entry::Occupied(mut occupied_entry) => {
                         let stats = occupied_entry.get_mut();
-                        stats.min = f64::min(stats.min, remote_stats.min);
-                        stats.max = f64::max(stats.max, remote_stats.max);
+                        stats.merge_min_max(&remote_stats);
                     }
                 }
             }
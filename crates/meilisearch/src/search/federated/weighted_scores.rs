@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 
+use meilisearch_types::milli;
 use meilisearch_types::milli::score_details::{self, WeightedScoreValue};
 
 pub fn compare(
@@ -86,3 +87,17 @@ pub fn compare(
         }
     }
 }
+
+/// The contribution of a hit at the zero-based `rank` of the results of its query to its fused score,
+/// when the hits are merged by reciprocal rank.
+pub fn reciprocal_rank(rank: usize, k: u32, weight: f64) -> f64 {
+    weight * milli::reciprocal_rank(k, rank)
+}
+
+/// Compares the fused scores of two hits merged by reciprocal rank.
+pub fn compare_fused(left_fused_score: f64, right_fused_score: f64) -> Ordering {
+    if (left_fused_score - right_fused_score).abs() <= f64::EPSILON {
+        return Ordering::Equal;
+    }
+    left_fused_score.total_cmp(&right_fused_score)
+}
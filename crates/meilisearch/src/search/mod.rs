@@ -1,11 +1,12 @@
 use core::fmt;
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::Infallible;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{take_cf_content, DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 use either::Either;
 use index_scheduler::RoFeatures;
 use indexmap::IndexMap;
@@ -16,9 +17,12 @@ use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::prompt::Prompt;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
+use meilisearch_types::milli::vector::chunking::ChunkPosition;
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::vector::Embedder;
+use meilisearch_types::milli::vector::sparse::{self, QueryVector};
+use meilisearch_types::milli::vector::{Embedder, Quantization, VectorDistance};
 use meilisearch_types::milli::{
     FacetValueHit, InternalError, OrderBy, PatternMatch, SearchForFacetValues, TimeBudget,
 };
@@ -55,13 +59,15 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_RRF_K: fn() -> u32 = || milli::HybridFusion::DEFAULT_RRF_K;
+pub const DEFAULT_RERANK_TOP_K: fn() -> usize = || 50;
 
 #[derive(Clone, Default, PartialEq, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
-    #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
+    #[deserr(default, error = DeserrJsonError<InvalidSearchVector>, from(Option<QueryVector>) = QueryVector::into_embedding)]
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
@@ -100,6 +106,12 @@ pub struct SearchQuery {
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, FacetRangesQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHierarchicalFacets>)]
+    pub hierarchical_facets: Option<BTreeMap<String, Option<String>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
     #[deserr(error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     #[schema(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     pub highlight_pre_tag: String,
@@ -117,6 +129,8 @@ pub struct SearchQuery {
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
     pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerank>)]
+    pub rerank: Option<RerankQuery>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserr, ToSchema, Serialize)]
@@ -179,6 +193,9 @@ impl fmt::Debug for SearchQuery {
             sort,
             distinct,
             facets,
+            facet_ranges,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -186,6 +203,7 @@ impl fmt::Debug for SearchQuery {
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            rerank,
         } = self;
 
         let mut debug = f.debug_struct("SearchQuery");
@@ -219,6 +237,9 @@ impl fmt::Debug for SearchQuery {
         if let Some(hybrid) = hybrid {
             debug.field("hybrid", &hybrid);
         }
+        if let Some(rerank) = rerank {
+            debug.field("rerank", &rerank);
+        }
         if let Some(attributes_to_search_on) = attributes_to_search_on {
             debug.field("attributes_to_search_on", &attributes_to_search_on);
         }
@@ -234,6 +255,15 @@ impl fmt::Debug for SearchQuery {
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
+        if let Some(hierarchical_facets) = hierarchical_facets {
+            debug.field("hierarchical_facets", &hierarchical_facets);
+        }
+        if let Some(disjunctive_facets) = disjunctive_facets {
+            debug.field("disjunctive_facets", &disjunctive_facets);
+        }
         debug.field("matching_strategy", &matching_strategy);
 
         // Then everything related to the formatting
@@ -285,13 +315,72 @@ pub struct HybridQuery {
     pub semantic_ratio: SemanticRatio,
     #[deserr(error = DeserrJsonError<InvalidSearchEmbedder>)]
     pub embedder: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFusion>)]
+    #[schema(default)]
+    #[serde(default)]
+    pub fusion: Fusion,
+    #[deserr(default = DEFAULT_RRF_K(), error = DeserrJsonError<InvalidSearchRrfK>)]
+    #[schema(default = DEFAULT_RRF_K)]
+    pub rrf_k: u32,
+}
+
+impl HybridQuery {
+    pub fn milli_fusion(&self) -> milli::HybridFusion {
+        self.fusion.to_milli(self.rrf_k)
+    }
+}
+
+/// Reorders the top hits of the search with a reranker of the index.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchRerank>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct RerankQuery {
+    #[deserr(error = DeserrJsonError<InvalidSearchReranker>)]
+    pub reranker: String,
+    /// The number of top hits sent to the reranker, at most the `pagination.maxTotalHits` of the index
+    #[deserr(default = DEFAULT_RERANK_TOP_K(), error = DeserrJsonError<InvalidSearchRerankTopK>)]
+    #[schema(default = DEFAULT_RERANK_TOP_K)]
+    pub top_k: usize,
+}
+
+/// How the results of several searches are merged together.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserr, ToSchema, Serialize)]
+#[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
+pub enum Fusion {
+    /// Compare the ranking scores of the hits
+    #[default]
+    Score,
+    /// Compare the ranks of the hits in their result lists, using reciprocal rank fusion
+    Rrf,
+}
+
+impl Fusion {
+    pub fn to_milli(self, rrf_k: u32) -> milli::HybridFusion {
+        match self {
+            Fusion::Score => milli::HybridFusion::Score,
+            Fusion::Rrf => milli::HybridFusion::ReciprocalRank { k: rrf_k },
+        }
+    }
 }
 
 #[derive(Clone)]
 pub enum SearchKind {
     KeywordOnly,
-    SemanticOnly { embedder_name: String, embedder: Arc<Embedder>, quantized: bool },
-    Hybrid { embedder_name: String, embedder: Arc<Embedder>, quantized: bool, semantic_ratio: f32 },
+    SemanticOnly {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        quantization: Quantization,
+        distance: VectorDistance,
+    },
+    Hybrid {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        quantization: Quantization,
+        distance: VectorDistance,
+        semantic_ratio: f32,
+        fusion: milli::HybridFusion,
+    },
 }
 
 impl SearchKind {
@@ -300,17 +389,17 @@ impl SearchKind {
         index_uid: String,
         index: &Index,
         embedder_name: &str,
-        vector_len: Option<usize>,
+        vector: Option<&[f32]>,
     ) -> Result<Self, ResponseError> {
-        let (embedder_name, embedder, quantized) = Self::embedder(
+        let (embedder_name, embedder, quantization, distance) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
             embedder_name,
-            vector_len,
+            vector,
             Route::Search,
         )?;
-        Ok(Self::SemanticOnly { embedder_name, embedder, quantized })
+        Ok(Self::SemanticOnly { embedder_name, embedder, quantization, distance })
     }
 
     pub(crate) fn hybrid(
@@ -319,17 +408,18 @@ impl SearchKind {
         index: &Index,
         embedder_name: &str,
         semantic_ratio: f32,
-        vector_len: Option<usize>,
+        fusion: milli::HybridFusion,
+        vector: Option<&[f32]>,
     ) -> Result<Self, ResponseError> {
-        let (embedder_name, embedder, quantized) = Self::embedder(
+        let (embedder_name, embedder, quantization, distance) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
             embedder_name,
-            vector_len,
+            vector,
             Route::Search,
         )?;
-        Ok(Self::Hybrid { embedder_name, embedder, quantized, semantic_ratio })
+        Ok(Self::Hybrid { embedder_name, embedder, quantization, distance, semantic_ratio, fusion })
     }
 
     pub(crate) fn embedder(
@@ -337,14 +427,14 @@ impl SearchKind {
         index_uid: String,
         index: &Index,
         embedder_name: &str,
-        vector_len: Option<usize>,
+        vector: Option<&[f32]>,
         route: Route,
-    ) -> Result<(String, Arc<Embedder>, bool), ResponseError> {
+    ) -> Result<(String, Arc<Embedder>, Quantization, VectorDistance), ResponseError> {
         let rtxn = index.read_txn()?;
         let embedder_configs = index.embedding_configs(&rtxn)?;
         let embedders = index_scheduler.embedders(index_uid, embedder_configs)?;
 
-        let (embedder, _, quantized) = embedders
+        let (embedder, _, quantization, distance) = embedders
             .get(embedder_name)
             .ok_or(match route {
                 Route::Search | Route::MultiSearch => {
@@ -356,8 +446,27 @@ impl SearchKind {
             })
             .map_err(milli::Error::from)?;
 
-        if let Some(vector_len) = vector_len {
-            if vector_len != embedder.dimensions() {
+        if let Some(vector) = vector {
+            let vector_len = vector.len();
+            if embedder.sparse() || sparse::is_sparse(vector) {
+                if let Err(reason) = sparse::check(vector, embedder.sparse(), embedder.dimensions())
+                {
+                    return Err(meilisearch_types::milli::Error::UserError(
+                        meilisearch_types::milli::UserError::InvalidSparseVector { reason },
+                    )
+                    .into());
+                }
+            } else if embedder.multi_vector() {
+                if vector_len == 0 || vector_len % embedder.dimensions() != 0 {
+                    return Err(meilisearch_types::milli::Error::UserError(
+                        meilisearch_types::milli::UserError::InvalidMultiVectorDimensions {
+                            expected: embedder.dimensions(),
+                            found: vector_len,
+                        },
+                    )
+                    .into());
+                }
+            } else if vector_len != embedder.dimensions() {
                 return Err(meilisearch_types::milli::Error::UserError(
                     meilisearch_types::milli::UserError::InvalidVectorDimensions {
                         expected: embedder.dimensions(),
@@ -368,7 +477,7 @@ impl SearchKind {
             }
         }
 
-        Ok((embedder_name.to_owned(), embedder, quantized))
+        Ok((embedder_name.to_owned(), embedder, quantization, distance))
     }
 }
 
@@ -410,6 +519,116 @@ impl SearchQuery {
     }
 }
 
+/// How the numbers of a facet are split into buckets in the `facetRanges` of a search.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum FacetRangesQuery {
+    /// Consecutive buckets of the same width: `{ "interval": 50 }`.
+    Interval(FacetRangeInterval),
+    /// Explicit buckets: `[{ "to": 10 }, { "from": 10, "to": 100 }]`.
+    Ranges(Vec<FacetRangeQuery>),
+}
+
+impl Deserr<DeserrJsonError<InvalidSearchFacetRanges>> for FacetRangesQuery {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, DeserrJsonError<InvalidSearchFacetRanges>> {
+        match value {
+            deserr::Value::Map(_) => {
+                FacetRangeInterval::deserialize_from_value(value, location).map(Self::Interval)
+            }
+            deserr::Value::Sequence(_) => {
+                Vec::<FacetRangeQuery>::deserialize_from_value(value, location).map(Self::Ranges)
+            }
+            value => Err(take_cf_content(DeserrJsonError::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::Map, deserr::ValueKind::Sequence],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+impl From<FacetRangesQuery> for milli::FacetRanges {
+    fn from(query: FacetRangesQuery) -> Self {
+        match query {
+            FacetRangesQuery::Interval(FacetRangeInterval { interval }) => {
+                milli::FacetRanges::Interval(interval)
+            }
+            FacetRangesQuery::Ranges(ranges) => milli::FacetRanges::Ranges(
+                ranges
+                    .into_iter()
+                    .map(|FacetRangeQuery { from, to }| milli::FacetRange { from, to })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetRanges>, rename_all = camelCase, deny_unknown_fields, validate = validate_facet_range_interval -> DeserrJsonError<InvalidSearchFacetRanges>)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetRangeInterval {
+    pub interval: f64,
+}
+
+fn validate_facet_range_interval<E: DeserializeError>(
+    interval: FacetRangeInterval,
+    location: ValuePointerRef,
+) -> Result<FacetRangeInterval, E> {
+    if !interval.interval.is_finite() || interval.interval <= 0.0 {
+        return Err(take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: format!(
+                    "the interval must be a strictly positive number, but found `{}`",
+                    interval.interval
+                ),
+            },
+            location,
+        )));
+    }
+    Ok(interval)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetRanges>, rename_all = camelCase, deny_unknown_fields, validate = validate_facet_range -> DeserrJsonError<InvalidSearchFacetRanges>)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetRangeQuery {
+    /// The inclusive lower bound of the bucket, unbounded when missing.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    /// The exclusive upper bound of the bucket, unbounded when missing.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+}
+
+fn validate_facet_range<E: DeserializeError>(
+    range: FacetRangeQuery,
+    location: ValuePointerRef,
+) -> Result<FacetRangeQuery, E> {
+    if let (Some(from), Some(to)) = (range.from, range.to) {
+        if from > to {
+            return Err(take_cf_content(E::error::<Infallible>(
+                None,
+                ErrorKind::Unexpected {
+                    msg: format!(
+                        "`from` must be lower than or equal to `to`, but found `from: {from}` and `to: {to}`"
+                    ),
+                },
+                location,
+            )));
+        }
+    }
+    Ok(range)
+}
+
 /// A `SearchQuery` + an index UID and optional FederationOptions.
 // This struct contains the fields of `SearchQuery` inline.
 // This is because neither deserr nor serde support `flatten` when using `deny_unknown_fields.
@@ -423,7 +642,7 @@ pub struct SearchQueryWithIndex {
     pub index_uid: IndexUid,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
-    #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQ>, from(Option<QueryVector>) = QueryVector::into_embedding)]
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
@@ -459,6 +678,12 @@ pub struct SearchQueryWithIndex {
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, FacetRangesQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHierarchicalFacets>)]
+    pub hierarchical_facets: Option<BTreeMap<String, Option<String>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -473,6 +698,8 @@ pub struct SearchQueryWithIndex {
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
     pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerank>, default)]
+    pub rerank: Option<RerankQuery>,
 
     #[deserr(default)]
     pub federation_options: Option<FederationOptions>,
@@ -522,6 +749,9 @@ impl SearchQueryWithIndex {
             sort,
             distinct,
             facets,
+            facet_ranges,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -529,6 +759,7 @@ impl SearchQueryWithIndex {
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            rerank,
         } = query;
 
         SearchQueryWithIndex {
@@ -552,6 +783,9 @@ impl SearchQueryWithIndex {
             sort,
             distinct,
             facets,
+            facet_ranges,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -559,6 +793,7 @@ impl SearchQueryWithIndex {
             attributes_to_search_on,
             ranking_score_threshold,
             locales,
+            rerank,
             federation_options,
         }
     }
@@ -585,6 +820,9 @@ impl SearchQueryWithIndex {
             sort,
             distinct,
             facets,
+            facet_ranges,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -593,6 +831,7 @@ impl SearchQueryWithIndex {
             hybrid,
             ranking_score_threshold,
             locales,
+            rerank,
         } = self;
         (
             index_uid,
@@ -615,6 +854,9 @@ impl SearchQueryWithIndex {
                 sort,
                 distinct,
                 facets,
+                facet_ranges,
+                hierarchical_facets,
+                disjunctive_facets,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
@@ -623,6 +865,7 @@ impl SearchQueryWithIndex {
                 hybrid,
                 ranking_score_threshold,
                 locales,
+                rerank,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -751,6 +994,9 @@ pub struct SearchHit {
     pub ranking_score: Option<f64>,
     #[serde(default, rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
+    /// The chunk of the document that best matched the semantic query, when the embedder chunks documents.
+    #[serde(default, rename = "_semanticChunk", skip_serializing_if = "Option::is_none")]
+    pub semantic_chunk: Option<ChunkPosition>,
 }
 
 #[derive(Serialize, Clone, PartialEq, ToSchema)]
@@ -767,6 +1013,11 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeCount>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<BTreeMap<String, Value>>)]
+    pub hierarchical_facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
@@ -787,6 +1038,8 @@ impl fmt::Debug for SearchResult {
             hits_info,
             facet_distribution,
             facet_stats,
+            facet_ranges,
+            hierarchical_facet_distribution,
             semantic_hit_count,
             degraded,
             used_negative_operator,
@@ -810,6 +1063,12 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
+        if let Some(hierarchical_facet_distribution) = hierarchical_facet_distribution {
+            debug.field("hierarchical_facet_distribution", &hierarchical_facet_distribution);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
@@ -852,6 +1111,76 @@ pub enum HitsInfo {
 pub struct FacetStats {
     pub min: f64,
     pub max: f64,
+    // The min and max as RFC 3339 datetimes, only when the values of the facet are dates.
+    // The min and max above are then the number of seconds since the Unix epoch.
+    #[serde(rename = "minDate", default, skip_serializing_if = "Option::is_none")]
+    pub min_date: Option<String>,
+    #[serde(rename = "maxDate", default, skip_serializing_if = "Option::is_none")]
+    pub max_date: Option<String>,
+    // The following statistics are only computed for the attributes
+    // configured in the `faceting.facetStats` setting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p50: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p90: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p99: Option<f64>,
+}
+
+impl FacetStats {
+    fn new(
+        (facet_type, min, max): (milli::facet::FacetType, f64, f64),
+        extended: Option<milli::ExtendedFacetStats>,
+    ) -> Self {
+        let milli::ExtendedFacetStats { count, sum, avg, p50, p90, p99 } =
+            extended.unwrap_or_default();
+        let (min_date, max_date) = match facet_type {
+            milli::facet::FacetType::Date => (format_timestamp(min), format_timestamp(max)),
+            _ => (None, None),
+        };
+        FacetStats { min, max, min_date, max_date, count, sum, avg, p50, p90, p99 }
+    }
+
+    /// Merges the min and max computed on another index for the same facet.
+    ///
+    /// The dates are only kept when both sides are dates.
+    pub(crate) fn merge_min_max(&mut self, other: &FacetStats) {
+        let dates = self.min_date.is_some() && other.min_date.is_some();
+        if other.min < self.min {
+            self.min = other.min;
+            self.min_date.clone_from(&other.min_date);
+        }
+        if other.max > self.max {
+            self.max = other.max;
+            self.max_date.clone_from(&other.max_date);
+        }
+        if !dates {
+            self.min_date = None;
+            self.max_date = None;
+        }
+    }
+}
+
+/// Formats a number of seconds since the Unix epoch as an RFC 3339 datetime.
+fn format_timestamp(timestamp: f64) -> Option<String> {
+    let nanos = (timestamp * 1_000_000_000.0) as i128;
+    let datetime = time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?;
+    datetime.format(&time::format_description::well_known::Rfc3339).ok()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct FacetRangeCount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -887,11 +1216,15 @@ fn prepare_search<'t>(
     rtxn: &'t RoTxn,
     query: &'t SearchQuery,
     search_kind: &SearchKind,
+    rerank: Option<milli::RerankSearch>,
     time_budget: TimeBudget,
     features: RoFeatures,
 ) -> Result<(milli::Search<'t>, bool, usize, usize), ResponseError> {
     let mut search = index.search(rtxn);
     search.time_budget(time_budget);
+    if let Some(rerank) = rerank {
+        search.rerank(rerank);
+    }
     if let Some(ranking_score_threshold) = query.ranking_score_threshold {
         search.ranking_score_threshold(ranking_score_threshold.0);
     }
@@ -906,7 +1239,7 @@ fn prepare_search<'t>(
                 search.query(q);
             }
         }
-        SearchKind::SemanticOnly { embedder_name, embedder, quantized } => {
+        SearchKind::SemanticOnly { embedder_name, embedder, quantization, distance } => {
             let vector = match query.vector.clone() {
                 Some(vector) => vector,
                 None => {
@@ -922,9 +1255,15 @@ fn prepare_search<'t>(
                 }
             };
 
-            search.semantic(embedder_name.clone(), embedder.clone(), *quantized, Some(vector));
+            search.semantic(
+                embedder_name.clone(),
+                embedder.clone(),
+                *quantization,
+                *distance,
+                Some(vector),
+            );
         }
-        SearchKind::Hybrid { embedder_name, embedder, quantized, semantic_ratio: _ } => {
+        SearchKind::Hybrid { embedder_name, embedder, quantization, distance, .. } => {
             if let Some(q) = &query.q {
                 search.query(q);
             }
@@ -932,7 +1271,8 @@ fn prepare_search<'t>(
             search.semantic(
                 embedder_name.clone(),
                 embedder.clone(),
-                *quantized,
+                *quantization,
+                *distance,
                 query.vector.clone(),
             );
         }
@@ -1011,6 +1351,7 @@ pub fn perform_search(
     index: &Index,
     query: SearchQuery,
     search_kind: SearchKind,
+    rerank: Option<milli::RerankSearch>,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
 ) -> Result<SearchResult, ResponseError> {
@@ -1022,7 +1363,26 @@ pub fn perform_search(
     };
 
     let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+        prepare_search(index, &rtxn, &query, &search_kind, rerank, time_budget.clone(), features)?;
+
+    // the disjunctive facets are computed on the documents matching the query without the filter,
+    // which don't depend on the embedding of the query and are computed before the search.
+    let filter = match (&query.disjunctive_facets, &query.filter) {
+        (Some(_), Some(filter)) => parse_filter(filter, Code::InvalidSearchFilter, features)?,
+        _ => None,
+    };
+    let relaxed_filters =
+        query.disjunctive_facets.as_deref().map(|facets| relax_filter(facets, filter.as_ref()));
+    let unfiltered_candidates = match &relaxed_filters {
+        Some(relaxed_filters) if relaxed_filters.iter().any(|(_, relaxed)| relaxed.is_some()) => {
+            let has_vector_search = !matches!(search_kind, SearchKind::KeywordOnly);
+            let candidates = search
+                .execute_for_unfiltered_candidates(has_vector_search)
+                .map_err(|e| MeilisearchHttpError::from_milli(e, Some(index_uid.to_string())))?;
+            Some(candidates)
+        }
+        _ => None,
+    };
 
     let (
         milli::SearchResult {
@@ -1036,6 +1396,18 @@ pub fn perform_search(
         semantic_hit_count,
     ) = search_from_kind(index_uid, search_kind, search)?;
 
+    let disjunctive_facets = relaxed_filters
+        .map(|relaxed_filters| {
+            compute_disjunctive_facets(
+                relaxed_filters,
+                unfiltered_candidates.as_ref(),
+                index,
+                &rtxn,
+                &candidates,
+            )
+        })
+        .transpose()?;
+
     let SearchQuery {
         q,
         limit,
@@ -1052,13 +1424,18 @@ pub fn perform_search(
         show_ranking_score_details,
         sort,
         facets,
+        facet_ranges,
+        hierarchical_facets,
+        // already computed above
+        disjunctive_facets: _,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
         locales,
         // already used in prepare_search
         vector: _,
-        hybrid: _,
+        hybrid,
+        rerank: _,
         offset: _,
         ranking_score_threshold: _,
         matching_strategy: _,
@@ -1081,6 +1458,7 @@ pub fn perform_search(
         show_ranking_score,
         show_ranking_score_details,
         locales: locales.map(|l| l.iter().copied().map(Into::into).collect()),
+        semantic_embedder: hybrid.map(|hybrid| hybrid.embedder),
     };
 
     let documents = make_hits(
@@ -1109,7 +1487,22 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let (facet_distribution, facet_stats) = facets
+    let facet_ranges = facet_ranges
+        .map(|facet_ranges| compute_facet_ranges(facet_ranges, index, &rtxn, candidates.clone()))
+        .transpose()?;
+
+    let hierarchical_facet_distribution = hierarchical_facets
+        .map(|hierarchical_facets| {
+            compute_hierarchical_facet_distribution(
+                &hierarchical_facets,
+                index,
+                &rtxn,
+                candidates.clone(),
+            )
+        })
+        .transpose()?;
+
+    let (mut facet_distribution, mut facet_stats): (Option<_>, Option<_>) = facets
         .map(move |facets| {
             compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)
         })
@@ -1117,6 +1510,11 @@ pub fn perform_search(
         .map(|ComputedFacets { distribution, stats }| (distribution, stats))
         .unzip();
 
+    if let Some(ComputedFacets { distribution, stats }) = disjunctive_facets {
+        facet_distribution.get_or_insert_with(BTreeMap::new).extend(distribution);
+        facet_stats.get_or_insert_with(BTreeMap::new).extend(stats);
+    }
+
     let result = SearchResult {
         hits: documents,
         hits_info,
@@ -1124,6 +1522,8 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_ranges,
+        hierarchical_facet_distribution,
         degraded,
         used_negative_operator,
         semantic_hit_count,
@@ -1183,11 +1583,150 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
             ) => ResponseError::from_msg(error.to_string(), Code::InvalidMultiSearchFacets),
             (error, _) => error.into(),
         })?;
-    let stats = facet_distribution.compute_stats()?;
-    let stats = stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect();
+    let stats = compute_facet_stats(&facet_distribution)?;
     Ok(ComputedFacets { distribution, stats })
 }
 
+fn compute_facet_stats(
+    facet_distribution: &milli::FacetDistribution,
+) -> Result<BTreeMap<String, FacetStats>, ResponseError> {
+    let stats = facet_distribution.compute_typed_stats()?;
+    let mut extended_stats = facet_distribution.compute_extended_stats()?;
+    Ok(stats
+        .into_iter()
+        .map(|(name, typed_min_max)| {
+            let extended = extended_stats.remove(&name);
+            (name, FacetStats::new(typed_min_max, extended))
+        })
+        .collect())
+}
+
+fn compute_facet_ranges(
+    facet_ranges: BTreeMap<String, FacetRangesQuery>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: roaring::RoaringBitmap,
+) -> Result<BTreeMap<String, Vec<FacetRangeCount>>, ResponseError> {
+    let mut facet_distribution = index.facets_distribution(rtxn);
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let facet_ranges: BTreeMap<_, milli::FacetRanges> =
+        facet_ranges.into_iter().map(|(name, ranges)| (name, ranges.into())).collect();
+
+    let ranges = facet_distribution
+        .max_values_per_facet(max_values_by_facet)
+        .candidates(candidates)
+        .compute_ranges(&facet_ranges)?;
+
+    Ok(ranges
+        .into_iter()
+        .map(|(name, buckets)| {
+            let buckets = buckets
+                .into_iter()
+                .map(|milli::FacetRangeBucket { from, to, count }| FacetRangeCount {
+                    from,
+                    to,
+                    count,
+                })
+                .collect();
+            (name, buckets)
+        })
+        .collect())
+}
+
+fn compute_hierarchical_facet_distribution(
+    hierarchical_facets: &BTreeMap<String, Option<String>>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: roaring::RoaringBitmap,
+) -> Result<BTreeMap<String, IndexMap<String, u64>>, ResponseError> {
+    let mut facet_distribution = index.facets_distribution(rtxn);
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    Ok(facet_distribution
+        .max_values_per_facet(max_values_by_facet)
+        .candidates(candidates)
+        .compute_hierarchical(hierarchical_facets)?)
+}
+
+/// The filter of each disjunctive facet, without the clauses that only constrain this facet.
+///
+/// `None` when the facet isn't constrained by the filter and the candidates can be reused,
+/// `Some(None)` when the facet is the only one constrained by the filter.
+type RelaxedFilters<'a> = Vec<(&'a str, Option<Option<Filter<'a>>>)>;
+
+fn relax_filter<'a>(
+    disjunctive_facets: &'a [String],
+    filter: Option<&Filter<'a>>,
+) -> RelaxedFilters<'a> {
+    disjunctive_facets
+        .iter()
+        .map(|facet| {
+            let relaxed = filter.and_then(|filter| {
+                let relaxed = filter.without_attribute(facet);
+                (relaxed.as_ref() != Some(filter)).then_some(relaxed)
+            });
+            (facet.as_str(), relaxed)
+        })
+        .collect()
+}
+
+/// Computes the distribution and stats of each disjunctive facet on the documents matching
+/// the query and the filter, without the clauses of the filter that only constrain this facet.
+///
+/// The relaxed filters are applied to the documents matching the query without any filter,
+/// which must be provided as soon as a filter is relaxed.
+fn compute_disjunctive_facets(
+    relaxed_filters: RelaxedFilters<'_>,
+    unfiltered_candidates: Option<&roaring::RoaringBitmap>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: &roaring::RoaringBitmap,
+) -> Result<ComputedFacets, ResponseError> {
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+    let sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
+
+    let mut computed = ComputedFacets::default();
+    for (facet, relaxed) in relaxed_filters {
+        let facet_candidates = match (relaxed, unfiltered_candidates) {
+            (Some(Some(relaxed)), Some(unfiltered)) => relaxed.evaluate(rtxn, index)? & unfiltered,
+            (Some(None), Some(unfiltered)) => unfiltered.clone(),
+            _ => candidates.clone(),
+        };
+
+        let mut facet_distribution = index.facets_distribution(rtxn);
+        facet_distribution
+            .max_values_per_facet(max_values_by_facet)
+            .facets([(facet, sort_facet_values_by.get(facet))])
+            .candidates(facet_candidates);
+
+        let distribution = facet_distribution.execute().map_err(|error| match error {
+            error @ milli::Error::UserError(milli::UserError::InvalidFacetsDistribution {
+                ..
+            }) => ResponseError::from_msg(error.to_string(), Code::InvalidSearchDisjunctiveFacets),
+            error => error.into(),
+        })?;
+        computed.distribution.extend(distribution);
+        computed.stats.extend(compute_facet_stats(&facet_distribution)?);
+    }
+
+    Ok(computed)
+}
+
 pub fn search_from_kind(
     index_uid: String,
     search_kind: SearchKind,
@@ -1207,8 +1746,8 @@ pub fn search_from_kind(
             let semantic_hit_count = results.document_scores.len() as u32;
             (results, Some(semantic_hit_count))
         }
-        SearchKind::Hybrid { semantic_ratio, .. } => search
-            .execute_hybrid(*semantic_ratio)
+        SearchKind::Hybrid { semantic_ratio, fusion, .. } => search
+            .execute_hybrid(*semantic_ratio, *fusion)
             .map_err(|e| MeilisearchHttpError::from_milli(e, Some(index_uid)))?,
     };
     Ok((milli_result, semantic_hit_count))
@@ -1228,6 +1767,8 @@ struct AttributesFormat {
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     locales: Option<Vec<Language>>,
+    /// The embedder of the semantic part of the query, if any.
+    semantic_embedder: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1268,6 +1809,8 @@ struct HitMaker<'a> {
     sort: Option<Vec<String>>,
     show_matches_position: bool,
     locales: Option<Vec<Language>>,
+    /// The prompt of the embedder of the semantic part of the query, if it chunks documents.
+    chunked_prompt: Option<Prompt>,
 }
 
 impl<'a> HitMaker<'a> {
@@ -1377,6 +1920,14 @@ impl<'a> HitMaker<'a> {
 
         let embedding_configs = index.embedding_configs(rtxn)?;
 
+        let chunked_prompt = format
+            .semantic_embedder
+            .as_deref()
+            .and_then(|name| embedding_configs.iter().find(|config| config.name == name))
+            .filter(|config| config.config.prompt.chunking.is_some())
+            .map(|config| Prompt::try_from(config.config.prompt.clone()))
+            .transpose()?;
+
         Ok(Self {
             index,
             rtxn,
@@ -1393,6 +1944,7 @@ impl<'a> HitMaker<'a> {
             show_matches_position: format.show_matches_position,
             sort: format.sort,
             locales: format.locales,
+            chunked_prompt,
         })
     }
 
@@ -1470,18 +2022,32 @@ impl<'a> HitMaker<'a> {
         let ranking_score_details =
             self.show_ranking_score_details.then(|| ScoreDetails::to_json_map(score.iter()));
 
+        let semantic_chunk = match (&self.chunked_prompt, semantic_chunk_index(score)) {
+            (Some(prompt), Some(chunk)) => prompt.locate_chunk(self.index, self.rtxn, id, chunk)?,
+            _ => None,
+        };
+
         let hit = SearchHit {
             document,
             formatted,
             matches_position,
             ranking_score_details,
             ranking_score,
+            semantic_chunk,
         };
 
         Ok(hit)
     }
 }
 
+/// The index of the embedding of the document that matched the semantic query, if the document was found by it.
+fn semantic_chunk_index(score: &[ScoreDetails]) -> Option<u8> {
+    score.iter().find_map(|details| match details {
+        ScoreDetails::Vector(vector) => vector.chunk,
+        _ => None,
+    })
+}
+
 fn make_hits<'a>(
     index: &Index,
     rtxn: &RoTxn<'_>,
@@ -1549,7 +2115,7 @@ pub fn perform_facet_search(
     });
 
     let (search, _, _, _) =
-        prepare_search(index, &rtxn, &search_query, &search_kind, time_budget, features)?;
+        prepare_search(index, &rtxn, &search_query, &search_kind, None, time_budget, features)?;
     let mut facet_search = SearchForFacetValues::new(
         facet_name,
         search,
@@ -1578,7 +2144,8 @@ pub fn perform_similar(
     query: SimilarQuery,
     embedder_name: String,
     embedder: Arc<Embedder>,
-    quantized: bool,
+    quantization: Quantization,
+    distance: VectorDistance,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
 ) -> Result<SimilarResult, ResponseError> {
@@ -1620,7 +2187,8 @@ pub fn perform_similar(
         &rtxn,
         embedder_name,
         embedder,
-        quantized,
+        quantization,
+        distance,
     );
 
     if let Some(ref filter) = query.filter {
@@ -1661,6 +2229,8 @@ pub fn perform_similar(
         show_ranking_score,
         show_ranking_score_details,
         locales: None,
+        // similar documents are scored against all the embeddings of the target
+        semantic_embedder: None,
     };
 
     let hits = make_hits(
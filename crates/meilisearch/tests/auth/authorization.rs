@@ -70,6 +70,11 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PATCH",   "/experimental-features") =>                           hashset!{"experimental.update", "*"},
             ("GET",   "/network") =>                                           hashset!{"network.get", "*"},
             ("PATCH",   "/network") =>                                         hashset!{"network.update", "*"},
+            ("GET",     "/webhooks") =>                                        hashset!{"webhooks.get", "*"},
+            ("POST",    "/webhooks") =>                                        hashset!{"webhooks.update", "*"},
+            ("GET",     "/webhooks/mywebhook/") =>                             hashset!{"webhooks.get", "*"},
+            ("PATCH",   "/webhooks/mywebhook/") =>                             hashset!{"webhooks.update", "*"},
+            ("DELETE",  "/webhooks/mywebhook/") =>                             hashset!{"webhooks.update", "*"},
         };
 
         authorizations
@@ -93,7 +93,9 @@ async fn import_dump_v1_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -102,7 +104,10 @@ async fn import_dump_v1_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -266,7 +271,9 @@ async fn import_dump_v1_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -275,7 +282,10 @@ async fn import_dump_v1_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -438,7 +448,9 @@ async fn import_dump_v1_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -447,7 +459,10 @@ async fn import_dump_v1_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -596,7 +611,9 @@ async fn import_dump_v2_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -605,7 +622,10 @@ async fn import_dump_v2_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -766,7 +786,9 @@ async fn import_dump_v2_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -775,7 +797,10 @@ async fn import_dump_v2_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -935,7 +960,9 @@ async fn import_dump_v2_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -944,7 +971,10 @@ async fn import_dump_v2_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1093,7 +1123,9 @@ async fn import_dump_v3_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1102,7 +1134,10 @@ async fn import_dump_v3_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1263,7 +1298,9 @@ async fn import_dump_v3_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1272,7 +1309,10 @@ async fn import_dump_v3_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1432,7 +1472,9 @@ async fn import_dump_v3_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1441,7 +1483,10 @@ async fn import_dump_v3_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1590,7 +1635,9 @@ async fn import_dump_v4_movie_raw() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1599,7 +1646,10 @@ async fn import_dump_v4_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1760,7 +1810,9 @@ async fn import_dump_v4_movie_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1769,7 +1821,10 @@ async fn import_dump_v4_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1929,7 +1984,9 @@ async fn import_dump_v4_rubygems_with_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -1938,7 +1995,10 @@ async fn import_dump_v4_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -2218,7 +2278,9 @@ async fn import_dump_v6_containing_experimental_features() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -2227,7 +2289,10 @@ async fn import_dump_v6_containing_experimental_features() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###);
 
@@ -2450,7 +2515,9 @@ async fn generate_and_import_dump_containing_vectors() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -2468,7 +2535,10 @@ async fn generate_and_import_dump_containing_vectors() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "###);
 
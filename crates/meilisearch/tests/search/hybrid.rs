@@ -368,6 +368,94 @@ async fn invalid_semantic_ratio() {
     "###);
 }
 
+#[actix_rt::test]
+async fn reciprocal_rank_fusion() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    // keyword ranks: 2, 3, 1; semantic ranks: 3, 2, 1
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.8, "embedder": "default", "fusion": "rrf", "rrfK": 1}, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"2"},{"id":"1"}]"###);
+    snapshot!(response["semanticHitCount"], @"3");
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.2, "embedder": "default", "fusion": "rrf", "rrfK": 1}, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2"},{"id":"3"},{"id":"1"}]"###);
+    snapshot!(response["semanticHitCount"], @"0");
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.8, "embedder": "default", "fusion": "rrf", "rrfK": 1}, "attributesToRetrieve": ["id"], "offset": 1, "limit": 1}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2"}]"###);
+
+    let (response, code) = index
+        .search_get(
+            &yaup::to_string(
+                &json!({"q": "Captain", "vector": [1.0, 1.0], "hybridEmbedder": "default", "hybridSemanticRatio": 0.2, "hybridFusion": "rrf", "hybridRrfK": 1, "attributesToRetrieve": ["id"]}),
+            )
+            .unwrap(),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2"},{"id":"3"},{"id":"1"}]"###);
+}
+
+#[actix_rt::test]
+async fn invalid_fusion() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"embedder": "default", "fusion": "rank"}}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Unknown value `rank` at `.hybrid.fusion`: expected one of `score`, `rrf`",
+      "code": "invalid_search_fusion",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_fusion"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"embedder": "default", "fusion": "rrf", "rrfK": -1}}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_rrf_k""###);
+
+    let (response, code) = index
+        .search_get(
+            &yaup::to_string(&json!({"q": "Captain", "hybridFusion": "rrf"})).unwrap(),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`hybridEmbedder` is mandatory when `hybridFusion` or `hybridRrfK` is present",
+      "code": "invalid_search_hybrid_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hybrid_query"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn single_document() {
     let server = Server::new().await;
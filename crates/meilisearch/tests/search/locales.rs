@@ -1011,6 +1011,22 @@ async fn invalid_localized_attributes_rules() {
       "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
     }
     "###);
+
+    let (response, _) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                {"attributePatterns": ["*_ja"], "locales": ["jpn"], "stemming": true}
+            ]
+        }))
+        .await;
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.localizedAttributes[0]`: `stemming` requires at least one locale supporting stemming, expected one of ara, dan, deu, ell, eng, fin, fra, hun, ita, nld, nob, por, ron, rus, spa, swe, tam, tur",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
 }
 
 #[actix_rt::test]
@@ -1335,3 +1351,34 @@ async fn german_search() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn stemming_search() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    let documents = json!([
+      {"id": 1, "title": "The cat runs"},
+      {"id": 2, "title": "A running dog"},
+      {"id": 3, "title": "Ran away"},
+    ]);
+    index.add_documents(documents, None).await;
+    let (response, _) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                {"attributePatterns": ["title"], "locales": ["eng"], "stemming": true}
+            ]
+        }))
+        .await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, _) = index.settings().await;
+    snapshot!(response["localizedAttributes"], @r###"[{"attributePatterns":["title"],"locales":["eng"],"stemming":true}]"###);
+
+    index
+        .search(json!({"q": "running", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(response["hits"], @r###"[{"id":2},{"id":1}]"###);
+            snapshot!(code, @"200 OK");
+        })
+        .await;
+}
@@ -571,6 +571,428 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_facet_ranges() {
+    let index = shared_index_with_nested_documents().await;
+
+    index
+        .search(
+            json!({
+                "facetRanges": { "doggos.age": { "interval": 3 } }
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["facetRanges"]), @r###"
+                {
+                  "doggos.age": [
+                    {
+                      "from": 0.0,
+                      "to": 3.0,
+                      "count": 1
+                    },
+                    {
+                      "from": 3.0,
+                      "to": 6.0,
+                      "count": 2
+                    },
+                    {
+                      "from": 6.0,
+                      "to": 9.0,
+                      "count": 2
+                    }
+                  ]
+                }
+                "###);
+                assert!(response.get("facetDistribution").is_none());
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "father = jean",
+                "facetRanges": { "doggos.age": [{ "to": 5 }, { "from": 5 }] }
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["facetRanges"]), @r###"
+                {
+                  "doggos.age": [
+                    {
+                      "to": 5.0,
+                      "count": 1
+                    },
+                    {
+                      "from": 5.0,
+                      "count": 0
+                    }
+                  ]
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_hierarchical_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "categories": { "lvl0": "Books", "lvl1": "Books > Comics" } },
+        { "id": 2, "categories": { "lvl0": "Electronics", "lvl1": "Electronics > Phones" } },
+        { "id": 3, "categories": { "lvl0": "Electronics", "lvl1": "Electronics > Phones" } },
+        { "id": 4, "categories": { "lvl0": "Electronics", "lvl1": "Electronics > Laptops" } },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["categories.lvl0", "categories.lvl1"],
+            "faceting": {
+                "hierarchicalFacets": {
+                    "categories": {
+                        "attributes": ["categories.lvl0", "categories.lvl1"],
+                        "separator": " > "
+                    }
+                }
+            }
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "hierarchicalFacets": { "categories": null } })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hierarchicalFacetDistribution"]), @r###"
+    {
+      "categories": {
+        "Books": 1,
+        "Electronics": 3
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "id > 1",
+            "hierarchicalFacets": { "categories": "Electronics" }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hierarchicalFacetDistribution"]), @r###"
+    {
+      "categories": {
+        "Electronics > Laptops": 1,
+        "Electronics > Phones": 2
+      }
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "hierarchicalFacets": { "colors": null } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid hierarchical facet: `colors` is not a hierarchical facet of this index. Available hierarchical facets are: `categories`.",
+      "code": "invalid_search_hierarchical_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hierarchical_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_disjunctive_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "color": "red", "size": "M" },
+        { "id": 2, "color": "red", "size": "L" },
+        { "id": 3, "color": "blue", "size": "M" },
+        { "id": 4, "color": "blue", "size": "S" },
+        { "id": 5, "color": "green", "size": "M" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) =
+        index.update_settings(json!({ "filterableAttributes": ["color", "size"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": [["color = red", "color = blue"], "size = M"],
+            "facets": ["color"],
+            "disjunctiveFacets": ["size"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"2");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "blue": 1,
+        "red": 1
+      },
+      "size": {
+        "L": 1,
+        "M": 2,
+        "S": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "(color = red OR color = blue) AND size = M",
+            "disjunctiveFacets": ["color", "size"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "blue": 1,
+        "green": 1,
+        "red": 1
+      },
+      "size": {
+        "L": 1,
+        "M": 2,
+        "S": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "disjunctiveFacets": ["id"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid facet distribution: Attribute `id` is not filterable. Available filterable attributes patterns are: `color, size`.",
+      "code": "invalid_search_disjunctive_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_disjunctive_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_extended_facet_stats() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "color": "red", "price": 10 },
+        { "id": 2, "color": "red", "price": 20 },
+        { "id": 3, "color": "red", "price": [30, 40] },
+        { "id": 4, "color": "blue", "price": 100 },
+        { "id": 5, "color": "blue", "weight": 3 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["color", "price", "weight"],
+            "faceting": { "facetStats": { "price": ["count", "sum", "avg", "p50", "p90"] } }
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "filter": "color = red", "facets": ["price", "weight"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 10.0,
+        "max": 40.0,
+        "count": 4,
+        "sum": 100.0,
+        "avg": 25.0,
+        "p50": 20.0,
+        "p90": 40.0
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "facets": ["price", "weight"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "price": {
+        "min": 10.0,
+        "max": 100.0,
+        "count": 5,
+        "sum": 200.0,
+        "avg": 40.0,
+        "p50": 30.0,
+        "p90": 100.0
+      },
+      "weight": {
+        "min": 3.0,
+        "max": 3.0
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_analyzers() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index
+        .update_settings(json!({
+            "analyzers": [
+                { "attributePatterns": ["sku"], "tokenizer": "keyword" },
+                { "attributePatterns": ["part"], "tokenizer": "ngram", "minGram": 2, "maxGram": 4 }
+            ]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+    let documents = json!([
+        { "id": 1, "sku": "AB-12/X", "part": "XE42-Z" },
+        { "id": 2, "sku": "AB-12", "part": "QXE4" },
+        { "id": 3, "sku": "CD-34", "part": "W1" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["analyzers"]), @r###"
+    [
+      {
+        "attributePatterns": [
+          "sku"
+        ],
+        "tokenizer": "keyword",
+        "lowercase": true,
+        "asciiFolding": true
+      },
+      {
+        "attributePatterns": [
+          "part"
+        ],
+        "tokenizer": "ngram",
+        "minGram": 2,
+        "maxGram": 4,
+        "lowercase": true,
+        "asciiFolding": true
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "ab-12-x", "matchingStrategy": "all", "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "xe4", "matchingStrategy": "all", "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_synonym_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _code) = index
+        .update_settings(json!({
+            "rankingRules": ["words", "typo"],
+            "synonymRules": [
+                { "type": "oneWay", "input": "TV", "synonyms": ["television"], "penalty": 1 },
+                { "type": "altCorrection", "word": "tv", "corrections": ["telly"], "typos": 2 }
+            ]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+    let documents = json!([
+        { "id": 1, "title": "An old telly" },
+        { "id": 2, "title": "A television set" },
+        { "id": 3, "title": "A tv set" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["synonymRules"]), @r###"
+    [
+      {
+        "type": "oneWay",
+        "input": "TV",
+        "synonyms": [
+          "television"
+        ],
+        "penalty": 1
+      },
+      {
+        "type": "altCorrection",
+        "word": "tv",
+        "corrections": [
+          "telly"
+        ],
+        "typos": 2
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "q": "tv", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 3
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "q": "television", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      }
+    ]
+    "###);
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new().await;
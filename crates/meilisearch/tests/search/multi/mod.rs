@@ -585,6 +585,111 @@ async fn federation_two_search_two_indexes() {
     "###);
 }
 
+#[actix_rt::test]
+async fn federation_reciprocal_rank_fusion() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let index = server.index("nested");
+    let documents = NESTED_DOCUMENTS.clone();
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // 654 is second for `pésti` and first for `simba`, so it sums both contributions
+    let (response, code) = server
+        .multi_search(json!({"federation": {"fusion": "rrf"}, "queries": [
+        {"indexUid" : "test", "q": "glass", "attributesToRetrieve": ["id"]},
+        {"indexUid": "nested", "q": "pésti", "attributesToRetrieve": ["id"]},
+        {"indexUid": "nested", "q": "simba", "attributesToRetrieve": ["id"]},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 654,
+        "_federation": {
+          "indexUid": "nested",
+          "queriesPosition": 2,
+          "weightedRankingScore": 0.03252247488101534
+        }
+      },
+      {
+        "id": "450465",
+        "_federation": {
+          "indexUid": "test",
+          "queriesPosition": 0,
+          "weightedRankingScore": 0.01639344262295082
+        }
+      },
+      {
+        "id": 852,
+        "_federation": {
+          "indexUid": "nested",
+          "queriesPosition": 1,
+          "weightedRankingScore": 0.01639344262295082
+        }
+      }
+    ]
+    "###);
+
+    // the weight of a query multiplies its contributions
+    let (response, code) = server
+        .multi_search(json!({"federation": {"fusion": "rrf", "rrfK": 0}, "queries": [
+        {"indexUid" : "test", "q": "glass", "attributesToRetrieve": ["id"]},
+        {"indexUid": "nested", "q": "pésti", "attributesToRetrieve": ["id"], "federationOptions": {"weight": 3.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 852,
+        "_federation": {
+          "indexUid": "nested",
+          "queriesPosition": 1,
+          "weightedRankingScore": 3.0
+        }
+      },
+      {
+        "id": 654,
+        "_federation": {
+          "indexUid": "nested",
+          "queriesPosition": 1,
+          "weightedRankingScore": 1.5
+        }
+      },
+      {
+        "id": "450465",
+        "_federation": {
+          "indexUid": "test",
+          "queriesPosition": 0,
+          "weightedRankingScore": 1.0
+        }
+      }
+    ]
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {"fusion": "rank"}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `rank` at `.federation.fusion`: expected one of `score`, `rrf`",
+      "code": "invalid_multi_search_fusion",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_fusion"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn federation_multiple_search_multiple_indexes() {
     let server = Server::new().await;
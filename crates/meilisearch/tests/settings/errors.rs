@@ -308,6 +308,210 @@ async fn settings_bad_faceting() {
       "link": "https://docs.meilisearch.com/errors#invalid_settings_faceting"
     }
     "###);
+
+    let (response, code) = index
+        .update_settings_faceting(
+            json!({ "hierarchicalFacets": { "categories": { "attributes": [], "separator": " > " } } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.hierarchicalFacets.categories`: `attributes` must contain at least one attribute",
+      "code": "invalid_settings_faceting",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_faceting"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings_faceting(
+            json!({ "hierarchicalFacets": { "categories": { "attributes": ["lvl0"], "separator": " " } } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.hierarchicalFacets.categories`: `separator` must contain a non-whitespace character, but found `\" \"`",
+      "code": "invalid_settings_faceting",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_faceting"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings_faceting(json!({ "facetStats": { "price": ["p42"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `p42` at `.facetStats.price[0]`: expected one of `count`, `sum`, `avg`, `p50`, `p90`, `p99`",
+      "code": "invalid_settings_faceting",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_faceting"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_analyzers() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "analyzers": [{ "attributePatterns": ["sku"], "tokenizer": "whitespace" }] }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `whitespace` at `.analyzers[0].tokenizer`: expected one of `standard`, `keyword`, `ngram`, `edgeNgram`",
+      "code": "invalid_settings_analyzers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_analyzers"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "analyzers": [{ "attributePatterns": ["sku"], "tokenizer": "keyword", "minGram": 2 }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.analyzers[0]`: `minGram` and `maxGram` can only be specified with the `ngram` and `edgeNgram` tokenizers, but the tokenizer is `keyword`",
+      "code": "invalid_settings_analyzers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_analyzers"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "analyzers": [{ "attributePatterns": ["sku"], "tokenizer": "ngram", "minGram": 4, "maxGram": 2 }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.analyzers[0]`: `minGram` must be lower than or equal to `maxGram`, but found `4` and `2`",
+      "code": "invalid_settings_analyzers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_analyzers"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "analyzers": [{ "attributePatterns": ["sku"], "tokenizer": "edgeNgram", "maxGram": 21 }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.analyzers[0]`: `minGram` and `maxGram` must be between 1 and 20, but found `2` and `21`",
+      "code": "invalid_settings_analyzers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_analyzers"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_synonym_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index
+        .update_settings(json!({ "synonymRules": [{ "type": "oneWay", "input": "tv", "synonyms": ["television"], "penalty": 3 }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.synonymRules[0]`: `penalty` must be between 0 and 2, but found `3`",
+      "code": "invalid_settings_synonym_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_rules"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "synonymRules": [{ "type": "multiWay", "synonyms": ["tv"] }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.synonymRules[0]`: `synonyms` must contain at least two expressions, but found 1",
+      "code": "invalid_settings_synonym_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_rules"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "synonymRules": [{ "type": "altCorrection", "word": "tv", "corrections": ["telly"], "typos": 0 }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.synonymRules[0]`: `typos` must be between 1 and 2, but found `0`",
+      "code": "invalid_settings_synonym_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_rules"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "synonymRules": [{ "type": "placeholder", "placeholder": "<street>", "replacements": [] }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.synonymRules[0]`: `replacements` must contain at least one expression",
+      "code": "invalid_settings_synonym_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonym_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_rerankers() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.update_settings(json!({ "rerankers": { "mock": { "source": "cohere" } } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `cohere` at `.rerankers.mock.source`: expected one of `rest`, `huggingFace`",
+      "code": "invalid_settings_rerankers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_rerankers"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "rerankers": { "mock": { "source": "rest", "url": "http://localhost:7777" } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rerankers.mock`: `url`, `request` and `response` are mandatory for source `rest`",
+      "code": "invalid_settings_rerankers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_rerankers"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "rerankers": { "mock": { "source": "huggingFace", "model": "cross-encoder/ms-marco-MiniLM-L-6-v2", "url": "http://localhost:7777" } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rerankers.mock`: `url` is unavailable for source `huggingFace`",
+      "code": "invalid_settings_rerankers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_rerankers"
+    }
+    "###);
 }
 
 #[actix_rt::test]
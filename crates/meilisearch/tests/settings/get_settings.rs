@@ -144,7 +144,7 @@ test_setting_routes!(
     {
         setting: faceting,
         update_verb: patch,
-        default_value: {"maxValuesPerFacet": 100, "sortFacetValuesBy": {"*": "alpha"}}
+        default_value: {"maxValuesPerFacet": 100, "sortFacetValuesBy": {"*": "alpha"}, "hierarchicalFacets": {}, "facetStats": {}}
     },
     {
         setting: search_cutoff_ms,
@@ -166,6 +166,21 @@ test_setting_routes!(
         update_verb: put,
         default_value: "indexingTime"
     },
+    {
+        setting: analyzers,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: synonym_rules,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: rerankers,
+        update_verb: put,
+        default_value: {}
+    },
     {
         setting: proximity_precision,
         update_verb: put,
@@ -199,7 +214,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 23);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -219,7 +234,9 @@ async fn get_settings() {
             "maxValuesPerFacet": 100,
             "sortFacetValuesBy": {
                 "*": "alpha"
-            }
+            },
+            "hierarchicalFacets": {},
+            "facetStats": {}
         })
     );
     assert_eq!(
@@ -231,6 +248,9 @@ async fn get_settings() {
     assert_eq!(settings["proximityPrecision"], json!("byWord"));
     assert_eq!(settings["searchCutoffMs"], json!(null));
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
+    assert_eq!(settings["analyzers"], json!([]));
+    assert_eq!(settings["synonymRules"], json!([]));
+    assert_eq!(settings["rerankers"], json!({}));
     assert_eq!(settings["facetSearch"], json!(true));
     assert_eq!(settings["embedders"], json!({}));
 }
@@ -314,7 +334,9 @@ async fn secrets_are_hidden_in_settings() {
         "maxValuesPerFacet": 100,
         "sortFacetValuesBy": {
           "*": "alpha"
-        }
+        },
+        "hierarchicalFacets": {},
+        "facetStats": {}
       },
       "pagination": {
         "maxTotalHits": 1000
@@ -335,7 +357,10 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "analyzers": [],
+      "synonymRules": [],
+      "rerankers": {}
     }
     "#);
 
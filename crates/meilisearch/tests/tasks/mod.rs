@@ -2,7 +2,7 @@ mod errors;
 mod webhook;
 
 use meili_snap::insta::assert_json_snapshot;
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
@@ -1063,3 +1063,206 @@ async fn test_summarized_dump_creation() {
     }
     "###);
 }
+
+#[actix_web::test]
+async fn scheduled_task() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json"), ("RunAt", "2999-01-01T00:00:00Z")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]" }), @r###"
+    {
+      "taskUid": 0,
+      "indexUid": "test",
+      "status": "enqueued",
+      "type": "documentAdditionOrUpdate",
+      "enqueuedAt": "[date]",
+      "runAt": "2999-01-01T00:00:00Z"
+    }
+    "###);
+
+    // A task registered without a date is processed right away, the scheduled one is not
+    let (task, _) = index.add_documents(json!([{ "id": 2 }]), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, _) = index.get_task(0).await;
+    snapshot!(response["status"], @r###""enqueued""###);
+    snapshot!(response["scheduled"], @"true");
+
+    let (response, code) = server.tasks_filter("afterRunAt=2998-01-01T00:00:00Z").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"1");
+    snapshot!(response["results"][0]["uid"], @"0");
+
+    let (response, code) = server.tasks_filter("beforeRunAt=2998-01-01T00:00:00Z").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["total"], @"0");
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 3 }]"#,
+            vec![("Content-Type", "application/json"), ("RunAt", "tomorrow")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_run_at""###);
+}
+
+#[actix_web::test]
+async fn recurring_task_errors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json"), ("RunEvery", "daily")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_run_every""###);
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json"), ("RunEvery", "P1D")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Tasks of type `documentAdditionOrUpdate` cannot be recurring. The document additions, task deletions, task cancelations and database upgrades can only run once.",
+      "code": "invalid_task_run_every",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_task_run_every"
+    }
+    "###);
+}
+
+#[actix_web::test]
+async fn task_group_and_dependencies() {
+    let server = Server::new().await;
+    let products = server.index("products");
+    let products_v2 = server.index("products_v2");
+
+    let (response, code) = products
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json"), ("TaskGroup", "migration")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { ".enqueuedAt" => "[date]" }), @r###"
+    {
+      "taskUid": 0,
+      "indexUid": "products",
+      "status": "enqueued",
+      "type": "documentAdditionOrUpdate",
+      "enqueuedAt": "[date]",
+      "group": {
+        "name": "migration",
+        "commit": false
+      }
+    }
+    "###);
+
+    let (response, code) = products_v2
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![
+                ("Content-Type", "application/json"),
+                ("TaskGroup", "migration"),
+                ("TaskGroupCommit", "true"),
+            ],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    products_v2.wait_task(response.uid()).await.succeeded();
+    products.wait_task(0).await.succeeded();
+
+    let (response, code) = products
+        .raw_add_documents(
+            r#"[{ "id": 2 }]"#,
+            vec![("Content-Type", "application/json"), ("DependsOn", "0, 1")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(response["dependsOn"], @"[0,1]");
+    products.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = products
+        .raw_add_documents(
+            r#"[{ "id": 3 }]"#,
+            vec![("Content-Type", "application/json"), ("DependsOn", "12")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_depends_on""###);
+
+    let (response, code) = products
+        .raw_add_documents(
+            r#"[{ "id": 3 }]"#,
+            vec![("Content-Type", "application/json"), ("TaskGroupCommit", "true")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_group""###);
+}
+
+#[actix_web::test]
+async fn task_priority() {
+    let server = Server::new().await;
+    let index = server.index("products");
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json"), ("TaskPriority", "high")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(response["priority"], @r###""high""###);
+    let task = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(task["priority"], @r###""high""###);
+
+    let (batch, _) = server.get_batch(task["batchUid"].as_u64().unwrap() as u32).await;
+    snapshot!(json_string!(batch["stats"]["scheduling"]), @r###"
+    {
+      "priority": "high",
+      "policy": "fifo",
+      "skippedTasks": 0
+    }
+    "###);
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 2 }]"#,
+            vec![("Content-Type", "application/json"), ("TaskPriority", "urgent")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`urgent` is not a valid task priority. Available priorities are `low`, `normal`, `high`.",
+      "code": "invalid_task_priority",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_task_priority"
+    }
+    "###);
+}
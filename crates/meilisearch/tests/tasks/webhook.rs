@@ -8,7 +8,7 @@ use actix_http::body::MessageBody;
 use actix_web::dev::{ServiceFactory, ServiceResponse};
 use actix_web::web::{Bytes, Data};
 use actix_web::{post, App, HttpRequest, HttpResponse, HttpServer};
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use meilisearch::Opt;
 use tokio::sync::mpsc;
 use url::Url;
@@ -127,3 +127,170 @@ async fn test_basic_webhook() {
 
     server_handle.abort();
 }
+
+#[actix_web::test]
+async fn webhooks_crud() {
+    let server = Server::new().await;
+
+    let (response, code) = server.get_webhooks().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"{"results":[]}"###);
+
+    let (response, code) = server
+        .create_webhook(json!({
+            "url": "https://example.com/hook",
+            "headers": { "Authorization": "Bearer 0123456789" },
+            "secret": "my-secret",
+            "indexUids": ["movies", "products_*"],
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    snapshot!(json_string!(response, { ".uuid" => "[uuid]" }), @r###"
+    {
+      "uuid": "[uuid]",
+      "url": "https://example.com/hook",
+      "headers": {
+        "Authorization": "BeXXXX..."
+      },
+      "indexUids": [
+        "movies",
+        "products_*"
+      ],
+      "types": null,
+      "statuses": null
+    }
+    "###);
+    let uuid = response["uuid"].as_str().unwrap().to_string();
+
+    let (response, code) = server
+        .patch_webhook(
+            &uuid,
+            json!({ "indexUids": null, "types": ["documentAdditionOrUpdate"], "statuses": ["failed"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".uuid" => "[uuid]" }), @r###"
+    {
+      "uuid": "[uuid]",
+      "url": "https://example.com/hook",
+      "headers": {
+        "Authorization": "BeXXXX..."
+      },
+      "indexUids": null,
+      "types": [
+        "documentAdditionOrUpdate"
+      ],
+      "statuses": [
+        "failed"
+      ]
+    }
+    "###);
+
+    let (response, code) = server.get_webhook(&uuid).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["types"], @r###"["documentAdditionOrUpdate"]"###);
+    snapshot!(response["headers"], @r###"{"Authorization":"BeXXXX..."}"###);
+
+    let (response, code) = server.get_webhooks().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"].as_array().unwrap().len(), @"1");
+    snapshot!(response["results"][0]["headers"], @r###"{"Authorization":"BeXXXX..."}"###);
+
+    let (_response, code) = server.delete_webhook(&uuid).await;
+    snapshot!(code, @"204 No Content");
+
+    let (response, code) = server.get_webhook(&uuid).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""webhook_not_found""###);
+
+    let (response, code) = server.delete_webhook(&uuid).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""webhook_not_found""###);
+}
+
+#[actix_web::test]
+async fn webhooks_bad_settings() {
+    let server = Server::new().await;
+
+    let (response, code) = server.create_webhook(json!({ "headers": {} })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Missing field `.url`",
+      "code": "missing_webhook_url",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_webhook_url"
+    }
+    "###);
+
+    let (response, code) = server.create_webhook(json!({ "url": "not a url" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid `.url` (`not a url`): relative URL without a base",
+      "code": "invalid_webhook_url",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_webhook_url"
+    }
+    "###);
+
+    let (response, code) =
+        server.create_webhook(json!({ "url": "https://example.com", "types": ["doggo"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_webhook_types""###);
+
+    let (response, code) =
+        server.create_webhook(json!({ "url": "https://example.com", "statuses": ["doggo"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_webhook_statuses""###);
+
+    let (response, code) =
+        server.create_webhook(json!({ "url": "https://example.com", "secret": 42 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_webhook_secret""###);
+
+    let (response, code) = server.get_webhook("doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_webhook_uuid""###);
+
+    let (response, code) = server.create_webhook(json!({ "url": "https://example.com" })).await;
+    snapshot!(code, @"201 Created");
+    let uuid = response["uuid"].as_str().unwrap().to_string();
+
+    let (response, code) = server.patch_webhook(&uuid, json!({ "url": null })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Field `.url` cannot be set to `null`",
+      "code": "invalid_webhook_url",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_webhook_url"
+    }
+    "###);
+}
+
+#[actix_web::test]
+async fn filtered_webhook() {
+    let WebhookHandle { server_handle, url, mut receiver } = create_webhook_server().await;
+
+    let server = Server::new().await;
+    let (_response, code) = server
+        .create_webhook(json!({ "url": url, "secret": "my-secret", "indexUids": ["doggo"] }))
+        .await;
+    snapshot!(code, @"201 Created");
+
+    let (task, _status) = server.index("tamo").add_documents(json!({ "id": 0 }), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _status) = server.index("doggo").add_documents(json!({ "id": 0 }), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    // only the task of the `doggo` index is delivered
+    let payload = receiver.recv().await.unwrap();
+    let payload = String::from_utf8(payload).unwrap();
+    let tasks: Vec<serde_json::Value> =
+        payload.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    snapshot!(tasks.len(), @"1");
+    snapshot!(tasks[0]["indexUid"], @r###""doggo""###);
+
+    server_handle.abort();
+}
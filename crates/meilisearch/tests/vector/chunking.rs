@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use meili_snap::{json_string, snapshot};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use crate::common::{Server, Value};
+use crate::json;
+
+async fn create_mock() -> (MockServer, Value) {
+    let mock_server = MockServer::start().await;
+
+    let text_to_embedding: BTreeMap<_, _> = vec![
+        // text -> embedding
+        ("kefir", [1.0, 0.0, 0.0]),
+        ("intel", [0.0, 1.0, 0.0]),
+    ]
+    // turn into btree
+    .into_iter()
+    .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "data": text_to_embedding.get(text.as_str()).unwrap_or(&[0., 0., 1.]) }),
+            )
+        })
+        .mount(&mock_server)
+        .await;
+    let url = mock_server.uri();
+
+    let embedder_settings = json!({
+        "source": "rest",
+        "url": url,
+        "dimensions": 3,
+        "request": "{{text}}",
+        "response": {
+          "data": "{{embedding}}"
+        },
+        "documentTemplate": "{{doc.name}}",
+    });
+
+    (mock_server, embedder_settings)
+}
+
+#[actix_rt::test]
+async fn retrieve_chunking_in_the_settings() {
+    let (_mock, mut setting) = create_mock().await;
+    setting["chunking"] = json!({ "size": 2 });
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index.update_settings(json!({ "embedders": { "rest": setting } })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["rest"]["chunking"], @r#"{"unit":"tokens","size":2,"overlap":0}"#);
+}
+
+#[actix_rt::test]
+async fn overlap_must_be_lower_than_size() {
+    let (_mock, mut setting) = create_mock().await;
+    setting["chunking"] = json!({ "size": 2, "overlap": 2 });
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index.update_settings(json!({ "embedders": { "rest": setting } })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "Index `doggo`: `.embedders.rest.chunking`: `overlap` must be strictly lower than `size`, found overlap `2` and size `2`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn user_provided_cannot_be_chunked() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "chunking": { "size": 2 },
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["error"]["code"], @r#""invalid_settings_embedders""#);
+}
+
+#[actix_rt::test]
+async fn search_returns_the_matching_chunk() {
+    let (_mock, mut setting) = create_mock().await;
+    setting["chunking"] = json!({ "size": 1, "field": "description" });
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index.update_settings(json!({ "embedders": { "rest": setting } })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "description": "kefir, intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [0.0, 1.0, 0.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["_semanticChunk"], @r#"{"index":1,"start":7,"length":5}"#);
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["_semanticChunk"], @r#"{"index":0,"start":0,"length":5}"#);
+}
@@ -0,0 +1,120 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{Server, Value};
+use crate::json;
+
+fn hit_ids(response: &Value) -> Vec<String> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].to_string()).collect()
+}
+
+#[actix_rt::test]
+async fn retrieve_distance_in_the_settings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "distance": "euclidean",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3,"distance":"euclidean"}"#);
+}
+
+#[actix_rt::test]
+async fn change_distance_of_existing_embeddings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [10.0, 0.0, 0.0] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [0.0, 1.0, 0.0] }},
+      {"id": 2, "name": "intel", "_vectors": { "manual": [1.0, 0.0, 0.0] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let search = json!({
+        "vector": [1.0, 0.0, 0.0],
+        "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+        "showRankingScore": true,
+    });
+
+    // with the cosine distance, the magnitude of the embeddings doesn't matter
+    let (response, code) = index.search_post(search.clone()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["_rankingScore"], @"1.0");
+    snapshot!(response["hits"][1]["_rankingScore"], @"1.0");
+    snapshot!(hit_ids(&response)[2], @"1");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "distance": "euclidean",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    // with the euclidean distance, the closest embedding is the identical one
+    let (response, code) = index.search_post(search).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", hit_ids(&response)), @r#"["2", "1", "0"]"#);
+    snapshot!(response["hits"][0]["_rankingScore"], @"1.0");
+}
+
+#[actix_rt::test]
+async fn dot_product_cannot_be_binary_quantized() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "binaryQuantized": true,
+                  "distance": "dotProduct",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let ret = server.wait_task(response.uid()).await;
+    snapshot!(json_string!(ret["error"]), @r###"
+    {
+      "message": "Index `doggo`: `.embedders.manual.distance`: Cannot use the `dotProduct` distance with binary quantized embeddings.\n - Hint: Use another distance or disable `binaryQuantized`.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
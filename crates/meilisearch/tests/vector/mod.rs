@@ -1,9 +1,16 @@
 mod binary_quantized;
+mod chunking;
+mod distance;
+mod multi_vector;
 #[cfg(feature = "test-ollama")]
 mod ollama;
+mod onnx;
 mod openai;
+mod quantization;
+mod rerank;
 mod rest;
 mod settings;
+mod sparse;
 
 use std::str::FromStr;
 
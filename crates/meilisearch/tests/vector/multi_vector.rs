@@ -0,0 +1,123 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+fn hit_ids(response: &Value) -> Vec<String> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].to_string()).collect()
+}
+
+async fn create_multi_vector_index(server: &Server) -> Index<'_> {
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 2,
+                  "multiVector": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [[1.0, 0.0], [0.0, 1.0]] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [[1.0, 0.0], [1.0, 0.0]] }},
+      {"id": 2, "name": "intel", "_vectors": { "manual": [[0.0, -1.0]] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    index
+}
+
+#[actix_rt::test]
+async fn retrieve_multi_vector_in_the_settings() {
+    let server = Server::new().await;
+    let index = create_multi_vector_index(&server).await;
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":2,"multiVector":true}"#);
+}
+
+#[actix_rt::test]
+async fn search_with_max_sim() {
+    let server = Server::new().await;
+    let index = create_multi_vector_index(&server).await;
+
+    // the query is a bag of two embeddings
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0, 1.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", hit_ids(&response)), @r#"["0", "1", "2"]"#);
+    // kefir matches both query embeddings, echo only the first one
+    snapshot!(response["hits"][0]["_rankingScore"], @"1.0");
+    snapshot!(response["hits"][1]["_rankingScore"], @"0.75");
+    snapshot!(response["hits"][2]["_rankingScore"], @"0.25");
+
+    // a single embedding is a valid bag
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [0.0, 1.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(hit_ids(&response)[0], @"0");
+    snapshot!(response["hits"][0]["_rankingScore"], @"1.0");
+}
+
+#[actix_rt::test]
+async fn bad_multi_vector_query() {
+    let server = Server::new().await;
+    let index = create_multi_vector_index(&server).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid vector dimensions: the embedder is multi-vector, so the vector must be the concatenation of one or more embeddings of `2` dimensions, found: `3` values.",
+      "code": "invalid_vector_dimensions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vector_dimensions"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn multi_vector_is_only_for_user_provided() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "huggingFace",
+                  "multiVector": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let ret = server.wait_task(response.uid()).await;
+    snapshot!(ret["error"]["message"], @r###""`.embedders.manual`: Field `multiVector` unavailable for source `huggingFace`.\n  - note: `multiVector` is available for sources: `userProvided`\n  - note: available fields for source `huggingFace`: `source`, `model`, `revision`, `pooling`, `documentTemplate`, `documentTemplateMaxBytes`, `distribution`, `binaryQuantized`, `distance`, `chunking`, `modelPath`""###);
+}
@@ -0,0 +1,199 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+/// Launches a server loading its local models from the `models` directory of `dir`,
+/// which only contains an empty `model` directory.
+async fn server_with_models_dir(dir: &TempDir) -> Server {
+    let models_dir = dir.path().join("models");
+    std::fs::create_dir_all(models_dir.join("model")).unwrap();
+    Server::new_with_options(Opt { models_dir: Some(models_dir), ..default_settings(dir.path()) })
+        .await
+        .unwrap()
+}
+
+#[actix_rt::test]
+async fn model_path_is_mandatory() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx`: Missing field `modelPath` (note: this field is mandatory for source `onnx`)",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn threads_cannot_be_zero() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "modelPath": "/meilisearch/no/such/model",
+                  "threads": 0,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx.threads`: `threads` cannot be zero",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn local_models_are_disabled_without_models_dir() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "modelPath": "/meilisearch/no/such/model",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx.modelPath`: local models are disabled.\n - Hint: launch Meilisearch with `--models-dir` to load the models of a directory.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn model_path_must_be_inside_models_dir() {
+    let dir = TempDir::new().unwrap();
+    let server = server_with_models_dir(&dir).await;
+    let index = server.index("doggo");
+
+    for model_path in ["../models/../db", "/etc", "no/such/model"] {
+        let (response, code) = index
+            .update_settings(json!({
+              "embedders": {
+                  "onnx": {
+                      "source": "onnx",
+                      "modelPath": model_path,
+                  }
+              },
+            }))
+            .await;
+        snapshot!(code, @"400 Bad Request");
+        snapshot!(json_string!(response["code"]), @r#""invalid_settings_embedders""#);
+        assert_eq!(
+            response["message"],
+            format!("`.embedders.onnx.modelPath`: `{model_path}` is not an existing path inside the models directory.")
+        );
+    }
+}
+
+#[cfg(feature = "onnx")]
+#[actix_rt::test]
+async fn missing_model() {
+    let dir = TempDir::new().unwrap();
+    let server = server_with_models_dir(&dir).await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "modelPath": "model",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r#""failed""#);
+    snapshot!(json_string!(task["error"]["code"]), @r#""vector_embedding_error""#);
+    let model_path = dir.path().join("models/model").canonicalize().unwrap();
+    let message = task["error"]["message"].as_str().unwrap();
+    assert!(
+        message.starts_with(&format!("Index `doggo`: Error while generating embeddings: user error: could not load ONNX model at `{}`", model_path.join("model.onnx").display())),
+        "{message}"
+    );
+}
+
+#[cfg(not(feature = "onnx"))]
+#[actix_rt::test]
+async fn onnx_feature_is_disabled() {
+    let dir = TempDir::new().unwrap();
+    let server = server_with_models_dir(&dir).await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "modelPath": "model",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r#""failed""#);
+    snapshot!(task["error"]["message"], @r###""Index `doggo`: Error while generating embeddings: user error: embedders of source `onnx` are not available, Meilisearch must be compiled with the `onnx` feature to use them""###);
+}
+
+#[actix_rt::test]
+async fn dimensions_are_inferred() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "modelPath": "/meilisearch/no/such/model",
+                  "dimensions": 3,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx`: Field `dimensions` unavailable for source `onnx`.\n  - note: `dimensions` is available for sources: `openAi`, `ollama`, `userProvided`, `rest`\n  - note: available fields for source `onnx`: `source`, `pooling`, `documentTemplate`, `documentTemplateMaxBytes`, `distribution`, `binaryQuantized`, `distance`, `chunking`, `modelPath`, `threads`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
@@ -0,0 +1,211 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn retrieve_quantization_in_the_settings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "quantization": "int8",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3,"quantization":"int8"}"#);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "quantization": "binary",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3,"quantization":"binary"}"#);
+}
+
+#[actix_rt::test]
+async fn int8_quantize_documents() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "quantization": "int8",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [-1.2, -2.3, 3.2] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [2.5, 1.5, -130] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // Make sure the retrieved embeddings are close to the original ones
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions { retrieve_vectors: true, ..Default::default() })
+        .await;
+    let expected = [[-1.2, -2.3, 3.2], [2.5, 1.5, -130.0]];
+    for (document, expected) in documents["results"].as_array().unwrap().iter().zip(expected) {
+        let embeddings = document["_vectors"]["manual"]["embeddings"].as_array().unwrap();
+        snapshot!(embeddings.len(), @"1");
+        let embedding = embeddings[0].as_array().unwrap();
+        let (min, max) = expected
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| (min.min(*x), max.max(*x)));
+        let step = (max - min) / 255.0;
+        for (value, expected) in embedding.iter().zip(expected) {
+            let value = value.as_f64().unwrap();
+            assert!((value - expected).abs() <= step, "{value} is too far from {expected}");
+        }
+    }
+
+    let (response, code) = index
+        .search_post(json!({
+            "hybrid": { "embedder": "manual", "semanticRatio": 1.0 },
+            "vector": [-1.0, -2.0, 3.0],
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn try_to_change_int8_quantization() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "quantization": "int8",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "quantization": "none",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let ret = server.wait_task(response.uid()).await;
+    snapshot!(ret["error"], @r###"
+    {
+      "message": "Index `doggo`: `.embedders.manual.quantization`: Cannot change the quantization from `int8` to `none`.\n - Note: Quantization is a lossy operation, the embeddings can only be quantized further.\n - Hint: Add a new embedder with the `none` quantization and regenerate the vectors.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "quantization": "binary",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "quantization": "int8",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let ret = server.wait_task(response.uid()).await;
+    snapshot!(ret["error"], @r###"
+    {
+      "message": "Index `doggo`: `.embedders.manual.quantization`: Cannot change the quantization from `binary` to `int8`.\n - Note: Quantization is a lossy operation, the embeddings can only be quantized further.\n - Hint: Add a new embedder with the `int8` quantization and regenerate the vectors.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn quantization_and_binary_quantized_conflict() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "binaryQuantized": true,
+                  "quantization": "int8",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let ret = server.wait_task(response.uid()).await;
+    snapshot!(ret["error"], @r###"
+    {
+      "message": "Index `doggo`: `.embedders.manual.quantization`: `quantization` and `binaryQuantized` cannot be set at the same time.\n - Hint: Only use `quantization`.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
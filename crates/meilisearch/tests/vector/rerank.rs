@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+
+use meili_snap::{json_string, snapshot};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+use crate::vector::get_server_vector;
+
+async fn create_mock_reranker() -> (MockServer, Value) {
+    let mock_server = MockServer::start().await;
+
+    let document_to_score: BTreeMap<_, _> = vec![
+        // document -> score
+        ("kefir", 0.25),
+        ("echo", 0.5),
+        ("intel", 0.75),
+    ]
+    .into_iter()
+    .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let body: serde_json::Value = req.body_json().unwrap();
+            let scores: Vec<_> = body["documents"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|document| document_to_score.get(document.as_str().unwrap()).unwrap_or(&0.0))
+                .collect();
+            ResponseTemplate::new(200).set_body_json(json!({ "scores": scores }))
+        })
+        .mount(&mock_server)
+        .await;
+    let url = mock_server.uri();
+
+    let reranker_settings = json!({
+        "source": "rest",
+        "url": url,
+        "request": {
+            "query": "{{query}}",
+            "documents": ["{{document}}", "{{..}}"]
+        },
+        "response": {
+            "scores": ["{{score}}", "{{..}}"]
+        },
+        "documentTemplate": "{{doc.name}}",
+    });
+
+    (mock_server, reranker_settings)
+}
+
+async fn add_reranker_and_documents(server: &Server, index: &Index<'_>, setting: Value) {
+    let (response, code) = index
+        .update_settings(json!({
+          "rerankers": {
+              "mock": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "kind": "dog"},
+      {"id": 1, "name": "echo", "kind": "dog"},
+      {"id": 2, "name": "intel", "kind": "dog"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+}
+
+#[actix_rt::test]
+async fn rerank_top_hits() {
+    let (_mock, setting) = create_mock_reranker().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+    add_reranker_and_documents(&server, &index, setting).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "rerank": { "reranker": "mock" },
+            "showRankingScoreDetails": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"].as_array().unwrap().iter().map(|hit| hit["name"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      "intel",
+      "echo",
+      "kefir"
+    ]
+    "###);
+    snapshot!(json_string!(response["hits"][0]["_rankingScoreDetails"]["rerank"]), @r###"
+    {
+      "order": 0,
+      "reranker": "mock",
+      "score": 0.75
+    }
+    "###);
+
+    // pages are taken from the reranked hits
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "rerank": { "reranker": "mock" },
+            "offset": 1,
+            "limit": 1,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "echo",
+        "kind": "dog"
+      }
+    ]
+    "###);
+
+    // only the top k hits are reranked
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "rerank": { "reranker": "mock", "topK": 1 },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"].as_array().unwrap().iter().map(|hit| hit["name"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      "kefir",
+      "echo",
+      "intel"
+    ]
+    "###);
+
+    // the hits below the ranking score threshold are removed
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "rerank": { "reranker": "mock" },
+            "rankingScoreThreshold": 0.4,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"].as_array().unwrap().iter().map(|hit| hit["name"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      "intel",
+      "echo"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn rerank_with_get() {
+    let (_mock, setting) = create_mock_reranker().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+    add_reranker_and_documents(&server, &index, setting).await;
+
+    let (response, code) = index.search_get("?q=dog&rerankReranker=mock&limit=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "name": "intel",
+        "kind": "dog"
+      }
+    ]
+    "###);
+
+    let (response, code) = index.search_get("?q=dog&rerankTopK=2").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`rerankReranker` is mandatory when `rerankTopK` is present",
+      "code": "invalid_search_rerank",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn rerank_unknown_reranker() {
+    let (_mock, setting) = create_mock_reranker().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+    add_reranker_and_documents(&server, &index, setting).await;
+
+    let (response, code) =
+        index.search_post(json!({ "q": "dog", "rerank": { "reranker": "kefir" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Cannot find reranker with name `kefir`.",
+      "code": "invalid_search_reranker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_reranker"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn rerank_top_k_above_max_total_hits() {
+    let (_mock, setting) = create_mock_reranker().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+    add_reranker_and_documents(&server, &index, setting).await;
+
+    let (response, code) = index
+        .search_post(json!({ "q": "dog", "rerank": { "reranker": "mock", "topK": 1001 } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `rerank.topK`: `1001` is greater than the `pagination.maxTotalHits` of the index (`1000`).\n - Hint: lower `rerank.topK` or raise `pagination.maxTotalHits` in the settings of the index.",
+      "code": "invalid_search_rerank_top_k",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank_top_k"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "pagination": { "maxTotalHits": 2 } })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.search_get("?q=dog&rerankReranker=mock&rerankTopK=3").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_rerank_top_k""###);
+
+    let (response, code) =
+        index.search_post(json!({ "q": "dog", "rerank": { "reranker": "mock", "topK": 2 } })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"].as_array().unwrap().len(), @"2");
+}
@@ -1,6 +1,8 @@
 use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use tempfile::TempDir;
 
-use crate::common::{GetAllDocumentsOptions, Server};
+use crate::common::{default_settings, GetAllDocumentsOptions, Server};
 use crate::json;
 use crate::vector::generate_default_user_provided_documents;
 
@@ -17,7 +19,7 @@ async fn field_unavailable_for_source() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "`.embedders.manual`: Field `documentTemplate` unavailable for source `userProvided`.\n  - note: `documentTemplate` is available for sources: `openAi`, `huggingFace`, `ollama`, `rest`\n  - note: available fields for source `userProvided`: `source`, `dimensions`, `distribution`, `binaryQuantized`",
+      "message": "`.embedders.manual`: Field `documentTemplate` unavailable for source `userProvided`.\n  - note: `documentTemplate` is available for sources: `openAi`, `huggingFace`, `ollama`, `rest`, `onnx`\n  - note: available fields for source `userProvided`: `source`, `dimensions`, `distribution`, `binaryQuantized`, `distance`, `multiVector`, `sparse`",
       "code": "invalid_settings_embedders",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
@@ -32,7 +34,7 @@ async fn field_unavailable_for_source() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "`.embedders.default`: Field `revision` unavailable for source `openAi`.\n  - note: `revision` is available for sources: `huggingFace`\n  - note: available fields for source `openAi`: `source`, `model`, `apiKey`, `dimensions`, `documentTemplate`, `documentTemplateMaxBytes`, `url`, `distribution`, `binaryQuantized`",
+      "message": "`.embedders.default`: Field `revision` unavailable for source `openAi`.\n  - note: `revision` is available for sources: `huggingFace`\n  - note: available fields for source `openAi`: `source`, `model`, `apiKey`, `dimensions`, `documentTemplate`, `documentTemplateMaxBytes`, `url`, `distribution`, `binaryQuantized`, `distance`, `chunking`",
       "code": "invalid_settings_embedders",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
@@ -559,7 +561,7 @@ async fn composite_checks() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "`.embedders.test.searchEmbedder`: Field `distribution` unavailable for source `huggingFace` for the search embedder.\n  - note: available fields for source `huggingFace` for the search embedder: `source`, `model`, `revision`, `pooling`\n  - note: `distribution` is available when source `huggingFace` is not for the search embedder",
+      "message": "`.embedders.test.searchEmbedder`: Field `distribution` unavailable for source `huggingFace` for the search embedder.\n  - note: available fields for source `huggingFace` for the search embedder: `source`, `model`, `revision`, `pooling`, `modelPath`\n  - note: `distribution` is available when source `huggingFace` is not for the search embedder",
       "code": "invalid_settings_embedders",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
@@ -816,7 +818,7 @@ async fn composite_checks() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "`.embedders.test.searchEmbedder`: Field `binaryQuantized` unavailable for source `huggingFace` for the search embedder.\n  - note: available fields for source `huggingFace` for the search embedder: `source`, `model`, `revision`, `pooling`\n  - note: `binaryQuantized` is available when source `huggingFace` is not for the search embedder",
+      "message": "`.embedders.test.searchEmbedder`: Field `binaryQuantized` unavailable for source `huggingFace` for the search embedder.\n  - note: available fields for source `huggingFace` for the search embedder: `source`, `model`, `revision`, `pooling`, `modelPath`\n  - note: `binaryQuantized` is available when source `huggingFace` is not for the search embedder",
       "code": "invalid_settings_embedders",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
@@ -855,7 +857,7 @@ async fn composite_checks() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(response, @r###"
     {
-      "message": "`.embedders.test.searchEmbedder`: Field `documentTemplate` unavailable for source `huggingFace` for the search embedder.\n  - note: available fields for source `huggingFace` for the search embedder: `source`, `model`, `revision`, `pooling`\n  - note: `documentTemplate` is available when source `huggingFace` is not for the search embedder",
+      "message": "`.embedders.test.searchEmbedder`: Field `documentTemplate` unavailable for source `huggingFace` for the search embedder.\n  - note: available fields for source `huggingFace` for the search embedder: `source`, `model`, `revision`, `pooling`, `modelPath`\n  - note: `documentTemplate` is available when source `huggingFace` is not for the search embedder",
       "code": "invalid_settings_embedders",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
@@ -1066,3 +1068,83 @@ async fn composite_checks() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn hugging_face_model_path() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "default": {
+                  "source": "openAi",
+                  "modelPath": "/meilisearch/no/such/model",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""`.embedders.default`: Field `modelPath` unavailable for source `openAi`.\n  - note: `modelPath` is available for sources: `huggingFace`, `onnx`\n  - note: available fields for source `openAi`: `source`, `model`, `apiKey`, `dimensions`, `documentTemplate`, `documentTemplateMaxBytes`, `url`, `distribution`, `binaryQuantized`, `distance`, `chunking`""###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "default": {
+                  "source": "huggingFace",
+                  "modelPath": "/meilisearch/no/such/model",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""`.embedders.default.modelPath`: local models are disabled.\n - Hint: launch Meilisearch with `--models-dir` to load the models of a directory.""###);
+
+    let dir = TempDir::new().unwrap();
+    let models_dir = dir.path().join("models");
+    std::fs::create_dir_all(&models_dir).unwrap();
+    std::fs::write(models_dir.join("model.txt"), "not a model").unwrap();
+    let server = Server::new_with_options(Opt {
+        models_dir: Some(models_dir.clone()),
+        ..default_settings(dir.path())
+    })
+    .await
+    .unwrap();
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "default": {
+                  "source": "huggingFace",
+                  "modelPath": "../db",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""`.embedders.default.modelPath`: `../db` is not an existing path inside the models directory.""###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "default": {
+                  "source": "huggingFace",
+                  "modelPath": "model.txt",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""failed""###);
+    snapshot!(json_string!(task["error"]["code"]), @r###""vector_embedding_error""###);
+    let model_path = models_dir.join("model.txt").canonicalize().unwrap();
+    assert_eq!(
+        task["error"]["message"],
+        format!(
+            "Index `doggo`: Error while generating embeddings: user error: `modelPath` `{}` is not a directory",
+            model_path.display()
+        )
+    );
+}
@@ -0,0 +1,212 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+fn hit_ids(response: &Value) -> Vec<String> {
+    response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].to_string()).collect()
+}
+
+async fn create_sparse_index(server: &Server) -> Index<'_> {
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 10,
+                  "sparse": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": { "embeddings": {"0": 1.0, "5": 0.5}, "regenerate": false } }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [{"5": 1.0}] }},
+      {"id": 2, "name": "intel", "_vectors": { "manual": [{"7": 1.0}, {"8": 0.5}] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    index
+}
+
+#[actix_rt::test]
+async fn retrieve_sparse_in_the_settings() {
+    let server = Server::new().await;
+    let index = create_sparse_index(&server).await;
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":10,"sparse":true}"#);
+}
+
+#[actix_rt::test]
+async fn search_with_sparse_vector() {
+    let server = Server::new().await;
+    let index = create_sparse_index(&server).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": {"5": 1.0, "0": 0.2},
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "limit": 2,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    // echo scores 1.0 and kefir 0.2 + 0.5
+    snapshot!(format!("{:?}", hit_ids(&response)), @r#"["1", "0"]"#);
+
+    // the best embedding of a document is used
+    let (response, code) = index
+        .search_post(json!({
+            "vector": {"8": 1.0},
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "limit": 1,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", hit_ids(&response)), @r#"["2"]"#);
+}
+
+#[actix_rt::test]
+async fn retrieve_sparse_vectors() {
+    let server = Server::new().await;
+    let index = create_sparse_index(&server).await;
+
+    let (document, code) = index.get_document(0, Some(json!({"retrieveVectors": true}))).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document["_vectors"]), @r###"
+    {
+      "manual": {
+        "embeddings": [
+          {
+            "0": 1.0,
+            "5": 0.5
+          }
+        ],
+        "regenerate": false
+      }
+    }
+    "###);
+
+    let (document, code) = index.get_document(2, Some(json!({"retrieveVectors": true}))).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document["_vectors"]), @r###"
+    {
+      "manual": {
+        "embeddings": [
+          {
+            "7": 1.0
+          },
+          {
+            "8": 0.5
+          }
+        ],
+        "regenerate": false
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn dense_vector_for_sparse_embedder() {
+    let server = Server::new().await;
+    let index = create_sparse_index(&server).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid vector: the vector is an array of numbers, but the embedder is sparse and expects an object mapping dimensions to weights.",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+
+    let documents = json!({"id": 3, "name": "max", "_vectors": { "manual": [{"12": 1.0}] }});
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(value.uid()).await;
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "Index `doggo`: Invalid embedding in document with id `3` in `._vectors.manual`.\n  - note: embedding #0 has dimension `12`, but the embedder has 10 dimensions",
+      "code": "invalid_vectors_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vectors_type"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn sparse_conflicts_with_multi_vector() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 10,
+                  "sparse": true,
+                  "multiVector": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["error"]["message"], @r###""Index `doggo`: `.embedders.manual.sparse`: sparse embeddings cannot be multi-vector""###);
+}
+
+#[actix_rt::test]
+async fn cannot_change_sparse() {
+    let server = Server::new().await;
+    let index = create_sparse_index(&server).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "sparse": false,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["error"]["message"], @r###""Index `doggo`: `.embedders.manual.sparse`: Cannot change the embeddings from sparse to dense.\n  - Hint: Remove the embedder and add it again to change the kind of its embeddings.""###);
+}
+
+#[actix_rt::test]
+async fn sparse_is_only_for_user_provided_and_rest() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "huggingFace",
+                  "sparse": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let ret = server.wait_task(response.uid()).await;
+    snapshot!(ret["error"]["message"], @r###""`.embedders.manual`: Field `sparse` unavailable for source `huggingFace`.\n  - note: `sparse` is available for sources: `userProvided`, `rest`\n  - note: available fields for source `huggingFace`: `source`, `model`, `revision`, `pooling`, `documentTemplate`, `documentTemplateMaxBytes`, `distribution`, `binaryQuantized`, `distance`, `chunking`, `modelPath`""###);
+}
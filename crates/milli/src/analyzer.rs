@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::attribute_patterns::PatternMatch;
+use crate::AttributePatterns;
+
+/// The bounds of the gram lengths accepted by the n-gram tokenizers.
+pub const MIN_GRAM_LENGTH: u8 = 1;
+pub const MAX_GRAM_LENGTH: u8 = 20;
+
+/// A rule that replaces the default tokenization and normalization of the matching attributes.
+///
+/// The attribute patterns follow the same syntax as the localized attributes rules
+/// and, when several rules match an attribute, only the first one is applied.
+///
+/// Unlike the default tokenizer, an analyzer is language agnostic: it splits the text
+/// on every character that is neither a letter nor a digit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzerRule {
+    pub attribute_patterns: AttributePatterns,
+    pub tokenizer: AnalyzerTokenizer,
+    pub lowercase: bool,
+    pub ascii_folding: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnalyzerTokenizer {
+    /// Indexes every word of the text.
+    Standard,
+    /// Indexes the whole text as a single word, dropping the characters
+    /// that are neither letters nor digits, e.g. `AB-12/X` becomes `ab12x`.
+    Keyword,
+    /// Indexes every word along with all its substrings whose length is in the given bounds.
+    #[serde(rename_all = "camelCase")]
+    Ngram { min_gram: u8, max_gram: u8 },
+    /// Indexes every word along with all its prefixes whose length is in the given bounds.
+    #[serde(rename_all = "camelCase")]
+    EdgeNgram { min_gram: u8, max_gram: u8 },
+}
+
+impl AnalyzerRule {
+    pub fn match_str(&self, str: &str) -> PatternMatch {
+        self.attribute_patterns.match_str(str)
+    }
+
+    /// Returns the first rule of the list matching the given attribute.
+    pub fn find<'a>(rules: &'a [AnalyzerRule], attribute: &str) -> Option<&'a AnalyzerRule> {
+        rules.iter().find(|rule| rule.match_str(attribute) == PatternMatch::Match)
+    }
+
+    /// Splits a document value into the words to index along with their relative positions.
+    ///
+    /// The grams of a word share the position of the word they are extracted from.
+    pub fn analyze(&self, text: &str) -> Vec<(u32, String)> {
+        let mut tokens = Vec::new();
+        match self.tokenizer {
+            AnalyzerTokenizer::Standard => {
+                for (position, word) in (0..).zip(words(text)) {
+                    tokens.push((position, self.normalize(word)));
+                }
+            }
+            AnalyzerTokenizer::Keyword => {
+                let keyword = self.keyword(text);
+                if !keyword.is_empty() {
+                    tokens.push((0, keyword));
+                }
+            }
+            AnalyzerTokenizer::Ngram { min_gram, max_gram } => {
+                for (position, word) in (0..).zip(words(text)) {
+                    let word = self.normalize(word);
+                    let chars: Vec<_> = word.char_indices().map(|(i, _)| i).collect();
+                    for (start_index, &start) in chars.iter().enumerate() {
+                        for len in min_gram as usize..=max_gram as usize {
+                            let gram = match chars.get(start_index + len) {
+                                Some(&end) => &word[start..end],
+                                None if start_index + len == chars.len() => &word[start..],
+                                None => break,
+                            };
+                            if gram.len() != word.len() {
+                                tokens.push((position, gram.to_string()));
+                            }
+                        }
+                    }
+                    tokens.push((position, word));
+                }
+            }
+            AnalyzerTokenizer::EdgeNgram { min_gram, max_gram } => {
+                for (position, word) in (0..).zip(words(text)) {
+                    let word = self.normalize(word);
+                    let ends = word.char_indices().map(|(i, _)| i).skip(1).chain([word.len()]);
+                    for (len, end) in (1..).zip(ends) {
+                        if len > max_gram || end == word.len() {
+                            break;
+                        }
+                        if len >= min_gram {
+                            tokens.push((position, word[..end].to_string()));
+                        }
+                    }
+                    tokens.push((position, word));
+                }
+            }
+        }
+        tokens
+    }
+
+    /// Splits a search query into the words to look for in the attributes analyzed by this rule.
+    ///
+    /// The words of the query are not split into grams, they are directly
+    /// looked up among the grams extracted from the documents.
+    pub fn analyze_query(&self, query: &str) -> Vec<String> {
+        match self.tokenizer {
+            AnalyzerTokenizer::Keyword => {
+                let keyword = self.keyword(query);
+                if keyword.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![keyword]
+                }
+            }
+            AnalyzerTokenizer::Standard
+            | AnalyzerTokenizer::Ngram { .. }
+            | AnalyzerTokenizer::EdgeNgram { .. } => {
+                words(query).map(|word| self.normalize(word)).collect()
+            }
+        }
+    }
+
+    fn keyword(&self, text: &str) -> String {
+        words(text).map(|word| self.normalize(word)).collect()
+    }
+
+    fn normalize(&self, word: &str) -> String {
+        let word = if self.ascii_folding {
+            word.nfkd().filter(|c| !is_combining_mark(*c)).collect()
+        } else {
+            word.to_string()
+        };
+        if self.lowercase {
+            word.to_lowercase()
+        } else {
+            word
+        }
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::{AnalyzerRule, AnalyzerTokenizer};
+
+    fn rule(tokenizer: AnalyzerTokenizer, lowercase: bool, ascii_folding: bool) -> AnalyzerRule {
+        AnalyzerRule {
+            attribute_patterns: vec![S("*")].into(),
+            tokenizer,
+            lowercase,
+            ascii_folding,
+        }
+    }
+
+    fn words(tokens: Vec<(u32, String)>) -> Vec<String> {
+        tokens.into_iter().map(|(position, word)| format!("{position}:{word}")).collect()
+    }
+
+    #[test]
+    fn standard() {
+        let analyzer = rule(AnalyzerTokenizer::Standard, true, true);
+        assert_eq!(
+            words(analyzer.analyze("Crème Brûlée, déjà-vu")),
+            ["0:creme", "1:brulee", "2:deja", "3:vu"]
+        );
+
+        let analyzer = rule(AnalyzerTokenizer::Standard, false, true);
+        assert_eq!(words(analyzer.analyze("Crème Brûlée")), ["0:Creme", "1:Brulee"]);
+
+        let analyzer = rule(AnalyzerTokenizer::Standard, true, false);
+        assert_eq!(words(analyzer.analyze("Crème Brûlée")), ["0:crème", "1:brûlée"]);
+        assert_eq!(analyzer.analyze_query("BRÛLÉE crème"), ["brûlée", "crème"]);
+    }
+
+    #[test]
+    fn keyword() {
+        let analyzer = rule(AnalyzerTokenizer::Keyword, true, true);
+        assert_eq!(words(analyzer.analyze("AB-12/X")), ["0:ab12x"]);
+        assert_eq!(analyzer.analyze_query("ab 12 x"), ["ab12x"]);
+        assert!(analyzer.analyze(" -- ").is_empty());
+        assert!(analyzer.analyze_query("").is_empty());
+    }
+
+    #[test]
+    fn ngram() {
+        let analyzer = rule(AnalyzerTokenizer::Ngram { min_gram: 2, max_gram: 3 }, true, true);
+        assert_eq!(
+            words(analyzer.analyze("Xé42 a")),
+            ["0:xe", "0:xe4", "0:e4", "0:e42", "0:42", "0:xe42", "1:a"]
+        );
+        assert_eq!(analyzer.analyze_query("E42"), ["e42"]);
+    }
+
+    #[test]
+    fn edge_ngram() {
+        let analyzer = rule(AnalyzerTokenizer::EdgeNgram { min_gram: 2, max_gram: 4 }, true, true);
+        assert_eq!(
+            words(analyzer.analyze("Héllo ab a")),
+            ["0:he", "0:hel", "0:hell", "0:hello", "1:ab", "2:a"]
+        );
+    }
+}
@@ -15,6 +15,7 @@ use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::documents::{self, DocumentsBatchCursorError};
 use crate::thread_pool_no_abort::PanicCatched;
 use crate::vector::settings::EmbeddingSettings;
+use crate::vector::VectorDistance;
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
@@ -150,10 +151,39 @@ and can not be more than 511 bytes.", .document_id.to_string()
         valid_patterns: BTreeSet<String>,
         matching_rule_indices: HashMap<String, usize>,
     },
+    #[error("Invalid facet ranges: Attribute `{}` is not filterable. {}",
+        .field,
+        match .valid_patterns.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes patterns are: `{}`.",
+                .valid_patterns.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")),
+        }
+    )]
+    InvalidFacetRangesAttribute { field: String, valid_patterns: BTreeSet<String> },
+    #[error("Invalid facet ranges: An interval of `{interval}` on attribute `{field}` produces {buckets_count} buckets, but at most {max_buckets} buckets can be returned per attribute.\nHint: use a larger interval or increase `faceting.maxValuesPerFacet`.")]
+    TooManyFacetRangeBuckets {
+        field: String,
+        interval: f64,
+        buckets_count: u64,
+        max_buckets: usize,
+    },
+    #[error("Invalid hierarchical facet: `{}` is not a hierarchical facet of this index. {}",
+        .name,
+        match .valid_names.is_empty() {
+            true => "This index does not have configured hierarchical facets.".to_string(),
+            false => format!("Available hierarchical facets are: `{}`.",
+                .valid_names.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")),
+        }
+    )]
+    InvalidHierarchicalFacetName { name: String, valid_names: BTreeSet<String> },
+    #[error("Invalid hierarchical facet: Attribute `{field}` of the hierarchical facet `{name}` is not filterable.\nHint: add `{field}` to the `filterableAttributes` of the index.")]
+    HierarchicalFacetAttributeNotFilterable { name: String, field: String },
     #[error(transparent)]
     InvalidGeoField(#[from] Box<GeoError>),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
     InvalidVectorDimensions { expected: usize, found: usize },
+    #[error("Invalid vector dimensions: the embedder is multi-vector, so the vector must be the concatenation of one or more embeddings of `{}` dimensions, found: `{}` values.", .expected, .found)]
+    InvalidMultiVectorDimensions { expected: usize, found: usize },
     #[error("Invalid vector dimensions in document with id `{document_id}` in `._vectors.{embedder_name}`.\n  - note: embedding #{embedding_index} has dimensions {found}\n  - note: embedder `{embedder_name}` requires {expected}")]
     InvalidIndexingVectorDimensions {
         embedder_name: String,
@@ -162,6 +192,15 @@ and can not be more than 511 bytes.", .document_id.to_string()
         expected: usize,
         found: usize,
     },
+    #[error("Invalid vector: the vector {reason}.")]
+    InvalidSparseVector { reason: String },
+    #[error("Invalid embedding in document with id `{document_id}` in `._vectors.{embedder_name}`.\n  - note: embedding #{embedding_index} {reason}")]
+    InvalidIndexingSparseVector {
+        embedder_name: String,
+        document_id: String,
+        embedding_index: usize,
+        reason: String,
+    },
     #[error("The `_vectors` field in the document with id: `{document_id}` is not an object. Was expecting an object with a key for each embedder with manually provided vectors, but instead got `{value}`")]
     InvalidVectorsMapType { document_id: String, value: Value },
     #[error("Bad embedder configuration in the document with id: `{document_id}`. {error}")]
@@ -293,6 +332,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidSearchEmbedder(String),
     #[error("Cannot find embedder with name `{0}`.")]
     InvalidSimilarEmbedder(String),
+    #[error("Cannot find reranker with name `{0}`.")]
+    InvalidSearchReranker(String),
     #[error("Too many vectors for document with id {0}: found {1}, but limited to 256.")]
     TooManyVectors(String, usize),
     #[error("`.embedders.{embedder_name}`: Field `{field}` unavailable for source `{source_}`{for_context}.{available_sources}{available_fields}{available_contexts}",
@@ -371,12 +412,24 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidSettingsEmbedder { embedder_name: String, message: String },
     #[error("`.embedders.{embedder_name}.dimensions`: `dimensions` cannot be zero")]
     InvalidSettingsDimensions { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.threads`: `threads` cannot be zero")]
+    InvalidSettingsThreads { embedder_name: String },
     #[error(
         "`.embedders.{embedder_name}.binaryQuantized`: Cannot disable the binary quantization.\n - Note: Binary quantization is a lossy operation that cannot be reverted.\n - Hint: Add a new embedder that is non-quantized and regenerate the vectors."
     )]
     InvalidDisableBinaryQuantization { embedder_name: String },
+    #[error(
+        "`.embedders.{embedder_name}.distance`: Cannot use the `{distance}` distance with binary quantized embeddings.\n - Hint: Use another distance or disable `binaryQuantized`."
+    )]
+    InvalidDistanceForBinaryQuantization { embedder_name: String, distance: VectorDistance },
     #[error("`.embedders.{embedder_name}.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
     InvalidSettingsDocumentTemplateMaxBytes { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.chunking`: {reason}")]
+    InvalidSettingsChunking { embedder_name: String, reason: String },
+    #[error("`.embedders.{embedder_name}.sparse`: {reason}")]
+    InvalidSettingsSparse { embedder_name: String, reason: String },
+    #[error("`.embedders.{embedder_name}.quantization`: {reason}")]
+    InvalidSettingsQuantization { embedder_name: String, reason: String },
     #[error("`.embedders.{embedder_name}.url`: could not parse `{url}`: {inner_error}")]
     InvalidUrl { embedder_name: String, inner_error: url::ParseError, url: String },
     #[error("Document editions cannot modify a document's primary key")]
@@ -442,6 +495,8 @@ pub enum GeoError {
     BadLatitude { document_id: Value, value: Value },
     #[error("Could not parse longitude in the document with the id: `{document_id}`. Was expecting a finite number but instead got `{value}`.")]
     BadLongitude { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not a valid GeoJSON geometry: {reason}.")]
+    BadGeoJson { document_id: Value, reason: String },
 }
 
 #[allow(dead_code)]
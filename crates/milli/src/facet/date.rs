@@ -0,0 +1,45 @@
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, Time};
+
+/// Parses an RFC 3339 datetime (`2024-01-01T00:00:00Z`) or an ISO-8601 calendar date
+/// (`2024-01-01`, interpreted as midnight UTC) into the number of seconds elapsed since
+/// the Unix epoch.
+///
+/// This is the representation used to store dates in the facet number databases, which
+/// makes them usable with the comparison filters and the sort ranking rule.
+pub fn parse_date(value: &str) -> Option<f64> {
+    let datetime = if let Ok(datetime) = OffsetDateTime::parse(value, &Rfc3339) {
+        datetime
+    } else if let Ok(date) = Date::parse(
+        value,
+        format_description!("[year repr:full base:calendar]-[month repr:numerical]-[day]"),
+    ) {
+        date.with_time(Time::MIDNIGHT).assume_utc()
+    } else {
+        return None;
+    };
+
+    Some(datetime.unix_timestamp_nanos() as f64 / 1_000_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(parse_date("1970-01-01T00:00:00Z"), Some(0.0));
+        assert_eq!(parse_date("2024-01-01T00:00:00Z"), Some(1704067200.0));
+        assert_eq!(parse_date("2024-01-01T02:00:00+02:00"), Some(1704067200.0));
+        assert_eq!(parse_date("2024-01-01T00:00:00.5Z"), Some(1704067200.5));
+        assert_eq!(parse_date("2024-01-01"), Some(1704067200.0));
+        assert_eq!(parse_date("1969-12-31T23:59:59Z"), Some(-1.0));
+
+        assert_eq!(parse_date("2024-01-01T00:00:00"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("1704067200"), None);
+        assert_eq!(parse_date("hello"), None);
+        assert_eq!(parse_date(""), None);
+    }
+}
@@ -1,6 +1,8 @@
+mod date;
 mod facet_type;
 mod facet_value;
 pub mod value_encoding;
 
+pub use self::date::parse_date;
 pub use self::facet_type::FacetType;
 pub use self::facet_value::FacetValue;
@@ -116,10 +116,17 @@ impl Metadata {
         &self,
         rules: &'rules [LocalizedAttributesRule],
     ) -> Option<&'rules [Language]> {
+        self.localized_attributes_rule(rules).map(LocalizedAttributesRule::locales)
+    }
+
+    pub fn localized_attributes_rule<'rules>(
+        &self,
+        rules: &'rules [LocalizedAttributesRule],
+    ) -> Option<&'rules LocalizedAttributesRule> {
         let localized_attributes_rule_id = self.localized_attributes_rule_id?.get();
         // - 1: `localized_attributes_rule_id` is NonZero
         let rule = rules.get((localized_attributes_rule_id - 1) as usize).unwrap();
-        Some(rule.locales())
+        Some(rule)
     }
 
     pub fn filterable_attributes<'rules>(
@@ -5,7 +5,7 @@ use utoipa::ToSchema;
 
 use crate::{
     attribute_patterns::{match_distinct_field, match_field_legacy, PatternMatch},
-    constants::RESERVED_GEO_FIELD_NAME,
+    constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME},
     AttributePatterns,
 };
 
@@ -35,6 +35,14 @@ impl FilterableAttributesRule {
         matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEO_FIELD_NAME)
     }
 
+    /// Check if the rule is the geojson field.
+    ///
+    /// prefer using `index.is_geojson_enabled` or `index.is_geojson_filtering_enabled`
+    /// to check if the `_geojson` geometries are indexed.
+    pub fn has_geojson(&self) -> bool {
+        matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEOJSON_FIELD_NAME)
+    }
+
     /// Get the features of the rule.
     pub fn features(&self) -> FilterableAttributesFeatures {
         match self {
@@ -0,0 +1,381 @@
+use std::slice;
+
+use rstar::primitives::{GeomWithData, Rectangle};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{distance_between_two_points, is_point_in_polygon, DocumentId};
+
+/// A GeoJsonShape is the bounding box, expressed in terms of latitude and longitude, of a
+/// geometry stored in the `_geojson` field of a document. Its metadata is a tuple composed of
+/// 1. the DocumentId of the associated document and 2. the geometry itself, which is used to
+///    refine the matches found with the bounding box.
+pub type GeoJsonShape = GeomWithData<Rectangle<[f64; 2]>, (DocumentId, GeoJsonGeometry)>;
+
+/// A geometry extracted from the `_geojson` field of a document.
+///
+/// Unlike in GeoJSON, the positions are expressed as `[latitude, longitude]` to be consistent
+/// with the rest of the geo features. The lines and the edges of the polygons are straight
+/// lines in the latitude/longitude plane.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GeoJsonGeometry {
+    Point([f64; 2]),
+    MultiPoint(Vec<[f64; 2]>),
+    LineString(Vec<[f64; 2]>),
+    /// The first ring is the exterior of the polygon, the following ones are its holes.
+    /// Every ring is closed, its first and last positions are the same.
+    Polygon(Vec<Vec<[f64; 2]>>),
+}
+
+impl GeoJsonGeometry {
+    /// Parses a GeoJSON geometry object of type `Point`, `MultiPoint`, `LineString`, or `Polygon`.
+    ///
+    /// Returns the reason why the geometry is invalid in case of error.
+    pub fn from_json(value: &Value) -> Result<Self, String> {
+        let Value::Object(object) = value else {
+            return Err(format!("expected an object but instead got `{value}`"));
+        };
+        let Some(coordinates) = object.get("coordinates") else {
+            return Err("the `coordinates` field is missing".to_string());
+        };
+
+        match object.get("type") {
+            Some(Value::String(kind)) => match kind.as_str() {
+                "Point" => parse_position(coordinates).map(Self::Point),
+                "MultiPoint" => parse_positions(coordinates, 1).map(Self::MultiPoint),
+                "LineString" => parse_positions(coordinates, 2).map(Self::LineString),
+                "Polygon" => parse_rings(coordinates).map(Self::Polygon),
+                _ => Err(format!(
+                    "the geometry type `{kind}` is not supported, expected one of \
+                     `Point`, `MultiPoint`, `LineString`, or `Polygon`"
+                )),
+            },
+            Some(kind) => {
+                Err(format!("expected the `type` field to be a string but instead got `{kind}`"))
+            }
+            None => Err("the `type` field is missing".to_string()),
+        }
+    }
+
+    /// Associates the geometry with its document and bounding box so it can be stored in the rtree.
+    pub fn into_shape(self, docid: DocumentId) -> GeoJsonShape {
+        let mut positions = self.positions();
+        // a geometry always has at least one position
+        let first = *positions.next().unwrap();
+        let (lower, upper) = positions.fold((first, first), |(lower, upper), [lat, lng]| {
+            ([lower[0].min(*lat), lower[1].min(*lng)], [upper[0].max(*lat), upper[1].max(*lng)])
+        });
+        GeoJsonShape::new(Rectangle::from_corners(lower, upper), (docid, self))
+    }
+
+    /// Returns `true` if the point is inside the geometry. Only polygons can contain points,
+    /// a point lying in one of the holes of a polygon is not contained by the polygon.
+    pub fn contains(&self, point: &[f64; 2]) -> bool {
+        match self {
+            Self::Polygon(rings) => match rings.split_first() {
+                Some((exterior, holes)) => {
+                    is_point_in_polygon(point, exterior)
+                        && !holes.iter().any(|hole| is_point_in_polygon(point, hole))
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the point of the geometry which is the nearest from the given point.
+    pub fn nearest_point(&self, point: &[f64; 2]) -> [f64; 2] {
+        if self.contains(point) {
+            return *point;
+        }
+
+        let candidates = self.positions().copied().chain(
+            self.segments().map(|segment| nearest_point_on_segment(point, segment[0], segment[1])),
+        );
+
+        let mut nearest = None;
+        for candidate in candidates {
+            let distance = distance_between_two_points(point, &candidate);
+            match nearest {
+                Some((_, nearest_distance)) if nearest_distance <= distance => (),
+                _ => nearest = Some((candidate, distance)),
+            }
+        }
+
+        // a geometry always has at least one position
+        nearest.map(|(nearest, _)| nearest).unwrap()
+    }
+
+    /// Returns `true` if the geometry intersects the given polygon, which is implicitly closed
+    /// between its last and first vertices.
+    pub fn intersects_polygon(&self, polygon: &[[f64; 2]]) -> bool {
+        self.intersects_area(polygon, |point| is_point_in_polygon(point, polygon))
+    }
+
+    /// Returns `true` if the geometry intersects the bounding box. The bounding box must not
+    /// wrap around the earth, its bottom left longitude must be lower than its top right one.
+    pub fn intersects_bounding_box(&self, bottom_left: &[f64; 2], top_right: &[f64; 2]) -> bool {
+        let corners = [
+            *bottom_left,
+            [top_right[0], bottom_left[1]],
+            *top_right,
+            [bottom_left[0], top_right[1]],
+        ];
+        self.intersects_area(&corners, |[lat, lng]| {
+            (bottom_left[0]..=top_right[0]).contains(lat)
+                && (bottom_left[1]..=top_right[1]).contains(lng)
+        })
+    }
+
+    fn intersects_area(
+        &self,
+        area: &[[f64; 2]],
+        area_contains: impl Fn(&[f64; 2]) -> bool,
+    ) -> bool {
+        let area_edges = || area.iter().zip(area.iter().cycle().skip(1));
+
+        // one of the positions of the geometry is inside the area
+        self.positions().any(&area_contains)
+            // one of the lines of the geometry is crossing the edges of the area
+            || self.segments().any(|segment| {
+                area_edges().any(|(a, b)| segments_intersect(segment[0], segment[1], *a, *b))
+            })
+            // the area is entirely inside the polygon
+            || area.first().is_some_and(|point| self.contains(point))
+    }
+
+    fn positions(&self) -> impl Iterator<Item = &[f64; 2]> {
+        let (points, lines) = self.points_and_lines();
+        points.iter().chain(lines.iter().flatten())
+    }
+
+    fn segments(&self) -> impl Iterator<Item = &[[f64; 2]]> {
+        let (_, lines) = self.points_and_lines();
+        lines.iter().flat_map(|line| line.windows(2))
+    }
+
+    fn points_and_lines(&self) -> (&[[f64; 2]], &[Vec<[f64; 2]>]) {
+        match self {
+            Self::Point(point) => (slice::from_ref(point), &[]),
+            Self::MultiPoint(points) => (points, &[]),
+            Self::LineString(line) => (&[], slice::from_ref(line)),
+            Self::Polygon(rings) => (&[], rings),
+        }
+    }
+}
+
+/// Parses a GeoJSON position, `[longitude, latitude]` optionally followed by an altitude,
+/// into a `[latitude, longitude]` point.
+fn parse_position(value: &Value) -> Result<[f64; 2], String> {
+    let numbers = match value {
+        Value::Array(numbers) if (2..=3).contains(&numbers.len()) => numbers,
+        value => {
+            return Err(format!(
+                "expected a position of the form `[longitude, latitude]` but instead got `{value}`"
+            ))
+        }
+    };
+
+    let mut position = [0.0; 2];
+    for (coordinate, value) in position.iter_mut().zip(numbers) {
+        *coordinate = match value.as_f64() {
+            Some(number) if number.is_finite() => number,
+            _ => return Err(format!("expected a finite number but instead got `{value}`")),
+        };
+    }
+
+    let [lng, lat] = position;
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(format!(
+            "the latitude must be contained between -90 and 90 degrees but instead got `{lat}`"
+        ));
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(format!(
+            "the longitude must be contained between -180 and 180 degrees but instead got `{lng}`"
+        ));
+    }
+
+    Ok([lat, lng])
+}
+
+fn parse_positions(value: &Value, min_len: usize) -> Result<Vec<[f64; 2]>, String> {
+    match value {
+        Value::Array(positions) if positions.len() >= min_len => {
+            positions.iter().map(parse_position).collect()
+        }
+        value => Err(format!(
+            "expected an array of at least {min_len} position(s) but instead got `{value}`"
+        )),
+    }
+}
+
+fn parse_rings(value: &Value) -> Result<Vec<Vec<[f64; 2]>>, String> {
+    let rings = match value {
+        Value::Array(rings) if !rings.is_empty() => rings,
+        value => {
+            return Err(format!("expected an array of linear rings but instead got `{value}`"))
+        }
+    };
+
+    rings
+        .iter()
+        .map(|ring| {
+            let positions = parse_positions(ring, 4)?;
+            if positions.first() == positions.last() {
+                Ok(positions)
+            } else {
+                Err(format!(
+                    "the linear ring `{ring}` is not closed, \
+                     its first and last positions must be the same"
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Returns the nearest point from `point` on the segment `[a, b]`.
+///
+/// The segment is projected on a plane tangent to the earth at `point` where the longitudes
+/// are scaled by the cosine of the latitude, which is precise enough for small segments.
+fn nearest_point_on_segment(point: &[f64; 2], a: [f64; 2], b: [f64; 2]) -> [f64; 2] {
+    let scale = point[0].to_radians().cos().max(f64::EPSILON);
+    let project = |[lat, lng]: [f64; 2]| [lat - point[0], (lng - point[1]) * scale];
+    let [ay, ax] = project(a);
+    let [by, bx] = project(b);
+
+    let [dy, dx] = [by - ay, bx - ax];
+    let length = dx * dx + dy * dy;
+    if length == 0.0 {
+        return a;
+    }
+    let t = (-(ax * dx + ay * dy) / length).clamp(0.0, 1.0);
+
+    [point[0] + ay + t * dy, point[1] + (ax + t * dx) / scale]
+}
+
+/// Returns `true` if the segments `[a, b]` and `[c, d]` intersect, touching counts as intersecting.
+fn segments_intersect(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> bool {
+    fn orientation(p: [f64; 2], q: [f64; 2], r: [f64; 2]) -> f64 {
+        (q[0] - p[0]) * (r[1] - p[1]) - (q[1] - p[1]) * (r[0] - p[0])
+    }
+
+    // assuming `p`, `q`, and `r` are collinear, returns `true` if `r` lies on `[p, q]`
+    fn on_segment(p: [f64; 2], q: [f64; 2], r: [f64; 2]) -> bool {
+        r[0] >= p[0].min(q[0])
+            && r[0] <= p[0].max(q[0])
+            && r[1] >= p[1].min(q[1])
+            && r[1] <= p[1].max(q[1])
+    }
+
+    let abc = orientation(a, b, c);
+    let abd = orientation(a, b, d);
+    let cda = orientation(c, d, a);
+    let cdb = orientation(c, d, b);
+
+    if ((abc > 0.0 && abd < 0.0) || (abc < 0.0 && abd > 0.0))
+        && ((cda > 0.0 && cdb < 0.0) || (cda < 0.0 && cdb > 0.0))
+    {
+        return true;
+    }
+
+    (abc == 0.0 && on_segment(a, b, c))
+        || (abd == 0.0 && on_segment(a, b, d))
+        || (cda == 0.0 && on_segment(c, d, a))
+        || (cdb == 0.0 && on_segment(c, d, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_geometries() {
+        let point =
+            GeoJsonGeometry::from_json(&json!({ "type": "Point", "coordinates": [2.35, 48.85] }));
+        assert_eq!(point, Ok(GeoJsonGeometry::Point([48.85, 2.35])));
+
+        let line = GeoJsonGeometry::from_json(
+            &json!({ "type": "LineString", "coordinates": [[0, 0], [1, 1, 120]] }),
+        );
+        assert_eq!(line, Ok(GeoJsonGeometry::LineString(vec![[0.0, 0.0], [1.0, 1.0]])));
+
+        let polygon = GeoJsonGeometry::from_json(
+            &json!({ "type": "Polygon", "coordinates": [[[0, 0], [10, 0], [10, 10], [0, 0]]] }),
+        );
+        assert_eq!(
+            polygon,
+            Ok(GeoJsonGeometry::Polygon(vec![vec![
+                [0.0, 0.0],
+                [0.0, 10.0],
+                [10.0, 10.0],
+                [0.0, 0.0]
+            ]]))
+        );
+
+        insta::assert_snapshot!(
+            GeoJsonGeometry::from_json(&json!({ "type": "Circle", "coordinates": [0, 0] })).unwrap_err(),
+            @"the geometry type `Circle` is not supported, expected one of `Point`, `MultiPoint`, `LineString`, or `Polygon`"
+        );
+        insta::assert_snapshot!(
+            GeoJsonGeometry::from_json(&json!({ "type": "Point", "coordinates": [0, 91] })).unwrap_err(),
+            @"the latitude must be contained between -90 and 90 degrees but instead got `91`"
+        );
+        insta::assert_snapshot!(
+            GeoJsonGeometry::from_json(&json!({ "type": "LineString", "coordinates": [[0, 0]] })).unwrap_err(),
+            @"expected an array of at least 2 position(s) but instead got `[[0,0]]`"
+        );
+        insta::assert_snapshot!(
+            GeoJsonGeometry::from_json(&json!({ "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]] })).unwrap_err(),
+            @"the linear ring `[[0,0],[1,0],[1,1],[0,1]]` is not closed, its first and last positions must be the same"
+        );
+        insta::assert_snapshot!(
+            GeoJsonGeometry::from_json(&json!({ "coordinates": [0, 0] })).unwrap_err(),
+            @"the `type` field is missing"
+        );
+    }
+
+    #[test]
+    fn intersections() {
+        let line = GeoJsonGeometry::LineString(vec![[-5.0, 5.0], [5.0, 5.0]]);
+        // the line crosses the bounding box without any of its positions being inside
+        assert!(line.intersects_bounding_box(&[-1.0, 0.0], &[1.0, 10.0]));
+        assert!(!line.intersects_bounding_box(&[-1.0, 6.0], &[1.0, 10.0]));
+        assert!(line.intersects_polygon(&[[0.0, 0.0], [0.0, 10.0], [10.0, 0.0]]));
+        assert!(!line.intersects_polygon(&[[-1.0, 6.0], [1.0, 6.0], [0.0, 8.0]]));
+
+        let polygon = GeoJsonGeometry::Polygon(vec![
+            vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0], [0.0, 0.0]],
+            vec![[4.0, 4.0], [4.0, 6.0], [6.0, 6.0], [6.0, 4.0], [4.0, 4.0]],
+        ]);
+        // the bounding box is entirely inside the polygon
+        assert!(polygon.intersects_bounding_box(&[1.0, 1.0], &[2.0, 2.0]));
+        // the bounding box is entirely inside the hole of the polygon
+        assert!(!polygon.intersects_bounding_box(&[4.5, 4.5], &[5.5, 5.5]));
+        assert!(!polygon.intersects_bounding_box(&[20.0, 20.0], &[30.0, 30.0]));
+
+        let points = GeoJsonGeometry::MultiPoint(vec![[0.0, 0.0], [20.0, 20.0]]);
+        assert!(points.intersects_bounding_box(&[19.0, 19.0], &[21.0, 21.0]));
+        assert!(!points.intersects_bounding_box(&[1.0, 1.0], &[19.0, 19.0]));
+    }
+
+    #[test]
+    fn nearest_points() {
+        let polygon = GeoJsonGeometry::Polygon(vec![vec![
+            [0.0, 0.0],
+            [0.0, 10.0],
+            [10.0, 10.0],
+            [10.0, 0.0],
+            [0.0, 0.0],
+        ]]);
+        assert_eq!(polygon.nearest_point(&[5.0, 5.0]), [5.0, 5.0]);
+        assert_eq!(polygon.nearest_point(&[20.0, 20.0]), [10.0, 10.0]);
+        let [lat, lng] = polygon.nearest_point(&[5.0, 12.0]);
+        assert!((lat - 5.0).abs() < 0.1 && lng == 10.0, "{lat}, {lng}");
+
+        let points = GeoJsonGeometry::MultiPoint(vec![[0.0, 0.0], [20.0, 20.0]]);
+        assert_eq!(points.nearest_point(&[15.0, 15.0]), [20.0, 20.0]);
+    }
+}
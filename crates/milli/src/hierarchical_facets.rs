@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// A category tree spread over several attributes, one per level.
+///
+/// Each attribute contains the full path of the category from the root, the names
+/// of the ancestors being joined with the separator, e.g. `categories.lvl0: "Electronics"`,
+/// `categories.lvl1: "Electronics > Phones"`, `categories.lvl2: "Electronics > Phones > Android"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HierarchicalFacet {
+    /// The attributes of the levels, from the root to the leaves.
+    pub attributes: Vec<String>,
+    pub separator: String,
+}
+
+impl HierarchicalFacet {
+    /// Returns the attribute containing the children of the given path,
+    /// or `None` if the path is as deep as the hierarchy.
+    ///
+    /// A missing or empty path selects the root of the tree.
+    pub fn children_attribute(&self, path: Option<&str>) -> Option<&str> {
+        let depth = match path.map(str::trim).filter(|path| !path.is_empty()) {
+            Some(path) => path.split(self.separator.as_str()).count(),
+            None => 0,
+        };
+        self.attributes.get(depth).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::HierarchicalFacet;
+
+    #[test]
+    fn children_attribute() {
+        let facet = HierarchicalFacet {
+            attributes: vec![S("lvl0"), S("lvl1"), S("lvl2")],
+            separator: S(" > "),
+        };
+
+        assert_eq!(facet.children_attribute(None), Some("lvl0"));
+        assert_eq!(facet.children_attribute(Some("  ")), Some("lvl0"));
+        assert_eq!(facet.children_attribute(Some("Electronics")), Some("lvl1"));
+        assert_eq!(facet.children_attribute(Some("Electronics > Phones")), Some("lvl2"));
+        assert_eq!(facet.children_attribute(Some("Electronics > Phones > Android")), None);
+    }
+}
@@ -10,7 +10,9 @@ use roaring::RoaringBitmap;
 use rstar::RTree;
 use serde::{Deserialize, Serialize};
 
-use crate::constants::{self, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::constants::{
+    self, RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
 use crate::database_stats::DatabaseStats;
 use crate::documents::PrimaryKey;
 use crate::error::{InternalError, UserError};
@@ -22,15 +24,17 @@ use crate::heed_codec::facet::{
 };
 use crate::heed_codec::version::VersionCodec;
 use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
+use crate::hierarchical_facets::HierarchicalFacet;
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
+use crate::vector::rerank::RerankerConfig;
 use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
-    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
-    FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, FilterableAttributesRule, GeoPoint, LocalizedAttributesRule, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32,
-    BEU64,
+    default_criteria, AnalyzerRule, CboRoaringBitmapCodec, Criterion, DocumentId,
+    ExternalDocumentsIds, FacetDistribution, FacetStat, FieldDistribution, FieldId,
+    FieldIdMapMissingEntry, FieldIdWordCountCodec, FieldidsWeightsMap, FilterableAttributesRule,
+    GeoJsonShape, GeoPoint, LocalizedAttributesRule, ObkvCodec, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, SynonymRule, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -46,10 +50,13 @@ pub mod main_key {
     pub const FILTERABLE_FIELDS_KEY: &str = "filterable-fields";
     pub const SORTABLE_FIELDS_KEY: &str = "sortable-fields";
     pub const FIELD_DISTRIBUTION_KEY: &str = "fields-distribution";
+    pub const DATE_FACETED_FIELDS_KEY: &str = "date-faceted-fields";
     pub const FIELDS_IDS_MAP_KEY: &str = "fields-ids-map";
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEOJSON_FACETED_DOCUMENTS_IDS_KEY: &str = "geojson-faceted-documents-ids";
+    pub const GEOJSON_RTREE_KEY: &str = "geojson-rtree";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
@@ -59,6 +66,8 @@ pub mod main_key {
     pub const DICTIONARY_KEY: &str = "dictionary";
     pub const SYNONYMS_KEY: &str = "synonyms";
     pub const USER_DEFINED_SYNONYMS_KEY: &str = "user-defined-synonyms";
+    pub const WEIGHTED_SYNONYMS_KEY: &str = "weighted-synonyms";
+    pub const USER_DEFINED_SYNONYM_RULES_KEY: &str = "user-defined-synonym-rules";
     pub const WORDS_FST_KEY: &str = "words-fst";
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
     pub const CREATED_AT_KEY: &str = "created-at";
@@ -70,14 +79,19 @@ pub mod main_key {
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
+    pub const HIERARCHICAL_FACETS: &str = "hierarchical-facets";
+    pub const FACET_STATS: &str = "facet-stats";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
+    pub const ANALYZERS: &str = "analyzers";
+    pub const RERANKERS: &str = "rerankers";
     pub const FACET_SEARCH: &str = "facet_search";
     pub const PREFIX_SEARCH: &str = "prefix_search";
     pub const DOCUMENTS_STATS: &str = "documents_stats";
+    pub const APPLIED_TASK_GROUP: &str = "applied_task_group";
 }
 
 pub mod db_name {
@@ -105,6 +119,8 @@ pub mod db_name {
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
+    pub const SPARSE_VECTORS: &str = "sparse-vectors";
+    pub const INT8_VECTORS: &str = "int8-vectors";
     pub const DOCUMENTS: &str = "documents";
 }
 
@@ -171,6 +187,10 @@ pub struct Index {
     pub embedder_category_id: Database<Str, U8>,
     /// Vector store based on arroy™.
     pub vector_arroy: arroy::Database<Unspecified>,
+    /// Inverted index of the sparse embeddings, see [`crate::vector::sparse::SparseVectors`].
+    pub sparse_vectors: Database<Bytes, Bytes>,
+    /// The 8-bit embeddings of the int8 quantized embedders, see [`crate::vector::int8::Int8Vectors`].
+    pub int8_vectors: Database<Bytes, Bytes>,
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
@@ -186,7 +206,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(26);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -228,6 +248,8 @@ impl Index {
         let embedder_category_id =
             env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID))?;
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
+        let sparse_vectors = env.create_database(&mut wtxn, Some(SPARSE_VECTORS))?;
+        let int8_vectors = env.create_database(&mut wtxn, Some(INT8_VECTORS))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
 
@@ -255,6 +277,8 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            sparse_vectors,
+            int8_vectors,
             embedder_category_id,
             documents,
         };
@@ -633,6 +657,76 @@ impl Index {
             None => Ok(RoaringBitmap::new()),
         }
     }
+
+    /* geojson rtree */
+
+    /// Writes the provided `rtree` which associates the bounding boxes of the `_geojson`
+    /// geometries to documents ids.
+    pub(crate) fn put_geojson_rtree(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        rtree: &RTree<GeoJsonShape>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>().put(
+            wtxn,
+            main_key::GEOJSON_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates the `_geojson` geometries to documents ids.
+    pub(crate) fn delete_geojson_rtree(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::GEOJSON_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates the bounding boxes of the `_geojson` geometries
+    /// to documents ids.
+    pub fn geojson_rtree(&self, rtxn: &RoTxn<'_>) -> Result<Option<RTree<GeoJsonShape>>> {
+        match self
+            .main
+            .remap_types::<Str, SerdeBincode<RTree<GeoJsonShape>>>()
+            .get(rtxn, main_key::GEOJSON_RTREE_KEY)?
+        {
+            Some(rtree) => Ok(Some(rtree)),
+            None => Ok(None),
+        }
+    }
+
+    /* geojson faceted */
+
+    /// Writes the documents ids that are faceted with a _geojson field.
+    pub(crate) fn put_geojson_faceted_documents_ids(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        docids: &RoaringBitmap,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, RoaringBitmapCodec>().put(
+            wtxn,
+            main_key::GEOJSON_FACETED_DOCUMENTS_IDS_KEY,
+            docids,
+        )
+    }
+
+    /// Delete the documents ids that are faceted with a _geojson field.
+    pub(crate) fn delete_geojson_faceted_documents_ids(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::GEOJSON_FACETED_DOCUMENTS_IDS_KEY)
+    }
+
+    /// Retrieve all the documents ids that are faceted with a _geojson field.
+    pub fn geojson_faceted_documents_ids(&self, rtxn: &RoTxn<'_>) -> heed::Result<RoaringBitmap> {
+        match self
+            .main
+            .remap_types::<Str, RoaringBitmapCodec>()
+            .get(rtxn, main_key::GEOJSON_FACETED_DOCUMENTS_IDS_KEY)?
+        {
+            Some(docids) => Ok(docids),
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
     /* field distribution */
 
     /// Writes the field distribution which associates every field name with
@@ -659,6 +753,33 @@ impl Index {
             .unwrap_or_default())
     }
 
+    /* date faceted fields */
+
+    /// Writes the ids of the faceted fields in which dates were indexed.
+    pub(crate) fn put_date_faceted_fields(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        fields: &BTreeSet<FieldId>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeSet<FieldId>>>().put(
+            wtxn,
+            main_key::DATE_FACETED_FIELDS_KEY,
+            fields,
+        )
+    }
+
+    /// Returns the ids of the faceted fields in which dates were indexed.
+    ///
+    /// A field is kept once a date was indexed in it, even when its dates are removed
+    /// later, so that only the other fields can skip looking for dates.
+    pub fn date_faceted_fields(&self, rtxn: &RoTxn<'_>) -> heed::Result<BTreeSet<FieldId>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeSet<FieldId>>>()
+            .get(rtxn, main_key::DATE_FACETED_FIELDS_KEY)?
+            .unwrap_or_default())
+    }
+
     /* displayed fields */
 
     /// Writes the fields that must be displayed in the defined order.
@@ -947,6 +1068,30 @@ impl Index {
         Ok(geo_filter)
     }
 
+    /// Returns true if the `_geojson` geometries are indexed, which is the case
+    /// when either `_geo` or `_geojson` is filterable or sortable.
+    pub fn is_geojson_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
+        let geojson_filter = self.is_geojson_filtering_enabled(rtxn)?;
+        let geojson_sortable = self.is_geojson_sorting_enabled(rtxn)?;
+        Ok(geojson_filter || geojson_sortable)
+    }
+
+    /// Returns true if the geo sort can use the `_geojson` geometries.
+    pub fn is_geojson_sorting_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
+        let sortable_fields = self.sortable_fields(rtxn)?;
+        Ok(sortable_fields.contains(RESERVED_GEO_FIELD_NAME)
+            || sortable_fields.contains(RESERVED_GEOJSON_FIELD_NAME))
+    }
+
+    /// Returns true if the geo filters can use the `_geojson` geometries.
+    pub fn is_geojson_filtering_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
+        let geojson_filter = self
+            .filterable_attributes_rules(rtxn)?
+            .iter()
+            .any(|field| field.has_geo() || field.has_geojson());
+        Ok(geojson_filter)
+    }
+
     pub fn asc_desc_fields(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<String>> {
         let asc_desc_fields = self
             .criteria(rtxn)?
@@ -1240,6 +1385,51 @@ impl Index {
         Ok(self.synonyms(rtxn)?.remove(&words))
     }
 
+    /* synonym rules */
+
+    pub(crate) fn put_synonym_rules(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        weighted_synonyms: &HashMap<Vec<String>, Vec<(Vec<String>, u8)>>,
+        user_defined_rules: &[SynonymRule],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(
+            wtxn,
+            main_key::WEIGHTED_SYNONYMS_KEY,
+            weighted_synonyms,
+        )?;
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::USER_DEFINED_SYNONYM_RULES_KEY,
+            &user_defined_rules,
+        )
+    }
+
+    pub(crate) fn delete_synonym_rules(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::WEIGHTED_SYNONYMS_KEY)?;
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::USER_DEFINED_SYNONYM_RULES_KEY)
+    }
+
+    pub fn user_defined_synonym_rules(&self, rtxn: &RoTxn<'_>) -> heed::Result<Vec<SynonymRule>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::USER_DEFINED_SYNONYM_RULES_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the normalized synonyms defined by the synonym rules, along with their penalty.
+    pub fn weighted_synonyms(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<HashMap<Vec<String>, Vec<(Vec<String>, u8)>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<_>>()
+            .get(rtxn, main_key::WEIGHTED_SYNONYMS_KEY)?
+            .unwrap_or_default())
+    }
+
     /* words prefixes fst */
 
     /// Writes the FST which is the words prefixes dictionary of the engine.
@@ -1547,6 +1737,52 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::SORT_FACET_VALUES_BY)
     }
 
+    pub fn hierarchical_facets(
+        &self,
+        txn: &RoTxn<'_>,
+    ) -> heed::Result<BTreeMap<String, HierarchicalFacet>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(txn, main_key::HIERARCHICAL_FACETS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_hierarchical_facets(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, HierarchicalFacet>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::HIERARCHICAL_FACETS, val)
+    }
+
+    pub(crate) fn delete_hierarchical_facets(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::HIERARCHICAL_FACETS)
+    }
+
+    pub fn facet_stats(
+        &self,
+        txn: &RoTxn<'_>,
+    ) -> heed::Result<BTreeMap<String, BTreeSet<FacetStat>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(txn, main_key::FACET_STATS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_facet_stats(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, BTreeSet<FacetStat>>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::FACET_STATS, val)
+    }
+
+    pub(crate) fn delete_facet_stats(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::FACET_STATS)
+    }
+
     pub fn pagination_max_total_hits(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
@@ -1651,6 +1887,48 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
     }
 
+    /// The analyzers replacing the default tokenization of the matching attributes.
+    pub fn analyzers(&self, rtxn: &RoTxn<'_>) -> heed::Result<Vec<AnalyzerRule>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::ANALYZERS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_analyzers(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &[AnalyzerRule],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::ANALYZERS, &val)
+    }
+
+    pub(crate) fn delete_analyzers(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::ANALYZERS)
+    }
+
+    /// The rerankers that can reorder the top hits of a search, by name.
+    pub fn rerankers(&self, rtxn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, RerankerConfig>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::RERANKERS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_rerankers(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, RerankerConfig>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::RERANKERS, val)
+    }
+
+    pub(crate) fn delete_rerankers(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::RERANKERS)
+    }
+
     /// Put the embedding configs:
     /// 1. The name of the embedder
     /// 2. The configuration option for this embedder
@@ -1691,6 +1969,18 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::SEARCH_CUTOFF)
     }
 
+    /// Writes the uid of the last task of the task group whose changes are part of this
+    /// write transaction, so that a task group interrupted while being committed can tell
+    /// which of its indexes it was already committed to.
+    pub fn put_applied_task_group(&self, wtxn: &mut RwTxn<'_>, task: u32) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU32>().put(wtxn, main_key::APPLIED_TASK_GROUP, &task)
+    }
+
+    /// Returns the uid of the last task of the last task group committed to this index.
+    pub fn applied_task_group(&self, rtxn: &RoTxn<'_>) -> Result<Option<u32>> {
+        Ok(self.main.remap_types::<Str, BEU32>().get(rtxn, main_key::APPLIED_TASK_GROUP)?)
+    }
+
     pub fn embeddings(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1700,8 +1990,14 @@ impl Index {
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            let reader = ArroyWrapper::new(
+                self.vector_arroy,
+                embedder_id,
+                config.config.quantization(),
+                config.config.distance(),
+                config.config.sparse().then_some(self.sparse_vectors),
+                self.int8_vectors,
+            );
             let embeddings = reader.item_vectors(rtxn, docid)?;
             res.insert(config.name.to_owned(), embeddings);
         }
@@ -1718,8 +2014,14 @@ impl Index {
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            let reader = ArroyWrapper::new(
+                self.vector_arroy,
+                embedder_id,
+                config.config.quantization(),
+                config.config.distance(),
+                config.config.sparse().then_some(self.sparse_vectors),
+                self.int8_vectors,
+            );
             reader.aggregate_stats(rtxn, &mut stats)?;
         }
         Ok(stats)
@@ -1764,6 +2066,8 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            sparse_vectors,
+            int8_vectors,
             embedder_category_id,
             documents,
         } = self;
@@ -1835,6 +2139,8 @@ impl Index {
             field_id_docid_facet_strings.stat(rtxn).map(compute_size)?,
         );
         sizes.insert("vector_arroy", vector_arroy.stat(rtxn).map(compute_size)?);
+        sizes.insert("sparse_vectors", sparse_vectors.stat(rtxn).map(compute_size)?);
+        sizes.insert("int8_vectors", int8_vectors.stat(rtxn).map(compute_size)?);
         sizes.insert("embedder_category_id", embedder_category_id.stat(rtxn).map(compute_size)?);
         sizes.insert("documents", documents.stat(rtxn).map(compute_size)?);
 
@@ -1880,7 +2186,7 @@ pub(crate) mod tests {
     use memmap2::Mmap;
     use tempfile::TempDir;
 
-    use crate::constants::RESERVED_GEO_FIELD_NAME;
+    use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
     use crate::error::{Error, InternalError};
     use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
     use crate::progress::Progress;
@@ -3085,6 +3391,37 @@ pub(crate) mod tests {
         db_snap!(index, geo_faceted_documents_ids); // ensure that no documents were inserted
     }
 
+    #[test]
+    fn invalid_geojson_field() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_string());
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                    RESERVED_GEO_FIELD_NAME.to_string(),
+                )]);
+            })
+            .unwrap();
+
+        let err = index
+            .add_documents(
+                documents!({ "id" : "doggo", RESERVED_GEOJSON_FIELD_NAME: { "type": "LineString", "coordinates": [[1, 2]] }}),
+            )
+            .unwrap_err();
+        insta::assert_snapshot!(err, @r###"The `_geojson` field in the document with the id: `"doggo"` is not a valid GeoJSON geometry: expected an array of at least 2 position(s) but instead got `[[1,2]]`."###);
+
+        let err = index
+            .add_documents(
+                documents!({ "id" : "doggo", RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [200, 2] }}),
+            )
+            .unwrap_err();
+        insta::assert_snapshot!(err, @r###"The `_geojson` field in the document with the id: `"doggo"` is not a valid GeoJSON geometry: the longitude must be contained between -180 and 180 degrees but instead got `200`."###);
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.geojson_faceted_documents_ids(&rtxn).unwrap().is_empty());
+    }
+
     #[test]
     fn swapping_searchable_attributes() {
         // See https://github.com/meilisearch/meilisearch/issues/4484
@@ -8,6 +8,7 @@ pub static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 #[macro_use]
 pub mod documents;
 
+mod analyzer;
 mod asc_desc;
 mod attribute_patterns;
 mod criterion;
@@ -17,7 +18,9 @@ mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 mod filterable_attributes_rules;
+mod geojson;
 pub mod heed_codec;
+mod hierarchical_facets;
 pub mod index;
 mod localized_attributes_rules;
 pub mod order_by_map;
@@ -25,6 +28,7 @@ pub mod prompt;
 pub mod proximity;
 pub mod score_details;
 mod search;
+mod synonym_rules;
 mod thread_pool_no_abort;
 pub mod update;
 pub mod vector;
@@ -53,6 +57,7 @@ use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
 pub use {charabia as tokenizer, heed, rhai};
 
+pub use self::analyzer::{AnalyzerRule, AnalyzerTokenizer, MAX_GRAM_LENGTH, MIN_GRAM_LENGTH};
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
 pub use self::attribute_patterns::AttributePatterns;
 pub use self::attribute_patterns::PatternMatch;
@@ -67,20 +72,28 @@ pub use self::filterable_attributes_rules::{
     FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
     FilterableAttributesRule,
 };
+pub use self::geojson::{GeoJsonGeometry, GeoJsonShape};
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
     CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec,
     RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec,
     UncheckedU8StrStrCodec,
 };
+pub use self::hierarchical_facets::HierarchicalFacet;
 pub use self::index::Index;
-pub use self::localized_attributes_rules::LocalizedAttributesRule;
-pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
+pub use self::localized_attributes_rules::{supports_stemming, LocalizedAttributesRule, Stemmers};
+pub use self::search::facet::{
+    ExtendedFacetStats, FacetRange, FacetRangeBucket, FacetRanges, FacetStat, FacetValueHit,
+    SearchForFacetValues,
+};
+pub use self::search::hybrid::{reciprocal_rank, HybridFusion};
 pub use self::search::similar::Similar;
 pub use self::search::{
     FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    RerankSearch, Search, SearchResult, SemanticSearch, TermsMatchingStrategy,
+    DEFAULT_VALUES_PER_FACET,
 };
+pub use self::synonym_rules::{SynonymRule, MAX_SYNONYM_PENALTY};
 pub use self::update::ChannelCongestion;
 
 pub use arroy;
@@ -193,6 +206,11 @@ impl TimeBudget {
 
         self.started_at.elapsed() > self.budget
     }
+
+    /// The instant at which the budget is exceeded, or `None` when it is too far away to be represented.
+    pub fn deadline(&self) -> Option<std::time::Instant> {
+        self.started_at.checked_add(self.budget)
+    }
 }
 
 // Convert an absolute word position into a relative position.
@@ -328,6 +346,29 @@ pub fn distance_between_two_points(a: &[f64; 2], b: &[f64; 2]) -> f64 {
     a.haversine_distance_to(&b).meters()
 }
 
+/// Returns `true` if the point is inside the polygon, both expressed in terms of latitude
+/// and longitude. The edges of the polygon are straight lines in the latitude/longitude plane
+/// and the polygon is implicitly closed between its last and first vertices.
+pub fn is_point_in_polygon(point: &[f64; 2], polygon: &[[f64; 2]]) -> bool {
+    let [lat, lng] = *point;
+    let Some(mut previous) = polygon.last() else { return false };
+
+    // ray casting: count the number of edges crossed by a ray going east from the point
+    let mut inside = false;
+    for current in polygon {
+        let [lat_a, lng_a] = *current;
+        let [lat_b, lng_b] = *previous;
+        if (lat_a > lat) != (lat_b > lat)
+            && lng < (lng_b - lng_a) * (lat - lat_a) / (lat_b - lat_a) + lng_a
+        {
+            inside = !inside;
+        }
+        previous = current;
+    }
+
+    inside
+}
+
 /// Convert a point expressed in terms of latitude and longitude to a point in the
 /// cartesian coordinate expressed in terms of x, y and z.
 pub fn lat_lng_to_xyz(coord: &[f64; 2]) -> [f64; 3] {
@@ -456,4 +497,31 @@ mod tests {
 
         assert_eq!(&actual, expected);
     }
+
+    #[test]
+    fn test_is_point_in_polygon() {
+        let square = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        assert!(is_point_in_polygon(&[5.0, 5.0], &square));
+        assert!(is_point_in_polygon(&[0.5, 9.5], &square));
+        assert!(!is_point_in_polygon(&[-5.0, 5.0], &square));
+        assert!(!is_point_in_polygon(&[5.0, 15.0], &square));
+
+        // a concave "U" shape, the notch is not part of the polygon
+        let u_shape = [
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 3.0],
+            [2.0, 3.0],
+            [2.0, 7.0],
+            [10.0, 7.0],
+            [10.0, 10.0],
+            [0.0, 10.0],
+        ];
+        assert!(is_point_in_polygon(&[1.0, 5.0], &u_shape));
+        assert!(is_point_in_polygon(&[5.0, 1.0], &u_shape));
+        assert!(is_point_in_polygon(&[5.0, 9.0], &u_shape));
+        assert!(!is_point_in_polygon(&[5.0, 5.0], &u_shape));
+
+        assert!(!is_point_in_polygon(&[5.0, 5.0], &[]));
+    }
 }
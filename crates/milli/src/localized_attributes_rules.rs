@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use charabia::Language;
+use rust_stemmers::{Algorithm, Stemmer};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -16,16 +17,25 @@ use crate::{AttributePatterns, FieldId};
 /// The pattern `attribute_name*` matches any attribute name that starts with `attribute_name`.
 /// The pattern `*attribute_name` matches any attribute name that ends with `attribute_name`.
 /// The pattern `*attribute_name*` matches any attribute name that contains `attribute_name`.
+///
+/// When `stemming` is enabled, the stems of the words of the matching attributes are indexed
+/// alongside the words, in every locale of the rule that has a stemmer.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct LocalizedAttributesRule {
     pub attribute_patterns: AttributePatterns,
     #[schema(value_type = Vec<String>)]
     pub locales: Vec<Language>,
+    #[serde(default)]
+    pub stemming: bool,
 }
 
 impl LocalizedAttributesRule {
     pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
-        Self { attribute_patterns: AttributePatterns::from(attribute_patterns), locales }
+        Self {
+            attribute_patterns: AttributePatterns::from(attribute_patterns),
+            locales,
+            stemming: false,
+        }
     }
 
     pub fn match_str(&self, str: &str) -> PatternMatch {
@@ -35,11 +45,85 @@ impl LocalizedAttributesRule {
     pub fn locales(&self) -> &[Language] {
         &self.locales
     }
+
+    /// The locales of the rule whose words are stemmed, empty if stemming is disabled.
+    pub fn stemming_locales(&self) -> Vec<Language> {
+        if self.stemming {
+            self.locales
+                .iter()
+                .copied()
+                .filter(|&locale| stemmer_algorithm(locale).is_some())
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Returns `true` if the words written in this language can be stemmed.
+pub fn supports_stemming(language: Language) -> bool {
+    stemmer_algorithm(language).is_some()
+}
+
+fn stemmer_algorithm(language: Language) -> Option<Algorithm> {
+    let algorithm = match language {
+        Language::Ara => Algorithm::Arabic,
+        Language::Dan => Algorithm::Danish,
+        Language::Deu => Algorithm::German,
+        Language::Ell => Algorithm::Greek,
+        Language::Eng => Algorithm::English,
+        Language::Fin => Algorithm::Finnish,
+        Language::Fra => Algorithm::French,
+        Language::Hun => Algorithm::Hungarian,
+        Language::Ita => Algorithm::Italian,
+        Language::Nld => Algorithm::Dutch,
+        Language::Nob => Algorithm::Norwegian,
+        Language::Por => Algorithm::Portuguese,
+        Language::Ron => Algorithm::Romanian,
+        Language::Rus => Algorithm::Russian,
+        Language::Spa => Algorithm::Spanish,
+        Language::Swe => Algorithm::Swedish,
+        Language::Tam => Algorithm::Tamil,
+        Language::Tur => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(algorithm)
+}
+
+/// The Snowball stemmers of a set of locales.
+#[derive(Default)]
+pub struct Stemmers {
+    stemmers: Vec<Stemmer>,
+}
+
+impl Stemmers {
+    pub fn new(locales: &[Language]) -> Self {
+        let stemmers =
+            locales.iter().filter_map(|&locale| stemmer_algorithm(locale)).map(Stemmer::create);
+        Self { stemmers: stemmers.collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stemmers.is_empty()
+    }
+
+    /// Returns the stems of a normalized word that differ from it, without duplicates.
+    pub fn stems(&self, word: &str) -> Vec<String> {
+        let mut stems: Vec<String> = Vec::new();
+        for stemmer in &self.stemmers {
+            let stem = stemmer.stem(word);
+            if !stem.is_empty() && stem != word && !stems.iter().any(|s| *s == stem) {
+                stems.push(stem.into_owned());
+            }
+        }
+        stems
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LocalizedFieldIds {
     field_id_to_locales: HashMap<FieldId, Vec<Language>>,
+    stemming_locales: Vec<Language>,
 }
 
 impl LocalizedFieldIds {
@@ -49,6 +133,7 @@ impl LocalizedFieldIds {
         fields_ids: I,
     ) -> Self {
         let mut field_id_to_locales = HashMap::new();
+        let mut stemming_locales = Vec::new();
 
         if let Some(rules) = rules {
             let fields = fields_ids.filter_map(|field_id| {
@@ -60,6 +145,7 @@ impl LocalizedFieldIds {
                 for rule in rules {
                     if rule.match_str(field_name) == PatternMatch::Match {
                         locales.extend(rule.locales.iter());
+                        stemming_locales.extend(rule.stemming_locales());
                         // Take the first rule that matches
                         break;
                     }
@@ -73,7 +159,10 @@ impl LocalizedFieldIds {
             }
         }
 
-        Self { field_id_to_locales }
+        stemming_locales.sort();
+        stemming_locales.dedup();
+
+        Self { field_id_to_locales, stemming_locales }
     }
 
     pub fn all_locales(&self) -> Vec<Language> {
@@ -90,4 +179,9 @@ impl LocalizedFieldIds {
         locales.dedup();
         locales
     }
+
+    /// The locales in which the words of at least one of the fields are stemmed.
+    pub fn stemming_locales(&self) -> &[Language] {
+        &self.stemming_locales
+    }
 }
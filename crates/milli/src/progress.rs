@@ -192,6 +192,7 @@ macro_rules! make_atomic_progress {
 
 make_atomic_progress!(Document alias AtomicDocumentStep => "document");
 make_atomic_progress!(Payload alias AtomicPayloadStep => "payload");
+make_atomic_progress!(Embedding alias AtomicEmbeddingStep => "embedding");
 
 make_enum_progress! {
     pub enum MergingWordCache {
@@ -8,33 +8,41 @@ use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::num::NonZeroUsize;
+use std::sync::RwLock;
 
 use bumpalo::Bump;
 use document::ParseableDocument;
 use error::{NewPromptError, RenderPromptError};
 use fields::{BorrowedFields, OwnedFields};
+use heed::RoTxn;
+use liquid::{ObjectView, ValueView};
 
 use self::context::Context;
 use self::document::Document;
 use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::update::del_add::DelAdd;
-use crate::GlobalFieldsIdsMap;
+use crate::update::new::document::DocumentFromDb;
+use crate::vector::chunking::{ChunkPosition, Chunking};
+use crate::{DocumentId, GlobalFieldsIdsMap, Index};
 
 pub struct Prompt {
     template: liquid::Template,
     template_text: String,
     max_bytes: Option<NonZeroUsize>,
+    chunking: Option<Chunking>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PromptData {
     pub template: String,
     pub max_bytes: Option<NonZeroUsize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunking: Option<Chunking>,
 }
 
 impl From<Prompt> for PromptData {
     fn from(value: Prompt) -> Self {
-        Self { template: value.template_text, max_bytes: value.max_bytes }
+        Self { template: value.template_text, max_bytes: value.max_bytes, chunking: value.chunking }
     }
 }
 
@@ -42,7 +50,7 @@ impl TryFrom<PromptData> for Prompt {
     type Error = NewPromptError;
 
     fn try_from(value: PromptData) -> Result<Self, Self::Error> {
-        Prompt::new(value.template, value.max_bytes)
+        Ok(Prompt::new(value.template, value.max_bytes)?.with_chunking(value.chunking))
     }
 }
 
@@ -53,6 +61,7 @@ impl Clone for Prompt {
             template: new_template(&template_text).unwrap(),
             template_text,
             max_bytes: self.max_bytes,
+            chunking: self.chunking.clone(),
         }
     }
 }
@@ -83,13 +92,18 @@ impl Default for Prompt {
             template: default_template(),
             template_text: default_template_text().into(),
             max_bytes: Some(default_max_bytes()),
+            chunking: None,
         }
     }
 }
 
 impl Default for PromptData {
     fn default() -> Self {
-        Self { template: default_template_text().into(), max_bytes: Some(default_max_bytes()) }
+        Self {
+            template: default_template_text().into(),
+            max_bytes: Some(default_max_bytes()),
+            chunking: None,
+        }
     }
 }
 
@@ -103,6 +117,7 @@ impl Prompt {
                 .map_err(NewPromptError::cannot_parse_template)?,
             template_text: template,
             max_bytes,
+            chunking: None,
         };
 
         // render template with special object that's OK with `doc.*` and `fields.*`
@@ -113,6 +128,28 @@ impl Prompt {
         Ok(this)
     }
 
+    pub fn with_chunking(mut self, chunking: Option<Chunking>) -> Self {
+        self.chunking = chunking;
+        self
+    }
+
+    /// How the rendered texts are split before being embedded, if they are.
+    pub fn chunking(&self) -> Option<&Chunking> {
+        self.chunking.as_ref()
+    }
+
+    /// Splits a rendered text into the texts to embed.
+    ///
+    /// Without chunking, the rendered text is embedded as a whole.
+    pub fn split<'t>(&self, rendered: &'t str) -> Vec<&'t str> {
+        match &self.chunking {
+            Some(chunking) => {
+                chunking.split(rendered).into_iter().map(|chunk| chunk.text).collect()
+            }
+            None => vec![rendered],
+        }
+    }
+
     pub fn render_document<
         'a,       // lifetime of the borrow of the document
         'doc: 'a, // lifetime of the allocator, will live for an entire chunk of documents
@@ -124,6 +161,9 @@ impl Prompt {
         doc_alloc: &'doc Bump,
     ) -> Result<&'doc str, RenderPromptError> {
         let document = ParseableDocument::new(document, doc_alloc);
+        if let Some(field) = self.chunked_field() {
+            return Ok(doc_alloc.alloc_str(&render_field(&document, field)));
+        }
         let fields = BorrowedFields::new(&document, field_id_map, doc_alloc);
         let context = Context::new(&document, &fields);
         let mut rendered = bumpalo::collections::Vec::with_capacity_in(
@@ -147,16 +187,99 @@ impl Prompt {
         field_id_map: &FieldIdMapWithMetadata,
     ) -> Result<String, RenderPromptError> {
         let document = Document::new(document, side, field_id_map.as_fields_ids_map());
+        if let Some(field) = self.chunked_field() {
+            return Ok(render_field(&document, field));
+        }
         let fields = OwnedFields::new(&document, field_id_map);
         let context = Context::new(&document, &fields);
 
         let mut rendered =
             self.template.render(&context).map_err(RenderPromptError::missing_context)?;
-        if let Some(max_bytes) = self.max_bytes {
+        // chunked texts are embedded in full, each chunk being bounded by the chunk size
+        if let (Some(max_bytes), None) = (self.max_bytes, &self.chunking) {
             truncate(&mut rendered, max_bytes.get());
         }
         Ok(rendered)
     }
+
+    /// Locates the chunk of a document of the index from which its `chunk`-th embedding was generated.
+    ///
+    /// Returns `None` when the prompt does not chunk documents, or when the chunk does not exist anymore.
+    pub fn locate_chunk(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        docid: DocumentId,
+        chunk: u8,
+    ) -> crate::Result<Option<ChunkPosition>> {
+        let Some(chunking) = &self.chunking else { return Ok(None) };
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let Some(document) = DocumentFromDb::new(docid, rtxn, index, &fields_ids_map)? else {
+            return Ok(None);
+        };
+        let external_docid = index
+            .external_id_of(rtxn, std::iter::once(docid))?
+            .into_iter()
+            .next()
+            .transpose()?
+            .unwrap_or_default();
+
+        let fields_ids_map = RwLock::new(index.fields_ids_map_with_metadata(rtxn)?);
+        let global_fields_ids_map = RefCell::new(GlobalFieldsIdsMap::new(&fields_ids_map));
+        let doc_alloc = Bump::new();
+        let rendered =
+            self.render_document(&external_docid, document, &global_fields_ids_map, &doc_alloc)?;
+
+        Ok(chunking.split(rendered).get(chunk as usize).map(|found| ChunkPosition {
+            index: chunk,
+            start: found.start,
+            length: found.length(),
+        }))
+    }
+
+    /// Renders documents of the index, in the order of `docids`, truncated to the maximum size of the prompt.
+    ///
+    /// Documents that do not exist anymore are rendered as an empty text.
+    pub fn render_stored_documents(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        docids: &[DocumentId],
+    ) -> crate::Result<Vec<String>> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let fields_ids_map_with_metadata = RwLock::new(index.fields_ids_map_with_metadata(rtxn)?);
+        let global_fields_ids_map =
+            RefCell::new(GlobalFieldsIdsMap::new(&fields_ids_map_with_metadata));
+        let max_bytes = self.max_bytes.unwrap_or_else(default_max_bytes).get();
+        let mut doc_alloc = Bump::new();
+
+        let mut rendered_documents = Vec::with_capacity(docids.len());
+        for (&docid, external_docid) in
+            docids.iter().zip(index.external_id_of(rtxn, docids.iter().copied())?)
+        {
+            let external_docid = external_docid?;
+            let Some(document) = DocumentFromDb::new(docid, rtxn, index, &fields_ids_map)? else {
+                rendered_documents.push(String::new());
+                continue;
+            };
+            let mut rendered = self
+                .render_document(&external_docid, document, &global_fields_ids_map, &doc_alloc)?
+                .to_owned();
+            truncate(&mut rendered, max_bytes);
+            rendered_documents.push(rendered);
+            doc_alloc.reset();
+        }
+        Ok(rendered_documents)
+    }
+
+    fn chunked_field(&self) -> Option<&str> {
+        self.chunking.as_ref().and_then(|chunking| chunking.field.as_deref())
+    }
+}
+
+/// Renders the value of a top-level field of the document, or an empty text when it is missing.
+fn render_field(document: &dyn ObjectView, field: &str) -> String {
+    document.get(field).map(|value| value.render().to_string()).unwrap_or_default()
 }
 
 fn truncate(s: &mut String, max_bytes: usize) {
@@ -17,6 +17,8 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    /// Score given by a reranker to the top hits of a search.
+    Rerank(Rerank),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -34,6 +36,7 @@ enum RankOrValue<'a> {
     Sort(&'a Sort),
     GeoSort(&'a GeoSort),
     Score(f64),
+    RerankScore(f64),
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -62,6 +65,7 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Rerank(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -93,6 +97,7 @@ impl ScoreDetails {
                 RankOrValue::Sort(s) => ScoreValue::Sort(s),
                 RankOrValue::GeoSort(g) => ScoreValue::GeoSort(g),
                 RankOrValue::Score(s) => ScoreValue::Score(s),
+                RankOrValue::RerankScore(s) => ScoreValue::Score(s),
             })
     }
 
@@ -117,6 +122,7 @@ impl ScoreDetails {
                     WeightedScoreValue::GeoSort { asc: g.ascending, distance: g.distance() }
                 }
                 RankOrValue::Score(s) => WeightedScoreValue::VectorSort(s * weight),
+                RankOrValue::RerankScore(s) => WeightedScoreValue::WeightedScore(s * weight),
             })
     }
 
@@ -134,6 +140,7 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::Rerank(rerank) => RankOrValue::RerankScore(rerank.score),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -291,6 +298,15 @@ impl ScoreDetails {
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Rerank(rerank) => {
+                    let details = serde_json::json!({
+                        "order": order,
+                        "reranker": rerank.reranker,
+                        "score": rerank.score,
+                    });
+                    details_map.insert("rerank".into(), details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -543,6 +559,18 @@ impl PartialOrd for GeoSort {
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Vector {
     pub similarity: Option<f32>,
+    /// Index of the embedding of the document closest to the target.
+    ///
+    /// When the embedder chunks documents, this is the index of the best matching chunk.
+    pub chunk: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rerank {
+    /// Name of the reranker that scored the document.
+    pub reranker: String,
+    /// Relevance of the document to the query according to the reranker, between 0 and 1.
+    pub score: f64,
 }
 
 impl GeoSort {
@@ -1,25 +1,35 @@
+use std::cell::OnceCell;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Display;
-use std::ops::ControlFlow;
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
+use deserr::Deserr;
 use heed::types::Bytes;
 use heed::BytesDecode;
 use indexmap::IndexMap;
+use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::attribute_patterns::match_field_legacy;
-use crate::facet::FacetType;
+use crate::facet::{parse_date, FacetType};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
-use crate::{Error, FieldId, FilterableAttributesRule, Index, PatternMatch, Result, UserError};
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
+use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
+use crate::{
+    normalize_facet, DocumentId, Error, FieldId, FilterableAttributesRule, HierarchicalFacet,
+    Index, PatternMatch, Result, UserError,
+};
 
 /// The default number of values by facets that will
 /// be fetched from the key-value store.
@@ -48,11 +58,105 @@ impl Display for OrderBy {
     }
 }
 
+/// How the numbers of a facet should be split into buckets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FacetRanges {
+    /// Consecutive buckets of the given width, aligned on multiples of it,
+    /// covering every value of the candidates.
+    Interval(f64),
+    /// Explicit buckets, returned in the same order.
+    Ranges(Vec<FacetRange>),
+}
+
+/// A bucket of numbers, the lower bound is inclusive and the upper bound exclusive.
+/// A missing bound means that the bucket is unbounded on this side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+/// The number of candidates having a value in the given range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRangeBucket {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+    pub count: u64,
+}
+
+/// A statistic that can be computed on the numbers of a facet, in addition to the min and max.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Deserr, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum FacetStat {
+    /// The number of values.
+    Count,
+    /// The sum of the values.
+    Sum,
+    /// The arithmetic mean of the values.
+    Avg,
+    /// The median of the values.
+    P50,
+    /// The 90th percentile of the values.
+    P90,
+    /// The 99th percentile of the values.
+    P99,
+}
+
+/// The statistics computed on the numbers of a facet, only the requested ones are set.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ExtendedFacetStats {
+    pub count: Option<u64>,
+    pub sum: Option<f64>,
+    pub avg: Option<f64>,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+impl ExtendedFacetStats {
+    /// Computes the requested statistics from the distinct values in ascending order,
+    /// along with their number of occurrences.
+    fn compute(requested: &BTreeSet<FacetStat>, values: &[(f64, u64)]) -> Self {
+        let count: u64 = values.iter().map(|(_, count)| count).sum();
+        let sum: f64 = values.iter().map(|(value, count)| value * *count as f64).sum();
+        // nearest-rank method: the smallest value such that at least
+        // `percent`% of the values are lower than or equal to it.
+        let percentile = |percent: u64| {
+            let rank = (count * percent).div_ceil(100).max(1);
+            let mut seen = 0;
+            values
+                .iter()
+                .find(|(_, count)| {
+                    seen += count;
+                    seen >= rank
+                })
+                .map(|(value, _)| *value)
+        };
+
+        let mut stats = ExtendedFacetStats::default();
+        for stat in requested {
+            match stat {
+                FacetStat::Count => stats.count = Some(count),
+                FacetStat::Sum => stats.sum = Some(sum),
+                FacetStat::Avg => stats.avg = Some(sum / count as f64),
+                FacetStat::P50 => stats.p50 = percentile(50),
+                FacetStat::P90 => stats.p90 = percentile(90),
+                FacetStat::P99 => stats.p99 = percentile(99),
+            }
+        }
+        stats
+    }
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
+    date_faceted_fields: OnceCell<BTreeSet<FieldId>>,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -64,6 +168,7 @@ impl<'a> FacetDistribution<'a> {
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
+            date_faceted_fields: OnceCell::new(),
             rtxn,
             index,
         }
@@ -113,6 +218,8 @@ impl<'a> FacetDistribution<'a> {
 
                 let db = self.index.field_id_docid_facet_f64s;
                 for docid in candidates {
+                    // the dates are reported by the `Date` facet type
+                    let dates = self.document_dates(field_id, docid)?;
                     key_buffer.truncate(mem::size_of::<FieldId>());
                     key_buffer.extend_from_slice(&docid.to_be_bytes());
                     let iter = db
@@ -122,6 +229,9 @@ impl<'a> FacetDistribution<'a> {
 
                     for result in iter {
                         let ((_, _, value), ()) = result?;
+                        if dates.iter().any(|(timestamp, _)| *timestamp == value) {
+                            continue;
+                        }
                         *lexicographic_distribution.entry(value.to_string()).or_insert(0) += 1;
                     }
                 }
@@ -133,6 +243,7 @@ impl<'a> FacetDistribution<'a> {
                 );
             }
             FacetType::String => {
+                let is_date_field = self.is_date_field(field_id)?;
                 let mut normalized_distribution = BTreeMap::new();
                 let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
 
@@ -147,6 +258,10 @@ impl<'a> FacetDistribution<'a> {
 
                     for result in iter {
                         let ((_, _, normalized_value), original_value) = result?;
+                        // the dates are reported by the `Date` facet type
+                        if is_date_field && parse_date(original_value).is_some() {
+                            continue;
+                        }
                         let (_, count) = normalized_distribution
                             .entry(normalized_value)
                             .or_insert_with(|| (original_value, 0));
@@ -163,11 +278,86 @@ impl<'a> FacetDistribution<'a> {
                     .map(|(_normalized, (original, count))| (original.to_string(), count));
                 distribution.extend(iter);
             }
+            FacetType::Date if self.is_date_field(field_id)? => {
+                let mut chronological_distribution = BTreeMap::new();
+
+                for docid in candidates {
+                    for (timestamp, original_value) in self.document_dates(field_id, docid)? {
+                        let (_, count) = chronological_distribution
+                            .entry(OrderedFloat(timestamp))
+                            .or_insert_with(|| (original_value, 0));
+                        *count += 1;
+                    }
+                }
+
+                let iter = chronological_distribution
+                    .into_iter()
+                    .take(self.max_values_per_facet.saturating_sub(distribution.len()))
+                    .map(|(_timestamp, (original, count))| (original.to_string(), count));
+                distribution.extend(iter);
+            }
+            FacetType::Date => (),
         }
 
         Ok(())
     }
 
+    /// Whether dates were indexed in this field, the other fields are never searched for dates.
+    fn is_date_field(&self, field_id: FieldId) -> heed::Result<bool> {
+        if let Some(fields) = self.date_faceted_fields.get() {
+            return Ok(fields.contains(&field_id));
+        }
+        let fields = self.index.date_faceted_fields(self.rtxn)?;
+        Ok(self.date_faceted_fields.get_or_init(|| fields).contains(&field_id))
+    }
+
+    /// The dates of a document on the given field, along with their original string.
+    ///
+    /// The dates are stored both as strings and as numbers, a single date is returned for
+    /// the different strings of the same document representing the same instant.
+    fn document_dates(
+        &self,
+        field_id: FieldId,
+        docid: DocumentId,
+    ) -> heed::Result<Vec<(f64, &'a str)>> {
+        if !self.is_date_field(field_id)? {
+            return Ok(Vec::new());
+        }
+
+        let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
+        key_buffer.extend_from_slice(&docid.to_be_bytes());
+        let iter = self
+            .index
+            .field_id_docid_facet_strings
+            .remap_key_type::<Bytes>()
+            .prefix_iter(self.rtxn, &key_buffer)?
+            .remap_key_type::<FieldDocIdFacetStringCodec>();
+
+        let mut dates = Vec::new();
+        for result in iter {
+            let (_, original_value) = result?;
+            if let Some(timestamp) = parse_date(original_value) {
+                dates.push((timestamp, original_value));
+            }
+        }
+        dates.sort_unstable_by(|(left, _), (right, _)| left.total_cmp(right));
+        dates.dedup_by(|(left, _), (right, _)| left == right);
+
+        Ok(dates)
+    }
+
+    /// The original string of the date of the document stored as this number,
+    /// or `None` if the number doesn't come from a date.
+    fn original_date(
+        &self,
+        field_id: FieldId,
+        docid: DocumentId,
+        value: f64,
+    ) -> heed::Result<Option<&'a str>> {
+        let dates = self.document_dates(field_id, docid)?;
+        Ok(dates.into_iter().find(|(timestamp, _)| *timestamp == value).map(|(_, date)| date))
+    }
+
     /// There is too much documents, we use the facet levels to move throught
     /// the facet values, to find the candidates and values associated.
     fn facet_numbers_distribution_from_facet_levels(
@@ -187,9 +377,14 @@ impl<'a> FacetDistribution<'a> {
             self.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>(),
             field_id,
             candidates,
-            |facet_key, nbr_docids, _| {
+            |facet_key, nbr_docids, any_docid| {
                 let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
-                distribution.insert(facet_key.to_string(), nbr_docids);
+                // the dates are reported with their original string, the type of a value
+                // is determined by one of the documents sharing it
+                match self.original_date(field_id, any_docid, facet_key)? {
+                    Some(date) => distribution.insert(date.to_string(), nbr_docids),
+                    None => distribution.insert(facet_key.to_string(), nbr_docids),
+                };
                 if distribution.len() == self.max_values_per_facet {
                     Ok(ControlFlow::Break(()))
                 } else {
@@ -211,6 +406,7 @@ impl<'a> FacetDistribution<'a> {
             OrderBy::Count => count_iterate_over_facet_distribution,
         };
 
+        let is_date_field = self.is_date_field(field_id)?;
         search_function(
             self.rtxn,
             self.index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>(),
@@ -234,6 +430,11 @@ impl<'a> FacetDistribution<'a> {
                     }
                 };
 
+                // the dates were already reported with the numbers
+                if is_date_field && parse_date(&original_string).is_some() {
+                    return Ok(ControlFlow::Continue(()));
+                }
+
                 distribution.insert(original_string, nbr_docids);
                 if distribution.len() == self.max_values_per_facet {
                     Ok(ControlFlow::Break(()))
@@ -249,7 +450,7 @@ impl<'a> FacetDistribution<'a> {
         field_id: FieldId,
         order_by: OrderBy,
     ) -> heed::Result<IndexMap<String, u64>> {
-        use FacetType::{Number, String};
+        use FacetType::{Date, Number, String};
 
         let mut distribution = IndexMap::new();
         match (order_by, &self.candidates) {
@@ -258,6 +459,7 @@ impl<'a> FacetDistribution<'a> {
                 // to those candidates. We also enter here for facet strings for performance reasons.
                 self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
                 self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
+                self.facet_distribution_from_documents(field_id, Date, cnd, &mut distribution)?;
             }
             _ => {
                 let universe;
@@ -288,6 +490,16 @@ impl<'a> FacetDistribution<'a> {
     }
 
     pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
+        let stats = self.compute_typed_stats()?;
+        Ok(stats.into_iter().map(|(name, (_, min, max))| (name, (min, max))).collect())
+    }
+
+    /// Computes the min and max of the numbers of the candidates, for each selected field,
+    /// along with the type of these numbers.
+    ///
+    /// The type is [`FacetType::Date`] when both the min and the max come from dates,
+    /// and [`FacetType::Number`] otherwise.
+    pub fn compute_typed_stats(&self) -> Result<BTreeMap<String, (FacetType, f64, f64)>> {
         let candidates = if let Some(candidates) = self.candidates.clone() {
             candidates
         } else {
@@ -301,34 +513,373 @@ impl<'a> FacetDistribution<'a> {
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
             if self.select_field(name, &filterable_attributes_rules) {
-                let min_value = if let Some(min_value) = crate::search::facet::facet_min_value(
-                    self.index,
-                    self.rtxn,
-                    fid,
-                    candidates.clone(),
-                )? {
-                    min_value
-                } else {
-                    continue;
-                };
-                let max_value = if let Some(max_value) = crate::search::facet::facet_max_value(
-                    self.index,
-                    self.rtxn,
-                    fid,
-                    candidates.clone(),
-                )? {
-                    max_value
-                } else {
-                    continue;
+                let db = self
+                    .index
+                    .facet_id_f64_docids
+                    .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+                let min = ascending_facet_sort(self.rtxn, db, fid, candidates.clone())?;
+                let (min_value, min_is_date) =
+                    if let Some(min) = self.typed_extreme_value(fid, min)? {
+                        min
+                    } else {
+                        continue;
+                    };
+                let max = descending_facet_sort(self.rtxn, db, fid, candidates.clone())?;
+                let (max_value, max_is_date) =
+                    if let Some(max) = self.typed_extreme_value(fid, max)? {
+                        max
+                    } else {
+                        continue;
+                    };
+
+                let facet_type =
+                    if min_is_date && max_is_date { FacetType::Date } else { FacetType::Number };
+                distribution.insert(name.to_string(), (facet_type, min_value, max_value));
+            }
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns the first number yielded by a facet sort, and whether it comes from a date.
+    fn typed_extreme_value<'t>(
+        &self,
+        field_id: FieldId,
+        mut extreme_it: impl Iterator<Item = heed::Result<(RoaringBitmap, &'t [u8])>>,
+    ) -> Result<Option<(f64, bool)>> {
+        let (docids, extreme_value) = match extreme_it.next() {
+            Some(result) => result?,
+            None => return Ok(None),
+        };
+        let extreme_value =
+            OrderedF64Codec::bytes_decode(extreme_value).map_err(heed::Error::Decoding)?;
+        let is_date = match docids.min() {
+            Some(docid) => self.original_date(field_id, docid, extreme_value)?.is_some(),
+            None => false,
+        };
+
+        Ok(Some((extreme_value, is_date)))
+    }
+
+    /// Computes the statistics configured in the `facetStats` of the faceting settings
+    /// on the numbers of the candidates, for each selected field.
+    ///
+    /// The statistics are computed on the values rather than on the documents:
+    /// a document with several numbers in the same field counts once per number.
+    pub fn compute_extended_stats(&self) -> Result<BTreeMap<String, ExtendedFacetStats>> {
+        let candidates = if let Some(candidates) = self.candidates.clone() {
+            candidates
+        } else {
+            return Ok(Default::default());
+        };
+
+        let facet_stats = self.index.facet_stats(self.rtxn)?;
+        if facet_stats.is_empty() {
+            return Ok(Default::default());
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        self.check_faceted_fields(&filterable_attributes_rules)?;
+
+        let mut stats = BTreeMap::new();
+        for (fid, name) in fields_ids_map.iter() {
+            let Some(requested) = facet_stats.get(name).filter(|requested| !requested.is_empty())
+            else {
+                continue;
+            };
+            if !self.select_field(name, &filterable_attributes_rules) {
+                continue;
+            }
+
+            let mut values = Vec::new();
+            lexicographically_iterate_over_facet_distribution(
+                self.rtxn,
+                self.index
+                    .facet_id_f64_docids
+                    .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>(),
+                fid,
+                &candidates,
+                |facet_key, nbr_docids, _| {
+                    let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
+                    values.push((facet_key, nbr_docids));
+                    Ok(ControlFlow::Continue(()))
+                },
+            )?;
+
+            if !values.is_empty() {
+                stats.insert(name.to_string(), ExtendedFacetStats::compute(requested, &values));
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Count the candidates falling in each bucket of the requested facet ranges.
+    ///
+    /// Unlike the other methods, the fields are not selected by the `facets` but by the keys
+    /// of `facet_ranges`. The number of buckets generated by an interval is limited by the
+    /// maximum number of values per facet.
+    pub fn compute_ranges<S: AsRef<str>>(
+        &self,
+        facet_ranges: &BTreeMap<S, FacetRanges>,
+    ) -> Result<BTreeMap<String, Vec<FacetRangeBucket>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+
+        let universe;
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, ranges) in facet_ranges {
+            let name = name.as_ref();
+            if !matching_features(name, &filterable_attributes_rules)
+                .is_some_and(|(_, features)| features.is_filterable())
+            {
+                let valid_patterns =
+                    filtered_matching_patterns(&filterable_attributes_rules, &|features| {
+                        features.is_filterable()
+                    })
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+                return Err(Error::UserError(UserError::InvalidFacetRangesAttribute {
+                    field: name.to_string(),
+                    valid_patterns,
+                }));
+            }
+
+            let field_id = fields_ids_map.id(name);
+            let ranges = match ranges {
+                FacetRanges::Ranges(ranges) => ranges.clone(),
+                FacetRanges::Interval(interval) => match field_id {
+                    Some(field_id) => {
+                        self.interval_ranges(name, field_id, *interval, candidates)?
+                    }
+                    None => Vec::new(),
+                },
+            };
+
+            let mut buckets = Vec::with_capacity(ranges.len());
+            for FacetRange { from, to } in ranges {
+                let count = match field_id {
+                    Some(field_id) => self.count_in_range(field_id, from, to, candidates)?,
+                    None => 0,
                 };
+                buckets.push(FacetRangeBucket { from, to, count });
+            }
+
+            distribution.insert(name.to_string(), buckets);
+        }
+
+        Ok(distribution)
+    }
 
-                distribution.insert(name.to_string(), (min_value, max_value));
+    /// Count the candidates of each child of the selected paths of the hierarchical facets,
+    /// a missing path selecting the root of the hierarchy.
+    ///
+    /// The children are sorted following the `sort_facet_values_by` of the attribute
+    /// they are stored in and there are at most `max_values_per_facet` of them.
+    pub fn compute_hierarchical<S: AsRef<str>>(
+        &self,
+        selected_paths: &BTreeMap<S, Option<String>>,
+    ) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        let hierarchical_facets = self.index.hierarchical_facets(self.rtxn)?;
+        let sort_facet_values_by = self.index.sort_facet_values_by(self.rtxn)?;
+
+        let universe;
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
             }
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, path) in selected_paths {
+            let name = name.as_ref();
+            let Some(hierarchy) = hierarchical_facets.get(name) else {
+                return Err(Error::UserError(UserError::InvalidHierarchicalFacetName {
+                    name: name.to_string(),
+                    valid_names: hierarchical_facets.keys().cloned().collect(),
+                }));
+            };
+
+            let path = path.as_deref();
+            let children = match hierarchy.children_attribute(path) {
+                Some(attribute) => {
+                    if !matching_features(attribute, &filterable_attributes_rules)
+                        .is_some_and(|(_, features)| features.is_filterable())
+                    {
+                        return Err(Error::UserError(
+                            UserError::HierarchicalFacetAttributeNotFilterable {
+                                name: name.to_string(),
+                                field: attribute.to_string(),
+                            },
+                        ));
+                    }
+
+                    match fields_ids_map.id(attribute) {
+                        Some(field_id) => {
+                            let order_by = sort_facet_values_by.get(attribute);
+                            self.children_distribution(
+                                field_id, hierarchy, path, order_by, candidates,
+                            )?
+                        }
+                        None => IndexMap::new(),
+                    }
+                }
+                // the selected path is a leaf of the tree
+                None => IndexMap::new(),
+            };
+
+            distribution.insert(name.to_string(), children);
         }
 
         Ok(distribution)
     }
 
+    /// Iterate over the normalized facet strings of the children attribute that are prefixed
+    /// by the selected path followed by the separator.
+    fn children_distribution(
+        &self,
+        field_id: FieldId,
+        hierarchy: &HierarchicalFacet,
+        path: Option<&str>,
+        order_by: OrderBy,
+        candidates: &RoaringBitmap,
+    ) -> Result<IndexMap<String, u64>> {
+        let prefix = match path.map(str::trim).filter(|path| !path.is_empty()) {
+            // The separator is usually surrounded by spaces that `normalize_facet` would trim,
+            // so we normalize it along with a sentinel character that we remove afterward.
+            Some(path) => {
+                let mut prefix = normalize_facet(&format!("{path}{}_", hierarchy.separator));
+                prefix.pop();
+                prefix
+            }
+            None => String::new(),
+        };
+
+        let mut children = Vec::new();
+        let key = FacetGroupKey { field_id, level: 0, left_bound: prefix.as_str() };
+        for result in self.index.facet_id_string_docids.prefix_iter(self.rtxn, &key)? {
+            if order_by == OrderBy::Lexicographic && children.len() >= self.max_values_per_facet {
+                break;
+            }
+
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            let docids = bitmap & candidates;
+            let Some(any_docid) = docids.min() else { continue };
+
+            let key: (FieldId, _, &str) = (field_id, any_docid, left_bound);
+            let original = match self.index.field_id_docid_facet_strings.get(self.rtxn, &key)? {
+                Some(original) => original.to_owned(),
+                None => {
+                    tracing::error!(
+                        "Missing original facet string. Using the normalized facet {} instead",
+                        left_bound
+                    );
+                    left_bound.to_string()
+                }
+            };
+            children.push((original, docids.len()));
+        }
+
+        if order_by == OrderBy::Count {
+            // the sort is stable, children with the same count stay sorted lexicographically
+            children.sort_by(|(_, left), (_, right)| right.cmp(left));
+            children.truncate(self.max_values_per_facet);
+        }
+
+        Ok(children.into_iter().collect())
+    }
+
+    /// Generate the consecutive ranges of width `interval` between the minimum
+    /// and the maximum values of the candidates.
+    fn interval_ranges(
+        &self,
+        name: &str,
+        field_id: FieldId,
+        interval: f64,
+        candidates: &RoaringBitmap,
+    ) -> Result<Vec<FacetRange>> {
+        let min = crate::search::facet::facet_min_value(
+            self.index,
+            self.rtxn,
+            field_id,
+            candidates.clone(),
+        )?;
+        let max = crate::search::facet::facet_max_value(
+            self.index,
+            self.rtxn,
+            field_id,
+            candidates.clone(),
+        )?;
+        let (min, max) = match min.zip(max) {
+            Some(min_max) => min_max,
+            None => return Ok(Vec::new()),
+        };
+
+        let first = (min / interval).floor();
+        let last = (max / interval).floor();
+        let buckets_count = (last - first) as u64 + 1;
+        if buckets_count > self.max_values_per_facet as u64 {
+            return Err(Error::UserError(UserError::TooManyFacetRangeBuckets {
+                field: name.to_string(),
+                interval,
+                buckets_count,
+                max_buckets: self.max_values_per_facet,
+            }));
+        }
+
+        // The bounds are computed from the bucket index rather than by accumulating
+        // the interval so that consecutive buckets always share the exact same bound.
+        Ok((0..buckets_count)
+            .map(|i| FacetRange {
+                from: Some((first + i as f64) * interval),
+                to: Some((first + i as f64 + 1.0) * interval),
+            })
+            .collect())
+    }
+
+    fn count_in_range(
+        &self,
+        field_id: FieldId,
+        from: Option<f64>,
+        to: Option<f64>,
+        candidates: &RoaringBitmap,
+    ) -> Result<u64> {
+        // the upper bound is exclusive, such a range is always empty
+        if from.zip(to).is_some_and(|(from, to)| from >= to) {
+            return Ok(0);
+        }
+
+        let left = from.map_or(Bound::Unbounded, Bound::Included);
+        let right = to.map_or(Bound::Unbounded, Bound::Excluded);
+
+        let mut docids = RoaringBitmap::new();
+        find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+            self.rtxn,
+            self.index.facet_id_f64_docids,
+            field_id,
+            &left,
+            &right,
+            Some(candidates),
+            &mut docids,
+        )?;
+
+        Ok(docids.intersection_len(candidates))
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
@@ -423,6 +974,7 @@ impl fmt::Debug for FacetDistribution<'_> {
             candidates,
             max_values_per_facet,
             default_order_by,
+            date_faceted_fields: _,
             rtxn: _,
             index: _,
         } = self;
@@ -438,13 +990,17 @@ impl fmt::Debug for FacetDistribution<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
     use std::iter;
 
     use big_s::S;
 
     use crate::documents::mmap_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, FilterableAttributesRule, OrderBy};
+    use crate::{
+        milli_snap, FacetDistribution, FacetRange, FacetRangeBucket, FacetRanges, FacetStat,
+        FilterableAttributesRule, HierarchicalFacet, OrderBy,
+    };
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -886,4 +1442,308 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn facet_dates() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("released")),
+                    FilterableAttributesRule::Field(S("mixed")),
+                    FilterableAttributesRule::Field(S("count")),
+                ])
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "released": "2024-01-01", "mixed": 12, "count": 1, "updated": "2024-01-01" },
+                { "id": 1, "released": "2023-06-15T12:00:00Z", "mixed": "2024-01-01", "count": 2 },
+                { "id": 2, "released": ["2024-01-01T00:00:00Z", "2024-01-01"], "mixed": "blue" },
+            ]))
+            .unwrap();
+
+        // only the fields in which dates were indexed are searched for dates
+        let txn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+        let date_faceted_fields: BTreeSet<_> = index
+            .date_faceted_fields(&txn)
+            .unwrap()
+            .into_iter()
+            .map(|fid| fields_ids_map.name(fid).unwrap())
+            .collect();
+        assert_eq!(date_faceted_fields, BTreeSet::from(["mixed", "released"]));
+        drop(txn);
+
+        // the fields made filterable are reindexed with the settings
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("released")),
+                    FilterableAttributesRule::Field(S("mixed")),
+                    FilterableAttributesRule::Field(S("count")),
+                    FilterableAttributesRule::Field(S("updated")),
+                ])
+            })
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let updated = index.fields_ids_map(&txn).unwrap().id("updated").unwrap();
+        assert!(index.date_faceted_fields(&txn).unwrap().contains(&updated));
+
+        // the dates are reported once, with their original string, in chronological order
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("released", OrderBy::default())))
+            .candidates([0, 1, 2].into_iter().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "released_from_documents", @r###"{"released": {"2023-06-15T12:00:00Z": 1, "2024-01-01": 2}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("released", OrderBy::default())))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "released_from_levels", @r###"{"released": {"2023-06-15T12:00:00Z": 1, "2024-01-01": 2}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("mixed", OrderBy::default())))
+            .candidates([0, 1, 2].into_iter().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "mixed", @r###"{"mixed": {"12": 1, "blue": 1, "2024-01-01": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets([("released", OrderBy::default()), ("mixed", OrderBy::default())])
+            .candidates([0, 1, 2].into_iter().collect())
+            .compute_typed_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "typed_stats", @r###"{"mixed": (Number, 12.0, 1704067200.0), "released": (Date, 1686830400.0, 1704067200.0)}"###);
+    }
+
+    #[test]
+    fn extended_facet_stats() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("price")),
+                    FilterableAttributesRule::Field(S("weight")),
+                ]);
+                settings.set_facet_stats(BTreeMap::from([(
+                    S("price"),
+                    BTreeSet::from([
+                        FacetStat::Count,
+                        FacetStat::Sum,
+                        FacetStat::Avg,
+                        FacetStat::P50,
+                        FacetStat::P90,
+                        FacetStat::P99,
+                    ]),
+                )]));
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "price": 1, "weight": 1 },
+            { "id": 1, "price": 2, "weight": 1 },
+            { "id": 2, "price": 3, "weight": 1 },
+            { "id": 3, "price": 4 },
+            { "id": 4, "price": 5 },
+            { "id": 5, "price": 6 },
+            { "id": 6, "price": 7 },
+            { "id": 7, "price": 8 },
+            { "id": 8, "price": 9 },
+            { "id": 9, "price": [10, 20] },
+            { "id": 10, "price": 10 },
+            { "id": 11, "price": "cheap" },
+            { "id": 12 }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((0..13).collect())
+            .compute_extended_stats()
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"price": ExtendedFacetStats { count: Some(12), sum: Some(85.0), avg: Some(7.083333333333333), p50: Some(6.0), p90: Some(10.0), p99: Some(20.0) }}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates([0, 1, 2, 11, 12].into_iter().collect())
+            .compute_extended_stats()
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"price": ExtendedFacetStats { count: Some(3), sum: Some(6.0), avg: Some(2.0), p50: Some(2.0), p90: Some(3.0), p99: Some(3.0) }}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("weight", OrderBy::default())))
+            .candidates((0..13).collect())
+            .compute_extended_stats()
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @"{}");
+    }
+
+    #[test]
+    fn facet_ranges() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("price"))])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "price": 5 },
+            { "id": 1, "price": 12 },
+            { "id": 2, "price": 49.5 },
+            { "id": 3, "price": 50 },
+            { "id": 4, "price": 99 },
+            { "id": 5, "price": 100 },
+            { "id": 6, "price": 150 },
+            { "id": 7, "price": -3 },
+            { "id": 8 },
+            { "id": 9, "price": "cheap" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let format = |map: BTreeMap<String, Vec<FacetRangeBucket>>| {
+            let mut output = String::new();
+            for (name, buckets) in map {
+                output.push_str(&name);
+                output.push(':');
+                for FacetRangeBucket { from, to, count } in buckets {
+                    let from = from.map_or(String::new(), |from| from.to_string());
+                    let to = to.map_or(String::new(), |to| to.to_string());
+                    output.push_str(&format!(" {from}..{to}={count}"));
+                }
+            }
+            output
+        };
+
+        let interval = BTreeMap::from([("price", FacetRanges::Interval(50.0))]);
+        let map = FacetDistribution::new(&txn, &index).compute_ranges(&interval).unwrap();
+        milli_snap!(format(map), @"price: -50..0=1 0..50=3 50..100=2 100..150=1 150..200=1");
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((0..4).collect())
+            .compute_ranges(&interval)
+            .unwrap();
+        milli_snap!(format(map), @"price: 0..50=3 50..100=1");
+
+        let ranges = BTreeMap::from([(
+            "price",
+            FacetRanges::Ranges(vec![
+                FacetRange { from: None, to: Some(10.0) },
+                FacetRange { from: Some(10.0), to: Some(100.0) },
+                FacetRange { from: Some(100.0), to: None },
+                FacetRange { from: Some(10.0), to: Some(10.0) },
+            ]),
+        )]);
+        let map = FacetDistribution::new(&txn, &index).compute_ranges(&ranges).unwrap();
+        milli_snap!(format(map), @"price: ..10=2 10..100=4 100..=2 10..10=0");
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((4..10).collect())
+            .compute_ranges(&ranges)
+            .unwrap();
+        milli_snap!(format(map), @"price: ..10=1 10..100=1 100..=2 10..10=0");
+
+        let error = FacetDistribution::new(&txn, &index)
+            .max_values_per_facet(4)
+            .compute_ranges(&interval)
+            .unwrap_err();
+        milli_snap!(error.to_string(), @r###"
+        Invalid facet ranges: An interval of `50` on attribute `price` produces 5 buckets, but at most 4 buckets can be returned per attribute.
+        Hint: use a larger interval or increase `faceting.maxValuesPerFacet`.
+        "###);
+
+        let not_filterable = BTreeMap::from([("id", FacetRanges::Interval(1.0))]);
+        let error =
+            FacetDistribution::new(&txn, &index).compute_ranges(&not_filterable).unwrap_err();
+        milli_snap!(error.to_string(), @"Invalid facet ranges: Attribute `id` is not filterable. Available filterable attributes patterns are: `price`.");
+    }
+
+    #[test]
+    fn hierarchical_facets() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings
+                    .set_filterable_fields(vec![FilterableAttributesRule::Field(S("categories"))]);
+                settings.set_hierarchical_facets(BTreeMap::from([(
+                    S("categories"),
+                    HierarchicalFacet {
+                        attributes: vec![
+                            S("categories.lvl0"),
+                            S("categories.lvl1"),
+                            S("categories.lvl2"),
+                        ],
+                        separator: S(" > "),
+                    },
+                )]));
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "categories": { "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > Android" } },
+            { "id": 1, "categories": { "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > iOS" } },
+            { "id": 2, "categories": { "lvl0": "Electronics", "lvl1": "Electronics > Laptops" } },
+            { "id": 3, "categories": { "lvl0": "Books", "lvl1": "Books > Comics" } },
+            { "id": 4, "categories": { "lvl0": "electronics", "lvl1": "electronics > phones" } }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .compute_hierarchical(&BTreeMap::from([("categories", None)]))
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Books": 1, "Electronics": 4}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .max_values_per_facet(1)
+            .compute_hierarchical(&BTreeMap::from([("categories", Some(S("")))]))
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Books": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .compute_hierarchical(&BTreeMap::from([("categories", Some(S("Electronics")))]))
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Electronics > Laptops": 1, "Electronics > Phones": 3}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates([1, 2].into_iter().collect())
+            .compute_hierarchical(&BTreeMap::from([(
+                "categories",
+                Some(S("electronics > Phones")),
+            )]))
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {"Electronics > Phones > iOS": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .compute_hierarchical(&BTreeMap::from([(
+                "categories",
+                Some(S("Electronics > Phones > Android")),
+            )]))
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"categories": {}}"###);
+
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_hierarchical(&BTreeMap::from([("colours", None)]))
+            .unwrap_err();
+        milli_snap!(error.to_string(), @"Invalid hierarchical facet: `colours` is not a hierarchical facet of this index. Available hierarchical facets are: `categories`.");
+    }
 }
@@ -7,18 +7,20 @@ pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
 use heed::types::LazyDecode;
 use memchr::memmem::Finder;
 use roaring::{MultiOps, RoaringBitmap};
+use rstar::AABB;
 use serde_json::Value;
 
 use super::facet_range_search;
 use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::error::{Error, UserError};
+use crate::facet::parse_date;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec, OrderedF64Codec,
 };
 use crate::index::db_name::FACET_ID_STRING_DOCIDS;
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, FieldId, FieldsIdsMap,
+    distance_between_two_points, is_point_in_polygon, lat_lng_to_xyz, FieldId, FieldsIdsMap,
     FilterableAttributesFeatures, FilterableAttributesRule, Index, InternalError, Result,
     SerializationError,
 };
@@ -227,6 +229,55 @@ impl<'a> Filter<'a> {
     pub fn use_contains_operator(&self) -> Option<&Token> {
         self.condition.use_contains_operator()
     }
+
+    /// Returns this filter without the clauses of its top-level conjunction that only
+    /// constrain the given attribute, or `None` if no clause remains.
+    ///
+    /// It is used to compute disjunctive facets: the distribution of such a facet
+    /// ignores the clauses selecting values of the facet itself.
+    pub fn without_attribute(&self, attribute: &str) -> Option<Self> {
+        fn conjunction<'f, 'a>(
+            condition: &'f FilterCondition<'a>,
+            clauses: &mut Vec<&'f FilterCondition<'a>>,
+        ) {
+            match condition {
+                FilterCondition::And(conditions) => {
+                    conditions.iter().for_each(|condition| conjunction(condition, clauses))
+                }
+                condition => clauses.push(condition),
+            }
+        }
+
+        fn only_constrains(condition: &FilterCondition, attribute: &str) -> bool {
+            match condition {
+                FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
+                    fid.value() == attribute
+                }
+                FilterCondition::Not(condition) => only_constrains(condition, attribute),
+                FilterCondition::Or(conditions) | FilterCondition::And(conditions) => {
+                    !conditions.is_empty()
+                        && conditions.iter().all(|condition| only_constrains(condition, attribute))
+                }
+                FilterCondition::GeoLowerThan { .. }
+                | FilterCondition::GeoBoundingBox { .. }
+                | FilterCondition::GeoPolygon { .. } => false,
+            }
+        }
+
+        let mut clauses = Vec::new();
+        conjunction(&self.condition, &mut clauses);
+        let mut clauses: Vec<_> = clauses
+            .into_iter()
+            .filter(|clause| !only_constrains(clause, attribute))
+            .cloned()
+            .collect();
+
+        match clauses.len() {
+            0 => None,
+            1 => clauses.pop().map(|condition| Self { condition }),
+            _ => Some(Self { condition: FilterCondition::And(clauses) }),
+        }
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -305,17 +356,17 @@ impl<'a> Filter<'a> {
                 ));
             }
             Condition::GreaterThan(val) => {
-                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+                (Excluded(parse_number_or_date(val)?), Included(f64::MAX))
             }
             Condition::GreaterThanOrEqual(val) => {
-                (Included(val.parse_finite_float()?), Included(f64::MAX))
+                (Included(parse_number_or_date(val)?), Included(f64::MAX))
             }
-            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(parse_number_or_date(val)?)),
             Condition::LowerThanOrEqual(val) => {
-                (Included(f64::MIN), Included(val.parse_finite_float()?))
+                (Included(f64::MIN), Included(parse_number_or_date(val)?))
             }
             Condition::Between { from, to } => {
-                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+                (Included(parse_number_or_date(from)?), Included(parse_number_or_date(to)?))
             }
             Condition::Null => {
                 let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
@@ -341,7 +392,7 @@ impl<'a> Filter<'a> {
                     )?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
-                let number = val.parse_finite_float().ok();
+                let number = parse_number_or_date(val).ok();
                 let number_docids = match number {
                     Some(n) => numbers_db
                         .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: n })?
@@ -561,7 +612,7 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::GeoLowerThan { point, radius } => {
-                if index.is_geo_filtering_enabled(rtxn)? {
+                if index.is_geojson_filtering_enabled(rtxn)? {
                     let base_point: [f64; 2] =
                         [point[0].parse_finite_float()?, point[1].parse_finite_float()?];
                     if !(-90.0..=90.0).contains(&base_point[0]) {
@@ -571,21 +622,21 @@ impl<'a> Filter<'a> {
                         return Err(point[1].as_external_error(BadGeoError::Lng(base_point[1])))?;
                     }
                     let radius = radius.parse_finite_float()?;
-                    let rtree = match index.geo_rtree(rtxn)? {
-                        Some(rtree) => rtree,
-                        None => return Ok(RoaringBitmap::new()),
+                    let mut result = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => {
+                            let xyz_base_point = lat_lng_to_xyz(&base_point);
+                            rtree
+                                .nearest_neighbor_iter(&xyz_base_point)
+                                .take_while(|point| {
+                                    distance_between_two_points(&base_point, &point.data.1)
+                                        <= radius + f64::EPSILON
+                                })
+                                .map(|point| point.data.0)
+                                .collect()
+                        }
+                        None => RoaringBitmap::new(),
                     };
-
-                    let xyz_base_point = lat_lng_to_xyz(&base_point);
-
-                    let result = rtree
-                        .nearest_neighbor_iter(&xyz_base_point)
-                        .take_while(|point| {
-                            distance_between_two_points(&base_point, &point.data.1)
-                                <= radius + f64::EPSILON
-                        })
-                        .map(|point| point.data.0)
-                        .collect();
+                    result |= geojson_within_radius(rtxn, index, &base_point, radius, universe)?;
 
                     Ok(result)
                 } else {
@@ -599,7 +650,7 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::GeoBoundingBox { top_right_point, bottom_left_point } => {
-                if index.is_geo_filtering_enabled(rtxn)? {
+                if index.is_geojson_filtering_enabled(rtxn)? {
                     let top_right: [f64; 2] = [
                         top_right_point[0].parse_finite_float()?,
                         top_right_point[1].parse_finite_float()?,
@@ -726,7 +777,15 @@ impl<'a> Filter<'a> {
                         )?
                     };
 
-                    Ok(selected_lat & selected_lng)
+                    let selected_geojson = geojson_within_bounding_box(
+                        rtxn,
+                        index,
+                        &bottom_left,
+                        &top_right,
+                        universe,
+                    )?;
+
+                    Ok((selected_lat & selected_lng) | selected_geojson)
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
@@ -739,10 +798,190 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                if index.is_geojson_filtering_enabled(rtxn)? {
+                    let mut polygon = Vec::with_capacity(points.len());
+                    for [lat, lng] in points {
+                        let point: [f64; 2] =
+                            [lat.parse_finite_float()?, lng.parse_finite_float()?];
+                        if !(-90.0..=90.0).contains(&point[0]) {
+                            return Err(lat.as_external_error(BadGeoError::Lat(point[0])))?;
+                        }
+                        if !(-180.0..=180.0).contains(&point[1]) {
+                            return Err(lng.as_external_error(BadGeoError::Lng(point[1])))?;
+                        }
+                        polygon.push(point);
+                    }
+
+                    let mut result = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree
+                            .locate_in_envelope_intersecting(&polygon_xyz_envelope(&polygon))
+                            .filter(|point| {
+                                universe.is_none_or(|universe| universe.contains(point.data.0))
+                            })
+                            .filter(|point| is_point_in_polygon(&point.data.1, &polygon))
+                            .map(|point| point.data.0)
+                            .collect(),
+                        None => RoaringBitmap::new(),
+                    };
+                    result |= geojson_within_polygon(rtxn, index, &polygon, universe)?;
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEO_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?
+                }
+            }
         }
     }
 }
 
+/// Returns the documents with a `_geojson` geometry which is at most `radius` meters away
+/// from the `center`.
+fn geojson_within_radius(
+    rtxn: &heed::RoTxn<'_>,
+    index: &Index,
+    center: &[f64; 2],
+    radius: f64,
+    universe: Option<&RoaringBitmap>,
+) -> Result<RoaringBitmap> {
+    let Some(rtree) = index.geojson_rtree(rtxn)? else { return Ok(RoaringBitmap::new()) };
+
+    // A degree of latitude is always longer than 110km, which makes
+    // this envelope slightly larger than the circle it contains.
+    let lat_delta = radius / 110_000.0;
+    let (min_lat, max_lat) = (center[0] - lat_delta, center[0] + lat_delta);
+    let lng_delta = lat_delta / min_lat.abs().max(max_lat.abs()).to_radians().cos();
+    let (min_lng, max_lng) = (center[1] - lng_delta, center[1] + lng_delta);
+    let envelope = if min_lat <= -90.0 || max_lat >= 90.0 || min_lng < -180.0 || max_lng > 180.0 {
+        // the circle contains a pole or crosses the antimeridian, we check every geometry
+        AABB::from_corners([-90.0, -180.0], [90.0, 180.0])
+    } else {
+        AABB::from_corners([min_lat, min_lng], [max_lat, max_lng])
+    };
+
+    Ok(rtree
+        .locate_in_envelope_intersecting(&envelope)
+        .filter(|shape| universe.is_none_or(|universe| universe.contains(shape.data.0)))
+        .filter(|shape| {
+            let nearest = shape.data.1.nearest_point(center);
+            distance_between_two_points(center, &nearest) <= radius + f64::EPSILON
+        })
+        .map(|shape| shape.data.0)
+        .collect())
+}
+
+/// Returns the documents with a `_geojson` geometry intersecting the bounding box.
+fn geojson_within_bounding_box(
+    rtxn: &heed::RoTxn<'_>,
+    index: &Index,
+    bottom_left: &[f64; 2],
+    top_right: &[f64; 2],
+    universe: Option<&RoaringBitmap>,
+) -> Result<RoaringBitmap> {
+    let Some(rtree) = index.geojson_rtree(rtxn)? else { return Ok(RoaringBitmap::new()) };
+
+    let bounding_boxes = if top_right[1] < bottom_left[1] {
+        // the bounding box is wrapping around the earth, we split it at the antimeridian
+        vec![(*bottom_left, [top_right[0], 180.0]), ([bottom_left[0], -180.0], *top_right)]
+    } else {
+        vec![(*bottom_left, *top_right)]
+    };
+
+    let mut result = RoaringBitmap::new();
+    for (bottom_left, top_right) in bounding_boxes {
+        let envelope = AABB::from_corners(bottom_left, top_right);
+        result.extend(
+            rtree
+                .locate_in_envelope_intersecting(&envelope)
+                .filter(|shape| universe.is_none_or(|universe| universe.contains(shape.data.0)))
+                .filter(|shape| shape.data.1.intersects_bounding_box(&bottom_left, &top_right))
+                .map(|shape| shape.data.0),
+        );
+    }
+
+    Ok(result)
+}
+
+/// Returns the documents with a `_geojson` geometry intersecting the polygon.
+fn geojson_within_polygon(
+    rtxn: &heed::RoTxn<'_>,
+    index: &Index,
+    polygon: &[[f64; 2]],
+    universe: Option<&RoaringBitmap>,
+) -> Result<RoaringBitmap> {
+    let Some(rtree) = index.geojson_rtree(rtxn)? else { return Ok(RoaringBitmap::new()) };
+
+    let envelope = AABB::from_points(polygon);
+    Ok(rtree
+        .locate_in_envelope_intersecting(&envelope)
+        .filter(|shape| universe.is_none_or(|universe| universe.contains(shape.data.0)))
+        .filter(|shape| shape.data.1.intersects_polygon(polygon))
+        .map(|shape| shape.data.0)
+        .collect())
+}
+
+/// Returns the envelope, in the cartesian coordinates of the points rtree, of the bounding
+/// box of the polygon in the latitude/longitude plane.
+fn polygon_xyz_envelope(polygon: &[[f64; 2]]) -> AABB<[f64; 3]> {
+    // Absorbs the rounding errors of the products below.
+    const MARGIN: f64 = 1e-9;
+
+    let range = |values: &[f64]| {
+        values.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        })
+    };
+    let (min_lat, max_lat) =
+        range(&polygon.iter().map(|[lat, _]| lat.to_radians()).collect::<Vec<_>>());
+    let (min_lng, max_lng) =
+        range(&polygon.iter().map(|[_, lng]| lng.to_radians()).collect::<Vec<_>>());
+    let contains = |min: f64, max: f64, angle: f64| min <= angle && angle <= max;
+
+    // The extremes of a trigonometric function over an interval are reached
+    // on its bounds or on the angles of the interval where it is 1 or -1.
+    let mut cos_lat = vec![min_lat.cos(), max_lat.cos()];
+    if contains(min_lat, max_lat, 0.0) {
+        cos_lat.push(1.0);
+    }
+    let mut cos_lng = vec![min_lng.cos(), max_lng.cos()];
+    if contains(min_lng, max_lng, 0.0) {
+        cos_lng.push(1.0);
+    }
+    let mut sin_lng = vec![min_lng.sin(), max_lng.sin()];
+    if contains(min_lng, max_lng, std::f64::consts::FRAC_PI_2) {
+        sin_lng.push(1.0);
+    }
+    if contains(min_lng, max_lng, -std::f64::consts::FRAC_PI_2) {
+        sin_lng.push(-1.0);
+    }
+
+    let product = |(a_min, a_max): (f64, f64), (b_min, b_max): (f64, f64)| {
+        range(&[a_min * b_min, a_min * b_max, a_max * b_min, a_max * b_max])
+    };
+    let (min_x, max_x) = product(range(&cos_lat), range(&cos_lng));
+    let (min_y, max_y) = product(range(&cos_lat), range(&sin_lng));
+
+    AABB::from_corners(
+        [min_x - MARGIN, min_y - MARGIN, min_lat.sin() - MARGIN],
+        [max_x + MARGIN, max_y + MARGIN, max_lat.sin() + MARGIN],
+    )
+}
+
+/// Parses the token as a finite float or, if it isn't one, as a date converted into
+/// its Unix timestamp, the way dates are stored in the facet number database.
+fn parse_number_or_date<'t>(token: &'t Token<'t>) -> std::result::Result<f64, FPError<'t>> {
+    match token.parse_finite_float() {
+        Ok(number) => Ok(number),
+        Err(error) => parse_date(token.value()).ok_or(error),
+    }
+}
+
 fn generate_filter_error(
     rtxn: &heed::RoTxn<'_>,
     index: &Index,
@@ -781,7 +1020,7 @@ mod tests {
     use meili_snap::snapshot;
     use roaring::RoaringBitmap;
 
-    use crate::constants::RESERVED_GEO_FIELD_NAME;
+    use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
     use crate::index::tests::TempIndex;
     use crate::{Filter, FilterableAttributesRule};
 
@@ -870,6 +1109,36 @@ mod tests {
         assert_eq!(condition, expected);
     }
 
+    #[test]
+    fn without_attribute() {
+        let filter = Filter::from_array(vec![
+            Either::Right("color = red AND size = M"),
+            Either::Left(vec!["color = blue", "NOT color IN [green, yellow]"]),
+            Either::Left(vec!["color = black", "price < 10"]),
+            Either::Right("_geoRadius(12, 13, 14)"),
+        ])
+        .unwrap()
+        .unwrap();
+
+        let expected = Filter::from_str(
+            "size = M AND (color = black OR price < 10) AND _geoRadius(12, 13, 14)",
+        )
+        .unwrap();
+        assert_eq!(filter.without_attribute("color"), expected);
+
+        let expected = Filter::from_str(
+            "color = red AND (color = blue OR NOT color IN [green, yellow]) AND (color = black OR price < 10) AND _geoRadius(12, 13, 14)",
+        )
+        .unwrap();
+        assert_eq!(filter.without_attribute("size"), expected);
+
+        assert_eq!(filter.without_attribute("price"), Some(filter.clone()));
+
+        let filter = Filter::from_str("(color = red OR color = blue)").unwrap().unwrap();
+        assert_eq!(filter.without_attribute("color"), None);
+        assert_eq!(filter.without_attribute("size"), Some(filter.clone()));
+    }
+
     #[test]
     fn not_filterable() {
         let index = TempIndex::new();
@@ -1049,6 +1318,205 @@ mod tests {
         assert_eq!(documents_ids, vec![0]);
     }
 
+    #[test]
+    fn polygon_xyz_envelope_contains_the_polygon() {
+        let polygons: &[&[[f64; 2]]] = &[
+            &[[0.0, 0.0], [0.0, 10.0], [10.0, 0.0]],
+            &[[-80.0, -170.0], [85.0, -170.0], [85.0, 170.0], [-80.0, 170.0]],
+            &[[20.0, 100.0], [-30.0, 180.0], [-30.0, 80.0]],
+            &[[-90.0, -180.0], [90.0, -180.0], [90.0, 180.0], [-90.0, 180.0]],
+        ];
+
+        for polygon in polygons {
+            let envelope = super::polygon_xyz_envelope(polygon);
+            let (min_lat, max_lat) = polygon.iter().fold((90.0, -90.0), |(min, max), [lat, _]| {
+                (f64::min(min, *lat), f64::max(max, *lat))
+            });
+            let (min_lng, max_lng) =
+                polygon.iter().fold((180.0, -180.0), |(min, max), [_, lng]| {
+                    (f64::min(min, *lng), f64::max(max, *lng))
+                });
+            for i in 0..=50 {
+                for j in 0..=50 {
+                    let lat = min_lat + (max_lat - min_lat) * i as f64 / 50.0;
+                    let lng = min_lng + (max_lng - min_lng) * j as f64 / 50.0;
+                    let point = crate::lat_lng_to_xyz(&[lat, lng]);
+                    assert!(
+                        rstar::Envelope::contains_point(&envelope, &point),
+                        "{polygon:?} doesn't contain [{lat}, {lng}]"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S(RESERVED_GEO_FIELD_NAME)),
+                    FilterableAttributesRule::Field(S("type")),
+                ]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 1, "type": "pizza", RESERVED_GEO_FIELD_NAME: { "lat": 1.0, "lng": 1.0 } },
+              { "id": 2, "type": "ice cream", RESERVED_GEO_FIELD_NAME: { "lat": 1.0, "lng": 5.0 } },
+              { "id": 3, "type": "pizza", RESERVED_GEO_FIELD_NAME: { "lat": 6.0, "lng": 6.0 } },
+              { "id": 4, "type": "pizza", RESERVED_GEO_FIELD_NAME: { "lat": 8.0, "lng": 1.0 } },
+              { "id": 5, "type": "pizza" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // a triangle that contains the first, second and fourth documents but not the third one
+        let triangle = "_geoPolygon([0, 0], [0, 10], [10, 0])";
+        let filter = Filter::from_str(triangle).unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 3]));
+
+        let filter = Filter::from_str(&format!("NOT {triangle}")).unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2, 4]));
+
+        let filter = Filter::from_str(&format!("type = pizza AND {triangle}")).unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 3]));
+
+        let filter =
+            Filter::from_str("type = \"ice cream\" OR _geoPolygon([7, 0], [10, 0], [10, 5])")
+                .unwrap()
+                .unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 3]));
+
+        let filter = Filter::from_str("_geoPolygon([0, 0], [0, 10], [100, 0])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Bad latitude `100`. Latitude must be contained between -90 and 90 degrees."
+            ),
+            "{}",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn geojson() {
+        let index = TempIndex::new();
+
+        // these geometries are indexed once the `_geo` field becomes filterable
+        index
+            .add_documents(documents!([
+              { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 1.0, "lng": 1.0 } },
+              { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "LineString", "coordinates": [[-5, 5], [5, 5]] } },
+              { "id": 3, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[20, 20], [30, 20], [30, 30], [20, 30], [20, 20]]] } },
+            ]))
+            .unwrap();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEO_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [100, 50] } },
+              { "id": 5, RESERVED_GEOJSON_FIELD_NAME: null },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            Filter::from_str(filter).unwrap().unwrap().evaluate(&rtxn, &index).unwrap()
+        };
+
+        // the line crosses the bounding box
+        assert_eq!(evaluate("_geoBoundingBox([6, 1], [4, -1])"), RoaringBitmap::from_iter([1]));
+        // the bounding box is entirely inside the polygon
+        assert_eq!(evaluate("_geoBoundingBox([26, 26], [24, 24])"), RoaringBitmap::from_iter([2]));
+        // the bounding box wraps around the earth
+        assert_eq!(
+            evaluate("_geoBoundingBox([60, -170], [40, 90])"),
+            RoaringBitmap::from_iter([3])
+        );
+
+        // the center of the circle is inside the polygon
+        assert_eq!(evaluate("_geoRadius(25, 25, 1000)"), RoaringBitmap::from_iter([2]));
+        // the end of the line is ~111km away from the center
+        assert_eq!(evaluate("_geoRadius(5, 6, 120000)"), RoaringBitmap::from_iter([1]));
+        assert_eq!(evaluate("_geoRadius(5, 6, 100000)"), RoaringBitmap::new());
+
+        assert_eq!(
+            evaluate("_geoPolygon([0, 0], [0, 12], [12, 0])"),
+            RoaringBitmap::from_iter([0, 1])
+        );
+        assert_eq!(
+            evaluate("NOT _geoPolygon([0, 0], [0, 12], [12, 0])"),
+            RoaringBitmap::from_iter([2, 3, 4])
+        );
+        drop(rtxn);
+
+        // updating and deleting the documents updates the geometries
+        index
+            .add_documents(documents!([
+              { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "MultiPoint", "coordinates": [[25, 25]] } },
+            ]))
+            .unwrap();
+        index.delete_documents(vec![S("3")]);
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("_geoRadius(25, 25, 1000)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap(), RoaringBitmap::from_iter([1]));
+    }
+
+    #[test]
+    fn geojson_filterable_on_its_own() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+              { "id": 0, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [5, 5] } },
+              { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [50, 50] } },
+            ]))
+            .unwrap();
+
+        // making `_geojson` filterable indexes the geometries of the existing documents
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEOJSON_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("_geoRadius(5, 5, 1000)").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap(), RoaringBitmap::from_iter([0]));
+        drop(rtxn);
+
+        // and removing it deletes them
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("id"))]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.geojson_rtree(&rtxn).unwrap().is_none());
+        let filter = Filter::from_str("_geoRadius(5, 5, 1000)").unwrap().unwrap();
+        assert!(filter.evaluate(&rtxn, &index).is_err());
+    }
+
     #[test]
     fn geo_radius_error() {
         let index = TempIndex::new();
@@ -1357,4 +1825,74 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_and_sort_dates() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                    "publishedAt".to_string(),
+                )]);
+                settings.set_sortable_fields(maplit::hashset! { S("publishedAt") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "publishedAt": "2023-12-31T23:59:59Z" },
+                { "id": 1, "publishedAt": "2024-01-01T00:00:00Z" },
+                { "id": 2, "publishedAt": "2024-01-01T12:00:00+02:00" },
+                { "id": 3, "publishedAt": "2024-03-15" },
+                { "id": 4, "publishedAt": "not a date" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("publishedAt > 2024-01-01T00:00:00Z").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2, 3]));
+
+        let filter = Filter::from_str("publishedAt >= 2024-01-01").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2, 3]));
+
+        let filter = Filter::from_str("publishedAt 2024-01-01T00:00:00Z TO 2024-01-01T10:00:00Z")
+            .unwrap()
+            .unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2]));
+
+        let filter = Filter::from_str("publishedAt = 2024-01-01T01:00:00+01:00").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1]));
+
+        let filter = Filter::from_str("publishedAt <= 2024-01-01t10:00:00.5z").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2]));
+
+        // The filter syntax only accepts the datetimes that are stored as dates
+        let error = Filter::from_str("publishedAt > 2024-01-01T00:00Z").unwrap_err();
+        assert!(
+            error.to_string().starts_with("Malformed datetime: `2024-01-01T00:00Z`"),
+            "{error}"
+        );
+        let error = Filter::from_str("publishedAt > 2024-01-01T00:00:00").unwrap_err();
+        assert!(
+            error.to_string().starts_with("Malformed datetime: `2024-01-01T00:00:00`"),
+            "{error}"
+        );
+
+        let filter = Filter::from_str("publishedAt = 'not a date'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([4]));
+
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.sort_criteria(vec![crate::AscDesc::Desc(crate::Member::Field(S("publishedAt")))]);
+        let crate::SearchResult { documents_ids, .. } = search.execute().unwrap();
+        assert_eq!(documents_ids, vec![3, 2, 1, 0, 4]);
+    }
 }
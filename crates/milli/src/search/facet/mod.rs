@@ -4,7 +4,10 @@ use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{
+    ExtendedFacetStats, FacetDistribution, FacetRange, FacetRangeBucket, FacetRanges, FacetStat,
+    OrderBy, DEFAULT_VALUES_PER_FACET,
+};
 pub use self::filter::{BadGeoError, Filter};
 pub use self::search::{FacetValueHit, SearchForFacetValues};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 
 use itertools::Itertools;
 use roaring::RoaringBitmap;
@@ -17,6 +19,34 @@ struct ScoreWithRatioResult {
 
 type ScoreWithRatio = (Vec<ScoreDetails>, f32);
 
+/// How the keyword and semantic results of a hybrid search are merged together.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HybridFusion {
+    /// Compare the ranking scores of the hits, weighted by the semantic ratio.
+    #[default]
+    Score,
+    /// Compare the ranks of the hits in their result lists, weighted by the semantic ratio.
+    ///
+    /// A hit at the zero-based `rank` of a list contributes `ratio / (k + rank + 1)` to its fused score.
+    ReciprocalRank { k: u32 },
+}
+
+impl HybridFusion {
+    /// The `k` constant used by reciprocal rank fusion when none is specified.
+    pub const DEFAULT_RRF_K: u32 = 60;
+}
+
+/// The contribution of a hit at the zero-based `rank` of a result list to its reciprocal rank fusion score.
+pub fn reciprocal_rank(k: u32, rank: usize) -> f64 {
+    1.0 / (k as f64 + rank as f64 + 1.0)
+}
+
+#[derive(Clone, Copy)]
+enum ResultSource {
+    Semantic,
+    Keyword,
+}
+
 #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
 fn compare_scores(
     &(ref left_scores, left_ratio): &ScoreWithRatio,
@@ -92,11 +122,6 @@ impl ScoreWithRatioResult {
         from: usize,
         length: usize,
     ) -> (SearchResult, u32) {
-        #[derive(Clone, Copy)]
-        enum ResultSource {
-            Semantic,
-            Keyword,
-        }
         let mut semantic_hit_count = 0;
 
         let mut documents_ids = Vec::with_capacity(
@@ -149,11 +174,103 @@ impl ScoreWithRatioResult {
             semantic_hit_count,
         )
     }
+
+    /// Merges the results by reciprocal rank rather than by score.
+    ///
+    /// A document returned by both searches sums its contributions, and keeps the scores of the search
+    /// that contributed the most.
+    #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
+    fn merge_by_rank(
+        vector_results: Self,
+        keyword_results: Self,
+        k: u32,
+        from: usize,
+        length: usize,
+    ) -> (SearchResult, u32) {
+        struct FusedHit {
+            docid: u32,
+            fused_score: f64,
+            best_contribution: f64,
+            scores: Vec<ScoreDetails>,
+            source: ResultSource,
+        }
+
+        let mut hits: Vec<FusedHit> = Vec::with_capacity(
+            vector_results.document_scores.len() + keyword_results.document_scores.len(),
+        );
+        let mut positions = HashMap::with_capacity(hits.capacity());
+
+        for (document_scores, source) in [
+            (vector_results.document_scores, ResultSource::Semantic),
+            (keyword_results.document_scores, ResultSource::Keyword),
+        ] {
+            for (rank, (docid, (scores, ratio))) in document_scores.into_iter().enumerate() {
+                let contribution = ratio as f64 * reciprocal_rank(k, rank);
+                match positions.entry(docid) {
+                    Entry::Occupied(entry) => {
+                        let hit: &mut FusedHit = &mut hits[*entry.get()];
+                        hit.fused_score += contribution;
+                        if contribution > hit.best_contribution {
+                            hit.best_contribution = contribution;
+                            hit.scores = scores;
+                            hit.source = source;
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(hits.len());
+                        hits.push(FusedHit {
+                            docid,
+                            fused_score: contribution,
+                            best_contribution: contribution,
+                            scores,
+                            source,
+                        });
+                    }
+                }
+            }
+        }
+
+        // the sort is stable, so semantic hits go first in case of a tie
+        hits.sort_by(|left, right| right.fused_score.total_cmp(&left.fused_score));
+
+        let mut semantic_hit_count = 0;
+        let mut documents_ids = Vec::with_capacity(length.min(hits.len()));
+        let mut document_scores = Vec::with_capacity(length.min(hits.len()));
+        for FusedHit { docid, scores, source, .. } in hits.into_iter().skip(from).take(length) {
+            if let ResultSource::Semantic = source {
+                semantic_hit_count += 1;
+            }
+            documents_ids.push(docid);
+            document_scores.push(scores);
+        }
+
+        (
+            SearchResult {
+                matching_words: keyword_results.matching_words,
+                candidates: vector_results.candidates | keyword_results.candidates,
+                documents_ids,
+                document_scores,
+                degraded: vector_results.degraded | keyword_results.degraded,
+                used_negative_operator: vector_results.used_negative_operator
+                    | keyword_results.used_negative_operator,
+            },
+            semantic_hit_count,
+        )
+    }
 }
 
 impl Search<'_> {
     #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
-    pub fn execute_hybrid(&self, semantic_ratio: f32) -> Result<(SearchResult, Option<u32>)> {
+    pub fn execute_hybrid(
+        &self,
+        semantic_ratio: f32,
+        fusion: HybridFusion,
+    ) -> Result<(SearchResult, Option<u32>)> {
+        if let Some(rerank) = &self.rerank {
+            return self
+                .execute_reranked(rerank, |search| search.execute_hybrid(semantic_ratio, fusion));
+        }
+
         // TODO: find classier way to achieve that than to reset vector and query params
         // create separate keyword and semantic searches
         let mut search = Search {
@@ -175,13 +292,17 @@ impl Search<'_> {
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
+            rerank: None,
         };
 
         let semantic = search.semantic.take();
         let keyword_results = search.execute()?;
 
-        // completely skip semantic search if the results of the keyword search are good enough
-        if self.results_good_enough(&keyword_results, semantic_ratio) {
+        // completely skip semantic search if the results of the keyword search are good enough.
+        // This doesn't hold when fusing by rank, as the best semantic hits always rank high.
+        if fusion == HybridFusion::Score
+            && self.results_good_enough(&keyword_results, semantic_ratio)
+        {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         }
 
@@ -190,7 +311,9 @@ impl Search<'_> {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         };
         // no embedder, no semantic search
-        let Some(SemanticSearch { vector, embedder_name, embedder, quantized }) = semantic else {
+        let Some(SemanticSearch { vector, embedder_name, embedder, quantization, distance }) =
+            semantic
+        else {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         };
 
@@ -217,8 +340,13 @@ impl Search<'_> {
             }
         };
 
-        search.semantic =
-            Some(SemanticSearch { vector: Some(vector_query), embedder_name, embedder, quantized });
+        search.semantic = Some(SemanticSearch {
+            vector: Some(vector_query),
+            embedder_name,
+            embedder,
+            quantization,
+            distance,
+        });
 
         // TODO: would be better to have two distinct functions at this point
         let vector_results = search.execute()?;
@@ -226,8 +354,21 @@ impl Search<'_> {
         let keyword_results = ScoreWithRatioResult::new(keyword_results, 1.0 - semantic_ratio);
         let vector_results = ScoreWithRatioResult::new(vector_results, semantic_ratio);
 
-        let (merge_results, semantic_hit_count) =
-            ScoreWithRatioResult::merge(vector_results, keyword_results, self.offset, self.limit);
+        let (merge_results, semantic_hit_count) = match fusion {
+            HybridFusion::Score => ScoreWithRatioResult::merge(
+                vector_results,
+                keyword_results,
+                self.offset,
+                self.limit,
+            ),
+            HybridFusion::ReciprocalRank { k } => ScoreWithRatioResult::merge_by_rank(
+                vector_results,
+                keyword_results,
+                k,
+                self.offset,
+                self.limit,
+            ),
+        };
         assert!(merge_results.documents_ids.len() <= self.limit);
         Ok((merge_results, Some(semantic_hit_count)))
     }
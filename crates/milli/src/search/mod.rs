@@ -9,9 +9,10 @@ use roaring::bitmap::RoaringBitmap;
 pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
 use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats};
+pub use self::rerank::RerankSearch;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
-use crate::vector::Embedder;
+use crate::vector::{Embedder, Quantization, VectorDistance};
 use crate::{
     execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
     Result, SearchContext, TimeBudget, UserError,
@@ -26,6 +27,7 @@ pub mod facet;
 mod fst_utils;
 pub mod hybrid;
 pub mod new;
+mod rerank;
 pub mod similar;
 
 #[derive(Debug, Clone)]
@@ -33,7 +35,8 @@ pub struct SemanticSearch {
     vector: Option<Vec<f32>>,
     embedder_name: String,
     embedder: Arc<Embedder>,
-    quantized: bool,
+    quantization: Quantization,
+    distance: VectorDistance,
 }
 
 pub struct Search<'a> {
@@ -56,6 +59,7 @@ pub struct Search<'a> {
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
+    rerank: Option<RerankSearch>,
 }
 
 impl<'a> Search<'a> {
@@ -79,6 +83,7 @@ impl<'a> Search<'a> {
             locales: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            rerank: None,
         }
     }
 
@@ -91,10 +96,12 @@ impl<'a> Search<'a> {
         &mut self,
         embedder_name: String,
         embedder: Arc<Embedder>,
-        quantized: bool,
+        quantization: Quantization,
+        distance: VectorDistance,
         vector: Option<Vec<f32>>,
     ) -> &mut Search<'a> {
-        self.semantic = Some(SemanticSearch { embedder_name, embedder, quantized, vector });
+        self.semantic =
+            Some(SemanticSearch { embedder_name, embedder, quantization, distance, vector });
         self
     }
 
@@ -186,7 +193,48 @@ impl<'a> Search<'a> {
         }
     }
 
+    /// Computes the candidates of the search as if it had no filter.
+    ///
+    /// The vector search is never executed, as it matches all the documents.
+    pub fn execute_for_unfiltered_candidates(
+        &self,
+        has_vector_search: bool,
+    ) -> Result<RoaringBitmap> {
+        if has_vector_search {
+            return Ok(self.index.documents_ids(self.rtxn)?);
+        }
+
+        let search = Search {
+            query: self.query.clone(),
+            filter: None,
+            offset: 0,
+            limit: 0,
+            sort_criteria: None,
+            distinct: self.distinct.clone(),
+            searchable_attributes: self.searchable_attributes,
+            geo_param: self.geo_param,
+            terms_matching_strategy: self.terms_matching_strategy,
+            scoring_strategy: self.scoring_strategy,
+            words_limit: self.words_limit,
+            exhaustive_number_hits: self.exhaustive_number_hits,
+            rtxn: self.rtxn,
+            index: self.index,
+            semantic: None,
+            time_budget: self.time_budget.clone(),
+            ranking_score_threshold: self.ranking_score_threshold,
+            locales: self.locales.clone(),
+            rerank: None,
+        };
+        Ok(search.execute()?.candidates)
+    }
+
     pub fn execute(&self) -> Result<SearchResult> {
+        if let Some(rerank) = &self.rerank {
+            return self
+                .execute_reranked(rerank, |search| Ok((search.execute()?, None)))
+                .map(|(result, _)| result);
+        }
+
         let mut ctx = SearchContext::new(self.index, self.rtxn)?;
 
         if let Some(searchable_attributes) = self.searchable_attributes {
@@ -230,24 +278,29 @@ impl<'a> Search<'a> {
             degraded,
             used_negative_operator,
         } = match self.semantic.as_ref() {
-            Some(SemanticSearch { vector: Some(vector), embedder_name, embedder, quantized }) => {
-                execute_vector_search(
-                    &mut ctx,
-                    vector,
-                    self.scoring_strategy,
-                    universe,
-                    &self.sort_criteria,
-                    &self.distinct,
-                    self.geo_param,
-                    self.offset,
-                    self.limit,
-                    embedder_name,
-                    embedder,
-                    *quantized,
-                    self.time_budget.clone(),
-                    self.ranking_score_threshold,
-                )?
-            }
+            Some(SemanticSearch {
+                vector: Some(vector),
+                embedder_name,
+                embedder,
+                quantization,
+                distance,
+            }) => execute_vector_search(
+                &mut ctx,
+                vector,
+                self.scoring_strategy,
+                universe,
+                &self.sort_criteria,
+                &self.distinct,
+                self.geo_param,
+                self.offset,
+                self.limit,
+                embedder_name,
+                embedder,
+                *quantization,
+                *distance,
+                self.time_budget.clone(),
+                self.ranking_score_threshold,
+            )?,
             _ => execute_search(
                 &mut ctx,
                 self.query.as_deref(),
@@ -313,6 +366,7 @@ impl fmt::Debug for Search<'_> {
             time_budget,
             ranking_score_threshold,
             locales,
+            rerank,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -334,6 +388,7 @@ impl fmt::Debug for Search<'_> {
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
+            .field("rerank.reranker_name", &rerank.as_ref().map(|rerank| &rerank.reranker_name))
             .finish()
     }
 }
@@ -7,10 +7,11 @@ use std::collections::VecDeque;
 use super::facet_string_values;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
+use crate::index::{db_name, main_key};
 use crate::score_details::{self, ScoreDetails};
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
-    SearchLogger,
+    distance_between_two_points, lat_lng_to_xyz, GeoJsonShape, GeoPoint, Index, InternalError,
+    Result, SearchContext, SearchLogger,
 };
 
 const FID_SIZE: usize = 2;
@@ -94,9 +95,13 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
     point: [f64; 2],
     field_ids: Option<[u16; 2]>,
     rtree: Option<RTree<GeoPoint>>,
+    geojson_rtree: Option<RTree<GeoJsonShape>>,
 
     cached_sorted_docids: VecDeque<(u32, [f64; 2])>,
+    // The documents with a `_geo` point, a `_geojson` geometry, or both
     geo_candidates: RoaringBitmap,
+    geo_point_candidates: RoaringBitmap,
+    geojson_candidates: RoaringBitmap,
 
     // Limit the number of docs in a single bucket to avoid unexpectedly large overhead
     max_bucket_size: u64,
@@ -108,6 +113,7 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
     pub fn new(
         parameter: Parameter,
         geo_faceted_docids: RoaringBitmap,
+        geojson_faceted_docids: RoaringBitmap,
         point: [f64; 2],
         ascending: bool,
     ) -> Result<Self> {
@@ -117,9 +123,12 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             strategy,
             ascending,
             point,
-            geo_candidates: geo_faceted_docids,
+            geo_candidates: &geo_faceted_docids | &geojson_faceted_docids,
+            geo_point_candidates: geo_faceted_docids,
+            geojson_candidates: geojson_faceted_docids,
             field_ids: None,
             rtree: None,
+            geojson_rtree: None,
             cached_sorted_docids: VecDeque::new(),
             max_bucket_size,
             distance_error_margin,
@@ -133,9 +142,32 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         ctx: &mut SearchContext<'_>,
         geo_candidates: &RoaringBitmap,
     ) -> Result<()> {
-        debug_assert!(self.field_ids.is_some(), "fill_buffer can't be called without the lat&lng");
         debug_assert!(self.cached_sorted_docids.is_empty());
 
+        let point_candidates = geo_candidates & &self.geo_point_candidates;
+        let points_exhausted = if point_candidates.is_empty() {
+            true
+        } else {
+            self.fill_buffer_with_points(ctx, &point_candidates)?
+        };
+
+        let geojson_candidates = geo_candidates & &self.geojson_candidates;
+        if !geojson_candidates.is_empty() {
+            self.fill_buffer_with_geojson(ctx, &geojson_candidates, points_exhausted)?;
+        }
+
+        Ok(())
+    }
+
+    /// Refill the internal buffer with the `_geo` points of the candidates.
+    /// Returns `false` if some candidates could not fit in the buffer.
+    fn fill_buffer_with_points(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        geo_candidates: &RoaringBitmap,
+    ) -> Result<bool> {
+        debug_assert!(self.field_ids.is_some(), "fill_buffer can't be called without the lat&lng");
+
         // lazily initialize the rtree if needed by the strategy, and cache it in `self.rtree`
         let rtree = if self.strategy.use_rtree(geo_candidates.len() as usize) {
             if let Some(rtree) = self.rtree.as_ref() {
@@ -152,7 +184,7 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         };
 
         let cache_size = self.strategy.cache_size();
-        if let Some(rtree) = rtree {
+        let exhausted = if let Some(rtree) = rtree {
             if self.ascending {
                 let point = lat_lng_to_xyz(&self.point);
                 for point in rtree.nearest_neighbor_iter(&point) {
@@ -176,6 +208,7 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
                     }
                 }
             }
+            self.cached_sorted_docids.len() < cache_size
         } else {
             // the iterative version
             let [lat, lng] = self.field_ids.unwrap();
@@ -188,8 +221,66 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             documents
                 .sort_by_cached_key(|(_, p)| distance_between_two_points(&self.point, p) as usize);
             self.cached_sorted_docids.extend(documents);
+            true
         };
 
+        Ok(exhausted)
+    }
+
+    /// Merge the nearest points of the `_geojson` geometries of the candidates in the internal
+    /// buffer, which is sorted by ascending distance to the target point.
+    ///
+    /// When the `_geo` points didn't all fit in the buffer, only the geometries that are in
+    /// the range of distances of the buffer are inserted, the others are left for the next refill.
+    fn fill_buffer_with_geojson(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        geojson_candidates: &RoaringBitmap,
+        points_exhausted: bool,
+    ) -> Result<()> {
+        let rtree = match self.geojson_rtree.as_ref() {
+            Some(rtree) => rtree,
+            None => {
+                let rtree = ctx.index.geojson_rtree(ctx.txn)?.ok_or(
+                    InternalError::DatabaseMissingEntry {
+                        db_name: db_name::MAIN,
+                        key: Some(main_key::GEOJSON_RTREE_KEY),
+                    },
+                )?;
+                &*self.geojson_rtree.insert(rtree)
+            }
+        };
+
+        let target = self.point;
+        let distance = |point: &[f64; 2]| distance_between_two_points(&target, point);
+        let (min_distance, max_distance) =
+            match (self.cached_sorted_docids.front(), self.cached_sorted_docids.back()) {
+                (Some((_, first)), Some((_, last))) if !points_exhausted => {
+                    (distance(first), distance(last))
+                }
+                _ => (f64::NEG_INFINITY, f64::INFINITY),
+            };
+
+        let ascending = self.ascending;
+        let geometries = rtree
+            .iter()
+            .filter(|shape| geojson_candidates.contains(shape.data.0))
+            .map(|shape| {
+                let nearest = shape.data.1.nearest_point(&target);
+                (shape.data.0, nearest, distance(&nearest))
+            })
+            // the points that are not in the buffer are further away in the sorting order
+            .filter(|(_, _, d)| if ascending { *d <= max_distance } else { *d >= min_distance });
+
+        let mut documents: Vec<_> = self
+            .cached_sorted_docids
+            .drain(..)
+            .map(|(id, point)| (id, point, distance(&point)))
+            .chain(geometries)
+            .collect();
+        documents.sort_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+        self.cached_sorted_docids.extend(documents.into_iter().map(|(id, point, _)| (id, point)));
+
         Ok(())
     }
 }
@@ -248,10 +339,12 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
             return Ok(());
         }
 
-        let fid_map = ctx.index.fields_ids_map(ctx.txn)?;
-        let lat = fid_map.id("_geo.lat").expect("geo candidates but no fid for lat");
-        let lng = fid_map.id("_geo.lng").expect("geo candidates but no fid for lng");
-        self.field_ids = Some([lat, lng]);
+        if !geo_candidates.is_disjoint(&self.geo_point_candidates) {
+            let fid_map = ctx.index.fields_ids_map(ctx.txn)?;
+            let lat = fid_map.id("_geo.lat").expect("geo candidates but no fid for lat");
+            let lng = fid_map.id("_geo.lng").expect("geo candidates but no fid for lng");
+            self.field_ids = Some([lat, lng]);
+        }
         self.fill_buffer(ctx, &geo_candidates)?;
         Ok(())
     }
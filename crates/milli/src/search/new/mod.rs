@@ -36,7 +36,8 @@ pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
 use query_term::{
-    located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
+    add_analyzed_words, located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase,
+    QueryTerm,
 };
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
@@ -57,10 +58,10 @@ use crate::index::PrefixSearch;
 use crate::localized_attributes_rules::LocalizedFieldIds;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
-use crate::vector::Embedder;
+use crate::vector::{Embedder, Quantization, VectorDistance};
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AnalyzerRule, AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, Stemmers,
+    TermsMatchingStrategy, TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -75,6 +76,7 @@ pub struct SearchContext<'ctx> {
     pub restricted_fids: Option<RestrictedFids>,
     pub prefix_search: PrefixSearch,
     pub vector_store_stats: Option<VectorStoreStats>,
+    pub stemmers: Stemmers,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -105,6 +107,7 @@ impl<'ctx> SearchContext<'ctx> {
             restricted_fids: None,
             prefix_search,
             vector_store_stats: None,
+            stemmers: Stemmers::default(),
         })
     }
 
@@ -355,7 +358,8 @@ fn get_ranking_rules_for_vector<'ctx>(
     target: &[f32],
     embedder_name: &str,
     embedder: &Embedder,
-    quantized: bool,
+    quantization: Quantization,
+    distance: VectorDistance,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     // query graph search
 
@@ -383,7 +387,8 @@ fn get_ranking_rules_for_vector<'ctx>(
                         limit_plus_offset,
                         embedder_name,
                         embedder,
-                        quantized,
+                        quantization,
+                        distance,
                     )?;
                     ranking_rules.push(Box::new(vector_sort));
                     vector = true;
@@ -563,9 +568,11 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     continue;
                 }
                 let geo_faceted_docids = ctx.index.geo_faceted_documents_ids(ctx.txn)?;
+                let geojson_faceted_docids = ctx.index.geojson_faceted_documents_ids(ctx.txn)?;
                 ranking_rules.push(Box::new(GeoSort::new(
                     geo_param,
                     geo_faceted_docids,
+                    geojson_faceted_docids,
                     point,
                     true,
                 )?));
@@ -575,9 +582,11 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     continue;
                 }
                 let geo_faceted_docids = ctx.index.geo_faceted_documents_ids(ctx.txn)?;
+                let geojson_faceted_docids = ctx.index.geojson_faceted_documents_ids(ctx.txn)?;
                 ranking_rules.push(Box::new(GeoSort::new(
                     geo_param,
                     geo_faceted_docids,
+                    geojson_faceted_docids,
                     point,
                     false,
                 )?));
@@ -613,7 +622,8 @@ pub fn execute_vector_search(
     length: usize,
     embedder_name: &str,
     embedder: &Embedder,
-    quantized: bool,
+    quantization: Quantization,
+    distance: VectorDistance,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
 ) -> Result<PartialSearchResult> {
@@ -629,7 +639,8 @@ pub fn execute_vector_search(
         vector,
         embedder_name,
         embedder,
-        quantized,
+        quantization,
+        distance,
     )?;
 
     let mut placeholder_search_logger = logger::DefaultSearchLogger;
@@ -711,6 +722,29 @@ pub fn execute_search(
             tokbuilder.words_dict(dictionary);
         }
 
+        let localized_attributes_rules = ctx.index.localized_attributes_rules(ctx.txn)?;
+        let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+        let searchable_fields = ctx.index.searchable_fields_ids(ctx.txn)?;
+
+        let localized_fields = match &ctx.restricted_fids {
+            // if AttributeToSearchOn is set, use the restricted list of ids
+            Some(restricted_fids) => {
+                let iter = restricted_fids
+                    .exact
+                    .iter()
+                    .chain(restricted_fids.tolerant.iter())
+                    .map(|(fid, _)| *fid);
+
+                LocalizedFieldIds::new(&localized_attributes_rules, &fields_ids_map, iter)
+            }
+            // Otherwise use the full list of ids coming from the index searchable fields
+            None => LocalizedFieldIds::new(
+                &localized_attributes_rules,
+                &fields_ids_map,
+                searchable_fields.into_iter(),
+            ),
+        };
+
         let db_locales;
         match locales {
             Some(locales) => {
@@ -720,29 +754,6 @@ pub fn execute_search(
             }
             None => {
                 // If no locales are specified, we use the locales specified in the localized attributes rules
-                let localized_attributes_rules = ctx.index.localized_attributes_rules(ctx.txn)?;
-                let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
-                let searchable_fields = ctx.index.searchable_fields_ids(ctx.txn)?;
-
-                let localized_fields = match &ctx.restricted_fids {
-                    // if AttributeToSearchOn is set, use the restricted list of ids
-                    Some(restricted_fids) => {
-                        let iter = restricted_fids
-                            .exact
-                            .iter()
-                            .chain(restricted_fids.tolerant.iter())
-                            .map(|(fid, _)| *fid);
-
-                        LocalizedFieldIds::new(&localized_attributes_rules, &fields_ids_map, iter)
-                    }
-                    // Otherwise use the full list of ids coming from the index searchable fields
-                    None => LocalizedFieldIds::new(
-                        &localized_attributes_rules,
-                        &fields_ids_map,
-                        searchable_fields.into_iter(),
-                    ),
-                };
-
                 db_locales = localized_fields.all_locales();
                 if !db_locales.is_empty() {
                     tokbuilder.allow_list(&db_locales);
@@ -750,6 +761,18 @@ pub fn execute_search(
             }
         };
 
+        // The query words are stemmed in the languages of the stemmed searched attributes,
+        // as long as the query is not restricted to other locales.
+        let stemming_locales: Vec<_> = localized_fields
+            .stemming_locales()
+            .iter()
+            .copied()
+            .filter(|locale| {
+                locales.is_none_or(|locales| locales.is_empty() || locales.contains(locale))
+            })
+            .collect();
+        ctx.stemmers = Stemmers::new(&stemming_locales);
+
         let tokenizer = tokbuilder.build();
         drop(entered);
 
@@ -762,6 +785,31 @@ pub fn execute_search(
             located_query_terms_from_tokens(ctx, tokens, words_limit)?;
         used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
 
+        // The analyzed attributes don't contain the words produced by the tokenizer
+        // but the ones produced by their analyzers, so we search for both.
+        let mut span_terms = Vec::new();
+        let analyzers = ctx.index.analyzers(ctx.txn)?;
+        if !analyzers.is_empty() {
+            let searched_fids: Vec<_> = match &ctx.restricted_fids {
+                Some(restricted_fids) => restricted_fids
+                    .exact
+                    .iter()
+                    .chain(restricted_fids.tolerant.iter())
+                    .map(|(fid, _)| *fid)
+                    .collect(),
+                None => ctx.index.searchable_fields_ids(ctx.txn)?,
+            };
+            let mut searched_analyzers = Vec::new();
+            for name in searched_fids.into_iter().filter_map(|fid| fields_ids_map.name(fid)) {
+                if let Some(analyzer) = AnalyzerRule::find(&analyzers, name) {
+                    if !searched_analyzers.contains(&analyzer) {
+                        searched_analyzers.push(analyzer);
+                    }
+                }
+            }
+            span_terms = add_analyzed_words(ctx, query, &searched_analyzers, &query_terms)?;
+        }
+
         let ignored_documents = resolve_negative_words(ctx, Some(&universe), &negative_words)?;
         let ignored_phrases = resolve_negative_phrases(ctx, &negative_phrases)?;
 
@@ -772,14 +820,15 @@ pub fn execute_search(
             // Do a placeholder search instead
             None
         } else {
-            Some(query_terms)
+            Some((query_terms, span_terms))
         }
     } else {
         None
     };
 
-    let bucket_sort_output = if let Some(query_terms) = query_terms {
-        let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
+    let bucket_sort_output = if let Some((query_terms, span_terms)) = query_terms {
+        let (graph, new_located_query_terms) =
+            QueryGraph::from_query(ctx, &query_terms, &span_terms)?;
         located_query_terms = Some(new_located_query_terms);
 
         let ranking_rules = get_ranking_rules_for_query_graph_search(
@@ -888,7 +937,7 @@ fn check_sort_criteria(
                 }
                 .into());
             }
-            Member::Geo(_) if !sortable_fields.contains(RESERVED_GEO_FIELD_NAME) => {
+            Member::Geo(_) if !ctx.index.is_geojson_sorting_enabled(ctx.txn)? => {
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
 
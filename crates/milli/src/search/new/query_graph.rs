@@ -96,6 +96,8 @@ impl QueryGraph {
         ctx: &mut SearchContext<'_>,
         // The terms here must be consecutive
         terms: &[LocatedQueryTerm],
+        // Additional terms spanning the positions of several consecutive terms
+        span_terms: &[LocatedQueryTerm],
     ) -> Result<(QueryGraph, Vec<LocatedQueryTerm>)> {
         let mut new_located_query_terms = terms.to_vec();
 
@@ -158,6 +160,25 @@ impl QueryGraph {
             (prev0, prev1, prev2) = (new_nodes, prev0, prev1);
         }
 
+        for span_term in span_terms {
+            let mut covered = terms.iter().enumerate().filter(|(_, term)| {
+                span_term.positions.contains(term.positions.start())
+                    && span_term.positions.contains(term.positions.end())
+            });
+            let Some((first, _)) = covered.next() else { continue };
+            let last = covered.last().map_or(first, |(last, _)| last);
+
+            new_located_query_terms.push(span_term.clone());
+            add_node(
+                &mut nodes_data,
+                QueryNodeData::Term(LocatedQueryTermSubset {
+                    term_subset: QueryTermSubset::full(span_term.value),
+                    positions: span_term.positions.clone(),
+                    term_ids: first as u8..=last as u8,
+                }),
+            );
+        }
+
         let root_node = Interned::from_raw(root_node);
         let end_node = Interned::from_raw(end_node);
         let mut nodes = FixedSizeInterner::new(
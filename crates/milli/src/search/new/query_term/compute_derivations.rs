@@ -14,7 +14,7 @@ use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
-use crate::{Result, MAX_WORD_LENGTH};
+use crate::{Result, MAX_SYNONYM_PENALTY, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NumberOfTypos {
@@ -183,6 +183,8 @@ pub fn partially_initialized_term_from_word(
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                one_typo_synonyms: <_>::default(),
+                two_typo_synonyms: <_>::default(),
             }
         });
     }
@@ -220,23 +222,7 @@ pub fn partially_initialized_term_from_word(
             }
         })?;
     }
-    let synonyms = ctx.index.synonyms(ctx.txn)?;
-    let mut synonym_word_count = 0;
-    let synonyms = synonyms
-        .get(&vec![word.to_owned()])
-        .cloned()
-        .unwrap_or_default()
-        .into_iter()
-        .take(limits::MAX_SYNONYM_PHRASE_COUNT)
-        .filter_map(|words| {
-            if synonym_word_count + words.len() > limits::MAX_SYNONYM_WORD_COUNT {
-                return None;
-            }
-            synonym_word_count += words.len();
-            let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            Some(ctx.phrase_interner.insert(Phrase { words }))
-        })
-        .collect();
+    let [synonyms, one_typo_synonyms, two_typo_synonyms] = find_synonyms(ctx, &[word.to_owned()])?;
     let zero_typo =
         ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
 
@@ -248,9 +234,37 @@ pub fn partially_initialized_term_from_word(
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        one_typo_synonyms,
+        two_typo_synonyms,
     })
 }
 
+/// Find the synonyms of the given words, grouped by their penalty.
+///
+/// The synonyms defined by the legacy `synonyms` setting have no penalty.
+pub(super) fn find_synonyms(
+    ctx: &mut SearchContext<'_>,
+    words: &[String],
+) -> Result<[BTreeSet<Interned<Phrase>>; MAX_SYNONYM_PENALTY as usize + 1]> {
+    let synonyms = ctx.index.synonyms(ctx.txn)?.remove(words).unwrap_or_default();
+    let weighted_synonyms = ctx.index.weighted_synonyms(ctx.txn)?.remove(words).unwrap_or_default();
+
+    let mut phrases: [BTreeSet<_>; MAX_SYNONYM_PENALTY as usize + 1] = Default::default();
+    let mut synonym_word_count = 0;
+    let synonyms = synonyms.into_iter().map(|words| (words, 0)).chain(weighted_synonyms);
+    for (words, penalty) in synonyms.take(limits::MAX_SYNONYM_PHRASE_COUNT) {
+        if synonym_word_count + words.len() > limits::MAX_SYNONYM_WORD_COUNT {
+            continue;
+        }
+        synonym_word_count += words.len();
+        let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
+        let phrase = ctx.phrase_interner.insert(Phrase { words });
+        phrases[penalty.min(MAX_SYNONYM_PENALTY) as usize].insert(phrase);
+    }
+
+    Ok(phrases)
+}
+
 fn find_split_words(ctx: &mut SearchContext<'_>, word: &str) -> Result<Option<Interned<Phrase>>> {
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
         Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
@@ -259,11 +273,24 @@ fn find_split_words(ctx: &mut SearchContext<'_>, word: &str) -> Result<Option<In
     }
 }
 
+/// Find the stems of the word that are in the index,
+/// in the languages stemmed by the searched attributes.
+fn find_stems(ctx: &mut SearchContext<'_>, word: &str) -> Result<BTreeSet<Interned<String>>> {
+    let mut stems = BTreeSet::new();
+    for stem in ctx.stemmers.stems(word) {
+        if ctx.index.contains_word(ctx.txn, &stem)? {
+            stems.insert(ctx.word_interner.insert(stem));
+        }
+    }
+    Ok(stems)
+}
+
 impl Interned<QueryTerm> {
     fn initialize_one_typo_subterm(self, ctx: &mut SearchContext<'_>) -> Result<()> {
         let self_mut = ctx.term_interner.get_mut(self);
 
         let allows_split_words = self_mut.allows_split_words();
+        let allows_stems = allows_split_words && self_mut.ngram_words.is_none();
         let QueryTerm {
             original,
             is_prefix,
@@ -298,6 +325,13 @@ impl Interned<QueryTerm> {
             None
         };
 
+        let stems = if allows_stems {
+            let original_str = ctx.word_interner.get(original).to_owned();
+            find_stems(ctx, original_str.as_str())?
+        } else {
+            BTreeSet::new()
+        };
+
         let self_mut = ctx.term_interner.get_mut(self);
 
         // Only add the split words to the derivations if:
@@ -315,7 +349,7 @@ impl Interned<QueryTerm> {
         } else {
             split_words
         };
-        let one_typo = OneTypoTerm { split_words, one_typo: one_typo_words };
+        let one_typo = OneTypoTerm { split_words, one_typo: one_typo_words, stems };
 
         self_mut.one_typo = Lazy::Init(one_typo);
 
@@ -323,6 +357,7 @@ impl Interned<QueryTerm> {
     }
     fn initialize_one_and_two_typo_subterm(self, ctx: &mut SearchContext<'_>) -> Result<()> {
         let self_mut = ctx.term_interner.get_mut(self);
+        let allows_stems = self_mut.ngram_words.is_none();
         let QueryTerm {
             original,
             is_prefix,
@@ -368,9 +403,11 @@ impl Interned<QueryTerm> {
         }
 
         let split_words = find_split_words(ctx, original_str.as_str())?;
+        let stems =
+            if allows_stems { find_stems(ctx, original_str.as_str())? } else { BTreeSet::new() };
         let self_mut = ctx.term_interner.get_mut(self);
 
-        let one_typo = OneTypoTerm { one_typo: one_typo_words, split_words };
+        let one_typo = OneTypoTerm { one_typo: one_typo_words, split_words, stems };
 
         let two_typo = TwoTypoTerm { two_typos: two_typo_words };
 
@@ -10,7 +10,8 @@ use std::ops::RangeInclusive;
 use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
 pub use parse_query::{
-    located_query_terms_from_tokens, make_ngram, number_of_typos_allowed, ExtractedTokens,
+    add_analyzed_words, located_query_terms_from_tokens, make_ngram, number_of_typos_allowed,
+    ExtractedTokens,
 };
 pub use phrase::Phrase;
 
@@ -51,6 +52,10 @@ pub struct QueryTerm {
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    /// The synonyms ranked as if they were one typo away from the original term
+    one_typo_synonyms: BTreeSet<Interned<Phrase>>,
+    /// The synonyms ranked as if they were two typos away from the original term
+    two_typo_synonyms: BTreeSet<Interned<Phrase>>,
 }
 
 // SubTerms will be in a dedup interner
@@ -73,6 +78,8 @@ struct OneTypoTerm {
     split_words: Option<Interned<Phrase>>,
     /// Words that are 1 typo away from the original word
     one_typo: BTreeSet<Interned<String>>,
+    /// The stems of the original word in the languages stemmed by the searched attributes
+    stems: BTreeSet<Interned<String>>,
 }
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 struct TwoTypoTerm {
@@ -260,18 +267,22 @@ impl QueryTermSubset {
 
         match &self.one_typo_subset {
             NTypoTermSubset::All => {
-                let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words: _, one_typo, stems }) =
+                    &original.one_typo
                 else {
                     panic!()
                 };
-                result.extend(one_typo.iter().copied().map(Word::Derived))
+                result.extend(one_typo.iter().copied().map(Word::Derived));
+                result.extend(stems.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
-                let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words: _, one_typo, stems }) =
+                    &original.one_typo
                 else {
                     panic!()
                 };
                 result.extend(one_typo.intersection(words).copied().map(Word::Derived));
+                result.extend(stems.intersection(words).copied().map(Word::Derived));
             }
             NTypoTermSubset::Nothing => {}
         };
@@ -305,14 +316,16 @@ impl QueryTermSubset {
 
         match &self.one_typo_subset {
             NTypoTermSubset::All => {
-                let Lazy::Init(OneTypoTerm { split_words, one_typo: _ }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words, one_typo: _, stems: _ }) =
+                    &original.one_typo
                 else {
                     panic!();
                 };
                 result.extend(split_words.iter().copied());
             }
             NTypoTermSubset::Subset { phrases, .. } => {
-                let Lazy::Init(OneTypoTerm { split_words, one_typo: _ }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words, one_typo: _, stems: _ }) =
+                    &original.one_typo
                 else {
                     panic!();
                 };
@@ -325,6 +338,19 @@ impl QueryTermSubset {
             NTypoTermSubset::Nothing => {}
         }
 
+        for (subset, synonyms) in [
+            (&self.one_typo_subset, &original.one_typo_synonyms),
+            (&self.two_typo_subset, &original.two_typo_synonyms),
+        ] {
+            match subset {
+                NTypoTermSubset::All => result.extend(synonyms.iter().copied()),
+                NTypoTermSubset::Subset { phrases, .. } => {
+                    result.extend(synonyms.intersection(phrases).copied())
+                }
+                NTypoTermSubset::Nothing => {}
+            }
+        }
+
         Ok(result)
     }
 
@@ -339,7 +365,15 @@ impl QueryTermSubset {
     }
     pub fn max_typo_cost(&self, ctx: &SearchContext<'_>) -> u8 {
         let t = ctx.term_interner.get(self.original);
-        match t.max_levenshtein_distance {
+        // the penalized synonyms cost as many typos as their penalty
+        let synonyms_cost = if !self.two_typo_subset.is_empty() && !t.two_typo_synonyms.is_empty() {
+            2
+        } else if !self.one_typo_subset.is_empty() && !t.one_typo_synonyms.is_empty() {
+            1
+        } else {
+            0
+        };
+        let typos_cost = match t.max_levenshtein_distance {
             0 => {
                 if t.allows_split_words() {
                     1
@@ -366,7 +400,8 @@ impl QueryTermSubset {
                 }
             }
             _ => panic!(),
-        }
+        };
+        typos_cost.max(synonyms_cost)
     }
     pub fn keep_only_exact_term(&mut self, ctx: &SearchContext<'_>) {
         if let Some(term) = self.exact_term(ctx) {
@@ -417,8 +452,8 @@ impl ZeroTypoTerm {
 }
 impl OneTypoTerm {
     fn is_empty(&self) -> bool {
-        let OneTypoTerm { split_words, one_typo } = self;
-        one_typo.is_empty() && split_words.is_none()
+        let OneTypoTerm { split_words, one_typo, stems } = self;
+        one_typo.is_empty() && split_words.is_none() && stems.is_empty()
     }
 }
 impl TwoTypoTerm {
@@ -437,7 +472,11 @@ impl QueryTerm {
             return false;
         };
 
-        self.zero_typo.is_empty() && one_typo.is_empty() && two_typo.is_empty()
+        self.zero_typo.is_empty()
+            && one_typo.is_empty()
+            && two_typo.is_empty()
+            && self.one_typo_synonyms.is_empty()
+            && self.two_typo_synonyms.is_empty()
     }
     fn allows_split_words(&self) -> bool {
         self.zero_typo.phrase.is_none()
@@ -496,8 +535,9 @@ impl QueryTerm {
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
-        if let Lazy::Init(OneTypoTerm { split_words, one_typo }) = &self.one_typo {
+        if let Lazy::Init(OneTypoTerm { split_words, one_typo, stems }) = &self.one_typo {
             words.extend(one_typo.iter().copied());
+            words.extend(stems.iter().copied());
             phrases.extend(split_words.iter().copied());
         };
 
@@ -505,6 +545,9 @@ impl QueryTerm {
             words.extend(two_typos.iter().copied());
         };
 
+        phrases.extend(self.one_typo_synonyms.iter().copied());
+        phrases.extend(self.two_typo_synonyms.iter().copied());
+
         (words.into_iter().collect(), phrases.into_iter().collect())
     }
 }
@@ -3,11 +3,11 @@ use std::collections::BTreeSet;
 use charabia::normalizer::NormalizedTokenIter;
 use charabia::{SeparatorKind, TokenKind};
 
-use super::compute_derivations::partially_initialized_term_from_word;
+use super::compute_derivations::{find_synonyms, partially_initialized_term_from_word};
 use super::{LocatedQueryTerm, ZeroTypoTerm};
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
 use crate::search::new::Word;
-use crate::{Result, SearchContext, MAX_WORD_LENGTH};
+use crate::{AnalyzerRule, AnalyzerTokenizer, Result, SearchContext, MAX_WORD_LENGTH};
 
 #[derive(Clone)]
 /// Extraction of the content of a query.
@@ -191,6 +191,88 @@ pub fn located_query_terms_from_tokens(
     Ok(ExtractedTokens { query_terms, negative_words, negative_phrases })
 }
 
+/// Add the words of the query, as split and normalized by the analyzers of the searched attributes,
+/// as zero-typo derivations of the single-word terms.
+///
+/// The words are only paired with the terms when an analyzer splits the query into
+/// as many words as there are single-word terms. The keyword of a keyword analyzer
+/// is instead returned as a term spanning the whole query when the query contains several words.
+/// The other analyzers are ignored.
+pub fn add_analyzed_words(
+    ctx: &mut SearchContext<'_>,
+    query: &str,
+    analyzers: &[&AnalyzerRule],
+    query_terms: &[LocatedQueryTerm],
+) -> Result<Vec<LocatedQueryTerm>> {
+    let word_terms: Vec<_> = query_terms
+        .iter()
+        .map(|term| term.value)
+        .filter(|&term| ctx.term_interner.get(term).zero_typo.phrase.is_none())
+        .collect();
+    let mut span_terms = Vec::new();
+
+    for analyzer in analyzers {
+        let mut words = analyzer.analyze_query(query);
+        // the keyword spans the whole query, which must then be made of words only
+        if analyzer.tokenizer == AnalyzerTokenizer::Keyword
+            && words.len() == 1
+            && word_terms.len() > 1
+            && word_terms.len() == query_terms.len()
+        {
+            let keyword = words.pop().unwrap();
+            let already_added = span_terms.iter().any(|term: &LocatedQueryTerm| {
+                ctx.word_interner.get(ctx.term_interner.get(term.value).original) == &keyword
+            });
+            if already_added {
+                continue;
+            }
+            if let Some(term) = make_span_term(ctx, &keyword, query_terms)? {
+                span_terms.push(term);
+            }
+            continue;
+        }
+        if words.len() != word_terms.len() {
+            continue;
+        }
+
+        for (&term, word) in word_terms.iter().zip(words) {
+            let original = ctx.term_interner.get(term).original;
+            if word.len() > MAX_WORD_LENGTH || ctx.word_interner.get(original) == &word {
+                continue;
+            }
+            let word = ctx.word_interner.insert(word);
+            let phrase = ctx.phrase_interner.insert(Phrase { words: vec![Some(word)] });
+            ctx.term_interner.get_mut(term).zero_typo.synonyms.insert(phrase);
+        }
+    }
+
+    Ok(span_terms)
+}
+
+/// Makes a term matching the given word over the positions of all the terms,
+/// unlike the n-grams it isn't limited to three words.
+fn make_span_term(
+    ctx: &mut SearchContext<'_>,
+    word: &str,
+    terms: &[LocatedQueryTerm],
+) -> Result<Option<LocatedQueryTerm>> {
+    if word.len() > MAX_WORD_LENGTH {
+        return Ok(None);
+    }
+    let words: Option<Vec<_>> =
+        terms.iter().map(|term| term.value.original_single_word(ctx)).collect();
+    let Some(words) = words else {
+        return Ok(None);
+    };
+
+    let start = *terms.first().unwrap().positions.start();
+    let end = *terms.last().unwrap().positions.end();
+    let term = partially_initialized_term_from_word(ctx, word, 0, false, true)?;
+    let term = QueryTerm { ngram_words: Some(words), ..term };
+
+    Ok(Some(LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end }))
+}
+
 pub fn number_of_typos_allowed<'ctx>(
     ctx: &SearchContext<'ctx>,
 ) -> Result<impl Fn(&str) -> u8 + 'ctx> {
@@ -258,14 +340,10 @@ pub fn make_ngram(
         partially_initialized_term_from_word(ctx, &ngram_str, max_nbr_typos, is_prefix, true)?;
 
     // Now add the synonyms
-    let index_synonyms = ctx.index.synonyms(ctx.txn)?;
-
-    term.zero_typo.synonyms.extend(
-        index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
-            let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
-        }),
-    );
+    let [synonyms, one_typo_synonyms, two_typo_synonyms] = find_synonyms(ctx, &words)?;
+    term.zero_typo.synonyms.extend(synonyms);
+    term.one_typo_synonyms.extend(one_typo_synonyms);
+    term.two_typo_synonyms.extend(two_typo_synonyms);
 
     let term = QueryTerm {
         original: ngram_str_interned,
@@ -275,6 +353,8 @@ pub fn make_ngram(
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        one_typo_synonyms: term.one_typo_synonyms,
+        two_typo_synonyms: term.two_typo_synonyms,
     };
 
     let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
@@ -339,6 +419,8 @@ impl PhraseBuilder {
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    one_typo_synonyms: BTreeSet::default(),
+                    two_typo_synonyms: BTreeSet::default(),
                 }
             }),
             positions: self.start..=self.end,
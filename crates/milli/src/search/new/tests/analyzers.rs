@@ -0,0 +1,161 @@
+/*!
+This module tests the following properties about the analyzers:
+
+1. The keyword analyzer indexes the whole value as a single word, which is found
+   when the query contains the same letters and digits, whatever the separators
+   and the number of words of the query
+2. The n-gram analyzers index the grams of the words, which are found by the query words
+3. The words of the query are searched both as produced by the tokenizer and as
+   normalized by the analyzers of the searched attributes
+4. The analyzers are also applied when the documents are reindexed after a settings update
+*/
+
+use big_s::S;
+
+use crate::index::tests::TempIndex;
+use crate::{
+    AnalyzerRule, AnalyzerTokenizer, Criterion, Search, SearchResult, TermsMatchingStrategy,
+};
+
+fn analyzer(attribute: &str, tokenizer: AnalyzerTokenizer, case_sensitive: bool) -> AnalyzerRule {
+    AnalyzerRule {
+        attribute_patterns: vec![S(attribute)].into(),
+        tokenizer,
+        lowercase: !case_sensitive,
+        ascii_folding: !case_sensitive,
+    }
+}
+
+fn analyzers() -> Vec<AnalyzerRule> {
+    vec![
+        analyzer("sku", AnalyzerTokenizer::Keyword, false),
+        analyzer("part", AnalyzerTokenizer::Ngram { min_gram: 2, max_gram: 4 }, false),
+        analyzer("title", AnalyzerTokenizer::Standard, true),
+    ]
+}
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key(S("id"));
+            s.set_searchable_fields(vec![S("sku"), S("part"), S("title")]);
+            s.set_criteria(vec![Criterion::Words]);
+            s.set_autorize_typos(false);
+            s.set_analyzers(analyzers());
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "sku": "AB-12/X",
+                "part": "XE42-Z",
+                "title": "Crème Brûlée",
+            },
+            {
+                "id": 1,
+                "sku": "AB-12",
+                "part": "ZZ99",
+                "title": "creme brulee",
+            },
+            {
+                "id": 2,
+                "sku": "CD-34",
+                "part": "QXE4",
+                "title": "CRÈME",
+            },
+            {
+                "id": 3,
+                "sku": "EF-56",
+                "part": "W1",
+                "title": "CREME",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { mut documents_ids, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+#[test]
+fn test_keyword_analyzer() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ab-12-x")), @"[0]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "AB 12")), @"[0, 1]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "cd 35")), @"[]");
+}
+
+#[test]
+fn test_keyword_analyzer_longer_than_ngrams() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 4,
+                "sku": "AB-12-X-9",
+                "part": "",
+                "title": "",
+            },
+        ]))
+        .unwrap();
+
+    // the n-grams of the query stop at three words
+    insta::assert_snapshot!(format!("{:?}", search(&index, "AB-12-X-9")), @"[4]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ab 12 x 9 ")), @"[4]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ab12x9")), @"[4]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ab 12 x 8")), @"[]");
+}
+
+#[test]
+fn test_ngram_analyzer() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "xe4")), @"[0, 2]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "e42 ")), @"[0]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "qxez ")), @"[]");
+}
+
+#[test]
+fn test_case_sensitive_analyzer() {
+    let index = create_index();
+
+    // `creme` is produced by the tokenizer and `CREME` by the analyzer
+    insta::assert_snapshot!(format!("{:?}", search(&index, "CREME ")), @"[1, 3]");
+}
+
+#[test]
+fn test_analyzers_applied_on_settings_update() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_analyzers(vec![analyzer(
+                "sku",
+                AnalyzerTokenizer::EdgeNgram { min_gram: 2, max_gram: 3 },
+                false,
+            )]);
+        })
+        .unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ab12x ")), @"[]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "cd ")), @"[2]");
+
+    index.update_settings(|s| s.reset_analyzers()).unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ab12x ")), @"[]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ab 12 x")), @"[0]");
+}
@@ -7,7 +7,7 @@ use heed::RoTxn;
 use itertools::Itertools;
 use maplit::hashset;
 
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::index::tests::TempIndex;
 use crate::score_details::ScoreDetails;
 use crate::search::new::tests::collect_field_values;
@@ -415,3 +415,33 @@ fn geo_sort_without_any_geo_faceted_documents() {
     insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 3]");
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
+
+#[test]
+fn geo_sort_with_geojson_geometries() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 0 } },
+            { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "LineString", "coordinates": [[-10, 2], [10, 2]] } },
+            { "id": 2, RESERVED_GEO_FIELD_NAME: { "lat": 3, "lng": 3 } },
+            { "id": 3, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[10, 10], [20, 10], [20, 20], [10, 20], [10, 10]]] } },
+            { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [1, 1] } },
+            { "id": 5 },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    // the geometries are sorted by the distance of their nearest point
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, _) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 4, 1, 2, 3, 5]");
+
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, _) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 2, 1, 4, 0, 5]");
+}
@@ -1,3 +1,4 @@
+pub mod analyzers;
 pub mod attribute_fid;
 pub mod attribute_position;
 pub mod cutoff;
@@ -12,9 +13,12 @@ pub mod ngram_split_words;
 pub mod proximity;
 pub mod proximity_typo;
 pub mod sort;
+pub mod stemming;
 pub mod stop_words;
+pub mod synonym_rules;
 pub mod typo;
 pub mod typo_proximity;
+pub mod vector_sort;
 pub mod words_tms;
 
 fn collect_field_values(
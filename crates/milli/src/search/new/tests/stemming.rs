@@ -0,0 +1,122 @@
+/*!
+This module tests the following properties about stemming:
+
+1. The stems of the words of the attributes with stemming enabled are indexed,
+   so that a query word matches the documents containing a word with the same stem
+2. A word matching through its stem costs as much as a word with one typo
+3. The words of the attributes without stemming are not stemmed
+4. The query words are not stemmed when the query is restricted to locales without stemming
+5. The stems are also indexed when the documents are reindexed after a settings update
+*/
+
+use big_s::S;
+use charabia::Language;
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, LocalizedAttributesRule, Search, SearchResult, TermsMatchingStrategy};
+
+fn localized_attributes_rules() -> Vec<LocalizedAttributesRule> {
+    vec![
+        LocalizedAttributesRule {
+            attribute_patterns: vec![S("title")].into(),
+            locales: vec![Language::Eng],
+            stemming: true,
+        },
+        LocalizedAttributesRule {
+            attribute_patterns: vec![S("notes")].into(),
+            locales: vec![Language::Eng],
+            stemming: false,
+        },
+    ]
+}
+
+fn create_index(stemming_before_documents: bool) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key(S("id"));
+            s.set_searchable_fields(vec![S("title"), S("notes")]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+            s.set_autorize_typos(false);
+            if stemming_before_documents {
+                s.set_localized_attributes_rules(localized_attributes_rules());
+            }
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "title": "The cat runs",
+            },
+            {
+                "id": 1,
+                "title": "A running dog",
+            },
+            {
+                "id": 2,
+                "title": "Run forest",
+            },
+            {
+                "id": 3,
+                "title": "Ran away",
+            },
+            {
+                "id": 4,
+                "notes": "running late",
+            },
+        ]))
+        .unwrap();
+
+    if !stemming_before_documents {
+        index
+            .update_settings(|s| s.set_localized_attributes_rules(localized_attributes_rules()))
+            .unwrap();
+    }
+
+    index
+}
+
+fn search(index: &TempIndex, query: &str, locales: Option<Vec<Language>>) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    if let Some(locales) = locales {
+        s.locales(locales);
+    }
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+#[test]
+fn test_stemmed_words() {
+    let index = create_index(true);
+
+    // the exact word ranks before the words sharing its stem
+    insta::assert_snapshot!(format!("{:?}", search(&index, "running ", None)), @"[1, 4, 0, 2]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "runs ", None)), @"[0, 1, 2]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "cats ", None)), @"[0]");
+}
+
+#[test]
+fn test_stemming_restricted_to_query_locales() {
+    let index = create_index(true);
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "runs ", Some(vec![Language::Eng]))), @"[0, 1, 2]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "runs ", Some(vec![Language::Fra]))), @"[0]");
+}
+
+#[test]
+fn test_stemming_applied_on_settings_update() {
+    let index = create_index(false);
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "running ", None)), @"[1, 4, 0, 2]");
+
+    index.update_settings(|s| s.reset_localized_attributes_rules()).unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "running ", None)), @"[1, 4]");
+}
@@ -0,0 +1,112 @@
+/*!
+This module tests the following properties about the synonym rules:
+
+1. A one-way synonym is only searched when its input is in the query
+2. A synonym with a penalty costs as many typos as its penalty
+3. A synonym without penalty is still ranked after the original word by the exactness rule
+4. The input and the synonyms of a rule can contain multiple words
+*/
+
+use big_s::S;
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, SynonymRule, TermsMatchingStrategy};
+
+fn create_index(criteria: Vec<Criterion>) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key(S("id"));
+            s.set_searchable_fields(vec![S("text")]);
+            s.set_criteria(criteria);
+            s.set_autorize_typos(false);
+            s.set_synonym_rules(vec![
+                SynonymRule::OneWay { input: S("tv"), synonyms: vec![S("television")], penalty: 1 },
+                SynonymRule::AltCorrection {
+                    word: S("tv"),
+                    corrections: vec![S("telly")],
+                    typos: 2,
+                },
+                SynonymRule::MultiWay { synonyms: vec![S("screen"), S("display")], penalty: 0 },
+                SynonymRule::OneWay { input: S("goggle box"), synonyms: vec![S("tv")], penalty: 0 },
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "a television set",
+            },
+            {
+                "id": 1,
+                "text": "a tv set",
+            },
+            {
+                "id": 2,
+                "text": "an old telly",
+            },
+            {
+                "id": 3,
+                "text": "a big screen",
+            },
+            {
+                "id": 4,
+                "text": "the goggle box",
+            },
+            {
+                "id": 5,
+                "text": "a wide display",
+            },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+#[test]
+fn test_one_way_synonyms() {
+    let index = create_index(vec![Criterion::Words, Criterion::Typo]);
+
+    // the synonyms of `tv` are not synonyms of each other, nor of `tv`
+    insta::assert_snapshot!(format!("{:?}", search(&index, "television ")), @"[0]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "telly ")), @"[2]");
+}
+
+#[test]
+fn test_synonym_penalties_rank_like_typos() {
+    let index = create_index(vec![Criterion::Words, Criterion::Typo]);
+
+    // the original word first, then the synonym with a penalty of 1, then the one with a penalty of 2
+    insta::assert_snapshot!(format!("{:?}", search(&index, "tv ")), @"[1, 0, 2]");
+    // the synonyms without penalty rank like the original word
+    insta::assert_snapshot!(format!("{:?}", search(&index, "display ")), @"[3, 5]");
+}
+
+#[test]
+fn test_exactness_ranks_original_words_first() {
+    let index = create_index(vec![Criterion::Words, Criterion::Exactness]);
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "display ")), @"[5, 3]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "screen ")), @"[3, 5]");
+}
+
+#[test]
+fn test_multi_word_synonyms() {
+    let index = create_index(vec![Criterion::Words, Criterion::Typo]);
+
+    // the synonym of the two words costs as much as their 2-gram
+    insta::assert_snapshot!(format!("{:?}", search(&index, "goggle box ")), @"[4, 1]");
+}
@@ -0,0 +1,171 @@
+/*!
+This module tests the recall of the `vector_sort` ranking rule when the search is filtered.
+
+Very selective filters are searched exhaustively, the other filters are applied while traversing the arroy trees.
+Binary quantized embeddings are always searched in the arroy trees.
+*/
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::update::Setting;
+use crate::vector::settings::{EmbedderSource, EmbeddingSettings};
+use crate::vector::{Embedder, VectorDistance};
+use crate::{Filter, FilterableAttributesRule, Index};
+
+const DOCUMENTS: usize = 2000;
+const DIMENSIONS: usize = 16;
+const BUCKETS: usize = 200;
+
+fn create_index(binary_quantized: bool) -> (TempIndex, Vec<Vec<f32>>) {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_filterable_fields(vec![FilterableAttributesRule::Field("bucket".to_owned())]);
+            s.set_embedder_settings(BTreeMap::from([(
+                "manual".to_owned(),
+                Setting::Set(EmbeddingSettings {
+                    source: Setting::Set(EmbedderSource::UserProvided),
+                    dimensions: Setting::Set(DIMENSIONS),
+                    binary_quantized: Setting::Set(binary_quantized),
+                    ..Default::default()
+                }),
+            )]));
+        })
+        .unwrap();
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let vectors: Vec<Vec<f32>> = (0..DOCUMENTS)
+        .map(|_| (0..DIMENSIONS).map(|_| rng.gen_range(-1.0..1.0)).collect())
+        .collect();
+    let documents: Vec<_> = vectors
+        .iter()
+        .enumerate()
+        .map(|(id, vector)| {
+            serde_json::json!({ "id": id, "bucket": id % BUCKETS, "_vectors": { "manual": vector } })
+        })
+        .collect();
+    let documents = serde_json::Value::Array(documents);
+    index.add_documents(documents!(documents)).unwrap();
+
+    (index, vectors)
+}
+
+/// The ids of the `limit` documents matching `keep` that are the closest to the target.
+fn exact_neighbors(
+    vectors: &[Vec<f32>],
+    target: &[f32],
+    limit: usize,
+    keep: impl Fn(usize) -> bool,
+) -> Vec<usize> {
+    let mut neighbors: Vec<_> = (0..vectors.len())
+        .filter(|id| keep(*id))
+        .map(|id| (id, VectorDistance::Cosine.between(target, &vectors[id])))
+        .collect();
+    neighbors.sort_by(|(_, left), (_, right)| left.total_cmp(right));
+    neighbors.into_iter().take(limit).map(|(id, _)| id).collect()
+}
+
+fn filtered_search(index: &Index, target: &[f32], filter: &str, limit: usize) -> Vec<usize> {
+    let rtxn = index.read_txn().unwrap();
+    let config = index.embedding_configs(&rtxn).unwrap().pop().unwrap().config;
+    let embedder = Arc::new(Embedder::new(config.embedder_options, 0).unwrap());
+
+    let mut search = index.search(&rtxn);
+    search.semantic(
+        "manual".to_owned(),
+        embedder,
+        Default::default(),
+        Default::default(),
+        Some(target.to_vec()),
+    );
+    search.filter(Filter::from_str(filter).unwrap().unwrap());
+    search.limit(limit);
+    let result = search.execute().unwrap();
+
+    collect_field_values(index, &rtxn, "id", &result.documents_ids)
+        .into_iter()
+        .map(|id| id.parse().unwrap())
+        .collect()
+}
+
+fn recall(found: &[usize], expected: &[usize]) -> f64 {
+    let found = found.iter().filter(|id| expected.contains(id)).count();
+    found as f64 / expected.len() as f64
+}
+
+#[test]
+fn very_selective_filter() {
+    let (index, vectors) = create_index(false);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    // each bucket contains 0.5% of the documents, so the candidates are compared one by one
+    for bucket in [3, 42, 199] {
+        let target: Vec<f32> = (0..DIMENSIONS).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let found = filtered_search(&index, &target, &format!("bucket = {bucket}"), 5);
+        let expected = exact_neighbors(&vectors, &target, 5, |id| id % BUCKETS == bucket);
+        assert_eq!(found, expected);
+    }
+}
+
+#[test]
+fn filter_keeping_fewer_documents_than_the_limit() {
+    let (index, vectors) = create_index(false);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    let target: Vec<f32> = (0..DIMENSIONS).map(|_| rng.gen_range(-1.0..1.0)).collect();
+    let found = filtered_search(&index, &target, "bucket IN [10, 11]", 50);
+    let expected = exact_neighbors(&vectors, &target, 50, |id| [10, 11].contains(&(id % BUCKETS)));
+    assert_eq!(expected.len(), 20);
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn broad_filter() {
+    let (index, vectors) = create_index(false);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    // a quarter of the documents are kept, the filter is applied while traversing the trees
+    let mut total_recall = 0.0;
+    let queries = 10;
+    for _ in 0..queries {
+        let target: Vec<f32> = (0..DIMENSIONS).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let found = filtered_search(&index, &target, "bucket < 50", 20);
+        let expected = exact_neighbors(&vectors, &target, 20, |id| id % BUCKETS < 50);
+        // the filter never makes the search return fewer hits than requested
+        assert_eq!(found.len(), 20);
+        assert!(found.iter().all(|id| id % BUCKETS < 50));
+        total_recall += recall(&found, &expected);
+    }
+    let recall = total_recall / queries as f64;
+    assert!(recall >= 0.5, "recall too low: {recall}");
+}
+
+#[test]
+fn very_selective_filter_binary_quantized() {
+    let (index, vectors) = create_index(true);
+    let mut rng = StdRng::seed_from_u64(7);
+
+    // the binary quantized embeddings can't be compared one by one with the float distance
+    for bucket in [3, 42, 199] {
+        let target: Vec<f32> = (0..DIMENSIONS).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let found = filtered_search(&index, &target, &format!("bucket = {bucket}"), 5);
+        assert_eq!(found.len(), 5);
+        assert!(found.iter().all(|id| id % BUCKETS == bucket));
+    }
+
+    let target: Vec<f32> = (0..DIMENSIONS).map(|_| rng.gen_range(-1.0..1.0)).collect();
+    let mut found = filtered_search(&index, &target, "bucket IN [10, 11]", 50);
+    let mut expected =
+        exact_neighbors(&vectors, &target, 50, |id| [10, 11].contains(&(id % BUCKETS)));
+    found.sort_unstable();
+    expected.sort_unstable();
+    assert_eq!(found, expected);
+}
@@ -6,18 +6,24 @@ use roaring::RoaringBitmap;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use super::VectorStoreStats;
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{ArroyWrapper, DistributionShift, Embedder};
+use crate::vector::{ArroyWrapper, DistributionShift, Embedder, Quantization, VectorDistance};
 use crate::{DocumentId, Result, SearchContext, SearchLogger};
 
 pub struct VectorSort<Q: RankingRuleQueryTrait> {
     query: Option<Q>,
     target: Vec<f32>,
     vector_candidates: RoaringBitmap,
-    cached_sorted_docids: std::vec::IntoIter<(DocumentId, f32)>,
+    /// The candidates with their best matching embedding and similarity, sorted by decreasing similarity.
+    cached_sorted_docids: std::vec::IntoIter<(DocumentId, Option<u8>, f32)>,
     limit: usize,
     distribution_shift: Option<DistributionShift>,
+    /// The target is a bag of embeddings of this dimension, documents are scored with MaxSim.
+    multi_vector: Option<usize>,
     embedder_index: u8,
-    quantized: bool,
+    quantization: Quantization,
+    distance: VectorDistance,
+    /// The embeddings are sparse and searched in the inverted index.
+    sparse: bool,
 }
 
 impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
@@ -28,7 +34,8 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         limit: usize,
         embedder_name: &str,
         embedder: &Embedder,
-        quantized: bool,
+        quantization: Quantization,
+        distance: VectorDistance,
     ) -> Result<Self> {
         let embedder_index = ctx
             .index
@@ -43,8 +50,11 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             cached_sorted_docids: Default::default(),
             limit,
             distribution_shift: embedder.distribution(),
+            multi_vector: embedder.multi_vector().then(|| embedder.dimensions()),
             embedder_index,
-            quantized,
+            quantization,
+            distance,
+            sparse: embedder.sparse(),
         })
     }
 
@@ -56,12 +66,50 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         let target = &self.target;
 
         let before = Instant::now();
-        let reader = ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
-        let results = reader.nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?;
+        let reader = ArroyWrapper::new(
+            ctx.index.vector_arroy,
+            self.embedder_index,
+            self.quantization,
+            self.distance,
+            self.sparse.then_some(ctx.index.sparse_vectors),
+            ctx.index.int8_vectors,
+        );
+        let (results, total_queries) = match self.multi_vector {
+            Some(dimensions) => {
+                let query: Vec<_> = target.chunks_exact(dimensions).collect();
+                let results = reader.nns_by_vectors_max_sim(
+                    ctx.txn,
+                    &query,
+                    self.limit,
+                    Some(vector_candidates),
+                )?;
+                // all the embeddings of a document participate in its score
+                let results = results
+                    .into_iter()
+                    .map(|(docid, similarity)| (docid, None, similarity))
+                    .collect();
+                (results, query.len())
+            }
+            None => {
+                let results = reader.nns_by_vector_with_embedding_index(
+                    ctx.txn,
+                    target,
+                    self.limit,
+                    Some(vector_candidates),
+                )?;
+                let results = results
+                    .into_iter()
+                    .map(|(docid, chunk, distance)| {
+                        (docid, Some(chunk), self.distance.similarity(distance))
+                    })
+                    .collect();
+                (results, 1)
+            }
+        };
         self.cached_sorted_docids = results.into_iter();
         *ctx.vector_store_stats.get_or_insert_default() += VectorStoreStats {
             total_time: before.elapsed(),
-            total_queries: 1,
+            total_queries,
             total_results: self.cached_sorted_docids.len(),
         };
 
@@ -105,13 +153,15 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    chunk: None,
+                }),
             }));
         }
 
-        for (docid, distance) in self.cached_sorted_docids.by_ref() {
+        for (docid, chunk, score) in self.cached_sorted_docids.by_ref() {
             if vector_candidates.contains(docid) {
-                let score = 1.0 - distance;
                 let score = self
                     .distribution_shift
                     .map(|distribution| distribution.shift(score))
@@ -119,7 +169,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
                 return Ok(Some(RankingRuleOutput {
                     query,
                     candidates: RoaringBitmap::from_iter([docid]),
-                    score: ScoreDetails::Vector(score_details::Vector { similarity: Some(score) }),
+                    score: ScoreDetails::Vector(score_details::Vector {
+                        similarity: Some(score),
+                        chunk,
+                    }),
                 }));
             }
         }
@@ -135,7 +188,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    chunk: None,
+                }),
             }));
         }
 
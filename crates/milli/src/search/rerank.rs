@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use roaring::RoaringBitmap;
+
+use super::{Search, SearchResult};
+use crate::prompt::Prompt;
+use crate::score_details::{self, ScoreDetails};
+use crate::vector::rerank::Reranker;
+use crate::{DocumentId, Result};
+
+/// The second stage of a search, reordering its top hits with a reranker.
+#[derive(Debug, Clone)]
+pub struct RerankSearch {
+    pub reranker_name: String,
+    pub reranker: Arc<Reranker>,
+    pub prompt: Arc<Prompt>,
+    /// The number of hits of the first stage sent to the reranker.
+    pub top_k: usize,
+}
+
+impl<'a> Search<'a> {
+    pub fn rerank(&mut self, rerank: RerankSearch) -> &mut Search<'a> {
+        self.rerank = Some(rerank);
+        self
+    }
+
+    /// Executes the first stage of the search for the `top_k` hits, or the requested page when it goes further,
+    /// then reranks the `top_k` hits before returning the requested page.
+    ///
+    /// The first stage always starts at the first hit, so that pages are taken from the same reranked hits.
+    pub(super) fn execute_reranked(
+        &self,
+        rerank: &RerankSearch,
+        execute: impl FnOnce(&Search<'a>) -> Result<(SearchResult, Option<u32>)>,
+    ) -> Result<(SearchResult, Option<u32>)> {
+        let first_stage = Search {
+            query: self.query.clone(),
+            filter: self.filter.clone(),
+            offset: 0,
+            limit: rerank.top_k.max(self.offset + self.limit),
+            sort_criteria: self.sort_criteria.clone(),
+            distinct: self.distinct.clone(),
+            searchable_attributes: self.searchable_attributes,
+            geo_param: self.geo_param,
+            terms_matching_strategy: self.terms_matching_strategy,
+            scoring_strategy: self.scoring_strategy,
+            words_limit: self.words_limit,
+            exhaustive_number_hits: self.exhaustive_number_hits,
+            rtxn: self.rtxn,
+            index: self.index,
+            semantic: self.semantic.clone(),
+            time_budget: self.time_budget.clone(),
+            ranking_score_threshold: self.ranking_score_threshold,
+            locales: self.locales.clone(),
+            rerank: None,
+        };
+        let (mut result, semantic_hit_count) = execute(&first_stage)?;
+
+        // there is nothing to compare the documents to in a placeholder search
+        let query = self.query.as_deref().filter(|query| !query.trim().is_empty());
+        if query.is_some() && self.time_budget.exceeded() {
+            // the first stage used up the time budget, the hits are returned in their original order
+            result.degraded = true;
+        } else if let Some(query) = query {
+            let top_k = rerank.top_k.min(result.documents_ids.len());
+            let documents = rerank.prompt.render_stored_documents(
+                self.index,
+                self.rtxn,
+                &result.documents_ids[..top_k],
+            )?;
+            match rerank.reranker.rerank(query, &documents, self.time_budget.deadline()) {
+                Ok(scores) => self.apply_rerank_scores(&mut result, &rerank.reranker_name, scores),
+                Err(error) => {
+                    tracing::error!(error=%error, "Reranking failed, returning the hits in their original order");
+                }
+            }
+        }
+
+        let SearchResult {
+            matching_words,
+            candidates,
+            documents_ids,
+            document_scores,
+            degraded,
+            used_negative_operator,
+        } = result;
+        let (documents_ids, document_scores): (Vec<_>, Vec<_>) = documents_ids
+            .into_iter()
+            .zip(document_scores)
+            .skip(self.offset)
+            .take(self.limit)
+            .unzip();
+        let semantic_hit_count = semantic_hit_count.map(|_| {
+            document_scores
+                .iter()
+                .filter(|scores| {
+                    scores.iter().any(|score| matches!(score, ScoreDetails::Vector(_)))
+                })
+                .count() as u32
+        });
+
+        Ok((
+            SearchResult {
+                matching_words,
+                candidates,
+                documents_ids,
+                document_scores,
+                degraded,
+                used_negative_operator,
+            },
+            semantic_hit_count,
+        ))
+    }
+
+    /// Reorders the first hits of the result by decreasing reranker score, keeping the order of the other hits.
+    ///
+    /// The reranked hits below the ranking score threshold are removed from the result.
+    fn apply_rerank_scores(
+        &self,
+        result: &mut SearchResult,
+        reranker_name: &str,
+        scores: Vec<f32>,
+    ) {
+        let top_k = scores.len();
+        let mut reranked: Vec<(DocumentId, Vec<ScoreDetails>, f32)> = result
+            .documents_ids
+            .drain(..top_k)
+            .zip(result.document_scores.drain(..top_k))
+            .zip(scores)
+            .map(|((docid, scores), score)| (docid, scores, score))
+            .collect();
+        // stable sort, so that the first stage breaks ties
+        reranked.sort_by(|(_, _, left), (_, _, right)| right.total_cmp(left));
+
+        let mut removed = RoaringBitmap::new();
+        let mut documents_ids = Vec::with_capacity(result.documents_ids.len() + top_k);
+        let mut document_scores = Vec::with_capacity(result.document_scores.len() + top_k);
+        for (docid, mut scores, score) in reranked {
+            let score = (score as f64).clamp(0.0, 1.0);
+            if self.ranking_score_threshold.is_some_and(|threshold| score < threshold) {
+                removed.insert(docid);
+                continue;
+            }
+            scores.insert(
+                0,
+                ScoreDetails::Rerank(score_details::Rerank {
+                    reranker: reranker_name.to_owned(),
+                    score,
+                }),
+            );
+            documents_ids.push(docid);
+            document_scores.push(scores);
+        }
+        documents_ids.append(&mut result.documents_ids);
+        document_scores.append(&mut result.document_scores);
+
+        result.documents_ids = documents_ids;
+        result.document_scores = document_scores;
+        result.candidates -= removed;
+    }
+}
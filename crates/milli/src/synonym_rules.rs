@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+/// The highest penalty of a synonym, a synonym with a penalty of `n`
+/// being ranked as if it was `n` typos away from the query word.
+pub const MAX_SYNONYM_PENALTY: u8 = 2;
+
+/// A rule defining which expressions of a query are also searched as other expressions.
+///
+/// The expressions are normalized by the tokenizer of the index, and the synonyms
+/// matched with a penalty rank below the ones matched without, as a typo would.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SynonymRule {
+    /// All the expressions are synonyms of each other.
+    MultiWay { synonyms: Vec<String>, penalty: u8 },
+    /// The synonyms are searched when the input is in the query, but not the other way around.
+    OneWay { input: String, synonyms: Vec<String>, penalty: u8 },
+    /// The corrections of a word are searched as if they were that many typos away from it.
+    AltCorrection { word: String, corrections: Vec<String>, typos: u8 },
+    /// The placeholder written in the documents is matched by any of its replacements.
+    Placeholder { placeholder: String, replacements: Vec<String> },
+}
+
+impl SynonymRule {
+    /// Returns the synonyms defined by this rule, as `(input, synonym, penalty)` triples.
+    pub fn expand(&self) -> Vec<(&str, &str, u8)> {
+        match self {
+            SynonymRule::MultiWay { synonyms, penalty } => synonyms
+                .iter()
+                .flat_map(|input| {
+                    synonyms
+                        .iter()
+                        .filter(move |synonym| *synonym != input)
+                        .map(move |synonym| (input.as_str(), synonym.as_str(), *penalty))
+                })
+                .collect(),
+            SynonymRule::OneWay { input, synonyms, penalty } => synonyms
+                .iter()
+                .map(|synonym| (input.as_str(), synonym.as_str(), *penalty))
+                .collect(),
+            SynonymRule::AltCorrection { word, corrections, typos } => corrections
+                .iter()
+                .map(|correction| (word.as_str(), correction.as_str(), *typos))
+                .collect(),
+            SynonymRule::Placeholder { placeholder, replacements } => replacements
+                .iter()
+                .map(|replacement| (replacement.as_str(), placeholder.as_str(), 0))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+
+    use super::SynonymRule;
+
+    #[test]
+    fn expand() {
+        let rule = SynonymRule::MultiWay { synonyms: vec![S("tv"), S("television")], penalty: 1 };
+        assert_eq!(rule.expand(), [("tv", "television", 1), ("television", "tv", 1)]);
+
+        let rule =
+            SynonymRule::OneWay { input: S("tv"), synonyms: vec![S("television")], penalty: 0 };
+        assert_eq!(rule.expand(), [("tv", "television", 0)]);
+
+        let rule = SynonymRule::AltCorrection {
+            word: S("car"),
+            corrections: vec![S("automobile")],
+            typos: 2,
+        };
+        assert_eq!(rule.expand(), [("car", "automobile", 2)]);
+
+        let rule =
+            SynonymRule::Placeholder { placeholder: S("<street>"), replacements: vec![S("st")] };
+        assert_eq!(rule.expand(), [("st", "<street>", 0)]);
+    }
+}
@@ -46,6 +46,8 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            sparse_vectors,
+            int8_vectors,
             embedder_category_id: _,
             documents,
         } = self.index;
@@ -60,8 +62,11 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
+        self.index.put_date_faceted_fields(self.wtxn, &Default::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
+        self.index.delete_geojson_rtree(self.wtxn)?;
+        self.index.delete_geojson_faceted_documents_ids(self.wtxn)?;
 
         // Remove all user-provided bits from the configs
         let mut configs = self.index.embedding_configs(self.wtxn)?;
@@ -93,6 +98,8 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         field_id_docid_facet_strings.clear(self.wtxn)?;
         // vector
         vector_arroy.clear(self.wtxn)?;
+        sparse_vectors.clear(self.wtxn)?;
+        int8_vectors.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
 
@@ -136,6 +143,7 @@ mod tests {
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
+        assert!(index.geojson_faceted_documents_ids(&rtxn).unwrap().is_empty());
 
         assert!(index.word_docids.is_empty(&rtxn).unwrap());
         assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
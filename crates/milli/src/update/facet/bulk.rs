@@ -74,7 +74,7 @@ impl<'i> FacetsUpdateBulk<'i> {
             FacetType::String => {
                 index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>()
             }
-            FacetType::Number => {
+            FacetType::Number | FacetType::Date => {
                 index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>()
             }
         };
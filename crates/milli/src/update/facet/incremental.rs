@@ -76,9 +76,10 @@ impl FacetsUpdateIncremental {
                     FacetType::String => index
                         .facet_id_string_docids
                         .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>(),
-                    FacetType::Number => index
+                    FacetType::Number | FacetType::Date => index
                         .facet_id_f64_docids
-                        .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>(),
+                        .remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>(
+                    ),
                 },
                 group_size,
                 max_group_size,
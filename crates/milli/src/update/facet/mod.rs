@@ -131,7 +131,7 @@ impl<'i> FacetsUpdate<'i> {
             FacetType::String => {
                 index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>()
             }
-            FacetType::Number => {
+            FacetType::Number | FacetType::Date => {
                 index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>()
             }
         };
@@ -680,7 +680,7 @@ pub(crate) fn sanity_checks(
         FacetType::String => {
             index.facet_id_string_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>()
         }
-        FacetType::Number => {
+        FacetType::Number | FacetType::Date => {
             index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>()
         }
     };
@@ -841,7 +841,7 @@ pub(crate) fn sanity_checks(
 fn facet_to_string(facet_value: &[u8], facet_type: FacetType) -> String {
     match facet_type {
         FacetType::String => bstr::BStr::new(facet_value).to_string(),
-        FacetType::Number => match OrderedF64Codec::bytes_decode(facet_value) {
+        FacetType::Number | FacetType::Date => match OrderedF64Codec::bytes_decode(facet_value) {
             Ok(value) => value.to_string(),
             Err(e) => format!("error: {e} (bytes: {facet_value:?}"),
         },
@@ -12,7 +12,10 @@ use super::helpers::{create_sorter, sorter_into_reader, GrenadParameters, KeepLa
 use crate::error::{InternalError, SerializationError};
 use crate::update::del_add::{del_add_from_two_obkvs, DelAdd, KvReaderDelAdd};
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
-use crate::{FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH};
+use crate::{
+    AnalyzerRule, FieldId, LocalizedAttributesRule, Result, Stemmers, MAX_POSITION_PER_ATTRIBUTE,
+    MAX_WORD_LENGTH,
+};
 
 /// Extracts the word and positions where this word appear and
 /// prefixes it by the document id.
@@ -223,14 +226,46 @@ fn tokens_from_document<'a>(
                 buffers.obkv_positions_buffer.clear();
                 let mut writer = KvWriterU16::new(&mut buffers.obkv_positions_buffer);
 
-                // convert json into a unique string.
+                // the analyzer of the field replaces the tokenizer.
+                let analyzer = settings
+                    .fields_ids_map
+                    .name(field_id)
+                    .and_then(|name| AnalyzerRule::find(&settings.analyzers, name));
+
+                // convert json into a unique string, unless the field is analyzed.
                 buffers.field_buffer.clear();
-                if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
+                if let Some(analyzer) = analyzer {
+                    let tokens = analyzed_tokens(analyzer, &value)
+                        .into_iter()
+                        .take_while(|(p, _)| *p < max_positions_per_attributes);
+
+                    let mut words = Vec::new();
+                    for (index, token) in tokens {
+                        // keep a word only if it is not empty and fit in a LMDB key.
+                        if !token.is_empty() && token.len() <= MAX_WORD_LENGTH {
+                            let position: u16 = index
+                                .try_into()
+                                .map_err(|_| SerializationError::InvalidNumberSerialization)?;
+                            words.push((position, token));
+                        }
+                    }
+                    write_words_positions(&mut writer, words, &mut buffers.words_buffer)?;
+
+                    // write positions into document.
+                    let positions = writer.into_inner()?;
+                    document_writer.insert(field_id, positions)?;
+                } else if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
                     // create an iterator of token with their positions.
-                    let locales = metadata.locales(&settings.localized_attributes_rules);
+                    let rule =
+                        metadata.localized_attributes_rule(&settings.localized_attributes_rules);
+                    let locales = rule.map(LocalizedAttributesRule::locales);
+                    let stemmers = rule.map_or_else(Stemmers::default, |rule| {
+                        Stemmers::new(&rule.stemming_locales())
+                    });
                     let tokens = process_tokens(tokenizer.tokenize_with_allow_list(field, locales))
                         .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
+                    let mut words = Vec::new();
                     for (index, token) in tokens {
                         // keep a word only if it is not empty and fit in a LMDB key.
                         let token = token.lemma().trim();
@@ -238,9 +273,12 @@ fn tokens_from_document<'a>(
                             let position: u16 = index
                                 .try_into()
                                 .map_err(|_| SerializationError::InvalidNumberSerialization)?;
-                            writer.insert(position, token.as_bytes())?;
+                            words.push((position, token.to_string()));
+                            // the stems of a word share its position.
+                            words.extend(stemmers.stems(token).into_iter().map(|s| (position, s)));
                         }
                     }
+                    write_words_positions(&mut writer, words, &mut buffers.words_buffer)?;
 
                     // write positions into document.
                     let positions = writer.into_inner()?;
@@ -286,6 +324,81 @@ fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a st
     }
 }
 
+/// Analyze every value of a field, the values of an array being as distant
+/// from each other as the words separated by an `Hard` separator.
+fn analyzed_tokens(analyzer: &AnalyzerRule, value: &Value) -> Vec<(u32, String)> {
+    fn inner(
+        analyzer: &AnalyzerRule,
+        value: &Value,
+        last_position: &mut Option<u32>,
+        tokens: &mut Vec<(u32, String)>,
+    ) {
+        let text = match value {
+            Value::Null | Value::Object(_) => return,
+            Value::Bool(boolean) => boolean.to_string(),
+            Value::Number(number) => number.to_string(),
+            Value::String(string) => string.clone(),
+            Value::Array(array) => {
+                for value in array {
+                    inner(analyzer, value, last_position, tokens);
+                }
+                return;
+            }
+        };
+
+        let offset = last_position.map_or(0, |position| position + 8);
+        for (position, token) in analyzer.analyze(&text) {
+            *last_position = Some(offset + position);
+            tokens.push((offset + position, token));
+        }
+    }
+
+    let mut tokens = Vec::new();
+    inner(analyzer, value, &mut None, &mut tokens);
+    tokens
+}
+
+/// Write the words into the positions obkv of a field, as a position can only be written once.
+///
+/// The words sharing a position, like the grams of a word, are written one after the other,
+/// each prefixed by its length as a big-endian `u16`.
+fn write_words_positions<W: io::Write>(
+    writer: &mut KvWriterU16<W>,
+    words: impl IntoIterator<Item = (u16, impl AsRef<str>)>,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    let mut current_position = None;
+    buffer.clear();
+    for (position, word) in words {
+        if let Some(current) = current_position.filter(|&current| current != position) {
+            writer.insert(current, buffer.as_slice())?;
+            buffer.clear();
+        }
+        current_position = Some(position);
+        let word = word.as_ref().as_bytes();
+        let length: u16 =
+            word.len().try_into().map_err(|_| SerializationError::InvalidNumberSerialization)?;
+        buffer.extend_from_slice(&length.to_be_bytes());
+        buffer.extend_from_slice(word);
+    }
+
+    if let Some(current) = current_position {
+        writer.insert(current, buffer.as_slice())?;
+    }
+
+    Ok(())
+}
+
+/// Iterates over the words written at a position of the positions obkv of a field.
+pub(super) fn words_at_position(mut words: &[u8]) -> impl Iterator<Item = &[u8]> {
+    std::iter::from_fn(move || {
+        let (length, rest) = words.split_first_chunk::<2>()?;
+        let (word, rest) = rest.split_at_checked(u16::from_be_bytes(*length).into())?;
+        words = rest;
+        Some(word)
+    })
+}
+
 /// take an iterator on tokens and compute their relative position depending on separator kinds
 /// if it's an `Hard` separator we add an additional relative proximity of 8 between words,
 /// else we keep the standard proximity of 1 between words.
@@ -327,4 +440,30 @@ struct Buffers {
     obkv_buffer: Vec<u8>,
     // buffer used to store the value data containing an obkv of tokens with their positions.
     obkv_positions_buffer: Vec<u8>,
+    // buffer used to join the words sharing a position.
+    words_buffer: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use obkv::{KvReaderU16, KvWriterU16};
+
+    use super::{words_at_position, write_words_positions};
+
+    #[test]
+    fn words_sharing_a_position() {
+        let mut writer = KvWriterU16::memory();
+        let words = [(0, "ab"), (0, "a\0b"), (1, "c"), (3, ""), (3, "d")];
+        write_words_positions(&mut writer, words, &mut Vec::new()).unwrap();
+        let positions = writer.into_inner().unwrap();
+
+        let positions: Vec<_> = KvReaderU16::from_slice(&positions)
+            .iter()
+            .map(|(position, words)| (position, words_at_position(words).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(
+            positions,
+            [(0, vec![&b"ab"[..], b"a\0b"]), (1, vec![&b"c"[..]]), (3, vec![&b""[..], b"d"])]
+        );
+    }
 }
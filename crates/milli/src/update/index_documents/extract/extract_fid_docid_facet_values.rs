@@ -15,6 +15,7 @@ use FilterableValues::{Empty, Null, Values};
 
 use super::helpers::{create_sorter, sorter_into_reader, GrenadParameters, KeepFirst};
 use crate::error::InternalError;
+use crate::facet::parse_date;
 use crate::facet::value_encoding::f64_into_bytes;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::index_documents::{create_writer, writer_into_reader};
@@ -540,6 +541,9 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             "Internal error, could not parse a geofield that has been validated. Please open an issue."
                         )
                     }
+                } else if let Some(timestamp) = parse_date(original) {
+                    // dates are also stored as their Unix timestamp to be comparable and sortable
+                    output_numbers.push(timestamp);
                 }
                 let normalized = crate::normalize_facet(original);
                 output_strings.push((normalized, original.clone()));
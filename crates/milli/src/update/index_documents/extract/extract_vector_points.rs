@@ -40,6 +40,7 @@ pub struct ExtractedVectorPoints {
     // embedder
     pub embedder_name: String,
     pub embedder: Arc<Embedder>,
+    pub prompt: Arc<Prompt>,
     pub add_to_user_provided: RoaringBitmap,
     pub remove_from_user_provided: RoaringBitmap,
 }
@@ -207,7 +208,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     if reindex_vectors {
         for (name, action) in settings_diff.embedding_config_updates.iter() {
             if let Some(action) = action.reindex() {
-                let Some((embedder_name, (embedder, prompt, _quantized))) =
+                let Some((embedder_name, (embedder, prompt, _quantized, _distance))) =
                     configs.remove_entry(name)
                 else {
                     tracing::error!(embedder = name, "Requested embedder config not found");
@@ -238,7 +239,8 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                 let action = match action {
                     ReindexAction::FullReindex => ExtractionAction::SettingsFullReindex,
                     ReindexAction::RegeneratePrompts => {
-                        let Some((_, old_prompt, _quantized)) = old_configs.get(name) else {
+                        let Some((_, old_prompt, _quantized, _distance)) = old_configs.get(name)
+                        else {
                             tracing::error!(embedder = name, "Old embedder config not found");
                             continue;
                         };
@@ -264,7 +266,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     } else {
         // document operation
 
-        for (embedder_name, (embedder, prompt, _quantized)) in configs.into_iter() {
+        for (embedder_name, (embedder, prompt, _quantized, _distance)) in configs.into_iter() {
             // (docid, _index) -> KvWriterDelAdd -> Vector
             let manual_vectors_writer = create_writer(
                 indexer.chunk_compression_type,
@@ -444,7 +446,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     for EmbedderVectorExtractor {
         embedder_name,
         embedder,
-        prompt: _,
+        prompt,
         prompts_writer,
         remove_vectors_writer,
         action,
@@ -467,6 +469,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
             remove_vectors: writer_into_reader(remove_vectors_writer)?,
             prompts: writer_into_reader(prompts_writer)?,
             embedder,
+            prompt,
             embedder_name,
             add_to_user_provided,
             remove_from_user_provided,
@@ -680,6 +683,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     prompt_reader: grenad::Reader<R>,
     indexer: GrenadParameters,
     embedder: Arc<Embedder>,
+    prompt: &Prompt,
     embedder_name: &str,
     possible_embedding_mistakes: &PossibleEmbeddingMistakes,
     unused_vectors_distribution: &UnusedVectorsDistribution,
@@ -689,11 +693,11 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     let n_vectors_per_chunk = embedder.prompt_count_in_chunk_hint(); // number of vectors in a single chunk
 
     // docid, state with embedding
-    let mut state_writer = create_writer(
+    let mut state_writer = DocumentEmbeddingsWriter::new(create_writer(
         indexer.chunk_compression_type,
         indexer.chunk_compression_level,
         tempfile::tempfile()?,
-    );
+    ));
 
     let mut chunks = Vec::with_capacity(n_chunks);
     let mut current_chunk = Vec::with_capacity(n_vectors_per_chunk);
@@ -704,21 +708,24 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     while let Some((key, value)) = cursor.move_on_next()? {
         let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
         // SAFETY: precondition, the grenad value was saved from a string
-        let prompt = unsafe { std::str::from_utf8_unchecked(value) };
-        if current_chunk.len() == current_chunk.capacity() {
-            chunks.push(std::mem::replace(
-                &mut current_chunk,
-                Vec::with_capacity(n_vectors_per_chunk),
-            ));
-            chunks_ids.push(std::mem::replace(
-                &mut current_chunk_ids,
-                Vec::with_capacity(n_vectors_per_chunk),
-            ));
-        };
-        current_chunk.push(prompt.to_owned());
-        current_chunk_ids.push(docid);
-
-        if chunks.len() == chunks.capacity() {
+        let rendered = unsafe { std::str::from_utf8_unchecked(value) };
+        // a document whose text is chunked is embedded once per chunk
+        for text in prompt.split(rendered) {
+            if current_chunk.len() == current_chunk.capacity() {
+                chunks.push(std::mem::replace(
+                    &mut current_chunk,
+                    Vec::with_capacity(n_vectors_per_chunk),
+                ));
+                chunks_ids.push(std::mem::replace(
+                    &mut current_chunk_ids,
+                    Vec::with_capacity(n_vectors_per_chunk),
+                ));
+            };
+            current_chunk.push(text.to_owned());
+            current_chunk_ids.push(docid);
+        }
+
+        if chunks.len() >= n_chunks {
             let chunked_embeds = embed_chunks(
                 &embedder,
                 std::mem::replace(&mut chunks, Vec::with_capacity(n_chunks)),
@@ -733,7 +740,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
                 .flat_map(|docids| docids.iter())
                 .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
             {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
+                state_writer.push(*docid, embeddings)?;
             }
             chunks_ids.clear();
         }
@@ -754,7 +761,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
             .flat_map(|docids| docids.iter())
             .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
         {
-            state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
+            state_writer.push(*docid, embeddings)?;
         }
     }
 
@@ -770,12 +777,47 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
 
         if let Some(embeds) = embeds.first() {
             for (docid, embeddings) in current_chunk_ids.iter().zip(embeds.iter()) {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
+                state_writer.push(*docid, embeddings)?;
+            }
+        }
+    }
+
+    state_writer.finish()
+}
+
+/// Writes the embeddings of each document, concatenating the embeddings of the chunks of a same document.
+///
+/// The embeddings must be pushed by increasing document id.
+struct DocumentEmbeddingsWriter {
+    writer: Writer<BufWriter<File>>,
+    current: Option<(DocumentId, Vec<f32>)>,
+}
+
+impl DocumentEmbeddingsWriter {
+    fn new(writer: Writer<BufWriter<File>>) -> Self {
+        Self { writer, current: None }
+    }
+
+    fn push(&mut self, docid: DocumentId, embedding: &[f32]) -> Result<()> {
+        match &mut self.current {
+            Some((current, embeddings)) if *current == docid => {
+                embeddings.extend_from_slice(embedding)
+            }
+            current => {
+                if let Some((docid, embeddings)) = current.replace((docid, embedding.to_vec())) {
+                    self.writer.insert(docid.to_be_bytes(), cast_slice(&embeddings))?;
+                }
             }
         }
+        Ok(())
     }
 
-    writer_into_reader(state_writer)
+    fn finish(mut self) -> Result<grenad::Reader<BufReader<File>>> {
+        if let Some((docid, embeddings)) = self.current.take() {
+            self.writer.insert(docid.to_be_bytes(), cast_slice(&embeddings))?;
+        }
+        writer_into_reader(self.writer)
+    }
 }
 
 fn embed_chunks(
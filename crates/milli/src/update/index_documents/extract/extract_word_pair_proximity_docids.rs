@@ -5,6 +5,7 @@ use std::{cmp, io};
 
 use obkv::KvReaderU16;
 
+use super::extract_docid_word_positions::words_at_position;
 use super::helpers::{
     create_sorter, create_writer, try_split_array_at, writer_into_reader, GrenadParameters,
     MergeDeladdCboRoaringBitmaps,
@@ -94,7 +95,7 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
 
                 // deletions
                 if let Some(deletion) = KvReaderDelAdd::from_slice(value).get(DelAdd::Deletion) {
-                    for (position, word) in KvReaderU16::from_slice(deletion).iter() {
+                    for (position, words) in KvReaderU16::from_slice(deletion).iter() {
                         // drain the proximity window until the head word is considered close to the word we are inserting.
                         while del_word_positions.front().is_some_and(|(_w, p)| {
                             index_proximity(*p as u32, position as u32) >= MAX_DISTANCE
@@ -105,9 +106,11 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
                             )?;
                         }
 
-                        // insert the new word.
-                        let word = std::str::from_utf8(word)?;
-                        del_word_positions.push_back((word.to_string(), position));
+                        // insert the new words.
+                        for word in words_at_position(words) {
+                            let word = std::str::from_utf8(word)?;
+                            del_word_positions.push_back((word.to_string(), position));
+                        }
                     }
 
                     while !del_word_positions.is_empty() {
@@ -127,7 +130,7 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
 
                 // additions
                 if let Some(addition) = KvReaderDelAdd::from_slice(value).get(DelAdd::Addition) {
-                    for (position, word) in KvReaderU16::from_slice(addition).iter() {
+                    for (position, words) in KvReaderU16::from_slice(addition).iter() {
                         // drain the proximity window until the head word is considered close to the word we are inserting.
                         while add_word_positions.front().is_some_and(|(_w, p)| {
                             index_proximity(*p as u32, position as u32) >= MAX_DISTANCE
@@ -138,9 +141,11 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
                             )?;
                         }
 
-                        // insert the new word.
-                        let word = std::str::from_utf8(word)?;
-                        add_word_positions.push_back((word.to_string(), position));
+                        // insert the new words.
+                        for word in words_at_position(words) {
+                            let word = std::str::from_utf8(word)?;
+                            add_word_positions.push_back((word.to_string(), position));
+                        }
                     }
 
                     while !add_word_positions.is_empty() {
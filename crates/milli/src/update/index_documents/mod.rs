@@ -82,6 +82,7 @@ pub struct IndexDocuments<'t, 'i, 'a, FP, FA> {
     added_documents: u64,
     deleted_documents: u64,
     embedders: EmbeddingConfigs,
+    arroy_progress: Progress,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -124,6 +125,7 @@ where
             added_documents: 0,
             deleted_documents: 0,
             embedders: Default::default(),
+            arroy_progress: Progress::default(),
         })
     }
 
@@ -174,6 +176,12 @@ where
         self
     }
 
+    /// The progress updated while the vector stores are built, quantized, or converted to another distance.
+    pub fn with_arroy_progress(mut self, progress: Progress) -> Self {
+        self.arroy_progress = progress;
+        self
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip_all,
@@ -259,7 +267,7 @@ where
         let pool_params = GrenadParameters {
             chunk_compression_type: self.indexer_config.chunk_compression_type,
             chunk_compression_level: self.indexer_config.chunk_compression_level,
-            max_memory: self.indexer_config.max_memory,
+            max_memory: self.indexer_config.max_memory_per_batch(),
             max_nb_chunks: self.indexer_config.max_nb_chunks, // default value, may be chosen.
         };
         let documents_chunk_size = match self.indexer_config.documents_chunk_size {
@@ -489,18 +497,37 @@ where
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
 
         // If an embedder wasn't used in the typedchunk but must be binary quantized
-        // we should insert it in `dimension`
+        // or change its distance, we should insert it in `dimension`
         for (name, action) in settings_diff.embedding_config_updates.iter() {
-            if action.is_being_quantized && !dimension.contains_key(name.as_str()) {
+            if (action.is_being_quantized || action.is_changing_distance)
+                && !dimension.contains_key(name.as_str())
+            {
                 let index = self.index.embedder_category_id.get(self.wtxn, name)?.ok_or(
                     InternalError::DatabaseMissingEntry {
                         db_name: "embedder_category_id",
                         key: None,
                     },
                 )?;
-                let reader =
-                    ArroyWrapper::new(self.index.vector_arroy, index, action.was_quantized);
-                let dim = reader.dimensions(self.wtxn)?;
+                let (quantization, distance, sparse) = settings_diff
+                    .old
+                    .embedding_configs
+                    .get(name)
+                    .map(|conf| (conf.2, conf.3, conf.0.sparse()))
+                    .unwrap_or_default();
+                let reader = ArroyWrapper::new(
+                    self.index.vector_arroy,
+                    index,
+                    quantization,
+                    distance,
+                    sparse.then_some(self.index.sparse_vectors),
+                    self.index.int8_vectors,
+                );
+                let dim = match reader.dimensions(self.wtxn) {
+                    Ok(dim) => dim,
+                    // the embedder doesn't have any vector yet, there is nothing to convert
+                    Err(arroy::Error::MissingMetadata(_)) => continue,
+                    Err(e) => return Err(e.into()),
+                };
                 dimension.insert(name.to_string(), dim);
             }
         }
@@ -513,21 +540,39 @@ where
             let embedder_index = self.index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
-            let embedder_config = settings_diff.embedding_config_updates.get(&embedder_name);
-            let was_quantized =
-                settings_diff.old.embedding_configs.get(&embedder_name).is_some_and(|conf| conf.2);
-            let is_quantizing = embedder_config.is_some_and(|action| action.is_being_quantized);
+            let (old_quantization, old_distance, sparse) = settings_diff
+                .old
+                .embedding_configs
+                .get(&embedder_name)
+                .map_or((Default::default(), Default::default(), false), |conf| {
+                    (conf.2, conf.3, conf.0.sparse())
+                });
+            let sparse_vectors = sparse.then_some(self.index.sparse_vectors);
+            let int8_vectors = self.index.int8_vectors;
+            let (quantization, distance) = settings_diff
+                .new
+                .embedding_configs
+                .get(&embedder_name)
+                .map_or((old_quantization, old_distance), |conf| (conf.2, conf.3));
+            let arroy_progress = &self.arroy_progress;
 
             pool.install(|| {
-                let mut writer = ArroyWrapper::new(vector_arroy, embedder_index, was_quantized);
+                let mut writer = ArroyWrapper::new(
+                    vector_arroy,
+                    embedder_index,
+                    old_quantization,
+                    old_distance,
+                    sparse_vectors,
+                    int8_vectors,
+                );
                 writer.build_and_quantize(
                     wtxn,
-                    // In the settings we don't have any progress to share
-                    &Progress::default(),
+                    arroy_progress,
                     &mut rng,
                     dimension,
-                    is_quantizing,
-                    self.indexer_config.max_memory,
+                    quantization,
+                    distance,
+                    self.indexer_config.max_memory_per_batch(),
                     cancel,
                 )?;
                 Result::Ok(())
@@ -2784,6 +2829,13 @@ mod tests {
                         search_embedder: Setting::NotSet,
                         indexing_embedder: Setting::NotSet,
                         binary_quantized: Setting::NotSet,
+                        quantization: Setting::NotSet,
+                        distance: Setting::NotSet,
+                        multi_vector: Setting::NotSet,
+                        chunking: Setting::NotSet,
+                        model_path: Setting::NotSet,
+                        threads: Setting::NotSet,
+                        sparse: Setting::NotSet,
                     }),
                 );
                 settings.set_embedder_settings(embedders);
@@ -2813,7 +2865,13 @@ mod tests {
         );
         let res = index
             .search(&rtxn)
-            .semantic(embedder_name, embedder, false, Some([0.0, 1.0, 2.0].to_vec()))
+            .semantic(
+                embedder_name,
+                embedder,
+                Default::default(),
+                Default::default(),
+                Some([0.0, 1.0, 2.0].to_vec()),
+            )
             .execute()
             .unwrap();
         assert_eq!(res.documents_ids.len(), 3);
@@ -126,7 +126,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             indexer_settings.chunk_compression_type,
             indexer_settings.chunk_compression_level,
             indexer_settings.max_nb_chunks,
-            indexer_settings.max_memory.map(|mem| mem / 2),
+            indexer_settings.max_memory_per_batch().map(|mem| mem / 2),
             true,
         );
 
@@ -137,7 +137,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             indexer_settings.chunk_compression_type,
             indexer_settings.chunk_compression_level,
             indexer_settings.max_nb_chunks,
-            indexer_settings.max_memory.map(|mem| mem / 2),
+            indexer_settings.max_memory_per_batch().map(|mem| mem / 2),
             true,
         );
         let documents_ids = index.documents_ids(wtxn)?;
@@ -827,7 +827,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                 self.indexer_settings.chunk_compression_type,
                 self.indexer_settings.chunk_compression_level,
                 self.indexer_settings.max_nb_chunks,
-                self.indexer_settings.max_memory.map(|mem| mem / 2),
+                self.indexer_settings.max_memory_per_batch().map(|mem| mem / 2),
                 true,
             ))
         } else {
@@ -841,10 +841,19 @@ impl<'a, 'i> Transform<'a, 'i> {
                 if let Some(WriteBackToDocuments { embedder_id, user_provided }) =
                     action.write_back()
                 {
+                    let (quantization, distance, sparse) = settings_diff
+                        .old
+                        .embedding_configs
+                        .get(name)
+                        .map(|conf| (conf.2, conf.3, conf.0.sparse()))
+                        .unwrap_or_default();
                     let reader = ArroyWrapper::new(
                         self.index.vector_arroy,
                         *embedder_id,
-                        action.was_quantized,
+                        quantization,
+                        distance,
+                        sparse.then_some(self.index.sparse_vectors),
+                        self.index.int8_vectors,
                     );
                     Some((name.as_str(), (reader, user_provided)))
                 } else {
@@ -865,7 +874,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                     self.indexer_settings.chunk_compression_type,
                     self.indexer_settings.chunk_compression_level,
                     self.indexer_settings.max_nb_chunks,
-                    self.indexer_settings.max_memory.map(|mem| mem / 2),
+                    self.indexer_settings.max_memory_per_batch().map(|mem| mem / 2),
                     true,
                 ))
             } else {
@@ -940,7 +949,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         let grenad_params = GrenadParameters {
             chunk_compression_type: self.indexer_settings.chunk_compression_type,
             chunk_compression_level: self.indexer_settings.chunk_compression_level,
-            max_memory: self.indexer_settings.max_memory,
+            max_memory: self.indexer_settings.max_memory_per_batch(),
             max_nb_chunks: self.indexer_settings.max_nb_chunks, // default value, may be chosen.
         };
 
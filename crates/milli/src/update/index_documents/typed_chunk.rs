@@ -16,7 +16,7 @@ use super::helpers::{
     MergeIgnoreValues,
 };
 use crate::external_documents_ids::{DocumentOperation, DocumentOperationKind};
-use crate::facet::FacetType;
+use crate::facet::{parse_date, FacetType};
 use crate::index::db_name::DOCUMENTS;
 use crate::index::IndexEmbeddingConfig;
 use crate::proximity::MAX_DISTANCE;
@@ -559,6 +559,8 @@ pub(crate) fn write_typed_chunk_into_index(
 
             let index_fid_docid_facet_strings =
                 index.field_id_docid_facet_strings.remap_types::<Bytes, Bytes>();
+            let mut date_faceted_fields = index.date_faceted_fields(wtxn)?;
+            let mut new_date_faceted_fields = false;
             let mut iter = merger.into_stream_merger_iter()?;
             while let Some((key, value)) = iter.next()? {
                 let reader = KvReaderDelAdd::from_slice(value);
@@ -573,8 +575,23 @@ pub(crate) fn write_typed_chunk_into_index(
                             index_fid_docid_facet_strings.put(wtxn, key, new)?
                         }
                     }
+
+                    // the facet distribution only looks for dates in the fields that contain some
+                    if let Some(original) = reader.get(DelAdd::Addition) {
+                        let (field_id_bytes, _) = try_split_array_at(key).unwrap();
+                        let field_id = FieldId::from_be_bytes(field_id_bytes);
+                        if !date_faceted_fields.contains(&field_id)
+                            && std::str::from_utf8(original).ok().and_then(parse_date).is_some()
+                        {
+                            date_faceted_fields.insert(field_id);
+                            new_date_faceted_fields = true;
+                        }
+                    }
                 }
             }
+            if new_date_faceted_fields {
+                index.put_date_faceted_fields(wtxn, &date_faceted_fields)?;
+            }
         }
         TypedChunk::GeoPoints(_) => {
             let span = tracing::trace_span!(target: "indexing::write_db", "geo_points");
@@ -664,10 +681,23 @@ pub(crate) fn write_typed_chunk_into_index(
             let embedder_index = index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
-            let binary_quantized =
-                settings_diff.old.embedding_configs.get(&embedder_name).is_some_and(|conf| conf.2);
-            // FIXME: allow customizing distance
-            let writer = ArroyWrapper::new(index.vector_arroy, embedder_index, binary_quantized);
+            // the vectors are written with the previous quantization and distance,
+            // they are converted when building the trees
+            let (quantization, distance, sparse) = settings_diff
+                .old
+                .embedding_configs
+                .get(&embedder_name)
+                .map_or((Default::default(), Default::default(), false), |conf| {
+                    (conf.2, conf.3, conf.0.sparse())
+                });
+            let writer = ArroyWrapper::new(
+                index.vector_arroy,
+                embedder_index,
+                quantization,
+                distance,
+                sparse.then_some(index.sparse_vectors),
+                index.int8_vectors,
+            );
 
             // remove vectors for docids we want them removed
             let merger = remove_vectors_builder.build();
@@ -683,12 +713,21 @@ pub(crate) fn write_typed_chunk_into_index(
             while let Some((key, value)) = iter.next()? {
                 let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
                 let data = pod_collect_to_vec(value);
-                // it is a code error to have embeddings and not expected_dimension
-                let embeddings = crate::vector::Embeddings::from_inner(data, expected_dimension)
-                    // code error if we somehow got the wrong dimension
-                    .unwrap();
+                // sparse embeddings have no fixed dimension, they are split by the writer
+                let (embeddings, embedding_count) = if sparse {
+                    let embedding_count = crate::vector::sparse::split(&data).count();
+                    (crate::vector::Embeddings::from_single_embedding(data), embedding_count)
+                } else {
+                    // it is a code error to have embeddings and not expected_dimension
+                    let embeddings =
+                        crate::vector::Embeddings::from_inner(data, expected_dimension)
+                            // code error if we somehow got the wrong dimension
+                            .unwrap();
+                    let embedding_count = embeddings.embedding_count();
+                    (embeddings, embedding_count)
+                };
 
-                if embeddings.embedding_count() > usize::from(u8::MAX) {
+                if embedding_count > usize::from(u8::MAX) {
                     let external_docid = if let Ok(Some(Ok(index))) = index
                         .external_id_of(wtxn, std::iter::once(docid))
                         .map(|it| it.into_iter().next())
@@ -699,7 +738,7 @@ pub(crate) fn write_typed_chunk_into_index(
                     };
                     return Err(crate::Error::UserError(crate::UserError::TooManyVectors(
                         external_docid,
-                        embeddings.embedding_count(),
+                        embedding_count,
                     )));
                 }
                 writer.add_items(wtxn, docid, &embeddings)?;
@@ -14,6 +14,8 @@ pub struct IndexerConfig {
     pub thread_pool: Option<ThreadPoolNoAbort>,
     pub max_positions_per_attributes: Option<u32>,
     pub skip_index_budget: bool,
+    /// The maximum number of batches of different indexes that are processed at the same time.
+    pub max_concurrent_batches: usize,
 }
 
 impl IndexerConfig {
@@ -21,10 +23,16 @@ impl IndexerConfig {
         GrenadParameters {
             chunk_compression_type: self.chunk_compression_type,
             chunk_compression_level: self.chunk_compression_level,
-            max_memory: self.max_memory,
+            max_memory: self.max_memory_per_batch(),
             max_nb_chunks: self.max_nb_chunks,
         }
     }
+
+    /// The memory a single batch can use, the `max_memory` is shared between the batches
+    /// that are processed at the same time.
+    pub fn max_memory_per_batch(&self) -> Option<usize> {
+        self.max_memory.map(|mm| mm / self.max_concurrent_batches.max(1))
+    }
 }
 
 impl Default for IndexerConfig {
@@ -39,6 +47,7 @@ impl Default for IndexerConfig {
             thread_pool: None,
             max_positions_per_attributes: None,
             skip_index_budget: false,
+            max_concurrent_batches: 1,
         }
     }
 }
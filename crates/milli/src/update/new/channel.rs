@@ -24,7 +24,10 @@ use super::thread_local::{FullySend, ThreadLocal};
 use super::StdResult;
 use crate::heed_codec::facet::{FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec};
 use crate::index::db_name;
-use crate::index::main_key::{GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
+use crate::index::main_key::{
+    GEOJSON_FACETED_DOCUMENTS_IDS_KEY, GEOJSON_RTREE_KEY, GEO_FACETED_DOCUMENTS_IDS_KEY,
+    GEO_RTREE_KEY,
+};
 use crate::update::new::KvReaderFieldId;
 use crate::vector::Embedding;
 use crate::{CboRoaringBitmapCodec, DocumentId, Error, Index, InternalError};
@@ -172,6 +175,11 @@ impl LargeVectors {
     pub fn read_embeddings(&self, dimensions: usize) -> impl Iterator<Item = &[f32]> {
         self.embeddings.chunks_exact(dimensions).map(bytemuck::cast_slice)
     }
+
+    /// Reads the embeddings as a single flat vector, as sparse embeddings have no fixed dimension.
+    pub fn read_all_embeddings(&self) -> Vec<f32> {
+        bytemuck::pod_collect_to_vec(&self.embeddings)
+    }
 }
 
 impl<'a> WriterBbqueueReceiver<'a> {
@@ -268,10 +276,10 @@ impl EntryHeader {
         Self::variant_size() + mem::size_of::<ArroyDeleteVector>()
     }
 
-    /// The `dimensions` corresponds to the number of `f32` in the embedding.
-    fn total_set_vectors_size(count: usize, dimensions: usize) -> usize {
-        let embedding_size = dimensions * mem::size_of::<f32>();
-        Self::variant_size() + mem::size_of::<ArroySetVectors>() + embedding_size * count
+    /// The `float_count` corresponds to the total number of `f32` in the embeddings.
+    fn total_set_vectors_size(float_count: usize) -> usize {
+        let embeddings_size = float_count * mem::size_of::<f32>();
+        Self::variant_size() + mem::size_of::<ArroySetVectors>() + embeddings_size
     }
 
     fn header_size(&self) -> usize {
@@ -518,13 +526,13 @@ impl<'b> ExtractorBbqueueSender<'b> {
         let refcell = self.producers.get().unwrap();
         let mut producer = refcell.0.borrow_mut_or_yield();
 
-        // If there are no vectors we specify the dimensions
-        // to zero to allocate no extra space at all
-        let dimensions = embeddings.first().map_or(0, |emb| emb.len());
+        // The embeddings are not required to share the same length,
+        // as sparse embeddings are written one after the other
+        let float_count = embeddings.iter().map(|emb| emb.len()).sum();
 
         let arroy_set_vector = ArroySetVectors { docid, embedder_id, _padding: [0; 3] };
         let payload_header = EntryHeader::ArroySetVectors(arroy_set_vector);
-        let total_length = EntryHeader::total_set_vectors_size(embeddings.len(), dimensions);
+        let total_length = EntryHeader::total_set_vectors_size(float_count);
         if total_length > max_grant {
             let mut value_file = tempfile::tempfile().map(BufWriter::new)?;
             for embedding in embeddings {
@@ -553,12 +561,12 @@ impl<'b> ExtractorBbqueueSender<'b> {
                 let (header_bytes, remaining) = grant.split_at_mut(header_size);
                 payload_header.serialize_into(header_bytes);
 
-                if dimensions != 0 {
-                    let output_iter =
-                        remaining.chunks_exact_mut(dimensions * mem::size_of::<f32>());
-                    for (embedding, output) in embeddings.iter().zip(output_iter) {
-                        output.copy_from_slice(bytemuck::cast_slice(embedding));
-                    }
+                let mut remaining = remaining;
+                for embedding in embeddings {
+                    let embedding_bytes: &[u8] = bytemuck::cast_slice(embedding);
+                    let (output, tail) = remaining.split_at_mut(embedding_bytes.len());
+                    output.copy_from_slice(embedding_bytes);
+                    remaining = tail;
                 }
 
                 Ok(())
@@ -937,15 +945,6 @@ impl EmbeddingSender<'_, '_> {
     ) -> crate::Result<()> {
         self.0.set_vectors(docid, embedder_id, &embeddings[..])
     }
-
-    pub fn set_vector(
-        &self,
-        docid: DocumentId,
-        embedder_id: u8,
-        embedding: Embedding,
-    ) -> crate::Result<()> {
-        self.0.set_vectors(docid, embedder_id, &[embedding])
-    }
 }
 
 #[derive(Clone, Copy)]
@@ -953,20 +952,36 @@ pub struct GeoSender<'a, 'b>(&'a ExtractorBbqueueSender<'b>);
 
 impl GeoSender<'_, '_> {
     pub fn set_rtree(&self, value: Mmap) -> StdResult<(), SendError<()>> {
+        self.set_large_main_entry(GEO_RTREE_KEY, value)
+    }
+
+    pub fn set_geo_faceted(&self, bitmap: &RoaringBitmap) -> crate::Result<()> {
+        self.set_main_bitmap(GEO_FACETED_DOCUMENTS_IDS_KEY, bitmap)
+    }
+
+    pub fn set_geojson_rtree(&self, value: Mmap) -> StdResult<(), SendError<()>> {
+        self.set_large_main_entry(GEOJSON_RTREE_KEY, value)
+    }
+
+    pub fn set_geojson_faceted(&self, bitmap: &RoaringBitmap) -> crate::Result<()> {
+        self.set_main_bitmap(GEOJSON_FACETED_DOCUMENTS_IDS_KEY, bitmap)
+    }
+
+    fn set_large_main_entry(&self, key: &str, value: Mmap) -> StdResult<(), SendError<()>> {
         self.0
             .sender
             .send(ReceiverAction::LargeEntry(LargeEntry {
                 database: Database::Main,
-                key: GEO_RTREE_KEY.to_string().into_bytes().into_boxed_slice(),
+                key: key.to_string().into_bytes().into_boxed_slice(),
                 value,
             }))
             .map_err(|_| SendError(()))
     }
 
-    pub fn set_geo_faceted(&self, bitmap: &RoaringBitmap) -> crate::Result<()> {
+    fn set_main_bitmap(&self, key: &str, bitmap: &RoaringBitmap) -> crate::Result<()> {
         let database = Database::Main;
         let value_length = bitmap.serialized_size();
-        let key = GEO_FACETED_DOCUMENTS_IDS_KEY.as_bytes();
+        let key = key.as_bytes();
         let key_length = key.len().try_into().ok().and_then(NonZeroU16::new).ok_or_else(|| {
             InternalError::StorePut {
                 database_name: database.database_name(),
@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::ops::DerefMut as _;
 
 use bumpalo::collections::Vec as BVec;
@@ -10,6 +10,7 @@ use serde_json::Value;
 use super::super::cache::BalancedCaches;
 use super::facet_document::extract_document_facets;
 use super::FacetKind;
+use crate::facet::parse_date;
 use crate::fields_ids_map::metadata::Metadata;
 use crate::filterable_attributes_rules::match_faceted_field;
 use crate::heed_codec::facet::OrderedF64Codec;
@@ -35,6 +36,7 @@ pub struct FacetedExtractorData<'a, 'b> {
     asc_desc_fields: &'a HashSet<String>,
     distinct_field: &'a Option<String>,
     is_geo_enabled: bool,
+    date_faceted_fields: &'a ThreadLocal<FullySend<RefCell<BTreeSet<FieldId>>>>,
 }
 
 impl<'extractor> Extractor<'extractor> for FacetedExtractorData<'_, '_> {
@@ -53,6 +55,7 @@ impl<'extractor> Extractor<'extractor> for FacetedExtractorData<'_, '_> {
         changes: impl Iterator<Item = Result<DocumentChange<'doc>>>,
         context: &DocumentChangeContext<Self::Data>,
     ) -> Result<()> {
+        let date_faceted_fields = &self.date_faceted_fields.get_or_default().0;
         for change in changes {
             let change = change?;
             FacetedDocidsExtractor::extract_document_change(
@@ -62,6 +65,7 @@ impl<'extractor> Extractor<'extractor> for FacetedExtractorData<'_, '_> {
                 self.asc_desc_fields,
                 self.distinct_field,
                 self.is_geo_enabled,
+                date_faceted_fields,
                 change,
                 self.sender,
             )?
@@ -81,6 +85,7 @@ impl FacetedDocidsExtractor {
         asc_desc_fields: &HashSet<String>,
         distinct_field: &Option<String>,
         is_geo_enabled: bool,
+        date_faceted_fields: &RefCell<BTreeSet<FieldId>>,
         document_change: DocumentChange,
         sender: &FieldIdDocidFacetSender,
     ) -> Result<()> {
@@ -111,6 +116,7 @@ impl FacetedDocidsExtractor {
                         fid,
                         meta,
                         filterable_attributes,
+                        date_faceted_fields,
                         depth,
                         value,
                     )
@@ -157,6 +163,7 @@ impl FacetedDocidsExtractor {
                             fid,
                             meta,
                             filterable_attributes,
+                            date_faceted_fields,
                             depth,
                             value,
                         )
@@ -183,6 +190,7 @@ impl FacetedDocidsExtractor {
                             fid,
                             meta,
                             filterable_attributes,
+                            date_faceted_fields,
                             depth,
                             value,
                         )
@@ -209,6 +217,7 @@ impl FacetedDocidsExtractor {
                         fid,
                         meta,
                         filterable_attributes,
+                        date_faceted_fields,
                         depth,
                         value,
                     )
@@ -231,6 +240,7 @@ impl FacetedDocidsExtractor {
         fid: FieldId,
         meta: Metadata,
         filterable_attributes: &[FilterableAttributesRule],
+        date_faceted_fields: &RefCell<BTreeSet<FieldId>>,
         depth: perm_json_p::Depth,
         value: &Value,
     ) -> Result<()> {
@@ -251,27 +261,20 @@ impl FacetedDocidsExtractor {
         match value {
             // Number
             // key: fid - level - orderedf64 - originalf64
-            Value::Number(number) => {
-                let mut ordered = [0u8; 16];
-                if number
-                    .as_f64()
-                    .and_then(|n| OrderedF64Codec::serialize_into(n, &mut ordered).ok())
-                    .is_some()
-                {
-                    let mut number = BVec::with_capacity_in(16, doc_alloc);
-                    number.extend_from_slice(&ordered);
-                    facet_fn(del_add_facet_value, fid, number, FacetKind::Number);
-
-                    buffer.clear();
-                    buffer.push(FacetKind::Number as u8);
-                    buffer.extend_from_slice(&fid.to_be_bytes());
-                    buffer.push(0); // level 0
-                    buffer.extend_from_slice(&ordered);
-                    cache_fn(cached_sorter, &buffer, docid)
-                } else {
-                    Ok(())
-                }
-            }
+            Value::Number(number) => match number.as_f64() {
+                Some(number) => Self::facet_number(
+                    doc_alloc,
+                    cached_sorter,
+                    &cache_fn,
+                    del_add_facet_value,
+                    &facet_fn,
+                    docid,
+                    fid,
+                    &mut buffer,
+                    number,
+                ),
+                None => Ok(()),
+            },
             // String
             // key: fid - level - truncated_string
             Value::String(s) if !s.is_empty() => {
@@ -286,7 +289,28 @@ impl FacetedDocidsExtractor {
                 buffer.extend_from_slice(&fid.to_be_bytes());
                 buffer.push(0); // level 0
                 buffer.extend_from_slice(truncated.as_bytes());
-                cache_fn(cached_sorter, &buffer, docid)
+                cache_fn(cached_sorter, &buffer, docid)?;
+
+                // Date
+                // Strings that are dates are also stored as their Unix timestamp,
+                // the fields containing dates are recorded for the facet distribution
+                match parse_date(s) {
+                    Some(timestamp) => {
+                        date_faceted_fields.borrow_mut_or_yield().insert(fid);
+                        Self::facet_number(
+                            doc_alloc,
+                            cached_sorter,
+                            &cache_fn,
+                            del_add_facet_value,
+                            &facet_fn,
+                            docid,
+                            fid,
+                            &mut buffer,
+                            timestamp,
+                        )
+                    }
+                    None => Ok(()),
+                }
             }
             // Bool is handled as a string
             Value::Bool(b) => {
@@ -346,6 +370,37 @@ impl FacetedDocidsExtractor {
             _ => Ok(()),
         }
     }
+
+    /// Number
+    /// key: fid - level - orderedf64 - originalf64
+    #[allow(clippy::too_many_arguments)]
+    fn facet_number<'extractor, 'doc>(
+        doc_alloc: &'doc Bump,
+        cached_sorter: &mut BalancedCaches<'extractor>,
+        cache_fn: impl Fn(&mut BalancedCaches<'extractor>, &[u8], u32) -> Result<()>,
+        del_add_facet_value: &mut DelAddFacetValue<'doc>,
+        facet_fn: impl Fn(&mut DelAddFacetValue<'doc>, FieldId, BVec<'doc, u8>, FacetKind),
+        docid: DocumentId,
+        fid: FieldId,
+        buffer: &mut BVec<'_, u8>,
+        number: f64,
+    ) -> Result<()> {
+        let mut ordered = [0u8; 16];
+        if OrderedF64Codec::serialize_into(number, &mut ordered).is_err() {
+            return Ok(());
+        }
+
+        let mut number = BVec::with_capacity_in(16, doc_alloc);
+        number.extend_from_slice(&ordered);
+        facet_fn(del_add_facet_value, fid, number, FacetKind::Number);
+
+        buffer.clear();
+        buffer.push(FacetKind::Number as u8);
+        buffer.extend_from_slice(&fid.to_be_bytes());
+        buffer.push(0); // level 0
+        buffer.extend_from_slice(&ordered);
+        cache_fn(cached_sorter, buffer, docid)
+    }
 }
 
 struct DelAddFacetValue<'doc> {
@@ -458,6 +513,7 @@ impl FacetedDocidsExtractor {
         indexing_context: IndexingContext<'fid, 'indexer, 'index, MSP>,
         extractor_allocs: &'extractor mut ThreadLocal<FullySend<Bump>>,
         sender: &FieldIdDocidFacetSender,
+        date_faceted_fields: &mut BTreeSet<FieldId>,
         step: IndexingStep,
     ) -> Result<Vec<BalancedCaches<'extractor>>>
     where
@@ -471,6 +527,7 @@ impl FacetedDocidsExtractor {
         let distinct_field = index.distinct_field(&rtxn)?.map(|s| s.to_string());
         let is_geo_enabled = index.is_geo_enabled(&rtxn)?;
         let datastore = ThreadLocal::new();
+        let date_faceted_fields_store = ThreadLocal::new();
 
         {
             let span =
@@ -486,6 +543,7 @@ impl FacetedDocidsExtractor {
                 asc_desc_fields: &asc_desc_fields,
                 distinct_field: &distinct_field,
                 is_geo_enabled,
+                date_faceted_fields: &date_faceted_fields_store,
             };
             extract(
                 document_changes,
@@ -497,6 +555,10 @@ impl FacetedDocidsExtractor {
             )?;
         }
 
+        for FullySend(fields) in date_faceted_fields_store {
+            date_faceted_fields.extend(fields.into_inner());
+        }
+
         Ok(datastore.into_iter().map(RefCell::into_inner).collect())
     }
 }
@@ -5,21 +5,27 @@ use std::{iter, mem, result};
 
 use bumpalo::Bump;
 use bytemuck::{bytes_of, pod_read_unaligned, Pod, Zeroable};
+use grenad::ReaderCursor;
 use heed::RoTxn;
 use serde_json::value::RawValue;
 use serde_json::Value;
 
-use crate::error::GeoError;
+use crate::constants::RESERVED_GEOJSON_FIELD_NAME;
+use crate::error::{GeoError, SerializationError};
 use crate::update::new::document::Document;
 use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
 use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::thread_local::MostlySend;
 use crate::update::new::DocumentChange;
-use crate::update::GrenadParameters;
-use crate::{lat_lng_to_xyz, DocumentId, GeoPoint, Index, InternalError, Result};
+use crate::update::{create_sorter, GrenadParameters, KeepFirst};
+use crate::{lat_lng_to_xyz, DocumentId, GeoJsonGeometry, GeoPoint, Index, InternalError, Result};
 
 pub struct GeoExtractor {
     grenad_parameters: GrenadParameters,
+    /// Whether the `_geo` points must be extracted, the `_geojson`
+    /// geometries can be extracted on their own.
+    geo_enabled: bool,
+    geojson_enabled: bool,
 }
 
 impl GeoExtractor {
@@ -28,8 +34,10 @@ impl GeoExtractor {
         index: &Index,
         grenad_parameters: GrenadParameters,
     ) -> Result<Option<Self>> {
-        if index.is_geo_enabled(rtxn)? {
-            Ok(Some(GeoExtractor { grenad_parameters }))
+        let geo_enabled = index.is_geo_enabled(rtxn)?;
+        let geojson_enabled = index.is_geojson_enabled(rtxn)?;
+        if geo_enabled || geojson_enabled {
+            Ok(Some(GeoExtractor { grenad_parameters, geo_enabled, geojson_enabled }))
         } else {
             Ok(None)
         }
@@ -64,11 +72,23 @@ pub struct GeoExtractorData<'extractor> {
     /// Contains a packed list of `ExtractedGeoPoint` of the inserted geo points
     /// data structures if we have spilled to disk.
     spilled_inserted: Option<BufWriter<File>>,
+    /// The `_geojson` geometries that were removed and inserted, following the same
+    /// rules as the geo points. They are keyed by document id and spilled to disk
+    /// by the sorters when they take too much memory.
+    removed_geometries: grenad::Sorter<KeepFirst>,
+    inserted_geometries: grenad::Sorter<KeepFirst>,
 }
 
 impl<'extractor> GeoExtractorData<'extractor> {
     pub fn freeze(self) -> Result<FrozenGeoExtractorData<'extractor>> {
-        let GeoExtractorData { removed, inserted, spilled_removed, spilled_inserted } = self;
+        let GeoExtractorData {
+            removed,
+            inserted,
+            spilled_removed,
+            spilled_inserted,
+            removed_geometries,
+            inserted_geometries,
+        } = self;
 
         Ok(FrozenGeoExtractorData {
             removed: removed.into_bump_slice(),
@@ -79,8 +99,32 @@ impl<'extractor> GeoExtractorData<'extractor> {
             spilled_inserted: spilled_inserted
                 .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
                 .transpose()?,
+            removed_geometries: geometries_sorter_into_cursors(removed_geometries)?,
+            inserted_geometries: geometries_sorter_into_cursors(inserted_geometries)?,
         })
     }
+
+    fn push_geometry(
+        sorter: &mut grenad::Sorter<KeepFirst>,
+        docid: DocumentId,
+        geometry: &GeoJsonGeometry,
+    ) -> Result<()> {
+        let bytes = bincode::serialize(geometry).map_err(InternalError::BincodeError)?;
+        sorter.insert(docid.to_be_bytes(), bytes)?;
+        Ok(())
+    }
+}
+
+fn geometries_sorter_into_cursors(
+    sorter: grenad::Sorter<KeepFirst>,
+) -> Result<Vec<ReaderCursor<BufReader<File>>>> {
+    sorter
+        .into_reader_cursors()?
+        .into_iter()
+        .map(ReaderCursor::into_inner)
+        .map(BufReader::new)
+        .map(|bufreader| Ok(grenad::Reader::new(bufreader)?.into_cursor()?))
+        .collect()
 }
 
 unsafe impl MostlySend for GeoExtractorData<'_> {}
@@ -90,6 +134,8 @@ pub struct FrozenGeoExtractorData<'extractor> {
     pub inserted: &'extractor [ExtractedGeoPoint],
     pub spilled_removed: Option<BufReader<File>>,
     pub spilled_inserted: Option<BufReader<File>>,
+    pub removed_geometries: Vec<ReaderCursor<BufReader<File>>>,
+    pub inserted_geometries: Vec<ReaderCursor<BufReader<File>>>,
 }
 
 impl FrozenGeoExtractorData<'_> {
@@ -112,6 +158,39 @@ impl FrozenGeoExtractorData<'_> {
             .map(Ok)
             .chain(iterator_over_spilled_geopoints(&mut self.spilled_inserted)?))
     }
+
+    pub fn iter_and_clear_removed_geometries(
+        &mut self,
+    ) -> impl Iterator<Item = Result<(DocumentId, GeoJsonGeometry)>> {
+        iterator_over_spilled_geometries(mem::take(&mut self.removed_geometries))
+    }
+
+    pub fn iter_and_clear_inserted_geometries(
+        &mut self,
+    ) -> impl Iterator<Item = Result<(DocumentId, GeoJsonGeometry)>> {
+        iterator_over_spilled_geometries(mem::take(&mut self.inserted_geometries))
+    }
+}
+
+fn iterator_over_spilled_geometries(
+    cursors: Vec<ReaderCursor<BufReader<File>>>,
+) -> impl Iterator<Item = Result<(DocumentId, GeoJsonGeometry)>> {
+    cursors.into_iter().flat_map(|mut cursor| {
+        iter::from_fn(move || match cursor.move_on_next() {
+            Ok(Some((key, value))) => Some(decode_geometry(key, value)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e.into())),
+        })
+    })
+}
+
+fn decode_geometry(key: &[u8], value: &[u8]) -> Result<(DocumentId, GeoJsonGeometry)> {
+    let docid = key
+        .try_into()
+        .map(DocumentId::from_be_bytes)
+        .map_err(|_| InternalError::from(SerializationError::Decoding { db_name: None }))?;
+    let geometry = bincode::deserialize(value).map_err(InternalError::BincodeError)?;
+    Ok((docid, geometry))
 }
 
 fn iterator_over_spilled_geopoints(
@@ -135,6 +214,22 @@ fn iterator_over_spilled_geopoints(
     }))
 }
 
+impl GeoExtractor {
+    fn geometries_sorter(&self) -> grenad::Sorter<KeepFirst> {
+        // the memory is shared by the sorters of the removed and inserted geometries
+        let max_memory = self.grenad_parameters.max_memory_by_thread().map(|mm| mm / 2);
+        create_sorter(
+            grenad::SortAlgorithm::Unstable,
+            KeepFirst,
+            self.grenad_parameters.chunk_compression_type,
+            self.grenad_parameters.chunk_compression_level,
+            self.grenad_parameters.max_nb_chunks,
+            max_memory,
+            false,
+        )
+    }
+}
+
 impl<'extractor> Extractor<'extractor> for GeoExtractor {
     type Data = RefCell<GeoExtractorData<'extractor>>;
 
@@ -144,6 +239,8 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
             inserted: bumpalo::collections::Vec::new_in(extractor_alloc),
             spilled_inserted: None,
             spilled_removed: None,
+            removed_geometries: self.geometries_sorter(),
+            inserted_geometries: self.geometries_sorter(),
         }))
     }
 
@@ -174,6 +271,7 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let current = deletion.current(rtxn, index, db_fields_ids_map)?;
                     let current_geo = current
                         .geo_field()?
+                        .filter(|_| self.geo_enabled)
                         .map(|geo| extract_geo_coordinates(external_id, geo))
                         .transpose()?;
 
@@ -184,6 +282,20 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                             None => data_ref.removed.push(geopoint),
                         }
                     }
+
+                    let current_geojson = current
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .filter(|_| self.geojson_enabled)
+                        .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                        .transpose()?;
+
+                    if let Some(geometry) = current_geojson.flatten() {
+                        GeoExtractorData::push_geometry(
+                            &mut data_ref.removed_geometries,
+                            docid,
+                            &geometry,
+                        )?;
+                    }
                 }
                 DocumentChange::Update(update) => {
                     let current = update.current(rtxn, index, db_fields_ids_map)?;
@@ -192,12 +304,14 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
 
                     let current_geo = current
                         .geo_field()?
+                        .filter(|_| self.geo_enabled)
                         .map(|geo| extract_geo_coordinates(external_id, geo))
                         .transpose()?;
 
                     let updated_geo = update
                         .merged(rtxn, index, db_fields_ids_map)?
                         .geo_field()?
+                        .filter(|_| self.geo_enabled)
                         .map(|geo| extract_geo_coordinates(external_id, geo))
                         .transpose()?;
 
@@ -221,6 +335,39 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                             }
                         }
                     }
+
+                    let current_geojson = current
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .filter(|_| self.geojson_enabled)
+                        .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                        .transpose()?
+                        .flatten();
+
+                    let updated_geojson = update
+                        .merged(rtxn, index, db_fields_ids_map)?
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .filter(|_| self.geojson_enabled)
+                        .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                        .transpose()?
+                        .flatten();
+
+                    if current_geojson != updated_geojson {
+                        if let Some(geometry) = current_geojson {
+                            GeoExtractorData::push_geometry(
+                                &mut data_ref.removed_geometries,
+                                docid,
+                                &geometry,
+                            )?;
+                        }
+
+                        if let Some(geometry) = updated_geojson {
+                            GeoExtractorData::push_geometry(
+                                &mut data_ref.inserted_geometries,
+                                docid,
+                                &geometry,
+                            )?;
+                        }
+                    }
                 }
                 DocumentChange::Insertion(insertion) => {
                     let external_id = insertion.external_document_id();
@@ -229,6 +376,7 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let inserted_geo = insertion
                         .inserted()
                         .geo_field()?
+                        .filter(|_| self.geo_enabled)
                         .map(|geo| extract_geo_coordinates(external_id, geo))
                         .transpose()?;
 
@@ -239,6 +387,21 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                             None => data_ref.inserted.push(geopoint),
                         }
                     }
+
+                    let inserted_geojson = insertion
+                        .inserted()
+                        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+                        .filter(|_| self.geojson_enabled)
+                        .map(|geojson| extract_geojson_geometry(external_id, geojson))
+                        .transpose()?;
+
+                    if let Some(geometry) = inserted_geojson.flatten() {
+                        GeoExtractorData::push_geometry(
+                            &mut data_ref.inserted_geometries,
+                            docid,
+                            &geometry,
+                        )?;
+                    }
                 }
             }
         }
@@ -317,6 +480,26 @@ pub fn extract_geo_coordinates(
     }
 }
 
+/// Extracts and validates the geometry of a document `_geojson` field.
+///
+/// It can be a GeoJSON `Point`, `MultiPoint`, `LineString`, or `Polygon` geometry object.
+pub fn extract_geojson_geometry(
+    external_id: &str,
+    raw_value: &RawValue,
+) -> Result<Option<GeoJsonGeometry>> {
+    match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
+        Value::Null => Ok(None),
+        value => match GeoJsonGeometry::from_json(&value) {
+            Ok(geometry) => Ok(Some(geometry)),
+            Err(reason) => Err(Box::new(GeoError::BadGeoJson {
+                document_id: Value::from(external_id),
+                reason,
+            })
+            .into()),
+        },
+    }
+}
+
 /// Extracts and validate that a serde JSON Value is actually a finite f64.
 pub fn extract_finite_float_from_value(value: Value) -> result::Result<f64, Value> {
     let number = match value {
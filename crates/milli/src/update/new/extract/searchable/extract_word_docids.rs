@@ -270,9 +270,11 @@ impl WordDocidsExtractors {
         let tokenizer = builder.build();
         let localized_attributes_rules =
             indexing_context.index.localized_attributes_rules(&rtxn)?.unwrap_or_default();
+        let analyzers = indexing_context.index.analyzers(&rtxn)?;
         let document_tokenizer = DocumentTokenizer {
             tokenizer: &tokenizer,
             localized_attributes_rules: &localized_attributes_rules,
+            analyzers: &analyzers,
             max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
         };
         let extractor_data = WordDocidsExtractorData {
@@ -9,8 +9,8 @@ use crate::update::new::extract::perm_json_p::{
     seek_leaf_values_in_array, seek_leaf_values_in_object, Depth,
 };
 use crate::{
-    FieldId, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule, Result, UserError,
-    MAX_WORD_LENGTH,
+    AnalyzerRule, FieldId, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule, Result,
+    Stemmers, UserError, MAX_WORD_LENGTH,
 };
 
 // todo: should be crate::proximity::MAX_DISTANCE but it has been forgotten
@@ -19,6 +19,7 @@ const MAX_DISTANCE: u32 = 8;
 pub struct DocumentTokenizer<'a> {
     pub tokenizer: &'a Tokenizer<'a>,
     pub localized_attributes_rules: &'a [LocalizedAttributesRule],
+    pub analyzers: &'a [AnalyzerRule],
     pub max_positions_per_attributes: u32,
 }
 
@@ -85,6 +86,40 @@ impl DocumentTokenizer<'_> {
             return Ok(());
         }
 
+        if let Some(analyzer) = AnalyzerRule::find(self.analyzers, field_name) {
+            let text = match value {
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                Value::String(text) => text.clone(),
+                _ => return Ok(()),
+            };
+
+            // the grams of a word share its position, relative to the start of the value.
+            let start_offset = *position;
+            for (index, token) in analyzer.analyze(&text) {
+                let index = start_offset + index;
+                if index >= self.max_positions_per_attributes {
+                    break;
+                }
+                // keep a word only if it is not empty and fit in a LMDB key.
+                if !token.is_empty() && token.len() <= MAX_WORD_LENGTH {
+                    *position = index;
+                    if let Ok(position) = (*position).try_into() {
+                        token_fn(field_name, field_id, position, &token)?;
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+
+        let localized_attributes_rule = self
+            .localized_attributes_rules
+            .iter()
+            .find(|rule| rule.match_str(field_name) == PatternMatch::Match);
+        let stemmers = localized_attributes_rule
+            .map_or_else(Stemmers::default, |rule| Stemmers::new(&rule.stemming_locales()));
+
         let text;
         let tokens = match value {
             Value::Number(n) => {
@@ -96,11 +131,7 @@ impl DocumentTokenizer<'_> {
                 self.tokenizer.tokenize(text.as_str())
             }
             Value::String(text) => {
-                let locales = self
-                    .localized_attributes_rules
-                    .iter()
-                    .find(|rule| rule.match_str(field_name) == PatternMatch::Match)
-                    .map(|rule| rule.locales());
+                let locales = localized_attributes_rule.map(|rule| rule.locales());
                 self.tokenizer.tokenize_with_allow_list(text.as_str(), locales)
             }
             _ => return Ok(()),
@@ -117,6 +148,10 @@ impl DocumentTokenizer<'_> {
                 *position = index;
                 if let Ok(position) = (*position).try_into() {
                     token_fn(field_name, field_id, position, token)?;
+                    // the stems of a word share its position.
+                    for stem in stemmers.stems(token) {
+                        token_fn(field_name, field_id, position, &stem)?;
+                    }
                 }
             }
         }
@@ -181,9 +216,10 @@ pub fn tokenizer_builder<'a>(
 
 #[cfg(test)]
 mod test {
+    use big_s::S;
     use bumpalo::Bump;
     use bumparaw_collections::RawMap;
-    use charabia::TokenizerBuilder;
+    use charabia::{Language, TokenizerBuilder};
     use meili_snap::snapshot;
     use rustc_hash::FxBuildHasher;
     use serde_json::json;
@@ -192,7 +228,7 @@ mod test {
     use super::*;
     use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
     use crate::update::new::document::{DocumentFromVersions, Versions};
-    use crate::FieldsIdsMap;
+    use crate::{AnalyzerTokenizer, FieldsIdsMap};
 
     #[test]
     fn test_tokenize_document() {
@@ -222,6 +258,7 @@ mod test {
         let document_tokenizer = DocumentTokenizer {
             tokenizer: &tb.build(),
             localized_attributes_rules: &[],
+            analyzers: &[],
             max_positions_per_attributes: 1000,
         };
 
@@ -299,4 +336,179 @@ mod test {
         }
         "###);
     }
+
+    #[test]
+    fn test_tokenize_document_with_analyzers() {
+        let mut fields_ids_map = FieldsIdsMap::new();
+
+        let document = json!({
+            "sku": "AB-12/X",
+            "part": ["Xé42", "B7"],
+            "title": "Crème Brûlée",
+        });
+
+        let _field_1_id = fields_ids_map.insert("sku").unwrap();
+        let _field_2_id = fields_ids_map.insert("part").unwrap();
+        let _field_3_id = fields_ids_map.insert("title").unwrap();
+
+        let analyzers = [
+            AnalyzerRule {
+                attribute_patterns: vec![S("sku")].into(),
+                tokenizer: AnalyzerTokenizer::Keyword,
+                lowercase: true,
+                ascii_folding: true,
+            },
+            AnalyzerRule {
+                attribute_patterns: vec![S("part")].into(),
+                tokenizer: AnalyzerTokenizer::EdgeNgram { min_gram: 2, max_gram: 3 },
+                lowercase: true,
+                ascii_folding: true,
+            },
+            AnalyzerRule {
+                attribute_patterns: vec![S("*")].into(),
+                tokenizer: AnalyzerTokenizer::Standard,
+                lowercase: false,
+                ascii_folding: false,
+            },
+        ];
+
+        let mut tb = TokenizerBuilder::default();
+        let document_tokenizer = DocumentTokenizer {
+            tokenizer: &tb.build(),
+            localized_attributes_rules: &[],
+            analyzers: &analyzers,
+            max_positions_per_attributes: 1000,
+        };
+
+        let fields_ids_map = FieldIdMapWithMetadata::new(
+            fields_ids_map,
+            MetadataBuilder::new(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                None,
+                None,
+                Default::default(),
+            ),
+        );
+
+        let fields_ids_map_lock = std::sync::RwLock::new(fields_ids_map);
+        let mut global_fields_ids_map = GlobalFieldsIdsMap::new(&fields_ids_map_lock);
+
+        let mut words = Vec::new();
+
+        let document = document.to_string();
+
+        let bump = Bump::new();
+        let document: &RawValue = serde_json::from_str(&document).unwrap();
+        let document = RawMap::from_raw_value_and_hasher(document, FxBuildHasher, &bump).unwrap();
+
+        let document = Versions::single(document);
+        let document = DocumentFromVersions::new(&document);
+
+        document_tokenizer
+            .tokenize_document(
+                document,
+                &mut global_fields_ids_map,
+                &mut |fname, _fid, pos, word| {
+                    words.push(format!("{fname}:{pos}:{word}"));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        snapshot!(format!("{:#?}", words), @r###"
+        [
+            "sku:0:ab12x",
+            "part:0:xe",
+            "part:0:xe4",
+            "part:0:xe42",
+            "part:8:b7",
+            "title:0:Crème",
+            "title:1:Brûlée",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn test_tokenize_document_with_stemming() {
+        let mut fields_ids_map = FieldsIdsMap::new();
+
+        let document = json!({
+            "title": "Running cats",
+            "description": "Running cats",
+        });
+
+        let _field_1_id = fields_ids_map.insert("title").unwrap();
+        let _field_2_id = fields_ids_map.insert("description").unwrap();
+
+        let localized_attributes_rules = [
+            LocalizedAttributesRule {
+                attribute_patterns: vec![S("title")].into(),
+                locales: vec![Language::Eng],
+                stemming: true,
+            },
+            LocalizedAttributesRule {
+                attribute_patterns: vec![S("description")].into(),
+                locales: vec![Language::Eng],
+                stemming: false,
+            },
+        ];
+
+        let mut tb = TokenizerBuilder::default();
+        let document_tokenizer = DocumentTokenizer {
+            tokenizer: &tb.build(),
+            localized_attributes_rules: &localized_attributes_rules,
+            analyzers: &[],
+            max_positions_per_attributes: 1000,
+        };
+
+        let fields_ids_map = FieldIdMapWithMetadata::new(
+            fields_ids_map,
+            MetadataBuilder::new(
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                None,
+                None,
+                Default::default(),
+            ),
+        );
+
+        let fields_ids_map_lock = std::sync::RwLock::new(fields_ids_map);
+        let mut global_fields_ids_map = GlobalFieldsIdsMap::new(&fields_ids_map_lock);
+
+        let mut words = Vec::new();
+
+        let document = document.to_string();
+
+        let bump = Bump::new();
+        let document: &RawValue = serde_json::from_str(&document).unwrap();
+        let document = RawMap::from_raw_value_and_hasher(document, FxBuildHasher, &bump).unwrap();
+
+        let document = Versions::single(document);
+        let document = DocumentFromVersions::new(&document);
+
+        document_tokenizer
+            .tokenize_document(
+                document,
+                &mut global_fields_ids_map,
+                &mut |fname, _fid, pos, word| {
+                    words.push(format!("{fname}:{pos}:{word}"));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        snapshot!(format!("{:#?}", words), @r###"
+        [
+            "title:0:running",
+            "title:0:run",
+            "title:1:cats",
+            "title:1:cat",
+            "description:0:running",
+            "description:1:cats",
+        ]
+        "###);
+    }
 }
@@ -15,7 +15,7 @@ use crate::update::new::DocumentChange;
 use crate::vector::error::{
     EmbedErrorKind, PossibleEmbeddingMistakes, UnusedVectorsDistributionBump,
 };
-use crate::vector::{Embedder, Embedding, EmbeddingConfigs};
+use crate::vector::{sparse, Embedder, Embedding, EmbeddingConfigs};
 use crate::{DocumentId, FieldDistribution, InternalError, Result, ThreadPoolNoAbort, UserError};
 
 pub struct EmbeddingExtractor<'a, 'b> {
@@ -60,7 +60,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
             UnusedVectorsDistributionBump::new_in(&context.doc_alloc);
 
         let mut all_chunks = BVec::with_capacity_in(embedders.len(), &context.doc_alloc);
-        for (embedder_name, (embedder, prompt, _is_quantized)) in embedders {
+        for (embedder_name, (embedder, prompt, _is_quantized, _distance)) in embedders {
             let embedder_id =
                 context.index.embedder_category_id.get(&context.rtxn, embedder_name)?.ok_or_else(
                     || InternalError::DatabaseMissingEntry {
@@ -329,24 +329,28 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.has_manual_generation.get_or_insert(external_docid);
         }
 
-        if self.texts.len() < self.texts.capacity() {
-            self.texts.push(rendered);
-            self.ids.push(docid);
-            return Ok(());
+        // all the chunks of a document are embedded in the same batch, so that its embeddings are sent at once
+        let texts = self.prompt.split(rendered);
+        if !self.texts.is_empty() && self.texts.len() + texts.len() > self.texts.capacity() {
+            Self::embed_chunks(
+                &mut self.texts,
+                &mut self.ids,
+                self.embedder,
+                self.embedder_id,
+                self.embedder_name,
+                self.possible_embedding_mistakes,
+                unused_vectors_distribution,
+                self.threads,
+                self.sender,
+                self.has_manual_generation.take(),
+            )?;
         }
 
-        Self::embed_chunks(
-            &mut self.texts,
-            &mut self.ids,
-            self.embedder,
-            self.embedder_id,
-            self.embedder_name,
-            self.possible_embedding_mistakes,
-            unused_vectors_distribution,
-            self.threads,
-            self.sender,
-            self.has_manual_generation.take(),
-        )
+        for text in texts {
+            self.texts.push(text);
+            self.ids.push(docid);
+        }
+        Ok(())
     }
 
     pub fn drain(
@@ -423,8 +427,11 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
 
         let res = match embedder.embed_index_ref(texts.as_slice(), threads) {
             Ok(embeddings) => {
-                for (docid, embedding) in ids.into_iter().zip(embeddings) {
-                    sender.set_vector(*docid, embedder_id, embedding).unwrap();
+                let mut embeddings = embeddings.into_iter();
+                // the chunks of a document are consecutive
+                for docids in ids.chunk_by(|left, right| left == right) {
+                    let document_embeddings = embeddings.by_ref().take(docids.len()).collect();
+                    sender.set_vectors(docids[0], embedder_id, document_embeddings).unwrap();
                 }
                 Ok(())
             }
@@ -501,8 +508,19 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         docid: DocumentId,
         embeddings: Vec<Embedding>,
     ) -> Result<()> {
+        let sparse_embedder = self.embedder.sparse();
         for (embedding_index, embedding) in embeddings.iter().enumerate() {
-            if embedding.len() != self.dimensions {
+            if sparse_embedder || sparse::is_sparse(embedding) {
+                if let Err(reason) = sparse::check(embedding, sparse_embedder, self.dimensions) {
+                    return Err(UserError::InvalidIndexingSparseVector {
+                        embedder_name: self.embedder_name.to_string(),
+                        document_id: external_docid.to_string(),
+                        embedding_index,
+                        reason,
+                    }
+                    .into());
+                }
+            } else if embedding.len() != self.dimensions {
                 return Err(UserError::InvalidIndexingVectorDimensions {
                     expected: self.dimensions,
                     found: embedding.len(),
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::atomic::AtomicBool;
 use std::sync::OnceLock;
 
@@ -19,7 +19,7 @@ use crate::update::new::extract::EmbeddingExtractor;
 use crate::update::new::merger::merge_and_send_rtree;
 use crate::update::new::{merge_and_send_docids, merge_and_send_facet_docids, FacetDatabases};
 use crate::vector::EmbeddingConfigs;
-use crate::{Result, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
+use crate::{FieldId, Result, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
 
 #[allow(clippy::too_many_arguments)]
 pub(super) fn extract_all<'pl, 'extractor, DC, MSP>(
@@ -31,6 +31,7 @@ pub(super) fn extract_all<'pl, 'extractor, DC, MSP>(
     extractor_allocs: &'extractor mut ThreadLocal<FullySend<Bump>>,
     finished_extraction: &AtomicBool,
     field_distribution: &mut BTreeMap<String, u64>,
+    date_faceted_fields: &mut BTreeSet<FieldId>,
     mut index_embeddings: Vec<IndexEmbeddingConfig>,
     document_ids: &mut RoaringBitmap,
     modified_docids: &mut RoaringBitmap,
@@ -90,6 +91,7 @@ where
                 indexing_context,
                 extractor_allocs,
                 &extractor_sender.field_id_docid_facet_sender(),
+                date_faceted_fields,
                 IndexingStep::ExtractingFacets,
             )?
         };
@@ -131,6 +131,7 @@ where
 
     let index_embeddings = index.embedding_configs(wtxn)?;
     let mut field_distribution = index.field_distribution(wtxn)?;
+    let mut date_faceted_fields = index.date_faceted_fields(wtxn)?;
     let mut document_ids = index.documents_ids(wtxn)?;
     let mut modified_docids = roaring::RoaringBitmap::new();
 
@@ -140,6 +141,7 @@ where
         let finished_extraction = &finished_extraction;
         // prevent moving the field_distribution and document_ids in the inner closure...
         let field_distribution = &mut field_distribution;
+        let date_faceted_fields = &mut date_faceted_fields;
         let document_ids = &mut document_ids;
         let modified_docids = &mut modified_docids;
         let extractor_handle =
@@ -154,6 +156,7 @@ where
                         &mut extractor_allocs,
                         finished_extraction,
                         field_distribution,
+                        date_faceted_fields,
                         index_embeddings,
                         document_ids,
                         modified_docids,
@@ -168,7 +171,7 @@ where
         let arroy_writers: Result<HashMap<_, _>> = embedders
             .inner_as_ref()
             .iter()
-            .map(|(embedder_name, (embedder, _, was_quantized))| {
+            .map(|(embedder_name, (embedder, _, quantization, distance))| {
                 let embedder_index = index.embedder_category_id.get(wtxn, embedder_name)?.ok_or(
                     InternalError::DatabaseMissingEntry {
                         db_name: "embedder_category_id",
@@ -177,7 +180,14 @@ where
                 )?;
 
                 let dimensions = embedder.dimensions();
-                let writer = ArroyWrapper::new(vector_arroy, embedder_index, *was_quantized);
+                let writer = ArroyWrapper::new(
+                    vector_arroy,
+                    embedder_index,
+                    *quantization,
+                    *distance,
+                    embedder.sparse().then_some(index.sparse_vectors),
+                    index.int8_vectors,
+                );
 
                 Ok((
                     embedder_index,
@@ -233,6 +243,7 @@ where
         new_primary_key,
         embedders,
         field_distribution,
+        date_faceted_fields,
         document_ids,
     )?;
 
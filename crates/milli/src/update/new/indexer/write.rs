@@ -55,10 +55,15 @@ pub fn write_to_db(
                 let LargeVectors { docid, embedder_id, .. } = large_vectors;
                 let (_, _, writer, dimensions) =
                     arroy_writers.get(&embedder_id).expect("requested a missing embedder");
-                let mut embeddings = Embeddings::new(*dimensions);
-                for embedding in large_vectors.read_embeddings(*dimensions) {
-                    embeddings.push(embedding.to_vec()).unwrap();
-                }
+                let embeddings = if writer.is_sparse() {
+                    Embeddings::from_single_embedding(large_vectors.read_all_embeddings())
+                } else {
+                    let mut embeddings = Embeddings::new(*dimensions);
+                    for embedding in large_vectors.read_embeddings(*dimensions) {
+                        embeddings.push(embedding.to_vec()).unwrap();
+                    }
+                    embeddings
+                };
                 writer.del_items(wtxn, *dimensions, docid)?;
                 writer.add_items(wtxn, docid, &embeddings)?;
             }
@@ -119,12 +124,14 @@ where
     let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
     for (_index, (_embedder_name, _embedder, writer, dimensions)) in arroy_writers {
         let dimensions = *dimensions;
+        let (quantization, distance) = (writer.quantization(), writer.distance());
         writer.build_and_quantize(
             wtxn,
             progress,
             &mut rng,
             dimensions,
-            false,
+            quantization,
+            distance,
             arroy_memory,
             must_stop_processing,
         )?;
@@ -142,6 +149,7 @@ pub(super) fn update_index(
     new_primary_key: Option<PrimaryKey<'_>>,
     embedders: EmbeddingConfigs,
     field_distribution: std::collections::BTreeMap<String, u64>,
+    date_faceted_fields: std::collections::BTreeSet<crate::FieldId>,
     document_ids: roaring::RoaringBitmap,
 ) -> Result<()> {
     index.put_fields_ids_map(wtxn, new_fields_ids_map.as_fields_ids_map())?;
@@ -151,6 +159,7 @@ pub(super) fn update_index(
     let mut inner_index_settings = InnerIndexSettings::from_index(index, wtxn, Some(embedders))?;
     inner_index_settings.recompute_searchables(wtxn, index)?;
     index.put_field_distribution(wtxn, &field_distribution)?;
+    index.put_date_faceted_fields(wtxn, &date_faceted_fields)?;
     index.put_documents_ids(wtxn, &document_ids)?;
     index.set_updated_at(wtxn, &OffsetDateTime::now_utc())?;
     let stats = DatabaseStats::new(index.documents.remap_data_type(), wtxn)?;
@@ -217,14 +226,20 @@ pub fn write_from_bbqueue(
                 let frame = frame_with_header.frame();
                 let (_, _, writer, dimensions) =
                     arroy_writers.get(&embedder_id).expect("requested a missing embedder");
-                let mut embeddings = Embeddings::new(*dimensions);
                 let all_embeddings = asvs.read_all_embeddings_into_vec(frame, aligned_embedding);
-                if embeddings.append(all_embeddings.to_vec()).is_err() {
-                    return Err(Error::UserError(UserError::InvalidVectorDimensions {
-                        expected: *dimensions,
-                        found: all_embeddings.len(),
-                    }));
-                }
+                // sparse embeddings have no fixed dimension, they are split by the writer
+                let embeddings = if writer.is_sparse() {
+                    Embeddings::from_single_embedding(all_embeddings.to_vec())
+                } else {
+                    let mut embeddings = Embeddings::new(*dimensions);
+                    if embeddings.append(all_embeddings.to_vec()).is_err() {
+                        return Err(Error::UserError(UserError::InvalidVectorDimensions {
+                            expected: *dimensions,
+                            found: all_embeddings.len(),
+                        }));
+                    }
+                    embeddings
+                };
                 writer.del_items(wtxn, *dimensions, docid)?;
                 writer.add_items(wtxn, docid, &embeddings)?;
             }
@@ -28,6 +28,8 @@ where
 {
     let mut rtree = index.geo_rtree(rtxn)?.unwrap_or_default();
     let mut faceted = index.geo_faceted_documents_ids(rtxn)?;
+    let mut geojson_rtree = index.geojson_rtree(rtxn)?.unwrap_or_default();
+    let mut geojson_faceted = index.geojson_faceted_documents_ids(rtxn)?;
 
     for data in datastore {
         if must_stop_processing() {
@@ -49,6 +51,21 @@ where
             let inserted = faceted.insert(extracted_geo_point.docid);
             debug_assert!(inserted);
         }
+
+        // The documents indexed before the support of the `_geojson` field
+        // may not have their geometry in the rtree, we can't assert its removal.
+        for result in frozen.iter_and_clear_removed_geometries() {
+            let (docid, geometry) = result?;
+            geojson_rtree.remove(&geometry.into_shape(docid));
+            geojson_faceted.remove(docid);
+        }
+
+        for result in frozen.iter_and_clear_inserted_geometries() {
+            let (docid, geometry) = result?;
+            geojson_rtree.insert(geometry.into_shape(docid));
+            let inserted = geojson_faceted.insert(docid);
+            debug_assert!(inserted);
+        }
     }
 
     let mut file = tempfile::tempfile()?;
@@ -59,6 +76,14 @@ where
     geo_sender.set_rtree(rtree_mmap).unwrap();
     geo_sender.set_geo_faceted(&faceted)?;
 
+    let mut file = tempfile::tempfile()?;
+    bincode::serialize_into(&mut file, &geojson_rtree).map_err(InternalError::BincodeError)?;
+    file.sync_all()?;
+
+    let geojson_rtree_mmap = unsafe { Mmap::map(&file)? };
+    geo_sender.set_geojson_rtree(geojson_rtree_mmap).unwrap();
+    geo_sender.set_geojson_faceted(&geojson_faceted)?;
+
     Ok(())
 }
 
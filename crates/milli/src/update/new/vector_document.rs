@@ -22,7 +22,7 @@ use crate::{DocumentId, Index, InternalError, Result, UserError};
 pub enum Embeddings<'doc> {
     FromJsonExplicit(&'doc RawValue),
     FromJsonImplicityUserProvided(&'doc RawValue),
-    FromDb(Vec<Embedding>),
+    FromDb(#[serde(serialize_with = "crate::vector::sparse::serialize_embeddings")] Vec<Embedding>),
 }
 impl<'doc> Embeddings<'doc> {
     pub fn into_vec(
@@ -119,8 +119,14 @@ impl<'t> VectorDocumentFromDb<'t> {
         embedder_id: u8,
         config: &IndexEmbeddingConfig,
     ) -> Result<VectorEntry<'t>> {
-        let reader =
-            ArroyWrapper::new(self.index.vector_arroy, embedder_id, config.config.quantized());
+        let reader = ArroyWrapper::new(
+            self.index.vector_arroy,
+            embedder_id,
+            config.config.quantization(),
+            config.config.distance(),
+            config.config.sparse().then_some(self.index.sparse_vectors),
+            self.index.int8_vectors,
+        );
         let vectors = reader.item_vectors(self.rtxn, self.docid)?;
 
         Ok(VectorEntry {
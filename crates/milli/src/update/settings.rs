@@ -15,9 +15,9 @@ use super::del_add::{DelAdd, DelAddOperation};
 use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::attribute_patterns::PatternMatch;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::criterion::Criterion;
-use crate::error::UserError;
+use crate::error::{GeoError, UserError};
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::filterable_attributes_rules::match_faceted_field;
 use crate::index::{
@@ -25,16 +25,21 @@ use crate::index::{
     DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
 };
 use crate::order_by_map::OrderByMap;
+use crate::progress::Progress;
 use crate::prompt::default_max_bytes;
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::vector::rerank::RerankerConfig;
 use crate::vector::settings::{
     EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext, ReindexAction,
     SubEmbeddingSettings, WriteBackToDocuments,
 };
-use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, Result};
+use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs, Quantization, VectorDistance};
+use crate::{
+    AnalyzerRule, FacetStat, FieldId, FilterableAttributesRule, GeoJsonGeometry, HierarchicalFacet,
+    Index, InternalError, LocalizedAttributesRule, Result, SynonymRule,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -155,6 +160,8 @@ pub struct Settings<'a, 't, 'i> {
     index: &'i Index,
 
     indexer_config: &'a IndexerConfig,
+    /// Updated while the vector stores are converted to a new quantization or distance.
+    progress: Progress,
 
     searchable_fields: Setting<Vec<String>>,
     displayed_fields: Setting<Vec<String>>,
@@ -167,6 +174,7 @@ pub struct Settings<'a, 't, 'i> {
     dictionary: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
     synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    synonym_rules: Setting<Vec<SynonymRule>>,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
     min_word_len_two_typos: Setting<u8>,
@@ -176,11 +184,15 @@ pub struct Settings<'a, 't, 'i> {
     exact_attributes: Setting<HashSet<String>>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<OrderByMap>,
+    hierarchical_facets: Setting<BTreeMap<String, HierarchicalFacet>>,
+    facet_stats: Setting<BTreeMap<String, BTreeSet<FacetStat>>>,
     pagination_max_total_hits: Setting<usize>,
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
     search_cutoff: Setting<u64>,
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    analyzers: Setting<Vec<AnalyzerRule>>,
+    rerankers: Setting<BTreeMap<String, RerankerConfig>>,
     prefix_search: Setting<PrefixSearch>,
     facet_search: Setting<bool>,
 }
@@ -205,6 +217,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             dictionary: Setting::NotSet,
             distinct_field: Setting::NotSet,
             synonyms: Setting::NotSet,
+            synonym_rules: Setting::NotSet,
             primary_key: Setting::NotSet,
             authorize_typos: Setting::NotSet,
             exact_words: Setting::NotSet,
@@ -213,17 +226,26 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             exact_attributes: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
+            hierarchical_facets: Setting::NotSet,
+            facet_stats: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
             search_cutoff: Setting::NotSet,
             localized_attributes_rules: Setting::NotSet,
+            analyzers: Setting::NotSet,
+            rerankers: Setting::NotSet,
             prefix_search: Setting::NotSet,
             facet_search: Setting::NotSet,
             indexer_config,
+            progress: Progress::default(),
         }
     }
 
+    pub fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
+    }
+
     pub fn reset_searchable_fields(&mut self) {
         self.searchable_fields = Setting::Reset;
     }
@@ -322,6 +344,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.synonyms = if synonyms.is_empty() { Setting::Reset } else { Setting::Set(synonyms) }
     }
 
+    pub fn reset_synonym_rules(&mut self) {
+        self.synonym_rules = Setting::Reset;
+    }
+
+    pub fn set_synonym_rules(&mut self, rules: Vec<SynonymRule>) {
+        self.synonym_rules = if rules.is_empty() { Setting::Reset } else { Setting::Set(rules) }
+    }
+
     pub fn reset_primary_key(&mut self) {
         self.primary_key = Setting::Reset;
     }
@@ -386,6 +416,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.sort_facet_values_by = Setting::Reset;
     }
 
+    pub fn set_hierarchical_facets(&mut self, value: BTreeMap<String, HierarchicalFacet>) {
+        self.hierarchical_facets = Setting::Set(value);
+    }
+
+    pub fn reset_hierarchical_facets(&mut self) {
+        self.hierarchical_facets = Setting::Reset;
+    }
+
+    pub fn set_facet_stats(&mut self, value: BTreeMap<String, BTreeSet<FacetStat>>) {
+        self.facet_stats = Setting::Set(value);
+    }
+
+    pub fn reset_facet_stats(&mut self) {
+        self.facet_stats = Setting::Reset;
+    }
+
     pub fn set_pagination_max_total_hits(&mut self, value: usize) {
         self.pagination_max_total_hits = Setting::Set(value);
     }
@@ -426,6 +472,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.localized_attributes_rules = Setting::Reset;
     }
 
+    pub fn set_analyzers(&mut self, value: Vec<AnalyzerRule>) {
+        self.analyzers = Setting::Set(value);
+    }
+
+    pub fn reset_analyzers(&mut self) {
+        self.analyzers = Setting::Reset;
+    }
+
+    pub fn set_rerankers(&mut self, value: BTreeMap<String, RerankerConfig>) {
+        self.rerankers = Setting::Set(value);
+    }
+
+    pub fn reset_rerankers(&mut self) {
+        self.rerankers = Setting::Reset;
+    }
+
     pub fn set_prefix_search(&mut self, value: PrefixSearch) {
         self.prefix_search = Setting::Set(value);
     }
@@ -483,13 +545,62 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             IndexDocumentsConfig::default(),
             &progress_callback,
             &should_abort,
-        )?;
+        )?
+        .with_arroy_progress(self.progress.clone());
 
         indexing_builder.execute_raw(output)?;
 
         Ok(())
     }
 
+    /// Rebuilds the rtree of the `_geojson` geometries from all the documents of the index,
+    /// or deletes it if neither `_geo` nor `_geojson` are filterable or sortable anymore.
+    fn update_geojson_rtree(&mut self) -> Result<()> {
+        self.index.delete_geojson_rtree(self.wtxn)?;
+        self.index.delete_geojson_faceted_documents_ids(self.wtxn)?;
+
+        if !self.index.is_geojson_enabled(self.wtxn)? {
+            return Ok(());
+        }
+        let fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
+        let Some(geojson_fid) = fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) else {
+            return Ok(());
+        };
+
+        let mut rtree = rstar::RTree::new();
+        let mut faceted = RoaringBitmap::new();
+        for result in self.index.all_documents(self.wtxn)? {
+            let (docid, document) = result?;
+            let Some(value) = document.get(geojson_fid) else { continue };
+            let value: serde_json::Value =
+                serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+            if value.is_null() {
+                continue;
+            }
+
+            match GeoJsonGeometry::from_json(&value) {
+                Ok(geometry) => {
+                    rtree.insert(geometry.into_shape(docid));
+                    faceted.insert(docid);
+                }
+                Err(reason) => {
+                    let external_id = self.index.external_id_of(self.wtxn, Some(docid))?;
+                    let document_id = external_id.into_iter().next().transpose()?;
+                    return Err(Box::new(GeoError::BadGeoJson {
+                        document_id: document_id.into(),
+                        reason,
+                    })
+                    .into());
+                }
+            }
+        }
+
+        self.index.put_geojson_rtree(self.wtxn, &rtree)?;
+        self.index.put_geojson_faceted_documents_ids(self.wtxn, &faceted)?;
+
+        Ok(())
+    }
+
     fn update_displayed(&mut self) -> Result<bool> {
         match self.displayed_fields {
             Setting::Set(ref fields) => {
@@ -595,6 +706,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         if changes && self.synonyms == Setting::NotSet {
             self.synonyms = Setting::Set(self.index.user_defined_synonyms(self.wtxn)?);
         }
+        if changes && self.synonym_rules == Setting::NotSet {
+            self.synonym_rules = Setting::Set(self.index.user_defined_synonym_rules(self.wtxn)?);
+        }
 
         Ok(changes)
     }
@@ -620,6 +734,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         if changes && self.synonyms == Setting::NotSet {
             self.synonyms = Setting::Set(self.index.user_defined_synonyms(self.wtxn)?);
         }
+        if changes && self.synonym_rules == Setting::NotSet {
+            self.synonym_rules = Setting::Set(self.index.user_defined_synonym_rules(self.wtxn)?);
+        }
 
         Ok(changes)
     }
@@ -645,71 +762,87 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         if changes && self.synonyms == Setting::NotSet {
             self.synonyms = Setting::Set(self.index.user_defined_synonyms(self.wtxn)?);
         }
+        if changes && self.synonym_rules == Setting::NotSet {
+            self.synonym_rules = Setting::Set(self.index.user_defined_synonym_rules(self.wtxn)?);
+        }
 
         Ok(changes)
     }
 
-    fn update_synonyms(&mut self) -> Result<bool> {
-        match self.synonyms {
-            Setting::Set(ref user_synonyms) => {
-                fn normalize(tokenizer: &Tokenizer<'_>, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
-                        .filter_map(|token| {
-                            if token.is_word() && !token.lemma().is_empty() {
-                                Some(token.lemma().to_string())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                }
+    /// Calls `f` with a function normalizing the synonyms like the search queries are.
+    fn with_synonyms_normalizer<T>(
+        &self,
+        f: impl FnOnce(&dyn Fn(&str) -> Vec<String>) -> T,
+    ) -> Result<T> {
+        fn normalize(tokenizer: &Tokenizer<'_>, text: &str) -> Vec<String> {
+            tokenizer
+                .tokenize(text)
+                .filter_map(|token| {
+                    if token.is_word() && !token.lemma().is_empty() {
+                        Some(token.lemma().to_string())
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        }
 
-                let mut builder = TokenizerBuilder::new();
-                let stop_words = self.index.stop_words(self.wtxn)?;
-                if let Some(ref stop_words) = stop_words {
-                    builder.stop_words(stop_words);
-                }
+        let mut builder = TokenizerBuilder::new();
+        let stop_words = self.index.stop_words(self.wtxn)?;
+        if let Some(ref stop_words) = stop_words {
+            builder.stop_words(stop_words);
+        }
 
-                let separators = self.index.allowed_separators(self.wtxn)?;
-                let separators: Option<Vec<_>> =
-                    separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref separators) = separators {
-                    builder.separators(separators);
-                }
+        let separators = self.index.allowed_separators(self.wtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            builder.separators(separators);
+        }
 
-                let dictionary = self.index.dictionary(self.wtxn)?;
-                let dictionary: Option<Vec<_>> =
-                    dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref dictionary) = dictionary {
-                    builder.words_dict(dictionary);
-                }
+        let dictionary = self.index.dictionary(self.wtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            builder.words_dict(dictionary);
+        }
 
-                let tokenizer = builder.build();
+        let tokenizer = builder.build();
 
-                let mut new_synonyms = HashMap::new();
-                for (word, synonyms) in user_synonyms {
-                    // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
-                    let normalized_synonyms: Vec<_> = synonyms
-                        .iter()
-                        .map(|synonym| normalize(&tokenizer, synonym))
-                        .filter(|synonym| !synonym.is_empty())
-                        .collect();
-
-                    // Store the normalized synonyms under the normalized word,
-                    // merging the possible duplicate words.
-                    if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
-                        let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
-                        entry.extend(normalized_synonyms.into_iter());
+        Ok(f(&|text| normalize(&tokenizer, text)))
+    }
+
+    fn update_synonyms(&mut self) -> Result<bool> {
+        match self.synonyms {
+            Setting::Set(ref user_synonyms) => {
+                let new_synonyms = self.with_synonyms_normalizer(|normalize| {
+                    let mut new_synonyms = HashMap::new();
+                    for (word, synonyms) in user_synonyms {
+                        // Normalize both the word and associated synonyms.
+                        let normalized_word = normalize(word);
+                        let normalized_synonyms: Vec<_> = synonyms
+                            .iter()
+                            .map(|synonym| normalize(synonym))
+                            .filter(|synonym| !synonym.is_empty())
+                            .collect();
+
+                        // Store the normalized synonyms under the normalized word,
+                        // merging the possible duplicate words.
+                        if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
+                            let entry =
+                                new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
+                            entry.extend(normalized_synonyms.into_iter());
+                        }
                     }
-                }
 
-                // Make sure that we don't have duplicate synonyms.
-                new_synonyms.iter_mut().for_each(|(_, synonyms)| {
-                    synonyms.sort_unstable();
-                    synonyms.dedup();
-                });
+                    // Make sure that we don't have duplicate synonyms.
+                    new_synonyms.iter_mut().for_each(|(_, synonyms)| {
+                        synonyms.sort_unstable();
+                        synonyms.dedup();
+                    });
+
+                    new_synonyms
+                })?;
 
                 let old_synonyms = self.index.synonyms(self.wtxn)?;
 
@@ -725,6 +858,45 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    fn update_synonym_rules(&mut self) -> Result<bool> {
+        match self.synonym_rules {
+            Setting::Set(ref rules) => {
+                let new_synonyms = self.with_synonyms_normalizer(|normalize| {
+                    let mut new_synonyms: HashMap<_, Vec<_>> = HashMap::new();
+                    for rule in rules {
+                        for (input, synonym, penalty) in rule.expand() {
+                            let input = normalize(input);
+                            let synonym = normalize(synonym);
+                            if !input.is_empty() && !synonym.is_empty() && input != synonym {
+                                new_synonyms.entry(input).or_default().push((synonym, penalty));
+                            }
+                        }
+                    }
+
+                    // When several rules define the same synonym, keep its lowest penalty.
+                    new_synonyms.iter_mut().for_each(|(_, synonyms)| {
+                        synonyms.sort_unstable();
+                        synonyms.dedup_by(|(synonym, _), (kept, _)| synonym == kept);
+                    });
+
+                    new_synonyms
+                })?;
+
+                let old_synonyms = self.index.weighted_synonyms(self.wtxn)?;
+                let old_rules = self.index.user_defined_synonym_rules(self.wtxn)?;
+
+                if new_synonyms != old_synonyms || *rules != old_rules {
+                    self.index.put_synonym_rules(self.wtxn, &new_synonyms, rules)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_synonym_rules(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_exact_attributes(&mut self) -> Result<bool> {
         match self.exact_attributes {
             Setting::Set(ref attrs) => {
@@ -926,6 +1098,34 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_hierarchical_facets(&mut self) -> Result<()> {
+        match self.hierarchical_facets.as_ref() {
+            Setting::Set(value) => {
+                self.index.put_hierarchical_facets(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_hierarchical_facets(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_facet_stats(&mut self) -> Result<()> {
+        match self.facet_stats.as_ref() {
+            Setting::Set(value) => {
+                self.index.put_facet_stats(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_facet_stats(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_pagination_max_total_hits(&mut self) -> Result<()> {
         match self.pagination_max_total_hits {
             Setting::Set(max) => {
@@ -1052,7 +1252,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             match joined {
                 // updated config
                 EitherOrBoth::Both((name, (old, user_provided)), (_, new)) => {
-                    let was_quantized = old.binary_quantized.set().unwrap_or_default();
+                    let was_quantized =
+                        old.quantization.set().map_or(
+                            old.binary_quantized.set().unwrap_or_default(),
+                            |quantization| quantization.is_binary(),
+                        );
                     let settings_diff = SettingsDiff::from_settings(&name, old, new)?;
                     match settings_diff {
                         SettingsDiff::Remove => {
@@ -1078,7 +1282,12 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                                 ),
                             );
                         }
-                        SettingsDiff::Reindex { action, updated_settings, quantize } => {
+                        SettingsDiff::Reindex {
+                            action,
+                            updated_settings,
+                            quantize,
+                            change_distance,
+                        } => {
                             tracing::debug!(
                                 embedder = name,
                                 user_provided = user_provided.len(),
@@ -1088,13 +1297,18 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             embedder_actions.insert(
                                 name.clone(),
                                 EmbedderAction::with_reindex(action, was_quantized)
-                                    .with_is_being_quantized(quantize),
+                                    .with_is_being_quantized(quantize)
+                                    .with_is_changing_distance(change_distance),
                             );
                             let new =
                                 validate_embedding_settings(Setting::Set(updated_settings), &name)?;
                             updated_configs.insert(name, (new, user_provided));
                         }
-                        SettingsDiff::UpdateWithoutReindex { updated_settings, quantize } => {
+                        SettingsDiff::UpdateWithoutReindex {
+                            updated_settings,
+                            quantize,
+                            change_distance,
+                        } => {
                             tracing::debug!(
                                 embedder = name,
                                 user_provided = user_provided.len(),
@@ -1102,10 +1316,12 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             );
                             let new =
                                 validate_embedding_settings(Setting::Set(updated_settings), &name)?;
-                            if quantize {
+                            if quantize || change_distance {
                                 embedder_actions.insert(
                                     name.clone(),
-                                    EmbedderAction::default().with_is_being_quantized(true),
+                                    EmbedderAction { was_quantized, ..Default::default() }
+                                        .with_is_being_quantized(quantize)
+                                        .with_is_changing_distance(change_distance),
                                 );
                             }
                             updated_configs.insert(name, (new, user_provided));
@@ -1210,6 +1426,36 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_analyzers(&mut self) -> Result<()> {
+        match &self.analyzers {
+            Setting::Set(new) => {
+                if self.index.analyzers(self.wtxn)? != *new {
+                    self.index.put_analyzers(self.wtxn, new)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_analyzers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_rerankers(&mut self) -> Result<()> {
+        match &self.rerankers {
+            Setting::Set(new) => {
+                self.index.put_rerankers(self.wtxn, new)?;
+            }
+            Setting::Reset => {
+                self.index.delete_rerankers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -1218,6 +1464,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.index.set_updated_at(self.wtxn, &OffsetDateTime::now_utc())?;
 
         let old_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn, None)?;
+        let old_geojson_enabled = self.index.is_geojson_enabled(self.wtxn)?;
 
         // never trigger re-indexing
         self.update_displayed()?;
@@ -1229,8 +1476,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_exact_words()?;
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
+        self.update_hierarchical_facets()?;
+        self.update_facet_stats()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_rerankers()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1240,12 +1490,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_separator_tokens()?;
         self.update_dictionary()?;
         self.update_synonyms()?;
+        self.update_synonym_rules()?;
         self.update_user_defined_searchable_attributes()?;
         self.update_exact_attributes()?;
         self.update_proximity_precision()?;
         self.update_prefix_search()?;
         self.update_facet_search()?;
         self.update_localized_attributes_rules()?;
+        self.update_analyzers()?;
 
         let embedding_config_updates = self.update_embedding_configs()?;
 
@@ -1269,6 +1521,10 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
         }
 
+        if old_geojson_enabled != self.index.is_geojson_enabled(self.wtxn)? {
+            self.update_geojson_rtree()?;
+        }
+
         Ok(())
     }
 }
@@ -1327,6 +1583,7 @@ impl InnerIndexSettingsDiff {
                 || old_settings.prefix_search != new_settings.prefix_search
                 || old_settings.localized_attributes_rules
                     != new_settings.localized_attributes_rules
+                || old_settings.analyzers != new_settings.analyzers
         };
 
         let cache_exact_attributes = old_settings.exact_attributes != new_settings.exact_attributes;
@@ -1349,11 +1606,13 @@ impl InnerIndexSettingsDiff {
 
         // if the user-defined searchables changed, then we need to reindex prompts.
         if cache_user_defined_searchables {
-            for (embedder_name, (config, _, _quantized)) in
+            for (embedder_name, (config, _, _quantization, _distance)) in
                 new_settings.embedding_configs.inner_as_ref()
             {
-                let was_quantized =
-                    old_settings.embedding_configs.get(embedder_name).is_some_and(|conf| conf.2);
+                let was_quantized = old_settings
+                    .embedding_configs
+                    .get(embedder_name)
+                    .is_some_and(|conf| conf.2.is_binary());
                 // skip embedders that don't use document templates
                 if !config.uses_document_template() {
                     continue;
@@ -1372,6 +1631,7 @@ impl InnerIndexSettingsDiff {
                         let EmbedderAction {
                             was_quantized: _,
                             is_being_quantized: _,
+                            is_changing_distance: _,
                             write_back: _, // We are deleting this embedder, so no point in regeneration
                             reindex: _,    // We are already fully reindexing
                         } = entry.get();
@@ -1520,6 +1780,7 @@ pub(crate) struct InnerIndexSettings {
     pub dictionary: Option<BTreeSet<String>>,
     pub fields_ids_map: FieldIdMapWithMetadata,
     pub localized_attributes_rules: Vec<LocalizedAttributesRule>,
+    pub analyzers: Vec<AnalyzerRule>,
     pub filterable_attributes_rules: Vec<FilterableAttributesRule>,
     pub asc_desc_fields: HashSet<String>,
     pub distinct_field: Option<String>,
@@ -1565,6 +1826,7 @@ impl InnerIndexSettings {
         };
         let localized_attributes_rules =
             index.localized_attributes_rules(rtxn)?.unwrap_or_default();
+        let analyzers = index.analyzers(rtxn)?;
         let filterable_attributes_rules = index.filterable_attributes_rules(rtxn)?;
         let sortable_fields = index.sortable_fields(rtxn)?;
         let asc_desc_fields = index.asc_desc_fields(rtxn)?;
@@ -1581,6 +1843,7 @@ impl InnerIndexSettings {
             dictionary,
             fields_ids_map,
             localized_attributes_rules,
+            analyzers,
             filterable_attributes_rules,
             asc_desc_fields,
             distinct_field,
@@ -1632,23 +1895,25 @@ impl InnerIndexSettings {
 fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingConfigs> {
     let res: Result<_> = embedding_configs
         .into_iter()
-        .map(
-            |IndexEmbeddingConfig {
-                 name,
-                 config: EmbeddingConfig { embedder_options, prompt, quantized },
-                 ..
-             }| {
-                let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
-
-                let embedder = Arc::new(
-                    // cache_cap: no cache needed for indexing purposes
-                    Embedder::new(embedder_options.clone(), 0)
-                        .map_err(crate::vector::Error::from)
-                        .map_err(crate::Error::from)?,
-                );
-                Ok((name, (embedder, prompt, quantized.unwrap_or_default())))
-            },
-        )
+        .map(|IndexEmbeddingConfig { name, config, .. }| {
+            let quantization = config.quantization();
+            let EmbeddingConfig {
+                embedder_options,
+                prompt,
+                quantized: _,
+                quantization: _,
+                distance,
+            } = config;
+            let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
+
+            let embedder = Arc::new(
+                // cache_cap: no cache needed for indexing purposes
+                Embedder::new(embedder_options.clone(), 0)
+                    .map_err(crate::vector::Error::from)
+                    .map_err(crate::Error::from)?,
+            );
+            Ok((name, (embedder, prompt, quantization, distance.unwrap_or_default())))
+        })
         .collect();
     res.map(EmbeddingConfigs::new)
 }
@@ -1706,10 +1971,24 @@ pub fn validate_embedding_settings(
         distribution,
         headers,
         binary_quantized: binary_quantize,
+        quantization,
+        distance,
+        multi_vector,
+        chunking,
+        model_path,
+        threads,
+        sparse,
     } = settings;
 
     let document_template = validate_prompt(name, document_template, document_template_max_bytes)?;
 
+    if let Some(chunking) = chunking.as_ref().set() {
+        chunking.validate().map_err(|reason| crate::error::UserError::InvalidSettingsChunking {
+            embedder_name: name.to_owned(),
+            reason,
+        })?;
+    }
+
     if let Some(0) = dimensions.set() {
         return Err(crate::error::UserError::InvalidSettingsDimensions {
             embedder_name: name.to_owned(),
@@ -1717,6 +1996,71 @@ pub fn validate_embedding_settings(
         .into());
     }
 
+    if let Some(0) = threads.set() {
+        return Err(crate::error::UserError::InvalidSettingsThreads {
+            embedder_name: name.to_owned(),
+        }
+        .into());
+    }
+
+    if let Setting::Set(true) = sparse {
+        let conflict = if let Setting::Set(true) = binary_quantize {
+            Some("sparse embeddings cannot be binary quantized".to_string())
+        } else if let Some(quantization) =
+            quantization.set().filter(|quantization| *quantization != Quantization::None)
+        {
+            Some(format!("sparse embeddings cannot be quantized, found `{quantization}`"))
+        } else if let Setting::Set(true) = multi_vector {
+            Some("sparse embeddings cannot be multi-vector".to_string())
+        } else if chunking.as_ref().set().is_some() {
+            Some("sparse embeddings cannot be chunked".to_string())
+        } else if let Some(distance) =
+            distance.set().filter(|distance| *distance != VectorDistance::DotProduct)
+        {
+            Some(format!(
+                "sparse embeddings are compared with the `dotProduct` distance, found `{distance}`"
+            ))
+        } else {
+            dimensions
+                .set()
+                .filter(|dimensions| *dimensions > crate::vector::sparse::MAX_DIMENSIONS)
+                .map(|dimensions| {
+                    format!(
+                        "sparse embeddings have at most {} dimensions, found {dimensions}",
+                        crate::vector::sparse::MAX_DIMENSIONS
+                    )
+                })
+        };
+        if let Some(reason) = conflict {
+            return Err(crate::error::UserError::InvalidSettingsSparse {
+                embedder_name: name.to_owned(),
+                reason,
+            }
+            .into());
+        }
+    }
+
+    if let (Setting::Set(true), Setting::Set(distance)) = (&binary_quantize, &distance) {
+        if !distance.supports_binary_quantization() {
+            return Err(crate::error::UserError::InvalidDistanceForBinaryQuantization {
+                embedder_name: name.to_owned(),
+                distance: *distance,
+            }
+            .into());
+        }
+    }
+
+    if let (Setting::Set(quantization), Setting::Set(distance)) = (&quantization, &distance) {
+        // int8 quantized embeddings are searched among binary quantized embeddings
+        if quantization.is_binary() && !distance.supports_binary_quantization() {
+            return Err(crate::error::UserError::InvalidSettingsQuantization {
+                embedder_name: name.to_owned(),
+                reason: format!("Cannot use the `{quantization}` quantization with the `{distance}` distance.\n - Hint: Use another distance or the `none` quantization."),
+            }
+            .into());
+        }
+    }
+
     if let Some(url) = url.as_ref().set() {
         url::Url::parse(url).map_err(|error| crate::error::UserError::InvalidUrl {
             embedder_name: name.to_owned(),
@@ -1753,6 +2097,13 @@ pub fn validate_embedding_settings(
             distribution,
             headers,
             binary_quantized: binary_quantize,
+            quantization,
+            distance,
+            multi_vector,
+            chunking,
+            model_path,
+            threads,
+            sparse,
         }));
     };
     EmbeddingSettings::check_settings(
@@ -1773,6 +2124,13 @@ pub fn validate_embedding_settings(
         &search_embedder,
         &indexing_embedder,
         &binary_quantize,
+        &quantization,
+        &distance,
+        &multi_vector,
+        &chunking,
+        &model_path,
+        &threads,
+        &sparse,
         &distribution,
     )?;
     match inferred_source {
@@ -1810,7 +2168,8 @@ pub fn validate_embedding_settings(
         EmbedderSource::Ollama
         | EmbedderSource::HuggingFace
         | EmbedderSource::UserProvided
-        | EmbedderSource::Rest => {}
+        | EmbedderSource::Rest
+        | EmbedderSource::Onnx => {}
         EmbedderSource::Composite => {
             if let Setting::Set(embedder) = &search_embedder {
                 if let Some(source) = embedder.source.set() {
@@ -1851,6 +2210,13 @@ pub fn validate_embedding_settings(
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
+                        &embedder.quantization,
+                        &embedder.distance,
+                        &embedder.multi_vector,
+                        &embedder.chunking,
+                        &embedder.model_path,
+                        &embedder.threads,
+                        &embedder.sparse,
                         &embedder.distribution,
                     )?;
                 } else {
@@ -1906,6 +2272,13 @@ pub fn validate_embedding_settings(
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
+                        &embedder.quantization,
+                        &embedder.distance,
+                        &embedder.multi_vector,
+                        &embedder.chunking,
+                        &embedder.model_path,
+                        &embedder.threads,
+                        &embedder.sparse,
                         &embedder.distribution,
                     )?;
                 } else {
@@ -1937,6 +2310,13 @@ pub fn validate_embedding_settings(
         distribution,
         headers,
         binary_quantized: binary_quantize,
+        quantization,
+        distance,
+        multi_vector,
+        chunking,
+        model_path,
+        threads,
+        sparse,
     }))
 }
 
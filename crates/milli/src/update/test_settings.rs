@@ -620,6 +620,60 @@ fn thai_synonyms() {
     assert_eq!(result.documents_ids.len(), 2);
 }
 
+#[test]
+fn set_and_reset_synonym_rules() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "The television of the living room" },
+            { "id": 1, "name": "A tv on the wall" },
+            { "id": 2, "name": "Main St." },
+        ]))
+        .unwrap();
+
+    index
+        .update_settings(|settings| {
+            settings.set_synonym_rules(vec![
+                SynonymRule::OneWay { input: S("TV"), synonyms: vec![S("Television")], penalty: 1 },
+                SynonymRule::Placeholder {
+                    placeholder: S("st"),
+                    replacements: vec![S("street"), S("strt")],
+                },
+            ]);
+        })
+        .unwrap();
+
+    // Ensure the synonyms are normalized and stored with their penalty
+    let rtxn = index.read_txn().unwrap();
+    let synonyms = index.weighted_synonyms(&rtxn).unwrap();
+    assert_eq!(synonyms.get(&vec![S("tv")]), Some(&vec![(vec![S("television")], 1)]));
+    assert_eq!(synonyms.get(&vec![S("street")]), Some(&vec![(vec![S("st")], 0)]));
+    assert_eq!(synonyms.get(&vec![S("television")]), None);
+    assert_eq!(index.user_defined_synonym_rules(&rtxn).unwrap().len(), 2);
+
+    // The synonyms only apply in the direction of the rules
+    let result = index.search(&rtxn).query("tv").execute().unwrap();
+    assert_eq!(result.documents_ids, vec![1, 0]);
+    let result = index.search(&rtxn).query("television").execute().unwrap();
+    assert_eq!(result.documents_ids, vec![0]);
+    let result = index.search(&rtxn).query("street").execute().unwrap();
+    assert_eq!(result.documents_ids, vec![2]);
+    drop(rtxn);
+
+    index
+        .update_settings(|settings| {
+            settings.reset_synonym_rules();
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    assert!(index.weighted_synonyms(&rtxn).unwrap().is_empty());
+    assert!(index.user_defined_synonym_rules(&rtxn).unwrap().is_empty());
+    let result = index.search(&rtxn).query("tv").execute().unwrap();
+    assert_eq!(result.documents_ids, vec![1]);
+}
+
 #[test]
 fn setting_searchable_recomputes_other_settings() {
     let index = TempIndex::new();
@@ -881,6 +935,7 @@ fn test_correct_settings_init() {
                 dictionary,
                 distinct_field,
                 synonyms,
+                synonym_rules,
                 primary_key,
                 authorize_typos,
                 min_word_len_two_typos,
@@ -889,11 +944,15 @@ fn test_correct_settings_init() {
                 exact_attributes,
                 max_values_per_facet,
                 sort_facet_values_by,
+                hierarchical_facets,
+                facet_stats,
                 pagination_max_total_hits,
                 proximity_precision,
                 embedder_settings,
                 search_cutoff,
                 localized_attributes_rules,
+                analyzers,
+                rerankers,
                 prefix_search,
                 facet_search,
             } = settings;
@@ -908,6 +967,7 @@ fn test_correct_settings_init() {
             assert!(matches!(dictionary, Setting::NotSet));
             assert!(matches!(distinct_field, Setting::NotSet));
             assert!(matches!(synonyms, Setting::NotSet));
+            assert!(matches!(synonym_rules, Setting::NotSet));
             assert!(matches!(primary_key, Setting::NotSet));
             assert!(matches!(authorize_typos, Setting::NotSet));
             assert!(matches!(min_word_len_two_typos, Setting::NotSet));
@@ -916,11 +976,15 @@ fn test_correct_settings_init() {
             assert!(matches!(exact_attributes, Setting::NotSet));
             assert!(matches!(max_values_per_facet, Setting::NotSet));
             assert!(matches!(sort_facet_values_by, Setting::NotSet));
+            assert!(matches!(hierarchical_facets, Setting::NotSet));
+            assert!(matches!(facet_stats, Setting::NotSet));
             assert!(matches!(pagination_max_total_hits, Setting::NotSet));
             assert!(matches!(proximity_precision, Setting::NotSet));
             assert!(matches!(embedder_settings, Setting::NotSet));
             assert!(matches!(search_cutoff, Setting::NotSet));
             assert!(matches!(localized_attributes_rules, Setting::NotSet));
+            assert!(matches!(analyzers, Setting::NotSet));
+            assert!(matches!(rerankers, Setting::NotSet));
             assert!(matches!(prefix_search, Setting::NotSet));
             assert!(matches!(facet_search, Setting::NotSet));
         })
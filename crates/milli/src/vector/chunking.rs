@@ -0,0 +1,170 @@
+use std::ops::Range;
+
+use charabia::TokenizerBuilder;
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Describes how the texts sent to an embedder are split into chunks that are embedded separately.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct Chunking {
+    /// The unit in which `size` and `overlap` are expressed.
+    #[serde(default)]
+    #[deserr(default)]
+    pub unit: ChunkingUnit,
+    /// The maximum number of units in a chunk.
+    pub size: usize,
+    /// The number of units shared by two consecutive chunks.
+    #[serde(default)]
+    #[deserr(default)]
+    pub overlap: usize,
+    /// The top-level field whose value is split into chunks, instead of the rendered document template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub field: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum ChunkingUnit {
+    /// Words, as segmented by the tokenizer. Separators are not counted.
+    #[default]
+    Tokens,
+    /// Unicode characters.
+    Characters,
+}
+
+/// A chunk of a text, located by its byte offset in the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chunk<'t> {
+    pub text: &'t str,
+    pub start: usize,
+}
+
+impl Chunk<'_> {
+    /// The length of the chunk in bytes.
+    pub fn length(&self) -> usize {
+        self.text.len()
+    }
+}
+
+/// Locates a chunk of a document, in bytes, in the value of the chunked field or in the rendered document template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkPosition {
+    /// The index of the chunk in the document.
+    pub index: u8,
+    /// The offset of the first byte of the chunk.
+    pub start: usize,
+    /// The length of the chunk in bytes.
+    pub length: usize,
+}
+
+impl Chunking {
+    /// The maximum number of chunks per document, as a document holds at most `u8::MAX` embeddings per embedder.
+    ///
+    /// The text past the last chunk is not embedded.
+    pub const MAX_CHUNKS: usize = u8::MAX as usize;
+
+    /// Returns `Err` with a description of the problem when the overlap does not allow chunks to make progress.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.overlap >= self.size {
+            return Err(format!(
+                "`overlap` must be strictly lower than `size`, found overlap `{}` and size `{}`",
+                self.overlap, self.size
+            ));
+        }
+        Ok(())
+    }
+
+    /// Splits the text into at most [`Self::MAX_CHUNKS`] chunks.
+    ///
+    /// A text that fits in a single chunk, including an empty text, is returned as a single chunk.
+    pub fn split<'t>(&self, text: &'t str) -> Vec<Chunk<'t>> {
+        let units = self.units(text);
+        let size = self.size.max(1);
+        if units.len() <= size {
+            return vec![Chunk { text, start: 0 }];
+        }
+
+        let step = size.saturating_sub(self.overlap).max(1);
+        let mut chunks = Vec::new();
+        let mut first = 0;
+        loop {
+            let last = (first + size).min(units.len()) - 1;
+            let range = units[first].start..units[last].end;
+            chunks.push(Chunk { text: &text[range.clone()], start: range.start });
+            if last + 1 == units.len() || chunks.len() == Self::MAX_CHUNKS {
+                break;
+            }
+            first += step;
+        }
+        chunks
+    }
+
+    fn units(&self, text: &str) -> Vec<Range<usize>> {
+        match self.unit {
+            ChunkingUnit::Characters => {
+                text.char_indices().map(|(index, c)| index..index + c.len_utf8()).collect()
+            }
+            ChunkingUnit::Tokens => {
+                let mut builder = TokenizerBuilder::default();
+                let tokenizer = builder.build();
+                tokenizer
+                    .tokenize(text)
+                    .filter(|token| !token.is_separator())
+                    .map(|token| token.byte_start..token.byte_end)
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Chunking, ChunkingUnit};
+
+    fn chunking(unit: ChunkingUnit, size: usize, overlap: usize) -> Chunking {
+        Chunking { unit, size, overlap, field: None }
+    }
+
+    #[test]
+    fn characters() {
+        let chunks = chunking(ChunkingUnit::Characters, 4, 1).split("ビーグル kefir");
+        let texts: Vec<_> = chunks.iter().map(|chunk| chunk.text).collect();
+        assert_eq!(texts, ["ビーグル", "ル ke", "efir"]);
+        assert_eq!(chunks[1].start, "ビーグ".len());
+        assert_eq!(chunks[1].length(), "ル ke".len());
+    }
+
+    #[test]
+    fn tokens() {
+        let chunks = chunking(ChunkingUnit::Tokens, 3, 1).split("kefir is a good doggo, intel too");
+        let texts: Vec<_> = chunks.iter().map(|chunk| chunk.text).collect();
+        assert_eq!(texts, ["kefir is a", "a good doggo", "doggo, intel too"]);
+    }
+
+    #[test]
+    fn short_text() {
+        let chunking = chunking(ChunkingUnit::Tokens, 3, 0);
+        assert_eq!(chunking.split("kefir").len(), 1);
+        assert_eq!(chunking.split("").len(), 1);
+    }
+
+    #[test]
+    fn max_chunks() {
+        let text = "a".repeat(1000);
+        let chunks = chunking(ChunkingUnit::Characters, 2, 0).split(&text);
+        assert_eq!(chunks.len(), Chunking::MAX_CHUNKS);
+    }
+
+    #[test]
+    fn validate() {
+        assert!(chunking(ChunkingUnit::Tokens, 3, 2).validate().is_ok());
+        assert!(chunking(ChunkingUnit::Tokens, 3, 3).validate().is_err());
+        assert!(chunking(ChunkingUnit::Tokens, 0, 0).validate().is_err());
+    }
+}
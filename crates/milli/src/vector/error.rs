@@ -57,6 +57,11 @@ pub enum EmbedErrorKind {
     TensorValue(candle_core::Error),
     #[error("could not run model:\n  - {0}")]
     ModelForward(candle_core::Error),
+    #[cfg(feature = "onnx")]
+    #[error("could not run ONNX model:\n  - {0}")]
+    OnnxRun(ort::Error),
+    #[error("unexpected output shape `{0:?}` for ONNX model, expected `[batch, tokens, dimensions]` or `[batch, dimensions]`")]
+    OnnxOutputShape(Vec<i64>),
     #[error("attempt to embed the following text in a configuration where embeddings must be user provided:\n  - `{0}`")]
     ManualEmbed(String),
     #[error("model not found. Meilisearch will not automatically download models from the Ollama library, please pull the model manually{}", option_info(.0.as_deref(), "server replied with "))]
@@ -99,6 +104,10 @@ pub enum EmbedErrorKind {
     UnexpectedDimension(usize, usize),
     #[error("no embedding was produced")]
     MissingEmbedding,
+    #[error("expected a response containing {0} scores, got {1}")]
+    RerankScoreCount(usize, usize),
+    #[error("the reranker returned a score for the document at index `{0}`, but only {1} documents were sent")]
+    RerankIndexOutOfBounds(usize, usize),
     #[error(transparent)]
     PanicInThreadPool(#[from] PanicCatched),
 }
@@ -127,6 +136,16 @@ impl EmbedError {
         Self { kind: EmbedErrorKind::ModelForward(inner), fault: FaultSource::Runtime }
     }
 
+    #[cfg(feature = "onnx")]
+    pub(crate) fn onnx_run(inner: ort::Error) -> Self {
+        Self { kind: EmbedErrorKind::OnnxRun(inner), fault: FaultSource::Runtime }
+    }
+
+    #[cfg(feature = "onnx")]
+    pub(crate) fn onnx_output_shape(shape: Vec<i64>) -> Self {
+        Self { kind: EmbedErrorKind::OnnxOutputShape(shape), fault: FaultSource::User }
+    }
+
     pub(crate) fn embed_on_manual_embedder(texts: String) -> EmbedError {
         Self { kind: EmbedErrorKind::ManualEmbed(texts), fault: FaultSource::User }
     }
@@ -210,6 +229,17 @@ impl EmbedError {
     pub(crate) fn rest_extraction_error(error: String) -> EmbedError {
         Self { kind: EmbedErrorKind::RestExtractionError(error), fault: FaultSource::Runtime }
     }
+
+    pub(crate) fn rerank_score_count(expected: usize, got: usize) -> EmbedError {
+        Self { kind: EmbedErrorKind::RerankScoreCount(expected, got), fault: FaultSource::Runtime }
+    }
+
+    pub(crate) fn rerank_index_out_of_bounds(index: usize, count: usize) -> EmbedError {
+        Self {
+            kind: EmbedErrorKind::RerankIndexOutOfBounds(index, count),
+            fault: FaultSource::Runtime,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -299,6 +329,14 @@ impl NewEmbedderError {
         }
     }
 
+    pub(crate) fn model_path_not_a_directory(path: PathBuf) -> Self {
+        Self { kind: NewEmbedderErrorKind::ModelPathNotADirectory(path), fault: FaultSource::User }
+    }
+
+    pub(crate) fn missing_weights(path: PathBuf) -> Self {
+        Self { kind: NewEmbedderErrorKind::MissingWeights(path), fault: FaultSource::User }
+    }
+
     pub fn new_api_fail(inner: ApiError) -> Self {
         Self { kind: NewEmbedderErrorKind::NewApiFail(inner), fault: FaultSource::Bug }
     }
@@ -319,6 +357,19 @@ impl NewEmbedderError {
         Self { kind: NewEmbedderErrorKind::LoadModel(inner), fault: FaultSource::Runtime }
     }
 
+    #[cfg(feature = "onnx")]
+    pub(crate) fn onnx_load_model(filename: PathBuf, inner: ort::Error) -> Self {
+        Self {
+            kind: NewEmbedderErrorKind::OnnxLoadModel { filename, inner },
+            fault: FaultSource::User,
+        }
+    }
+
+    #[cfg(not(feature = "onnx"))]
+    pub(crate) fn onnx_not_enabled() -> Self {
+        Self { kind: NewEmbedderErrorKind::OnnxNotEnabled, fault: FaultSource::User }
+    }
+
     pub fn could_not_determine_dimension(inner: EmbedError) -> NewEmbedderError {
         Self {
             kind: NewEmbedderErrorKind::CouldNotDetermineDimension(inner),
@@ -479,6 +530,10 @@ pub enum NewEmbedderErrorKind {
     PytorchWeight(candle_core::Error),
     #[error("could not build weights from Safetensor weights:\n  - {0}")]
     SafetensorWeight(candle_core::Error),
+    #[error("`modelPath` `{}` is not a directory", .0.display())]
+    ModelPathNotADirectory(PathBuf),
+    #[error("could not find the weights of the model in `{}`\n  - Hint: the directory must contain a `model.safetensors` or `pytorch_model.bin` file", .0.display())]
+    MissingWeights(PathBuf),
     #[error("could not spawn HG_HUB API client:\n  - {0}")]
     NewApiFail(ApiError),
     #[error("fetching file from HG_HUB failed:\n  - {0}")]
@@ -499,6 +554,11 @@ pub enum NewEmbedderErrorKind {
     CompositeEmbeddingCountMismatch { search_count: usize, index_count: usize },
     #[error("error while generating test embeddings.\n  - the embeddings produced at search time and indexing time are not similar enough.\n  - angular distance {distance:.2}\n  - Meilisearch requires a maximum distance of {MAX_COMPOSITE_DISTANCE}.\n  - Note: check that both embedders produce similar embeddings.{hint}")]
     CompositeEmbeddingValueMismatch { distance: f32, hint: CompositeEmbedderContainsHuggingFace },
+    #[cfg(feature = "onnx")]
+    #[error("could not load ONNX model at `{}`:\n  - {inner}", filename.display())]
+    OnnxLoadModel { filename: PathBuf, inner: ort::Error },
+    #[error("embedders of source `onnx` are not available, Meilisearch must be compiled with the `onnx` feature to use them")]
+    OnnxNotEnabled,
 }
 
 pub struct PossibleEmbeddingMistakes {
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use candle_core::Tensor;
 use candle_nn::VarBuilder;
 use candle_transformers::models::bert::{BertModel, Config, DTYPE};
@@ -36,6 +38,9 @@ pub struct EmbedderOptions {
     pub distribution: Option<DistributionShift>,
     #[serde(default)]
     pub pooling: OverridePooling,
+    /// Directory containing the files of the model, used instead of fetching `model` from the Hub.
+    #[serde(default)]
+    pub model_path: Option<String>,
 }
 
 #[derive(
@@ -67,8 +72,14 @@ impl EmbedderOptions {
             revision: Some("617ca489d9e86b49b8167676d8220688b99db36e".into()),
             distribution: None,
             pooling: OverridePooling::UseModel,
+            model_path: None,
         }
     }
+
+    /// The name of the model in logs and error messages.
+    fn model_name(&self) -> &str {
+        self.model_path.as_deref().unwrap_or(&self.model)
+    }
 }
 
 impl Default for EmbedderOptions {
@@ -90,7 +101,7 @@ pub struct Embedder {
 impl std::fmt::Debug for Embedder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Embedder")
-            .field("model", &self.options.model)
+            .field("model", &self.options.model_name())
             .field("tokenizer", &self.tokenizer)
             .field("options", &self.options)
             .field("pooling", &self.pooling)
@@ -99,7 +110,7 @@ impl std::fmt::Debug for Embedder {
 }
 
 #[derive(Clone, Copy, serde::Deserialize)]
-struct PoolingConfig {
+pub(super) struct PoolingConfig {
     #[serde(default)]
     pub pooling_mode_cls_token: bool,
     #[serde(default)]
@@ -122,7 +133,7 @@ pub enum Pooling {
     LastToken,
 }
 impl Pooling {
-    fn override_with(&mut self, pooling: OverridePooling) {
+    pub(super) fn override_with(&mut self, pooling: OverridePooling) {
         match pooling {
             OverridePooling::UseModel => {}
             OverridePooling::ForceCls => *self = Pooling::Cls,
@@ -149,93 +160,167 @@ impl From<PoolingConfig> for Pooling {
     }
 }
 
+/// The files of a model, either fetched from the Hub or read from a local directory.
+pub(super) struct ModelFiles {
+    pub config_filename: PathBuf,
+    pub tokenizer_filename: PathBuf,
+    pub weights_filename: PathBuf,
+    weight_source: WeightSource,
+    /// `None` when the model doesn't define a pooling method.
+    pub pooling: Option<PathBuf>,
+}
+
+impl ModelFiles {
+    /// Fetches the files of the model from the Hub, or reads them from `model_path` when it is set.
+    pub(super) fn fetch(
+        model: &str,
+        revision: Option<&str>,
+        model_path: Option<&str>,
+    ) -> Result<Self, NewEmbedderError> {
+        match model_path {
+            Some(model_path) => Self::from_directory(Path::new(model_path)),
+            None => Self::from_hub(model, revision),
+        }
+    }
+
+    fn from_hub(model: &str, revision: Option<&str>) -> Result<Self, NewEmbedderError> {
+        let repo = match revision {
+            Some(revision) => {
+                Repo::with_revision(model.to_owned(), RepoType::Model, revision.to_owned())
+            }
+            None => Repo::model(model.to_owned()),
+        };
+        let api = Api::new().map_err(NewEmbedderError::new_api_fail)?;
+        let api = api.repo(repo);
+        let config_filename = api.get("config.json").map_err(NewEmbedderError::api_get)?;
+        let tokenizer_filename = api.get("tokenizer.json").map_err(NewEmbedderError::api_get)?;
+        let (weights_filename, weight_source) = {
+            api.get("model.safetensors")
+                .map(|filename| (filename, WeightSource::Safetensors))
+                .or_else(|_| {
+                    api.get("pytorch_model.bin").map(|filename| (filename, WeightSource::Pytorch))
+                })
+                .map_err(NewEmbedderError::api_get)?
+        };
+        let pooling = match api.get("1_Pooling/config.json") {
+            Ok(pooling) => Some(pooling),
+            Err(hf_hub::api::sync::ApiError::RequestError(error))
+                if matches!(*error, ureq::Error::Status(404, _,)) =>
+            {
+                // ignore the error if the file simply doesn't exist
+                None
+            }
+            Err(error) => return Err(NewEmbedderError::api_get(error)),
+        };
+
+        Ok(Self { config_filename, tokenizer_filename, weights_filename, weight_source, pooling })
+    }
+
+    /// Reads the files of the model from a directory laid out like a repository of the Hub.
+    fn from_directory(directory: &Path) -> Result<Self, NewEmbedderError> {
+        if !directory.is_dir() {
+            return Err(NewEmbedderError::model_path_not_a_directory(directory.to_owned()));
+        }
+        let (weights_filename, weight_source) = [
+            ("model.safetensors", WeightSource::Safetensors),
+            ("pytorch_model.bin", WeightSource::Pytorch),
+        ]
+        .into_iter()
+        .map(|(filename, source)| (directory.join(filename), source))
+        .find(|(filename, _)| filename.is_file())
+        .ok_or_else(|| NewEmbedderError::missing_weights(directory.to_owned()))?;
+        let pooling = Some(directory.join("1_Pooling").join("config.json"))
+            .filter(|pooling_filename| pooling_filename.is_file());
+
+        Ok(Self {
+            config_filename: directory.join("config.json"),
+            tokenizer_filename: directory.join("tokenizer.json"),
+            weights_filename,
+            weight_source,
+            pooling,
+        })
+    }
+
+    /// Loads the weights of the model on the device.
+    pub(super) fn var_builder(
+        &self,
+        device: &candle_core::Device,
+    ) -> Result<VarBuilder<'static>, NewEmbedderError> {
+        Ok(match self.weight_source {
+            WeightSource::Pytorch => VarBuilder::from_pth(&self.weights_filename, DTYPE, device)
+                .map_err(NewEmbedderError::pytorch_weight)?,
+            WeightSource::Safetensors => unsafe {
+                VarBuilder::from_mmaped_safetensors(&[&self.weights_filename], DTYPE, device)
+                    .map_err(NewEmbedderError::safetensor_weight)?
+            },
+        })
+    }
+}
+
+/// The CUDA device when available, otherwise the CPU.
+pub(super) fn device() -> candle_core::Device {
+    match candle_core::Device::cuda_if_available(0) {
+        Ok(device) => device,
+        Err(error) => {
+            tracing::warn!(
+                "could not initialize CUDA device for Hugging Face model, defaulting to CPU: {}",
+                error
+            );
+            candle_core::Device::Cpu
+        }
+    }
+}
+
 impl Embedder {
     pub fn new(
         options: EmbedderOptions,
         cache_cap: usize,
     ) -> std::result::Result<Self, NewEmbedderError> {
-        let device = match candle_core::Device::cuda_if_available(0) {
-            Ok(device) => device,
-            Err(error) => {
-                tracing::warn!("could not initialize CUDA device for Hugging Face embedder, defaulting to CPU: {}", error);
-                candle_core::Device::Cpu
+        let device = device();
+        let model_files = ModelFiles::fetch(
+            &options.model,
+            options.revision.as_deref(),
+            options.model_path.as_deref(),
+        )?;
+
+        let mut pooling: Pooling = match model_files.pooling.clone() {
+            Some(pooling_filename) => {
+                let pooling = std::fs::read_to_string(&pooling_filename).map_err(|inner| {
+                    NewEmbedderError::open_pooling_config(pooling_filename.clone(), inner)
+                })?;
+
+                let pooling: PoolingConfig = serde_json::from_str(&pooling).map_err(|inner| {
+                    NewEmbedderError::deserialize_pooling_config(
+                        options.model_name().to_owned(),
+                        pooling_filename,
+                        inner,
+                    )
+                })?;
+                pooling.into()
             }
+            None => Pooling::default(),
         };
-        let repo = match options.revision.clone() {
-            Some(revision) => Repo::with_revision(options.model.clone(), RepoType::Model, revision),
-            None => Repo::model(options.model.clone()),
-        };
-        let (config_filename, tokenizer_filename, weights_filename, weight_source, pooling) = {
-            let api = Api::new().map_err(NewEmbedderError::new_api_fail)?;
-            let api = api.repo(repo);
-            let config = api.get("config.json").map_err(NewEmbedderError::api_get)?;
-            let tokenizer = api.get("tokenizer.json").map_err(NewEmbedderError::api_get)?;
-            let (weights, source) = {
-                api.get("model.safetensors")
-                    .map(|filename| (filename, WeightSource::Safetensors))
-                    .or_else(|_| {
-                        api.get("pytorch_model.bin")
-                            .map(|filename| (filename, WeightSource::Pytorch))
-                    })
-                    .map_err(NewEmbedderError::api_get)?
-            };
-            let pooling = match api.get("1_Pooling/config.json") {
-                Ok(pooling) => Some(pooling),
-                Err(hf_hub::api::sync::ApiError::RequestError(error))
-                    if matches!(*error, ureq::Error::Status(404, _,)) =>
-                {
-                    // ignore the error if the file simply doesn't exist
-                    None
-                }
-                Err(error) => return Err(NewEmbedderError::api_get(error)),
-            };
-            let mut pooling: Pooling = match pooling {
-                Some(pooling_filename) => {
-                    let pooling = std::fs::read_to_string(&pooling_filename).map_err(|inner| {
-                        NewEmbedderError::open_pooling_config(pooling_filename.clone(), inner)
-                    })?;
-
-                    let pooling: PoolingConfig =
-                        serde_json::from_str(&pooling).map_err(|inner| {
-                            NewEmbedderError::deserialize_pooling_config(
-                                options.model.clone(),
-                                pooling_filename,
-                                inner,
-                            )
-                        })?;
-                    pooling.into()
-                }
-                None => Pooling::default(),
-            };
-
-            pooling.override_with(options.pooling);
 
-            (config, tokenizer, weights, source, pooling)
-        };
+        pooling.override_with(options.pooling);
 
-        let config = std::fs::read_to_string(&config_filename)
+        let config_filename = &model_files.config_filename;
+        let config = std::fs::read_to_string(config_filename)
             .map_err(|inner| NewEmbedderError::open_config(config_filename.clone(), inner))?;
         let config: Config = serde_json::from_str(&config).map_err(|inner| {
             NewEmbedderError::deserialize_config(
-                options.model.clone(),
+                options.model_name().to_owned(),
                 config,
-                config_filename,
+                config_filename.clone(),
                 inner,
             )
         })?;
-        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
-            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+        let tokenizer_filename = &model_files.tokenizer_filename;
+        let mut tokenizer = Tokenizer::from_file(tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
 
-        let vb = match weight_source {
-            WeightSource::Pytorch => VarBuilder::from_pth(&weights_filename, DTYPE, &device)
-                .map_err(NewEmbedderError::pytorch_weight)?,
-            WeightSource::Safetensors => unsafe {
-                VarBuilder::from_mmaped_safetensors(&[weights_filename], DTYPE, &device)
-                    .map_err(NewEmbedderError::safetensor_weight)?
-            },
-        };
+        let vb = model_files.var_builder(&device)?;
 
-        tracing::debug!(model = options.model, weight=?weight_source, pooling=?pooling, "model config");
+        tracing::debug!(model = options.model_name(), weight=?model_files.weight_source, pooling=?pooling, "model config");
 
         let model = BertModel::load(vb, &config).map_err(NewEmbedderError::load_model)?;
 
@@ -352,7 +437,7 @@ impl Embedder {
 
     pub fn distribution(&self) -> Option<DistributionShift> {
         self.options.distribution.or_else(|| {
-            if self.options.model == "BAAI/bge-base-en-v1.5" {
+            if self.options.model_path.is_none() && self.options.model == "BAAI/bge-base-en-v1.5" {
                 Some(DistributionShift {
                     current_mean: ordered_float::OrderedFloat(0.85),
                     current_sigma: ordered_float::OrderedFloat(0.1),
@@ -0,0 +1,201 @@
+//! Embeddings quantized to 8-bit integers.
+//!
+//! Each dimension of an embedding is mapped linearly from the `[min, max]` range of its values to an `i8`,
+//! so that a quantized embedding takes about a quarter of the size of the original one.
+//!
+//! Embedders with the `int8` quantization build their arroy trees on binary quantized embeddings,
+//! then use the 8-bit embeddings to rescore the neighbors found in the trees, and to retrieve the embeddings.
+
+use arroy::ItemId;
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{RoTxn, RwTxn};
+
+use super::Embedding;
+
+/// How many more neighbors than requested are retrieved from the binary quantized trees before rescoring.
+pub const OVERSAMPLING: usize = 4;
+
+/// The size of the header of a quantized embedding: its minimum and the step between two consecutive integers.
+const HEADER_SIZE: usize = 2 * std::mem::size_of::<f32>();
+
+/// Quantizes an embedding, returning its encoded representation.
+pub fn quantize(embedding: &[f32]) -> Vec<u8> {
+    let (min, max) = embedding
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &x| (min.min(x), max.max(x)));
+    let (min, step) = if embedding.is_empty() || max <= min {
+        (if embedding.is_empty() { 0.0 } else { min }, 0.0)
+    } else {
+        (min, (max - min) / u8::MAX as f32)
+    };
+
+    let mut encoded = Vec::with_capacity(HEADER_SIZE + embedding.len());
+    encoded.extend_from_slice(&min.to_le_bytes());
+    encoded.extend_from_slice(&step.to_le_bytes());
+    encoded.extend(embedding.iter().map(|&x| {
+        let level = if step == 0.0 { 0.0 } else { ((x - min) / step).round() };
+        // maps the levels `0..=255` to `-128..=127`
+        (level.clamp(0.0, u8::MAX as f32) as i16 + i8::MIN as i16) as i8 as u8
+    }));
+    encoded
+}
+
+/// Restores an approximation of an embedding from its encoded representation.
+pub fn dequantize(encoded: &[u8]) -> Embedding {
+    let (header, levels) = encoded.split_at(HEADER_SIZE);
+    let min = f32::from_le_bytes(header[..4].try_into().unwrap());
+    let step = f32::from_le_bytes(header[4..].try_into().unwrap());
+    levels.iter().map(|&level| min + (level as i8 as i16 - i8::MIN as i16) as f32 * step).collect()
+}
+
+/// The 8-bit embeddings of an embedder.
+///
+/// The embeddings of an item are stored consecutively under `[embedder, item]`,
+/// in the same order as in the arroy stores of the embedder.
+#[derive(Debug, Clone, Copy)]
+pub struct Int8Vectors {
+    database: heed::Database<Bytes, Bytes>,
+    embedder_index: u8,
+}
+
+impl Int8Vectors {
+    pub fn new(database: heed::Database<Bytes, Bytes>, embedder_index: u8) -> Self {
+        Self { database, embedder_index }
+    }
+
+    fn key(&self, item: ItemId) -> [u8; 5] {
+        let [a, b, c, d] = item.to_be_bytes();
+        [self.embedder_index, a, b, c, d]
+    }
+
+    fn encoded_vectors(&self, rtxn: &RoTxn, item: ItemId) -> heed::Result<Vec<Vec<u8>>> {
+        let Some(bytes) = self.database.get(rtxn, &self.key(item))? else {
+            return Ok(Vec::new());
+        };
+        let (dimensions, encoded) = bytes.split_at(std::mem::size_of::<u32>());
+        let dimensions = u32::from_le_bytes(dimensions.try_into().unwrap()) as usize;
+        Ok(encoded.chunks_exact(HEADER_SIZE + dimensions).map(<[u8]>::to_vec).collect())
+    }
+
+    fn put_encoded_vectors(
+        &self,
+        wtxn: &mut RwTxn,
+        item: ItemId,
+        encoded: &[Vec<u8>],
+    ) -> heed::Result<()> {
+        let Some(first) = encoded.first() else {
+            self.database.delete(wtxn, &self.key(item))?;
+            return Ok(());
+        };
+        let dimensions = (first.len() - HEADER_SIZE) as u32;
+        let mut bytes = dimensions.to_le_bytes().to_vec();
+        encoded.iter().for_each(|encoded| bytes.extend_from_slice(encoded));
+        self.database.put(wtxn, &self.key(item), &bytes)
+    }
+
+    pub fn item_vectors(&self, rtxn: &RoTxn, item: ItemId) -> heed::Result<Vec<Embedding>> {
+        Ok(self.encoded_vectors(rtxn, item)?.iter().map(|encoded| dequantize(encoded)).collect())
+    }
+
+    /// Retrieves the embedding of the item stored at `index` in the arroy stores.
+    pub fn item_vector(
+        &self,
+        rtxn: &RoTxn,
+        item: ItemId,
+        index: u8,
+    ) -> heed::Result<Option<Embedding>> {
+        Ok(self.encoded_vectors(rtxn, item)?.get(index as usize).map(|encoded| dequantize(encoded)))
+    }
+
+    pub fn contains_item(&self, rtxn: &RoTxn, item: ItemId) -> heed::Result<bool> {
+        let database = self.database.remap_data_type::<DecodeIgnore>();
+        Ok(database.get(rtxn, &self.key(item))?.is_some())
+    }
+
+    /// Replaces all the embeddings of the item.
+    ///
+    /// Like in arroy, an item holds at most 256 embeddings, the remaining ones are silently ignored.
+    pub fn set_item_vectors<'e>(
+        &self,
+        wtxn: &mut RwTxn,
+        item: ItemId,
+        embeddings: impl IntoIterator<Item = &'e [f32]>,
+    ) -> heed::Result<()> {
+        let encoded: Vec<_> =
+            embeddings.into_iter().take(u8::MAX as usize + 1).map(quantize).collect();
+        self.put_encoded_vectors(wtxn, item, &encoded)
+    }
+
+    /// Adds an embedding after the existing embeddings of the item.
+    pub fn add_item(&self, wtxn: &mut RwTxn, item: ItemId, vector: &[f32]) -> heed::Result<()> {
+        let mut encoded = self.encoded_vectors(wtxn, item)?;
+        if encoded.len() <= u8::MAX as usize {
+            encoded.push(quantize(vector));
+        }
+        self.put_encoded_vectors(wtxn, item, &encoded)
+    }
+
+    /// Deletes all the embeddings of the item.
+    pub fn del_items(&self, wtxn: &mut RwTxn, item: ItemId) -> heed::Result<()> {
+        self.database.delete(wtxn, &self.key(item))?;
+        Ok(())
+    }
+
+    /// Deletes one embedding of the item, returning its index when it was found.
+    ///
+    /// Like in the arroy stores, the last embedding of the item takes the place of the deleted one.
+    pub fn del_item(
+        &self,
+        wtxn: &mut RwTxn,
+        item: ItemId,
+        vector: &[f32],
+    ) -> heed::Result<Option<u8>> {
+        let mut encoded = self.encoded_vectors(wtxn, item)?;
+        let quantized = quantize(vector);
+        let Some(position) = encoded.iter().position(|encoded| *encoded == quantized) else {
+            return Ok(None);
+        };
+        encoded.swap_remove(position);
+        self.put_encoded_vectors(wtxn, item, &encoded)?;
+        Ok(Some(position as u8))
+    }
+
+    pub fn clear(&self, wtxn: &mut RwTxn) -> heed::Result<()> {
+        let mut iter = self.database.prefix_iter_mut(wtxn, &[self.embedder_index])?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference on database entries.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quantization() {
+        let embedding = [-1.0, -0.5, 0.0, 0.25, 1.0];
+        let encoded = quantize(&embedding);
+        assert_eq!(encoded.len(), HEADER_SIZE + embedding.len());
+        let restored = dequantize(&encoded);
+        assert_eq!(restored.len(), embedding.len());
+        let step = 2.0 / 255.0;
+        for (original, restored) in embedding.iter().zip(&restored) {
+            assert!(
+                (original - restored).abs() <= step / 2.0 + f32::EPSILON,
+                "{original} {restored}"
+            );
+        }
+        // the bounds are exact
+        assert_eq!(restored[0], -1.0);
+        assert_eq!(restored[4], 1.0);
+    }
+
+    #[test]
+    fn constant_embeddings() {
+        assert_eq!(dequantize(&quantize(&[0.5, 0.5, 0.5])), [0.5, 0.5, 0.5]);
+        assert_eq!(dequantize(&quantize(&[])), Vec::<f32>::new());
+    }
+}
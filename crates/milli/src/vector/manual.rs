@@ -6,17 +6,26 @@ use crate::vector::Embedding;
 pub struct Embedder {
     dimensions: usize,
     distribution: Option<DistributionShift>,
+    multi_vector: bool,
+    sparse: bool,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct EmbedderOptions {
     pub dimensions: usize,
     pub distribution: Option<DistributionShift>,
+    pub multi_vector: Option<bool>,
+    pub sparse: Option<bool>,
 }
 
 impl Embedder {
     pub fn new(options: EmbedderOptions) -> Self {
-        Self { dimensions: options.dimensions, distribution: options.distribution }
+        Self {
+            dimensions: options.dimensions,
+            distribution: options.distribution,
+            multi_vector: options.multi_vector.unwrap_or_default(),
+            sparse: options.sparse.unwrap_or_default(),
+        }
     }
 
     pub fn embed<S: AsRef<str>>(&self, texts: &[S]) -> Result<Vec<Embedding>, EmbedError> {
@@ -41,6 +50,14 @@ impl Embedder {
         self.distribution
     }
 
+    pub fn multi_vector(&self) -> bool {
+        self.multi_vector
+    }
+
+    pub fn sparse(&self) -> bool {
+        self.sparse
+    }
+
     pub(crate) fn embed_index_ref(&self, texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
         texts.iter().map(|text| self.embed_one(text)).collect()
     }
@@ -3,9 +3,13 @@ use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
-use arroy::distances::{BinaryQuantizedCosine, Cosine};
+use arroy::distances::{
+    BinaryQuantizedCosine, BinaryQuantizedEuclidean, BinaryQuantizedManhattan, Cosine, DotProduct,
+    Euclidean, Manhattan,
+};
 use arroy::ItemId;
 use deserr::{DeserializeError, Deserr};
+use heed::types::Bytes;
 use heed::{RoTxn, RwTxn, Unspecified};
 use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
@@ -13,18 +17,25 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use self::error::{EmbedError, NewEmbedderError};
-use crate::progress::Progress;
+use self::int8::Int8Vectors;
+use self::sparse::SparseVectors;
+use crate::progress::{AtomicEmbeddingStep, Progress};
 use crate::prompt::{Prompt, PromptData};
 use crate::ThreadPoolNoAbort;
 
+pub mod chunking;
 pub mod composite;
 pub mod error;
 pub mod hf;
+pub mod int8;
 pub mod json_template;
 pub mod manual;
+pub mod onnx;
 pub mod openai;
 pub mod parsed_vectors;
+pub mod rerank;
 pub mod settings;
+pub mod sparse;
 
 pub mod ollama;
 pub mod rest;
@@ -36,25 +47,92 @@ pub type Embedding = Vec<f32>;
 pub const REQUEST_PARALLELISM: usize = 40;
 pub const MAX_COMPOSITE_DISTANCE: f32 = 0.01;
 
+/// A filter keeping at most `1 / BRUTE_FORCE_SELECTIVITY` of the embedded items is very selective,
+/// and its candidates are compared to the query one by one rather than searched in the arroy trees.
+const BRUTE_FORCE_SELECTIVITY: u64 = 100;
+/// The maximum number of candidates compared to the query one by one.
+const BRUTE_FORCE_MAX_CANDIDATES: u64 = 10_000;
+
+/// Runs `$body` with `$d` being an alias to the arroy distance matching
+/// the provided [`VectorDistance`] and whether the trees are binary quantized.
+macro_rules! with_arroy_distance {
+    ($distance:expr, $quantized:expr, $d:ident => $body:expr) => {
+        match ($distance, $quantized) {
+            (VectorDistance::Cosine, false) => {
+                type $d = Cosine;
+                $body
+            }
+            (VectorDistance::Cosine, true) => {
+                type $d = BinaryQuantizedCosine;
+                $body
+            }
+            // binary quantizing dot product embedders is forbidden by the settings
+            (VectorDistance::DotProduct, _) => {
+                type $d = DotProduct;
+                $body
+            }
+            (VectorDistance::Euclidean, false) => {
+                type $d = Euclidean;
+                $body
+            }
+            (VectorDistance::Euclidean, true) => {
+                type $d = BinaryQuantizedEuclidean;
+                $body
+            }
+            (VectorDistance::Manhattan, false) => {
+                type $d = Manhattan;
+                $body
+            }
+            (VectorDistance::Manhattan, true) => {
+                type $d = BinaryQuantizedManhattan;
+                $body
+            }
+        }
+    };
+}
+
 pub struct ArroyWrapper {
-    quantized: bool,
+    quantization: Quantization,
+    distance: VectorDistance,
     embedder_index: u8,
     database: arroy::Database<Unspecified>,
+    sparse: Option<SparseVectors>,
+    int8: Int8Vectors,
 }
 
 impl ArroyWrapper {
+    /// When `sparse_database` is set, the embeddings are sparse and stored in this database instead of arroy.
+    ///
+    /// The `int8_database` stores the 8-bit embeddings of the embedders using the [`Quantization::Int8`] quantization.
     pub fn new(
         database: arroy::Database<Unspecified>,
         embedder_index: u8,
-        quantized: bool,
+        quantization: Quantization,
+        distance: VectorDistance,
+        sparse_database: Option<heed::Database<Bytes, Bytes>>,
+        int8_database: heed::Database<Bytes, Bytes>,
     ) -> Self {
-        Self { database, embedder_index, quantized }
+        let sparse = sparse_database.map(|database| SparseVectors::new(database, embedder_index));
+        let int8 = Int8Vectors::new(int8_database, embedder_index);
+        Self { database, embedder_index, quantization, distance, sparse, int8 }
     }
 
     pub fn embedder_index(&self) -> u8 {
         self.embedder_index
     }
 
+    pub fn distance(&self) -> VectorDistance {
+        self.distance
+    }
+
+    pub fn quantization(&self) -> Quantization {
+        self.quantization
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        self.sparse.is_some()
+    }
+
     fn readers<'a, D: arroy::Distance>(
         &'a self,
         rtxn: &'a RoTxn<'a>,
@@ -73,15 +151,22 @@ impl ArroyWrapper {
         })
     }
 
+    /// The dimensions of the stored embeddings, always `0` for sparse embeddings.
     pub fn dimensions(&self, rtxn: &RoTxn) -> Result<usize, arroy::Error> {
-        let first_id = arroy_db_range_for_embedder(self.embedder_index).next().unwrap();
-        if self.quantized {
-            Ok(arroy::Reader::open(rtxn, first_id, self.quantized_db())?.dimensions())
-        } else {
-            Ok(arroy::Reader::open(rtxn, first_id, self.angular_db())?.dimensions())
+        if self.sparse.is_some() {
+            return Ok(0);
         }
+        let first_id = arroy_db_range_for_embedder(self.embedder_index).next().unwrap();
+        with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            Ok(arroy::Reader::open(rtxn, first_id, self.typed_db::<D>())?.dimensions())
+        })
     }
 
+    /// Builds the trees of the embedder.
+    ///
+    /// When `quantization` or `distance` differ from the ones of the wrapper,
+    /// the stored vectors are converted to the new quantization and distance before building.
+    /// Quantizing is lossy: a `quantization` the stored vectors cannot be converted to is ignored.
     #[allow(clippy::too_many_arguments)]
     pub fn build_and_quantize<R: rand::Rng + rand::SeedableRng>(
         &mut self,
@@ -89,33 +174,45 @@ impl ArroyWrapper {
         progress: &Progress,
         rng: &mut R,
         dimension: usize,
-        quantizing: bool,
+        quantization: Quantization,
+        distance: VectorDistance,
         arroy_memory: Option<usize>,
         cancel: &(impl Fn() -> bool + Sync + Send),
     ) -> Result<(), arroy::Error> {
+        // the inverted index of sparse embeddings is updated on write
+        if self.sparse.is_some() {
+            return Ok(());
+        }
+        let quantization = if self.quantization.can_convert_to(quantization) {
+            quantization
+        } else {
+            self.quantization
+        };
+        match (self.quantization, quantization) {
+            (Quantization::None, Quantization::Int8) => self.write_int8_vectors(wtxn, progress)?,
+            (Quantization::Int8, Quantization::Binary) => self.int8.clear(wtxn)?,
+            _ => (),
+        }
+        let quantized = quantization.is_binary();
+        let converting = quantized != self.quantization.is_binary() || distance != self.distance;
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
-                if writer.need_build(wtxn)? {
-                    writer.builder(rng).build(wtxn)?
-                } else if writer.is_empty(wtxn)? {
-                    break;
-                }
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
-                // If we are quantizing the databases, we can't know from meilisearch
+            with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+                let writer = arroy::Writer::new(self.typed_db::<D>(), index, dimension);
+                // If we are converting the databases, we can't know from meilisearch
                 // if the db was empty but still contained the wrong metadata, thus we need
-                // to quantize everything and can't stop early. Since this operation can
-                // only happens once in the life of an embedder, it's not very performances
-                // sensitive.
-                if quantizing && !self.quantized {
-                    let writer = writer.prepare_changing_distance::<BinaryQuantizedCosine>(wtxn)?;
-                    writer
-                        .builder(rng)
-                        .available_memory(arroy_memory.unwrap_or(usize::MAX))
-                        .progress(|step| progress.update_progress_from_arroy(step))
-                        .cancel(cancel)
-                        .build(wtxn)?;
+                // to convert everything and can't stop early. Since this operation only
+                // happens when the quantization or the distance of an embedder changes,
+                // it's not very performances sensitive.
+                if converting {
+                    with_arroy_distance!(distance, quantized, ND => {
+                        let writer = writer.prepare_changing_distance::<ND>(wtxn)?;
+                        writer
+                            .builder(rng)
+                            .available_memory(arroy_memory.unwrap_or(usize::MAX))
+                            .progress(|step| progress.update_progress_from_arroy(step))
+                            .cancel(cancel)
+                            .build(wtxn)?;
+                    })
                 } else if writer.need_build(wtxn)? {
                     writer
                         .builder(rng)
@@ -126,8 +223,40 @@ impl ArroyWrapper {
                 } else if writer.is_empty(wtxn)? {
                     break;
                 }
-            }
+            })
         }
+        self.quantization = quantization;
+        self.distance = distance;
+        Ok(())
+    }
+
+    /// Stores the 8-bit version of the full precision embeddings, before the trees are binary quantized.
+    fn write_int8_vectors(
+        &self,
+        wtxn: &mut RwTxn,
+        progress: &Progress,
+    ) -> Result<(), arroy::Error> {
+        with_arroy_distance!(self.distance, false, D => {
+            let db = self.typed_db::<D>();
+            // uses invariant: vectors are packed in the first writers, so every item is in the first one.
+            let items = match self.readers(wtxn, db).next() {
+                Some(reader) => reader?.item_ids().clone(),
+                None => return Ok(()),
+            };
+            let (current, step) = AtomicEmbeddingStep::new(items.len() as u32);
+            progress.update_progress(step);
+            for item in items {
+                let mut vectors = Vec::new();
+                for reader in self.readers(wtxn, db) {
+                    match reader?.item_vector(wtxn, item)? {
+                        Some(vector) => vectors.push(vector),
+                        None => break,
+                    }
+                }
+                self.int8.set_item_vectors(wtxn, item, vectors.iter().map(Vec::as_slice))?;
+                current.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+        });
         Ok(())
     }
 
@@ -141,17 +270,22 @@ impl ArroyWrapper {
         item_id: arroy::ItemId,
         embeddings: &Embeddings<f32>,
     ) -> Result<(), arroy::Error> {
+        if let Some(store) = &self.sparse {
+            // sparse embeddings are self-delimiting, regardless of the dimension of `embeddings`
+            let embeddings = sparse::split(embeddings.as_inner());
+            return Ok(store.set_item_vectors(wtxn, item_id, embeddings)?);
+        }
+        if self.quantization == Quantization::Int8 {
+            self.int8.set_item_vectors(wtxn, item_id, embeddings.iter())?;
+        }
         let dimension = embeddings.dimension();
         for (index, vector) in
             arroy_db_range_for_embedder(self.embedder_index).zip(embeddings.iter())
         {
-            if self.quantized {
-                arroy::Writer::new(self.quantized_db(), index, dimension)
+            with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+                arroy::Writer::new(self.typed_db::<D>(), index, dimension)
                     .add_item(wtxn, item_id, vector)?
-            } else {
-                arroy::Writer::new(self.angular_db(), index, dimension)
-                    .add_item(wtxn, item_id, vector)?
-            }
+            })
         }
         Ok(())
     }
@@ -163,11 +297,15 @@ impl ArroyWrapper {
         item_id: arroy::ItemId,
         vector: &[f32],
     ) -> Result<(), arroy::Error> {
-        if self.quantized {
-            self._add_item(wtxn, self.quantized_db(), item_id, vector)
-        } else {
-            self._add_item(wtxn, self.angular_db(), item_id, vector)
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.add_item(wtxn, item_id, vector)?);
         }
+        if self.quantization == Quantization::Int8 {
+            self.int8.add_item(wtxn, item_id, vector)?;
+        }
+        with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            self._add_item(wtxn, self.typed_db::<D>(), item_id, vector)
+        })
     }
 
     fn _add_item<D: arroy::Distance>(
@@ -196,17 +334,19 @@ impl ArroyWrapper {
         dimension: usize,
         item_id: arroy::ItemId,
     ) -> Result<(), arroy::Error> {
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.del_items(wtxn, item_id)?);
+        }
+        if self.quantization == Quantization::Int8 {
+            self.int8.del_items(wtxn, item_id)?;
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
-                if !writer.del_item(wtxn, item_id)? {
-                    break;
-                }
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
-                if !writer.del_item(wtxn, item_id)? {
-                    break;
-                }
+            let deleted = with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+                arroy::Writer::new(self.typed_db::<D>(), index, dimension)
+                    .del_item(wtxn, item_id)?
+            });
+            if !deleted {
+                break;
             }
         }
 
@@ -220,11 +360,52 @@ impl ArroyWrapper {
         item_id: arroy::ItemId,
         vector: &[f32],
     ) -> Result<bool, arroy::Error> {
-        if self.quantized {
-            self._del_item(wtxn, self.quantized_db(), item_id, vector)
-        } else {
-            self._del_item(wtxn, self.angular_db(), item_id, vector)
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.del_item(wtxn, item_id, vector)?);
+        }
+        if self.quantization == Quantization::Int8 {
+            return with_arroy_distance!(self.distance, true, D => {
+                self._del_int8_item(wtxn, self.typed_db::<D>(), item_id, vector)
+            });
+        }
+        with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            self._del_item(wtxn, self.typed_db::<D>(), item_id, vector)
+        })
+    }
+
+    /// Deletes one item of an int8 quantized embedder.
+    ///
+    /// The binary quantized vectors of the arroy stores cannot be compared with `vector`,
+    /// so the deleted embedding is found among the 8-bit embeddings.
+    fn _del_int8_item<D: arroy::Distance>(
+        &self,
+        wtxn: &mut RwTxn,
+        db: arroy::Database<D>,
+        item_id: arroy::ItemId,
+        vector: &[f32],
+    ) -> Result<bool, arroy::Error> {
+        let dimension = vector.len();
+        let Some(position) = self.int8.del_item(wtxn, item_id, vector)? else {
+            return Ok(false);
+        };
+        let mut stores = arroy_db_range_for_embedder(self.embedder_index);
+        let deleted_index = stores.nth(position as usize).unwrap();
+
+        // enforce invariant: vectors are packed in the first writers, the last vector takes the deleted place.
+        let mut last_index_with_a_vector = None;
+        for index in stores {
+            let writer = arroy::Writer::new(db, index, dimension);
+            let Some(candidate) = writer.item_vector(wtxn, item_id)? else {
+                break;
+            };
+            last_index_with_a_vector = Some((index, candidate));
         }
+        arroy::Writer::new(db, deleted_index, dimension).del_item(wtxn, item_id)?;
+        if let Some((last_index, vector)) = last_index_with_a_vector {
+            arroy::Writer::new(db, last_index, dimension).del_item(wtxn, item_id)?;
+            arroy::Writer::new(db, deleted_index, dimension).add_item(wtxn, item_id, &vector)?;
+        }
+        Ok(true)
     }
 
     fn _del_item<D: arroy::Distance>(
@@ -272,20 +453,20 @@ impl ArroyWrapper {
     }
 
     pub fn clear(&self, wtxn: &mut RwTxn, dimension: usize) -> Result<(), arroy::Error> {
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.clear(wtxn)?);
+        }
+        if self.quantization == Quantization::Int8 {
+            self.int8.clear(wtxn)?;
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
+            with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+                let writer = arroy::Writer::new(self.typed_db::<D>(), index, dimension);
                 if writer.is_empty(wtxn)? {
                     break;
                 }
                 writer.clear(wtxn)?;
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
-                if writer.is_empty(wtxn)? {
-                    break;
-                }
-                writer.clear(wtxn)?;
-            }
+            })
         }
         Ok(())
     }
@@ -296,20 +477,17 @@ impl ArroyWrapper {
         dimension: usize,
         item: arroy::ItemId,
     ) -> Result<bool, arroy::Error> {
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.contains_item(rtxn, item)?);
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            let contains = if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
-                if writer.is_empty(rtxn)? {
-                    break;
-                }
-                writer.contains_item(rtxn, item)?
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
+            let contains = with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+                let writer = arroy::Writer::new(self.typed_db::<D>(), index, dimension);
                 if writer.is_empty(rtxn)? {
                     break;
                 }
                 writer.contains_item(rtxn, item)?
-            };
+            });
             if contains {
                 return Ok(contains);
             }
@@ -324,11 +502,24 @@ impl ArroyWrapper {
         limit: usize,
         filter: Option<&RoaringBitmap>,
     ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
-        if self.quantized {
-            self._nns_by_item(rtxn, self.quantized_db(), item, limit, filter)
-        } else {
-            self._nns_by_item(rtxn, self.angular_db(), item, limit, filter)
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.nns_by_item(rtxn, item, limit, filter)?);
+        }
+        let exhaustive = with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            self.is_very_selective(rtxn, self.typed_db::<D>(), limit, filter)?
+        });
+        if exhaustive || self.quantization == Quantization::Int8 {
+            let mut results = Vec::new();
+            for vector in self.item_vectors(rtxn, item)? {
+                let neighbors = self.nns_by_vector(rtxn, &vector, limit, filter)?;
+                results.extend(neighbors);
+            }
+            results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+            return Ok(results);
         }
+        with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            self._nns_by_item(rtxn, self.typed_db::<D>(), item, limit, filter)
+        })
     }
 
     fn _nns_by_item<D: arroy::Distance>(
@@ -365,11 +556,54 @@ impl ArroyWrapper {
         limit: usize,
         filter: Option<&RoaringBitmap>,
     ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
-        if self.quantized {
-            self._nns_by_vector(rtxn, self.quantized_db(), vector, limit, filter)
-        } else {
-            self._nns_by_vector(rtxn, self.angular_db(), vector, limit, filter)
+        let results = self.nns_by_vector_with_embedding_index(rtxn, vector, limit, filter)?;
+        Ok(results.into_iter().map(|(item, _, distance)| (item, distance)).collect())
+    }
+
+    /// Like [`Self::nns_by_vector`], also returning the index of the embedding of the item that matched.
+    ///
+    /// For embedders that chunk documents, this is the index of the chunk the embedding was generated from.
+    /// An item with several embeddings can appear once per matching embedding.
+    pub fn nns_by_vector_with_embedding_index(
+        &self,
+        rtxn: &RoTxn,
+        vector: &[f32],
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, u8, f32)>, arroy::Error> {
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.nns_by_vector(rtxn, vector, limit, filter)?);
+        }
+        with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            self._nns_by_vector(rtxn, self.typed_db::<D>(), vector, limit, filter)
+        })
+    }
+
+    /// Whether the filter is selective enough for its candidates to be compared to the query one by one.
+    ///
+    /// Arroy only visits the filtered items while traversing its trees, but the traversal still
+    /// explores many nodes that lead to discarded items when few items are kept.
+    ///
+    /// Binary quantized embeddings are always searched in the arroy trees, as their distance
+    /// to the query can't be computed with [`VectorDistance::between`].
+    fn is_very_selective<D: arroy::Distance>(
+        &self,
+        rtxn: &RoTxn,
+        db: arroy::Database<D>,
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<bool, arroy::Error> {
+        if self.quantization.is_binary() {
+            return Ok(false);
         }
+        let Some(filter) = filter else { return Ok(false) };
+        // the first store contains all the embedded items
+        let Some(reader) = self.readers(rtxn, db).next().transpose()? else { return Ok(false) };
+        let items = reader.item_ids();
+        let candidates = filter.intersection_len(items);
+        Ok(candidates <= limit as u64
+            || (candidates <= BRUTE_FORCE_MAX_CANDIDATES
+                && candidates.saturating_mul(BRUTE_FORCE_SELECTIVITY) <= items.len()))
     }
 
     fn _nns_by_vector<D: arroy::Distance>(
@@ -379,52 +613,120 @@ impl ArroyWrapper {
         vector: &[f32],
         limit: usize,
         filter: Option<&RoaringBitmap>,
-    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+    ) -> Result<Vec<(ItemId, u8, f32)>, arroy::Error> {
         let mut results = Vec::new();
 
-        for reader in self.readers(rtxn, db) {
+        let rescoring = self.quantization == Quantization::Int8;
+        let oversampling = if rescoring { int8::OVERSAMPLING } else { 1 };
+        let exhaustive = self.is_very_selective(rtxn, db, limit, filter)?;
+
+        // there are at most 256 stores per embedder, so the index always fits in a u8
+        for (index, reader) in self.readers(rtxn, db).enumerate() {
             let reader = reader?;
-            let mut searcher = reader.nns(limit);
+            if let (true, Some(filter)) = (exhaustive, filter) {
+                let mut neighbors = Vec::new();
+                for item in filter & reader.item_ids() {
+                    let embedding = if rescoring {
+                        self.int8.item_vector(rtxn, item, index as u8)?
+                    } else {
+                        reader.item_vector(rtxn, item)?
+                    };
+                    if let Some(embedding) = embedding {
+                        neighbors.push((
+                            item,
+                            index as u8,
+                            self.distance.between(vector, &embedding),
+                        ));
+                    }
+                }
+                neighbors.sort_unstable_by_key(|(_, _, distance)| OrderedFloat(*distance));
+                neighbors.truncate(limit);
+                results.append(&mut neighbors);
+                continue;
+            }
+
+            let mut searcher = reader.nns(limit * oversampling);
             if let Some(filter) = filter {
                 searcher.candidates(filter);
             }
 
-            results.append(&mut searcher.by_vector(rtxn, vector)?);
+            let neighbors = searcher.by_vector(rtxn, vector)?;
+            if rescoring {
+                let mut rescored = Vec::with_capacity(neighbors.len());
+                for (item, distance) in neighbors {
+                    let distance = match self.int8.item_vector(rtxn, item, index as u8)? {
+                        Some(embedding) => self.distance.between(vector, &embedding),
+                        None => distance,
+                    };
+                    rescored.push((item, index as u8, distance));
+                }
+                rescored.sort_unstable_by_key(|(_, _, distance)| OrderedFloat(*distance));
+                rescored.truncate(limit);
+                results.append(&mut rescored);
+            } else {
+                results.extend(
+                    neighbors.into_iter().map(|(item, distance)| (item, index as u8, distance)),
+                );
+            }
+        }
+
+        results.sort_unstable_by_key(|(_, _, distance)| OrderedFloat(*distance));
+
+        Ok(results)
+    }
+
+    /// Retrieves the items closest to a bag of query embeddings, scored with MaxSim.
+    ///
+    /// The candidates are the nearest neighbors of each query embedding, and are then scored
+    /// against all their embeddings. Unlike [`Self::nns_by_vector`], the returned values are
+    /// similarities sorted by decreasing order.
+    pub fn nns_by_vectors_max_sim(
+        &self,
+        rtxn: &RoTxn,
+        query: &[impl AsRef<[f32]>],
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+        let mut candidates = RoaringBitmap::new();
+        for vector in query {
+            let neighbors = self.nns_by_vector(rtxn, vector.as_ref(), limit, filter)?;
+            candidates.extend(neighbors.into_iter().map(|(item, _)| item));
         }
 
-        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        let mut results = Vec::with_capacity(candidates.len() as usize);
+        for item in candidates {
+            let document = self.item_vectors(rtxn, item)?;
+            results.push((item, self.distance.max_sim(query, &document)));
+        }
+
+        results
+            .sort_unstable_by_key(|(_, similarity)| std::cmp::Reverse(OrderedFloat(*similarity)));
 
         Ok(results)
     }
 
     pub fn item_vectors(&self, rtxn: &RoTxn, item_id: u32) -> Result<Vec<Vec<f32>>, arroy::Error> {
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.item_vectors(rtxn, item_id)?);
+        }
+        if self.quantization == Quantization::Int8 {
+            return Ok(self.int8.item_vectors(rtxn, item_id)?);
+        }
         let mut vectors = Vec::new();
 
-        if self.quantized {
-            for reader in self.readers(rtxn, self.quantized_db()) {
+        with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            for reader in self.readers(rtxn, self.typed_db::<D>()) {
                 if let Some(vec) = reader?.item_vector(rtxn, item_id)? {
                     vectors.push(vec);
                 } else {
                     break;
                 }
             }
-        } else {
-            for reader in self.readers(rtxn, self.angular_db()) {
-                if let Some(vec) = reader?.item_vector(rtxn, item_id)? {
-                    vectors.push(vec);
-                } else {
-                    break;
-                }
-            }
-        }
+        });
         Ok(vectors)
     }
 
-    fn angular_db(&self) -> arroy::Database<Cosine> {
-        self.database.remap_data_type()
-    }
-
-    fn quantized_db(&self) -> arroy::Database<BinaryQuantizedCosine> {
+    fn typed_db<D: arroy::Distance>(&self) -> arroy::Database<D> {
         self.database.remap_data_type()
     }
 
@@ -433,18 +735,11 @@ impl ArroyWrapper {
         rtxn: &RoTxn,
         stats: &mut ArroyStats,
     ) -> Result<(), arroy::Error> {
-        if self.quantized {
-            for reader in self.readers(rtxn, self.quantized_db()) {
-                let reader = reader?;
-                let documents = reader.item_ids();
-                if documents.is_empty() {
-                    break;
-                }
-                stats.documents |= documents;
-                stats.number_of_embeddings += documents.len();
-            }
-        } else {
-            for reader in self.readers(rtxn, self.angular_db()) {
+        if let Some(sparse) = &self.sparse {
+            return Ok(sparse.aggregate_stats(rtxn, stats)?);
+        }
+        with_arroy_distance!(self.distance, self.quantization.is_binary(), D => {
+            for reader in self.readers(rtxn, self.typed_db::<D>()) {
                 let reader = reader?;
                 let documents = reader.item_ids();
                 if documents.is_empty() {
@@ -453,7 +748,7 @@ impl ArroyWrapper {
                 stats.documents |= documents;
                 stats.number_of_embeddings += documents.len();
             }
-        }
+        });
 
         Ok(())
     }
@@ -555,6 +850,8 @@ pub enum Embedder {
     Rest(rest::Embedder),
     /// An embedder composed of an embedder at search time and an embedder at indexing time.
     Composite(composite::Embedder),
+    /// An embedder based on running local ONNX models on the CPU.
+    Onnx(onnx::Embedder),
 }
 
 #[derive(Debug)]
@@ -606,12 +903,180 @@ pub struct EmbeddingConfig {
     pub prompt: PromptData,
     /// If this embedder is binary quantized
     pub quantized: Option<bool>,
-    // TODO: add metrics and anything needed
+    /// The quantization of the embeddings of this embedder, superseding `quantized` when set
+    #[serde(default)]
+    pub quantization: Option<Quantization>,
+    /// The distance used to compare the embeddings of this embedder
+    #[serde(default)]
+    pub distance: Option<VectorDistance>,
 }
 
 impl EmbeddingConfig {
+    /// Whether the arroy trees of this embedder are built on binary quantized embeddings.
     pub fn quantized(&self) -> bool {
-        self.quantized.unwrap_or_default()
+        self.quantization().is_binary()
+    }
+
+    pub fn quantization(&self) -> Quantization {
+        match (self.quantization, self.quantized) {
+            (Some(quantization), _) => quantization,
+            (None, Some(true)) => Quantization::Binary,
+            (None, _) => Quantization::None,
+        }
+    }
+
+    pub fn distance(&self) -> VectorDistance {
+        // sparse embeddings are always compared with the dot product
+        if self.sparse() {
+            return VectorDistance::DotProduct;
+        }
+        self.distance.unwrap_or_default()
+    }
+
+    pub fn sparse(&self) -> bool {
+        match &self.embedder_options {
+            EmbedderOptions::UserProvided(options) => options.sparse.unwrap_or_default(),
+            EmbedderOptions::Rest(options) => options.sparse.unwrap_or_default(),
+            _ => false,
+        }
+    }
+}
+
+/// The metric used to compare embeddings.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum VectorDistance {
+    /// Angle between the embeddings, regardless of their magnitude.
+    #[default]
+    Cosine,
+    /// Dot product of the embeddings, for models trained to use the magnitude of their embeddings.
+    DotProduct,
+    /// Euclidean (L2) distance between the embeddings.
+    Euclidean,
+    /// Manhattan (L1) distance between the embeddings.
+    Manhattan,
+}
+
+impl VectorDistance {
+    /// Converts a distance returned by arroy to a similarity score in the `[0, 1]` range,
+    /// `1` meaning that the embeddings are identical.
+    pub fn similarity(&self, distance: f32) -> f32 {
+        match self {
+            VectorDistance::Cosine => 1.0 - distance,
+            // arroy returns the opposite of the dot product, which is unbounded.
+            // We use a sigmoid so that the scores stay comparable to the other rules.
+            VectorDistance::DotProduct => 1.0 / (1.0 + distance.exp()),
+            VectorDistance::Euclidean | VectorDistance::Manhattan => 1.0 / (1.0 + distance),
+        }
+    }
+
+    /// Computes the distance between two embeddings the same way arroy does,
+    /// so that it can be passed to [`Self::similarity`].
+    pub fn between(&self, left: &[f32], right: &[f32]) -> f32 {
+        let dot = || left.iter().zip(right).map(|(l, r)| l * r).sum::<f32>();
+        let norm = |vector: &[f32]| vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        match self {
+            VectorDistance::Cosine => {
+                let norms = norm(left) * norm(right);
+                if norms > f32::EPSILON {
+                    let cos = (dot() / norms).clamp(-1.0, 1.0);
+                    (1.0 - cos) / 2.0
+                } else {
+                    0.0
+                }
+            }
+            VectorDistance::DotProduct => -dot(),
+            VectorDistance::Euclidean => {
+                left.iter().zip(right).map(|(l, r)| (l - r) * (l - r)).sum::<f32>().sqrt()
+            }
+            VectorDistance::Manhattan => left.iter().zip(right).map(|(l, r)| (l - r).abs()).sum(),
+        }
+    }
+
+    /// Late-interaction score of a bag of document embeddings against a bag of query embeddings.
+    ///
+    /// Each query embedding is matched with its most similar document embedding, and the similarities
+    /// are averaged over the query embeddings so that the score stays in the `[0, 1]` range.
+    pub fn max_sim(&self, query: &[impl AsRef<[f32]>], document: &[Vec<f32>]) -> f32 {
+        if query.is_empty() {
+            return 0.0;
+        }
+        let total: f32 = query
+            .iter()
+            .map(|query| {
+                document
+                    .iter()
+                    .map(|document| self.similarity(self.between(query.as_ref(), document)))
+                    .fold(0.0, f32::max)
+            })
+            .sum();
+        total / query.len() as f32
+    }
+
+    /// Whether the embeddings can be binary quantized with this distance.
+    pub fn supports_binary_quantization(&self) -> bool {
+        !matches!(self, VectorDistance::DotProduct)
+    }
+}
+
+impl std::fmt::Display for VectorDistance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            VectorDistance::Cosine => "cosine",
+            VectorDistance::DotProduct => "dotProduct",
+            VectorDistance::Euclidean => "euclidean",
+            VectorDistance::Manhattan => "manhattan",
+        };
+        f.write_str(s)
+    }
+}
+
+/// How the embeddings are compressed in the vector store.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash, Deserr, ToSchema,
+)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum Quantization {
+    /// The embeddings are stored as 32-bit floats.
+    #[default]
+    None,
+    /// Each dimension is stored as a single bit, dividing the size of the embeddings by 32.
+    Binary,
+    /// Each dimension is stored as an 8-bit integer, dividing the size of the embeddings by 4.
+    ///
+    /// The neighbors are searched among the binary quantized embeddings, then rescored with the 8-bit embeddings.
+    Int8,
+}
+
+impl Quantization {
+    /// Whether the arroy trees are built on binary quantized embeddings.
+    pub fn is_binary(&self) -> bool {
+        !matches!(self, Quantization::None)
+    }
+
+    /// Whether the embeddings can be converted from `self` to `other`.
+    ///
+    /// Quantizing is lossy, so that the embeddings can only be converted to a smaller representation.
+    pub fn can_convert_to(&self, other: Quantization) -> bool {
+        matches!(
+            (self, other),
+            (Quantization::None, _)
+                | (Quantization::Binary, Quantization::Binary)
+                | (Quantization::Int8, Quantization::Binary | Quantization::Int8)
+        )
+    }
+}
+
+impl std::fmt::Display for Quantization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Quantization::None => "none",
+            Quantization::Binary => "binary",
+            Quantization::Int8 => "int8",
+        };
+        f.write_str(s)
     }
 }
 
@@ -619,11 +1084,15 @@ impl EmbeddingConfig {
 ///
 /// Each configuration is mapped to a name.
 #[derive(Clone, Default)]
-pub struct EmbeddingConfigs(HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)>);
+pub struct EmbeddingConfigs(
+    HashMap<String, (Arc<Embedder>, Arc<Prompt>, Quantization, VectorDistance)>,
+);
 
 impl EmbeddingConfigs {
     /// Create the map from its internal component.s
-    pub fn new(data: HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)>) -> Self {
+    pub fn new(
+        data: HashMap<String, (Arc<Embedder>, Arc<Prompt>, Quantization, VectorDistance)>,
+    ) -> Self {
         Self(data)
     }
 
@@ -632,24 +1101,33 @@ impl EmbeddingConfigs {
     }
 
     /// Get an embedder configuration and template from its name.
-    pub fn get(&self, name: &str) -> Option<(Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn get(
+        &self,
+        name: &str,
+    ) -> Option<(Arc<Embedder>, Arc<Prompt>, Quantization, VectorDistance)> {
         self.0.get(name).cloned()
     }
 
-    pub fn inner_as_ref(&self) -> &HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn inner_as_ref(
+        &self,
+    ) -> &HashMap<String, (Arc<Embedder>, Arc<Prompt>, Quantization, VectorDistance)> {
         &self.0
     }
 
-    pub fn into_inner(self) -> HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn into_inner(
+        self,
+    ) -> HashMap<String, (Arc<Embedder>, Arc<Prompt>, Quantization, VectorDistance)> {
         self.0
     }
 }
 
 impl IntoIterator for EmbeddingConfigs {
-    type Item = (String, (Arc<Embedder>, Arc<Prompt>, bool));
+    type Item = (String, (Arc<Embedder>, Arc<Prompt>, Quantization, VectorDistance));
 
-    type IntoIter =
-        std::collections::hash_map::IntoIter<String, (Arc<Embedder>, Arc<Prompt>, bool)>;
+    type IntoIter = std::collections::hash_map::IntoIter<
+        String,
+        (Arc<Embedder>, Arc<Prompt>, Quantization, VectorDistance),
+    >;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
@@ -665,6 +1143,7 @@ pub enum EmbedderOptions {
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
     Composite(composite::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
 }
 
 impl Default for EmbedderOptions {
@@ -700,6 +1179,7 @@ impl Embedder {
             EmbedderOptions::Composite(options) => {
                 Self::Composite(composite::Embedder::new(options, cache_cap)?)
             }
+            EmbedderOptions::Onnx(options) => Self::Onnx(onnx::Embedder::new(options, cache_cap)?),
         })
     }
 
@@ -731,6 +1211,7 @@ impl Embedder {
                 .pop()
                 .ok_or_else(EmbedError::missing_embedding),
             Embedder::Composite(embedder) => embedder.search.embed_one(text, deadline),
+            Embedder::Onnx(embedder) => embedder.embed_one(text),
         }?;
 
         if let Some(cache) = self.cache() {
@@ -755,6 +1236,7 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.embed_index(text_chunks),
             Embedder::Rest(embedder) => embedder.embed_index(text_chunks, threads),
             Embedder::Composite(embedder) => embedder.index.embed_index(text_chunks, threads),
+            Embedder::Onnx(embedder) => embedder.embed_index(text_chunks, threads),
         }
     }
 
@@ -771,6 +1253,7 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.embed_index_ref(texts),
             Embedder::Rest(embedder) => embedder.embed_index_ref(texts, threads),
             Embedder::Composite(embedder) => embedder.index.embed_index_ref(texts, threads),
+            Embedder::Onnx(embedder) => embedder.embed_index_ref(texts, threads),
         }
     }
 
@@ -783,6 +1266,7 @@ impl Embedder {
             Embedder::UserProvided(_) => 100,
             Embedder::Rest(embedder) => embedder.chunk_count_hint(),
             Embedder::Composite(embedder) => embedder.index.chunk_count_hint(),
+            Embedder::Onnx(embedder) => embedder.chunk_count_hint(),
         }
     }
 
@@ -795,6 +1279,7 @@ impl Embedder {
             Embedder::UserProvided(_) => 1,
             Embedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::Composite(embedder) => embedder.index.prompt_count_in_chunk_hint(),
+            Embedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
         }
     }
 
@@ -807,6 +1292,7 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.dimensions(),
             Embedder::Rest(embedder) => embedder.dimensions(),
             Embedder::Composite(embedder) => embedder.dimensions(),
+            Embedder::Onnx(embedder) => embedder.dimensions(),
         }
     }
 
@@ -819,6 +1305,33 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.distribution(),
             Embedder::Rest(embedder) => embedder.distribution(),
             Embedder::Composite(embedder) => embedder.distribution(),
+            Embedder::Onnx(embedder) => embedder.distribution(),
+        }
+    }
+
+    /// Whether the documents and queries are bags of token-level embeddings compared with MaxSim.
+    pub fn multi_vector(&self) -> bool {
+        match self {
+            Embedder::UserProvided(embedder) => embedder.multi_vector(),
+            Embedder::HuggingFace(_)
+            | Embedder::OpenAi(_)
+            | Embedder::Ollama(_)
+            | Embedder::Rest(_)
+            | Embedder::Composite(_)
+            | Embedder::Onnx(_) => false,
+        }
+    }
+
+    /// Whether the embeddings are sparse, and stored in an inverted index instead of arroy.
+    pub fn sparse(&self) -> bool {
+        match self {
+            Embedder::UserProvided(embedder) => embedder.sparse(),
+            Embedder::Rest(embedder) => embedder.sparse(),
+            Embedder::HuggingFace(_)
+            | Embedder::OpenAi(_)
+            | Embedder::Ollama(_)
+            | Embedder::Composite(_)
+            | Embedder::Onnx(_) => false,
         }
     }
 
@@ -827,7 +1340,8 @@ impl Embedder {
             Embedder::HuggingFace(_)
             | Embedder::OpenAi(_)
             | Embedder::Ollama(_)
-            | Embedder::Rest(_) => true,
+            | Embedder::Rest(_)
+            | Embedder::Onnx(_) => true,
             Embedder::UserProvided(_) => false,
             Embedder::Composite(embedder) => embedder.index.uses_document_template(),
         }
@@ -841,6 +1355,7 @@ impl Embedder {
             Embedder::Ollama(embedder) => Some(embedder.cache()),
             Embedder::Rest(embedder) => Some(embedder.cache()),
             Embedder::Composite(embedder) => embedder.search.cache(),
+            Embedder::Onnx(embedder) => Some(embedder.cache()),
         }
     }
 }
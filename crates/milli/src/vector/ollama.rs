@@ -70,6 +70,7 @@ impl EmbedderOptions {
             request,
             response,
             headers: Default::default(),
+            sparse: None,
         })
     }
 }
@@ -0,0 +1,62 @@
+use std::convert::Infallible;
+
+use super::EmbedderOptions;
+use crate::vector::error::{EmbedError, NewEmbedderError};
+use crate::vector::{DistributionShift, Embedding, EmbeddingCache};
+use crate::ThreadPoolNoAbort;
+
+/// Stands for the ONNX embedder when Meilisearch is compiled without the `onnx` feature,
+/// it can never be created.
+#[derive(Debug)]
+pub struct Embedder {
+    never: Infallible,
+}
+
+impl Embedder {
+    pub fn new(
+        _options: EmbedderOptions,
+        _cache_cap: usize,
+    ) -> std::result::Result<Self, NewEmbedderError> {
+        Err(NewEmbedderError::onnx_not_enabled())
+    }
+
+    pub fn embed_one(&self, _text: &str) -> std::result::Result<Embedding, EmbedError> {
+        match self.never {}
+    }
+
+    pub fn embed_index(
+        &self,
+        _text_chunks: Vec<Vec<String>>,
+        _threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        match self.never {}
+    }
+
+    pub(crate) fn embed_index_ref(
+        &self,
+        _texts: &[&str],
+        _threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        match self.never {}
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        match self.never {}
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        match self.never {}
+    }
+
+    pub fn dimensions(&self) -> usize {
+        match self.never {}
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        match self.never {}
+    }
+
+    pub(in crate::vector) fn cache(&self) -> &EmbeddingCache {
+        match self.never {}
+    }
+}
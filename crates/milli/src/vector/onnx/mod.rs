@@ -0,0 +1,29 @@
+//! Embedder for local ONNX models.
+//!
+//! The ONNX Runtime is only available when Meilisearch is compiled with the `onnx` feature,
+//! otherwise the embedders of source `onnx` fail to be created.
+
+use super::hf::OverridePooling;
+use super::DistributionShift;
+
+#[cfg(not(feature = "onnx"))]
+mod disabled;
+#[cfg(feature = "onnx")]
+mod runtime;
+
+#[cfg(not(feature = "onnx"))]
+pub use self::disabled::Embedder;
+#[cfg(feature = "onnx")]
+pub use self::runtime::Embedder;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    /// Path to the `.onnx` file of the model, or to a directory containing a `model.onnx` file.
+    ///
+    /// The `tokenizer.json` file and the optional `1_Pooling/config.json` file are read from the directory of the model.
+    pub model_path: String,
+    /// Number of threads used by the runtime to perform a single inference.
+    pub threads: Option<usize>,
+    pub distribution: Option<DistributionShift>,
+    pub pooling: OverridePooling,
+}
@@ -0,0 +1,331 @@
+use std::path::{Path, PathBuf};
+
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::{Session, SessionInputValue};
+use ort::value::Tensor;
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+use rayon::slice::ParallelSlice as _;
+use tokenizers::{Encoding, PaddingParams, Tokenizer, TruncationParams};
+
+use super::EmbedderOptions;
+use crate::error::FaultSource;
+use crate::vector::error::{EmbedError, EmbedErrorKind, NewEmbedderError};
+use crate::vector::hf::{Pooling, PoolingConfig};
+use crate::vector::{DistributionShift, Embedding, EmbeddingCache, REQUEST_PARALLELISM};
+use crate::ThreadPoolNoAbort;
+
+/// Longer texts are truncated to this number of tokens before being passed to the model.
+const MAX_TOKENS: usize = 512;
+
+/// Perform embedding of documents and queries with a local ONNX model, on the CPU.
+pub struct Embedder {
+    session: Session,
+    tokenizer: Tokenizer,
+    options: EmbedderOptions,
+    /// Whether the model takes the `token_type_ids` input, like BERT models do.
+    token_type_ids: bool,
+    /// Name of the output holding the token embeddings, or the pooled embeddings.
+    output: String,
+    dimensions: usize,
+    pooling: Pooling,
+    cache: EmbeddingCache,
+}
+
+impl std::fmt::Debug for Embedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Embedder")
+            .field("model_path", &self.options.model_path)
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .field("output", &self.output)
+            .field("pooling", &self.pooling)
+            .finish()
+    }
+}
+
+impl Embedder {
+    pub fn new(
+        options: EmbedderOptions,
+        cache_cap: usize,
+    ) -> std::result::Result<Self, NewEmbedderError> {
+        let path = Path::new(&options.model_path);
+        let (model_filename, directory): (PathBuf, PathBuf) =
+            if path.extension().is_some_and(|extension| extension == "onnx") {
+                (path.to_owned(), path.parent().unwrap_or(Path::new("")).to_owned())
+            } else {
+                (path.join("model.onnx"), path.to_owned())
+            };
+
+        let session = Session::builder()
+            .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|builder| builder.with_intra_threads(options.threads.unwrap_or(1)))
+            .and_then(|builder| builder.commit_from_file(&model_filename))
+            .map_err(|inner| NewEmbedderError::onnx_load_model(model_filename.clone(), inner))?;
+
+        let token_type_ids = session.inputs.iter().any(|input| input.name == "token_type_ids");
+        // prefer the token embeddings so that the pooling method applies
+        let output = session
+            .outputs
+            .iter()
+            .map(|output| output.name.as_str())
+            .find(|name| matches!(*name, "last_hidden_state" | "token_embeddings"))
+            .or_else(|| session.outputs.first().map(|output| output.name.as_str()))
+            .unwrap_or("last_hidden_state")
+            .to_owned();
+
+        let tokenizer_filename = directory.join("tokenizer.json");
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
+
+        if let Some(pp) = tokenizer.get_padding_mut() {
+            pp.strategy = tokenizers::PaddingStrategy::BatchLongest
+        } else {
+            let pp = PaddingParams {
+                strategy: tokenizers::PaddingStrategy::BatchLongest,
+                ..Default::default()
+            };
+            tokenizer.with_padding(Some(pp));
+        }
+        if tokenizer.get_truncation().is_none() {
+            let truncation = TruncationParams { max_length: MAX_TOKENS, ..Default::default() };
+            tokenizer
+                .with_truncation(Some(truncation))
+                .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+        }
+
+        let pooling_filename = directory.join("1_Pooling").join("config.json");
+        let mut pooling: Pooling = match std::fs::read_to_string(&pooling_filename) {
+            Ok(pooling) => {
+                let pooling: PoolingConfig = serde_json::from_str(&pooling).map_err(|inner| {
+                    NewEmbedderError::deserialize_pooling_config(
+                        options.model_path.clone(),
+                        pooling_filename,
+                        inner,
+                    )
+                })?;
+                pooling.into()
+            }
+            // ignore the error if the file simply doesn't exist
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Pooling::default(),
+            Err(inner) => {
+                return Err(NewEmbedderError::open_pooling_config(pooling_filename, inner))
+            }
+        };
+        pooling.override_with(options.pooling);
+
+        tracing::debug!(model = options.model_path, output, pooling=?pooling, "model config");
+
+        let mut this = Self {
+            session,
+            tokenizer,
+            options,
+            token_type_ids,
+            output,
+            dimensions: 0,
+            pooling,
+            cache: EmbeddingCache::new(cache_cap),
+        };
+
+        let embeddings =
+            this.embed(&["test"]).map_err(NewEmbedderError::could_not_determine_dimension)?;
+        this.dimensions = embeddings.first().unwrap().len();
+
+        Ok(this)
+    }
+
+    /// Embeds the texts as a single batch.
+    pub fn embed<S: AsRef<str>>(&self, texts: &[S]) -> Result<Vec<Embedding>, EmbedError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let texts: Vec<&str> = texts.iter().map(AsRef::as_ref).collect();
+        let encodings = self.tokenizer.encode_batch(texts, true).map_err(EmbedError::tokenize)?;
+
+        let mut inputs: Vec<(&str, SessionInputValue<'_>)> = vec![
+            ("input_ids", input_tensor(&encodings, Encoding::get_ids)?.into()),
+            ("attention_mask", input_tensor(&encodings, Encoding::get_attention_mask)?.into()),
+        ];
+        if self.token_type_ids {
+            inputs
+                .push(("token_type_ids", input_tensor(&encodings, Encoding::get_type_ids)?.into()));
+        }
+
+        let outputs = self.session.run(inputs).map_err(EmbedError::onnx_run)?;
+        let (shape, data) = outputs[self.output.as_str()]
+            .try_extract_raw_tensor::<f32>()
+            .map_err(EmbedError::onnx_run)?;
+
+        match *shape.as_slice() {
+            // the model already pooled the embeddings
+            [_, dimensions] if dimensions > 0 => {
+                Ok(data.chunks_exact(dimensions as usize).map(<[f32]>::to_vec).collect())
+            }
+            [_, tokens, dimensions] if tokens > 0 && dimensions > 0 => {
+                let dimensions = dimensions as usize;
+                Ok(data
+                    .chunks_exact(tokens as usize * dimensions)
+                    .zip(&encodings)
+                    .map(|(token_embeddings, encoding)| {
+                        pool(
+                            self.pooling,
+                            token_embeddings,
+                            dimensions,
+                            encoding.get_attention_mask(),
+                        )
+                    })
+                    .collect())
+            }
+            _ => Err(EmbedError::onnx_output_shape(shape)),
+        }
+    }
+
+    pub fn embed_one(&self, text: &str) -> std::result::Result<Embedding, EmbedError> {
+        self.embed(&[text])?.pop().ok_or_else(EmbedError::missing_embedding)
+    }
+
+    pub fn embed_index(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            text_chunks.into_iter().map(move |chunk| self.embed(&chunk)).collect()
+        } else {
+            threads
+                .install(move || {
+                    text_chunks.into_par_iter().map(move |chunk| self.embed(&chunk)).collect()
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub(crate) fn embed_index_ref(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                .chunks(self.prompt_count_in_chunk_hint())
+                .map(move |chunk| self.embed(chunk))
+                .collect();
+
+            let embeddings = embeddings?;
+            Ok(embeddings.into_iter().flatten().collect())
+        } else {
+            threads
+                .install(move || {
+                    let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                        .par_chunks(self.prompt_count_in_chunk_hint())
+                        .map(move |chunk| self.embed(chunk))
+                        .collect();
+
+                    let embeddings = embeddings?;
+                    Ok(embeddings.into_iter().flatten().collect())
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    /// The batches of a chunk are embedded in parallel on the indexing threads.
+    pub fn chunk_count_hint(&self) -> usize {
+        std::thread::available_parallelism().map(|x| x.get()).unwrap_or(8)
+    }
+
+    /// The texts of a batch are padded to the longest one, keep the batches small.
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        8
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.options.distribution
+    }
+
+    pub(in crate::vector) fn cache(&self) -> &EmbeddingCache {
+        &self.cache
+    }
+}
+
+fn input_tensor(
+    encodings: &[Encoding],
+    ids: impl Fn(&Encoding) -> &[u32],
+) -> Result<Tensor<i64>, EmbedError> {
+    let tokens = encodings.first().map_or(0, Encoding::len);
+    let shape = vec![encodings.len() as i64, tokens as i64];
+    let data: Vec<i64> =
+        encodings.iter().flat_map(|encoding| ids(encoding).iter().map(|&id| id as i64)).collect();
+    Tensor::from_array((shape, data)).map_err(EmbedError::onnx_run)
+}
+
+/// Pools the embeddings of the tokens of a text, ignoring the padding tokens.
+fn pool(
+    pooling: Pooling,
+    token_embeddings: &[f32],
+    dimensions: usize,
+    attention_mask: &[u32],
+) -> Embedding {
+    let mut tokens = token_embeddings
+        .chunks_exact(dimensions)
+        .zip(attention_mask)
+        .filter(|&(_, &mask)| mask != 0)
+        .map(|(token, _)| token)
+        .peekable();
+    if tokens.peek().is_none() {
+        return vec![0.0; dimensions];
+    }
+
+    match pooling {
+        Pooling::Cls => token_embeddings[..dimensions].to_vec(),
+        Pooling::LastToken => tokens.last().unwrap().to_vec(),
+        Pooling::Max => tokens.fold(vec![f32::NEG_INFINITY; dimensions], |mut max, token| {
+            max.iter_mut().zip(token).for_each(|(max, &value)| *max = max.max(value));
+            max
+        }),
+        Pooling::Mean | Pooling::MeanSqrtLen => {
+            let mut count = 0;
+            let mut sum = vec![0.0; dimensions];
+            for token in tokens {
+                sum.iter_mut().zip(token).for_each(|(sum, value)| *sum += value);
+                count += 1;
+            }
+            let divisor = match pooling {
+                Pooling::MeanSqrtLen => (count as f32).sqrt(),
+                _ => count as f32,
+            };
+            sum.iter_mut().for_each(|sum| *sum /= divisor);
+            sum
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{pool, Pooling};
+
+    // two tokens of dimension 2, followed by a padding token
+    const TOKENS: [f32; 6] = [1.0, 4.0, 3.0, 0.0, 100.0, 100.0];
+    const MASK: [u32; 3] = [1, 1, 0];
+
+    #[test]
+    fn pooling_ignores_padding() {
+        assert_eq!(pool(Pooling::Mean, &TOKENS, 2, &MASK), [2.0, 2.0]);
+        assert_eq!(pool(Pooling::Max, &TOKENS, 2, &MASK), [3.0, 4.0]);
+        assert_eq!(pool(Pooling::Cls, &TOKENS, 2, &MASK), [1.0, 4.0]);
+        assert_eq!(pool(Pooling::LastToken, &TOKENS, 2, &MASK), [3.0, 0.0]);
+        assert_eq!(pool(Pooling::MeanSqrtLen, &TOKENS, 2, &MASK), [4.0 / 2f32.sqrt(); 2]);
+    }
+}
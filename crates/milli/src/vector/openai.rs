@@ -200,6 +200,7 @@ impl Embedder {
                     ]
                 }),
                 headers: Default::default(),
+                sparse: None,
             },
             cache_cap,
             super::rest::ConfigurationSource::OpenAi,
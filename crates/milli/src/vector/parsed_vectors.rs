@@ -5,7 +5,7 @@ use obkv::KvReader;
 use serde_json::value::RawValue;
 use serde_json::{from_slice, Value};
 
-use super::Embedding;
+use super::{sparse, Embedding};
 use crate::index::IndexEmbeddingConfig;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd};
 use crate::{DocumentId, FieldId, InternalError, UserError};
@@ -516,13 +516,27 @@ fn to_vector_map(
 }
 
 /// Represents either a vector or an array of multiple vectors.
-#[derive(serde::Serialize, Debug)]
-#[serde(transparent)]
+///
+/// Sparse vectors are objects mapping dimensions to weights.
+#[derive(Debug)]
 pub struct VectorOrArrayOfVectors {
-    #[serde(with = "either::serde_untagged_optional")]
     inner: Option<either::Either<Vec<Embedding>, Embedding>>,
 }
 
+impl serde::Serialize for VectorOrArrayOfVectors {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.inner {
+            None => serializer.serialize_none(),
+            Some(either::Either::Left(vectors)) => {
+                sparse::serialize_embeddings(vectors, serializer)
+            }
+            Some(either::Either::Right(vector)) => {
+                serde::Serialize::serialize(&sparse::SerializeEmbedding(vector), serializer)
+            }
+        }
+    }
+}
+
 impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
     fn deserialize_from_value<V: deserr::IntoValue>(
         value: deserr::Value<V>,
@@ -530,6 +544,11 @@ impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
     ) -> Result<Self, E> {
         match value {
             deserr::Value::Null => Ok(VectorOrArrayOfVectors { inner: None }),
+            deserr::Value::Map(_) => Ok(VectorOrArrayOfVectors {
+                inner: Some(either::Either::Right(sparse::deserialize_from_value(
+                    value, location,
+                )?)),
+            }),
             deserr::Value::Sequence(seq) => {
                 let mut iter = seq.into_iter();
                 match iter.next().map(|v| v.into_value()) {
@@ -554,6 +573,22 @@ impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
 
                         Ok(VectorOrArrayOfVectors { inner: Some(either::Either::Left(collect)) })
                     }
+                    Some(val @ deserr::Value::Map(_)) => {
+                        let first = sparse::deserialize_from_value(val, location.push_index(0))?;
+                        let mut collect = vec![first];
+                        let mut tail = iter
+                            .enumerate()
+                            .map(|(i, v)| {
+                                sparse::deserialize_from_value(
+                                    v.into_value(),
+                                    location.push_index(i + 1),
+                                )
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        collect.append(&mut tail);
+
+                        Ok(VectorOrArrayOfVectors { inner: Some(either::Either::Left(collect)) })
+                    }
                     Some(
                         val @ deserr::Value::Integer(_)
                         | val @ deserr::Value::NegativeInteger(_)
@@ -576,7 +611,11 @@ impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
                         None,
                         deserr::ErrorKind::IncorrectValueKind {
                             actual: value,
-                            accepted: &[deserr::ValueKind::Sequence, deserr::ValueKind::Float],
+                            accepted: &[
+                                deserr::ValueKind::Sequence,
+                                deserr::ValueKind::Map,
+                                deserr::ValueKind::Float,
+                            ],
                         },
                         location.push_index(0),
                     ))),
@@ -586,7 +625,11 @@ impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
                 None,
                 deserr::ErrorKind::IncorrectValueKind {
                     actual: value,
-                    accepted: &[deserr::ValueKind::Sequence, deserr::ValueKind::Null],
+                    accepted: &[
+                        deserr::ValueKind::Sequence,
+                        deserr::ValueKind::Map,
+                        deserr::ValueKind::Null,
+                    ],
                 },
                 location,
             ))),
@@ -679,4 +722,17 @@ mod test {
         ]
         "###);
     }
+
+    #[test]
+    fn sparse_vectors() {
+        let one = embedding_from_str(r#"{"12": 0.5, "3": 1.5}"#).unwrap();
+        let two = embedding_from_str(r#"[{"3": 1.5}, {"7": 0.25}]"#).unwrap();
+
+        assert_eq!(serde_json::to_string(&one).unwrap(), r#"{"3":1.5,"12":0.5}"#);
+        assert_eq!(serde_json::to_string(&two).unwrap(), r#"[{"3":1.5},{"7":0.25}]"#);
+        assert_eq!(two.into_array_of_vectors().unwrap().len(), 2);
+
+        let error = embedding_from_str(r#"[{"3": 1.5}, {"-1": 0.25}]"#).unwrap_err();
+        assert!(error.to_string().contains("found `-1`"), "{error}");
+    }
 }
@@ -0,0 +1,471 @@
+//! Rerankers score the relevance of documents to a query, to reorder the top hits of a search.
+//!
+//! A reranker is either a remote server queried through REST, or a local cross-encoder model
+//! from the Hugging Face Hub.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use candle_core::Tensor;
+use candle_nn::{Linear, Module as _};
+use candle_transformers::models::bert::{BertModel, Config};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokenizers::Tokenizer;
+
+use super::error::{EmbedError, NewEmbedderError};
+use super::hf::{device, ModelFiles};
+use super::json_template::ValueTemplate;
+use super::rest::{check_response, ConfigurationSource, Retry, REPEAT_PLACEHOLDER};
+use crate::prompt::PromptData;
+
+pub const QUERY_PLACEHOLDER: &str = "{{query}}";
+pub const DOCUMENT_PLACEHOLDER: &str = "{{document}}";
+pub const SCORE_PLACEHOLDER: &str = "{{score}}";
+pub const INDEX_PLACEHOLDER: &str = "{{index}}";
+
+/// The maximum number of tokens of a `(query, document)` pair scored by a cross-encoder.
+const MAX_PAIR_TOKENS: usize = 512;
+
+/// The configuration of a reranker, as stored in the index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankerConfig {
+    pub options: RerankerOptions,
+    /// The template used to render the documents sent to the reranker.
+    pub prompt: PromptData,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RerankerOptions {
+    Rest(RestRerankerOptions),
+    HuggingFace(HfRerankerOptions),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestRerankerOptions {
+    pub api_key: Option<String>,
+    pub url: String,
+    pub request: serde_json::Value,
+    pub response: serde_json::Value,
+    pub headers: BTreeMap<String, String>,
+}
+
+impl std::hash::Hash for RestRerankerOptions {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.api_key.hash(state);
+        self.url.hash(state);
+        self.headers.hash(state);
+        // skip hashing the request and response, like for the REST embedder
+    }
+}
+
+impl RestRerankerOptions {
+    /// Checks that the request and response templates are valid, without contacting the server.
+    pub fn check_templates(&self) -> Result<(), NewEmbedderError> {
+        RestRequest::new(self.request.clone())?;
+        RestResponse::new(self.response.clone())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HfRerankerOptions {
+    pub model: String,
+    pub revision: Option<String>,
+    /// Directory containing the files of the model, used instead of fetching `model` from the Hub.
+    #[serde(default)]
+    pub model_path: Option<String>,
+}
+
+/// The runtime representation of a reranker.
+#[derive(Debug)]
+pub enum Reranker {
+    Rest(RestReranker),
+    HuggingFace(CrossEncoder),
+}
+
+impl Reranker {
+    pub fn new(options: RerankerOptions) -> Result<Self, NewEmbedderError> {
+        Ok(match options {
+            RerankerOptions::Rest(options) => Self::Rest(RestReranker::new(options)?),
+            RerankerOptions::HuggingFace(options) => Self::HuggingFace(CrossEncoder::new(options)?),
+        })
+    }
+
+    /// Scores the relevance of each document to the query.
+    ///
+    /// The scores are returned in the order of the documents, and are expected between 0 and 1.
+    pub fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        match self {
+            Reranker::Rest(reranker) => reranker.rerank(query, documents, deadline),
+            Reranker::HuggingFace(reranker) => reranker.rerank(query, documents),
+        }
+    }
+}
+
+/// A reranker hosted behind a REST API.
+#[derive(Debug)]
+pub struct RestReranker {
+    client: ureq::Agent,
+    bearer: Option<String>,
+    headers: BTreeMap<String, String>,
+    url: String,
+    request: RestRequest,
+    response: RestResponse,
+}
+
+impl RestReranker {
+    pub fn new(options: RestRerankerOptions) -> Result<Self, NewEmbedderError> {
+        let bearer = options.api_key.as_deref().map(|api_key| format!("Bearer {api_key}"));
+        let client = ureq::AgentBuilder::new().timeout(std::time::Duration::from_secs(30)).build();
+
+        Ok(Self {
+            client,
+            bearer,
+            headers: options.headers,
+            url: options.url,
+            request: RestRequest::new(options.request)?,
+            response: RestResponse::new(options.response)?,
+        })
+    }
+
+    fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        let request = self.client.post(&self.url);
+        let request = if let Some(bearer) = &self.bearer {
+            request.set("Authorization", bearer)
+        } else {
+            request
+        };
+        let mut request = request.set("Content-Type", "application/json");
+        for (header, value) in &self.headers {
+            request = request.set(header.as_str(), value.as_str());
+        }
+
+        let body = self.request.inject(query, documents);
+
+        for attempt in 0..10 {
+            let response = request.clone().send_json(&body);
+            let result = check_response(response, ConfigurationSource::User)
+                .and_then(|response| self.response.extract_scores(response, documents.len()));
+
+            let retry_duration = match result {
+                Ok(scores) => return Ok(scores),
+                Err(retry) => {
+                    tracing::warn!("Failed: {}", retry.error);
+                    if let Some(deadline) = deadline {
+                        let now = std::time::Instant::now();
+                        if now > deadline {
+                            tracing::warn!("Could not rerank due to deadline");
+                            return Err(retry.into_error());
+                        }
+
+                        let duration_to_deadline = deadline - now;
+                        retry
+                            .into_duration(attempt)
+                            .map(|duration| duration.min(duration_to_deadline))
+                    } else {
+                        retry.into_duration(attempt)
+                    }
+                }
+            }?;
+
+            let retry_duration = retry_duration.min(std::time::Duration::from_secs(60));
+            let retry_duration = retry_duration
+                + rand::thread_rng().gen_range(std::time::Duration::ZERO..retry_duration);
+
+            tracing::warn!(
+                "Attempt #{}, retrying after {}ms.",
+                attempt,
+                retry_duration.as_millis()
+            );
+            std::thread::sleep(retry_duration);
+        }
+
+        let response = request.send_json(&body);
+        check_response(response, ConfigurationSource::User)
+            .and_then(|response| self.response.extract_scores(response, documents.len()))
+            .map_err(Retry::into_error)
+    }
+}
+
+/// The body sent to a REST reranker, containing the query and an array of documents.
+#[derive(Debug)]
+struct RestRequest {
+    template: ValueTemplate,
+}
+
+impl RestRequest {
+    fn new(template: serde_json::Value) -> Result<Self, NewEmbedderError> {
+        if !contains_string(&template, QUERY_PLACEHOLDER) {
+            return Err(NewEmbedderError::rest_could_not_parse_template(format!(
+                "in `request`: `request` must contain the query placeholder `{QUERY_PLACEHOLDER}`"
+            )));
+        }
+        let template = match ValueTemplate::new(template, DOCUMENT_PLACEHOLDER, REPEAT_PLACEHOLDER)
+        {
+            Ok(template) => template,
+            Err(error) => {
+                let message =
+                    error.error_message("request", DOCUMENT_PLACEHOLDER, REPEAT_PLACEHOLDER);
+                return Err(NewEmbedderError::rest_could_not_parse_template(message));
+            }
+        };
+        if !template.has_array_value() {
+            return Err(NewEmbedderError::rest_could_not_parse_template(format!(
+                "in `request`: `request` must contain an array of documents, such as `[\"{DOCUMENT_PLACEHOLDER}\", \"{REPEAT_PLACEHOLDER}\"]`"
+            )));
+        }
+        Ok(Self { template })
+    }
+
+    fn inject(&self, query: &str, documents: &[String]) -> serde_json::Value {
+        // unwrap: documents are never empty
+        let mut body =
+            self.template.inject(documents.iter().map(|s| serde_json::json!(s))).unwrap();
+        replace_string(&mut body, QUERY_PLACEHOLDER, query);
+        body
+    }
+}
+
+/// The response of a REST reranker, containing an array of scores.
+///
+/// When the scores are not in the order of the documents, the response must also
+/// contain the index of the document of each score.
+#[derive(Debug)]
+struct RestResponse {
+    scores: ValueTemplate,
+    indices: Option<ValueTemplate>,
+}
+
+impl RestResponse {
+    fn new(template: serde_json::Value) -> Result<Self, NewEmbedderError> {
+        let indices = if contains_string(&template, INDEX_PLACEHOLDER) {
+            match ValueTemplate::new(template.clone(), INDEX_PLACEHOLDER, REPEAT_PLACEHOLDER) {
+                Ok(indices) => Some(indices),
+                Err(error) => {
+                    let message =
+                        error.error_message("response", INDEX_PLACEHOLDER, REPEAT_PLACEHOLDER);
+                    return Err(NewEmbedderError::rest_could_not_parse_template(message));
+                }
+            }
+        } else {
+            None
+        };
+        let scores = match ValueTemplate::new(template, SCORE_PLACEHOLDER, REPEAT_PLACEHOLDER) {
+            Ok(scores) => scores,
+            Err(error) => {
+                let message =
+                    error.error_message("response", SCORE_PLACEHOLDER, REPEAT_PLACEHOLDER);
+                return Err(NewEmbedderError::rest_could_not_parse_template(message));
+            }
+        };
+        if !scores.has_array_value()
+            || indices.as_ref().is_some_and(|indices| !indices.has_array_value())
+        {
+            return Err(NewEmbedderError::rest_could_not_parse_template(format!(
+                "in `response`: `response` must contain an array of scores, such as `[\"{SCORE_PLACEHOLDER}\", \"{REPEAT_PLACEHOLDER}\"]`"
+            )));
+        }
+        Ok(Self { scores, indices })
+    }
+
+    fn extract_scores(
+        &self,
+        response: ureq::Response,
+        expected_count: usize,
+    ) -> Result<Vec<f32>, Retry> {
+        let response: serde_json::Value = response
+            .into_json()
+            .map_err(EmbedError::rest_response_deserialization)
+            .map_err(Retry::retry_later)?;
+
+        let indices: Option<Vec<usize>> = match &self.indices {
+            Some(indices) => match indices.extract(response.clone()) {
+                Ok(indices) => Some(indices),
+                Err(error) => {
+                    let message = error.error_message("response", INDEX_PLACEHOLDER, "an integer");
+                    return Err(Retry::give_up(EmbedError::rest_extraction_error(message)));
+                }
+            },
+            None => None,
+        };
+        let scores: Vec<f32> = match self.scores.extract(response) {
+            Ok(scores) => scores,
+            Err(error) => {
+                let message = error.error_message("response", SCORE_PLACEHOLDER, "a number");
+                return Err(Retry::give_up(EmbedError::rest_extraction_error(message)));
+            }
+        };
+
+        if scores.len() != expected_count {
+            return Err(Retry::give_up(EmbedError::rerank_score_count(
+                expected_count,
+                scores.len(),
+            )));
+        }
+
+        let Some(indices) = indices else { return Ok(scores) };
+        let mut ordered_scores = vec![0.0; expected_count];
+        for (index, score) in indices.into_iter().zip(scores) {
+            let Some(ordered_score) = ordered_scores.get_mut(index) else {
+                return Err(Retry::give_up(EmbedError::rerank_index_out_of_bounds(
+                    index,
+                    expected_count,
+                )));
+            };
+            *ordered_score = score;
+        }
+        Ok(ordered_scores)
+    }
+}
+
+fn contains_string(value: &serde_json::Value, needle: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s == needle,
+        serde_json::Value::Array(values) => {
+            values.iter().any(|value| contains_string(value, needle))
+        }
+        serde_json::Value::Object(map) => map.values().any(|value| contains_string(value, needle)),
+        _ => false,
+    }
+}
+
+fn replace_string(value: &mut serde_json::Value, needle: &str, replacement: &str) {
+    match value {
+        serde_json::Value::String(s) if s == needle => *s = replacement.to_owned(),
+        serde_json::Value::Array(values) => {
+            values.iter_mut().for_each(|value| replace_string(value, needle, replacement))
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(|value| replace_string(value, needle, replacement))
+        }
+        _ => (),
+    }
+}
+
+/// The parts of the configuration of a cross-encoder that are not exposed by [`Config`].
+#[derive(Deserialize)]
+struct CrossEncoderConfig {
+    hidden_size: usize,
+    #[serde(default)]
+    id2label: BTreeMap<String, String>,
+}
+
+/// A BERT cross-encoder, scoring `(query, document)` pairs with a classification head.
+pub struct CrossEncoder {
+    model: BertModel,
+    pooler: Linear,
+    classifier: Linear,
+    tokenizer: Tokenizer,
+    options: HfRerankerOptions,
+}
+
+impl std::fmt::Debug for CrossEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrossEncoder")
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl CrossEncoder {
+    pub fn new(options: HfRerankerOptions) -> Result<Self, NewEmbedderError> {
+        let device = device();
+        let model_files = ModelFiles::fetch(
+            &options.model,
+            options.revision.as_deref(),
+            options.model_path.as_deref(),
+        )?;
+        let model_name = options.model_path.as_deref().unwrap_or(&options.model);
+
+        let config_filename = &model_files.config_filename;
+        let config = std::fs::read_to_string(config_filename)
+            .map_err(|inner| NewEmbedderError::open_config(config_filename.clone(), inner))?;
+        let deserialize_error = |inner: serde_json::Error| {
+            NewEmbedderError::deserialize_config(
+                model_name.to_owned(),
+                config.clone(),
+                config_filename.clone(),
+                inner,
+            )
+        };
+        let bert_config: Config = serde_json::from_str(&config).map_err(deserialize_error)?;
+        let CrossEncoderConfig { hidden_size, id2label } =
+            serde_json::from_str(&config).map_err(deserialize_error)?;
+        let num_labels = id2label.len().max(1);
+
+        let tokenizer_filename = &model_files.tokenizer_filename;
+        let tokenizer = Tokenizer::from_file(tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
+
+        let vb = model_files.var_builder(&device)?;
+        let model =
+            BertModel::load(vb.clone(), &bert_config).map_err(NewEmbedderError::load_model)?;
+        let pooler = candle_nn::linear(hidden_size, hidden_size, vb.pp("bert.pooler.dense"))
+            .map_err(NewEmbedderError::load_model)?;
+        let classifier = candle_nn::linear(hidden_size, num_labels, vb.pp("classifier"))
+            .map_err(NewEmbedderError::load_model)?;
+
+        tracing::debug!(model = model_name, num_labels, "cross-encoder config");
+
+        Ok(Self { model, pooler, classifier, tokenizer, options })
+    }
+
+    fn rerank(&self, query: &str, documents: &[String]) -> Result<Vec<f32>, EmbedError> {
+        documents.iter().map(|document| self.score_one(query, document)).collect()
+    }
+
+    fn score_one(&self, query: &str, document: &str) -> Result<f32, EmbedError> {
+        let tokens =
+            self.tokenizer.encode((query, document), true).map_err(EmbedError::tokenize)?;
+        let len = tokens.get_ids().len().min(MAX_PAIR_TOKENS);
+        let device = &self.model.device;
+        let token_ids =
+            Tensor::new(&tokens.get_ids()[..len], device).map_err(EmbedError::tensor_shape)?;
+        let token_ids = Tensor::stack(&[token_ids], 0).map_err(EmbedError::tensor_shape)?;
+        let token_type_ids =
+            Tensor::new(&tokens.get_type_ids()[..len], device).map_err(EmbedError::tensor_shape)?;
+        let token_type_ids =
+            Tensor::stack(&[token_type_ids], 0).map_err(EmbedError::tensor_shape)?;
+
+        let hidden_states = self
+            .model
+            .forward(&token_ids, &token_type_ids, None)
+            .map_err(EmbedError::model_forward)?;
+        // the classification head reads the hidden state of the `[CLS]` token
+        let cls = hidden_states.narrow(1, 0, 1).map_err(EmbedError::tensor_shape)?;
+        let cls = cls.squeeze(1).map_err(EmbedError::tensor_shape)?;
+        let pooled = self.pooler.forward(&cls).map_err(EmbedError::model_forward)?;
+        let pooled = pooled.tanh().map_err(EmbedError::model_forward)?;
+        let logits = self.classifier.forward(&pooled).map_err(EmbedError::model_forward)?;
+        let logits: Vec<f32> = logits
+            .squeeze(0)
+            .map_err(EmbedError::tensor_shape)?
+            .to_vec1()
+            .map_err(EmbedError::tensor_value)?;
+
+        Ok(match logits.as_slice() {
+            [logit] => 1.0 / (1.0 + (-logit).exp()),
+            // the last label is the relevant one for multi-label classifiers
+            logits => {
+                let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let sum: f32 = logits.iter().map(|logit| (logit - max).exp()).sum();
+                logits.last().map_or(0.0, |logit| (logit - max).exp() / sum)
+            }
+        })
+    }
+}
@@ -10,7 +10,8 @@ use serde::{Deserialize, Serialize};
 use super::error::EmbedErrorKind;
 use super::json_template::ValueTemplate;
 use super::{
-    DistributionShift, EmbedError, Embedding, EmbeddingCache, NewEmbedderError, REQUEST_PARALLELISM,
+    sparse, DistributionShift, EmbedError, Embedding, EmbeddingCache, NewEmbedderError,
+    REQUEST_PARALLELISM,
 };
 use crate::error::FaultSource;
 use crate::ThreadPoolNoAbort;
@@ -90,6 +91,7 @@ struct EmbedderData {
     request: Request,
     response: Response,
     configuration_source: ConfigurationSource,
+    sparse: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -101,6 +103,9 @@ pub struct EmbedderOptions {
     pub request: serde_json::Value,
     pub response: serde_json::Value,
     pub headers: BTreeMap<String, String>,
+    /// Whether the server returns sparse embeddings, as objects mapping dimensions to weights.
+    #[serde(default)]
+    pub sparse: Option<bool>,
 }
 
 impl std::hash::Hash for EmbedderOptions {
@@ -109,6 +114,7 @@ impl std::hash::Hash for EmbedderOptions {
         self.distribution.hash(state);
         self.dimensions.hash(state);
         self.url.hash(state);
+        self.sparse.hash(state);
         // skip hashing the request and response
         // collisions in regular usage should be minimal,
         // and the list is limited to 256 values anyway
@@ -148,10 +154,14 @@ impl Embedder {
             response,
             configuration_source,
             headers: options.headers,
+            sparse: options.sparse.unwrap_or_default(),
         };
 
         let dimensions = if let Some(dimensions) = options.dimensions {
             dimensions
+        } else if data.sparse {
+            // the dimensions of a sparse embedding cannot be inferred from a single embedding
+            sparse::MAX_DIMENSIONS
         } else {
             infer_dimensions(&data)?
         };
@@ -266,6 +276,10 @@ impl Embedder {
         self.distribution
     }
 
+    pub fn sparse(&self) -> bool {
+        self.data.sparse
+    }
+
     pub(super) fn cache(&self) -> &EmbeddingCache {
         &self.cache
     }
@@ -344,7 +358,7 @@ where
     })
 }
 
-fn check_response(
+pub(super) fn check_response(
     response: Result<ureq::Response, ureq::Error>,
     configuration_source: ConfigurationSource,
 ) -> Result<ureq::Response, Retry> {
@@ -388,7 +402,12 @@ fn response_to_embedding(
         .map_err(EmbedError::rest_response_deserialization)
         .map_err(Retry::retry_later)?;
 
-    let embeddings = data.response.extract_embeddings(response).map_err(Retry::give_up)?;
+    let embeddings = if data.sparse {
+        data.response.extract_sparse_embeddings(response)
+    } else {
+        data.response.extract_embeddings(response)
+    }
+    .map_err(Retry::give_up)?;
 
     if embeddings.len() != expected_count {
         return Err(Retry::give_up(EmbedError::rest_response_embedding_count(
@@ -399,7 +418,13 @@ fn response_to_embedding(
 
     if let Some(dimensions) = expected_dimensions {
         for embedding in &embeddings {
-            if embedding.len() != dimensions {
+            if data.sparse {
+                if let Err(reason) = sparse::check(embedding, true, dimensions) {
+                    return Err(Retry::give_up(EmbedError::rest_extraction_error(format!(
+                        "the sparse embedding {reason}"
+                    ))));
+                }
+            } else if embedding.len() != dimensions {
                 return Err(Retry::give_up(EmbedError::rest_unexpected_dimension(
                     dimensions,
                     embedding.len(),
@@ -490,4 +515,24 @@ impl Response {
 
         Ok(embeddings)
     }
+
+    /// Extracts sparse embeddings, that are objects mapping dimensions to weights.
+    pub fn extract_sparse_embeddings(
+        &self,
+        response: serde_json::Value,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        let extracted_values: Vec<BTreeMap<u32, f32>> = match self.template.extract(response) {
+            Ok(extracted_values) => extracted_values,
+            Err(error) => {
+                let error_message = error.error_message(
+                    "response",
+                    "{{embedding}}",
+                    "an object mapping dimensions to weights",
+                );
+                return Err(EmbedError::rest_extraction_error(error_message));
+            }
+        };
+
+        Ok(extracted_values.into_iter().map(sparse::encode).collect())
+    }
 }
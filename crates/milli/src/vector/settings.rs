@@ -6,9 +6,10 @@ use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use super::chunking::Chunking;
 use super::composite::SubEmbedderOptions;
 use super::hf::OverridePooling;
-use super::{ollama, openai, DistributionShift, EmbedderOptions};
+use super::{ollama, openai, DistributionShift, EmbedderOptions, Quantization, VectorDistance};
 use crate::prompt::{default_max_bytes, PromptData};
 use crate::update::Setting;
 use crate::vector::EmbeddingConfig;
@@ -82,7 +83,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -169,6 +170,186 @@ pub struct EmbeddingSettings {
     pub binary_quantized: Setting<bool>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<Quantization>)]
+    /// How the embeddings of this embedder are compressed in the vector store.
+    ///
+    /// - `none`: embeddings are stored as 32-bit floats.
+    /// - `binary`: each dimension is stored as a single bit, like with `binaryQuantized`.
+    /// - `int8`: each dimension is stored as an 8-bit integer. The neighbors are searched among the binary quantized
+    ///   embeddings, then rescored with the 8-bit embeddings, which loses less relevancy than `binary` for 4 times
+    ///   less disk usage than `none`.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for all embedders
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When modified, embeddings are not regenerated, but they are quantized, which takes time.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `none`, or `binary` when `binaryQuantized` is `true`
+    ///
+    /// # Note
+    ///
+    /// - Quantization is a lossy operation: the quantization can only be changed from `none` to any other value,
+    ///   and from `int8` to `binary`.
+    /// - This parameter cannot be set together with `binaryQuantized`.
+    /// - `dotProduct` and sparse embeddings cannot be quantized.
+    pub quantization: Setting<Quantization>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<VectorDistance>)]
+    /// The metric used to compare the embeddings of this embedder.
+    ///
+    /// Use the metric the model was trained with, as indicated by its documentation.
+    /// The semantic score of the documents is derived from this metric.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for all embedders
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When modified, embeddings are not regenerated, but the vector store is rebuilt with the new metric, which takes time.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `cosine`
+    ///
+    /// # Note
+    ///
+    /// - `dotProduct` cannot be used with quantized embeddings.
+    pub distance: Setting<VectorDistance>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether each document embedding is a bag of token-level embeddings, as produced by late-interaction models.
+    ///
+    /// When enabled, the query vector is also a bag of embeddings, and documents are scored by averaging,
+    /// over the query embeddings, their best similarity with any of the document embeddings (MaxSim).
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `userProvided`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    ///
+    /// # Note
+    ///
+    /// - A document can hold at most 256 embeddings per embedder.
+    /// - The `vector` search parameter must be the concatenation of the query embeddings.
+    pub multi_vector: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<Chunking>)]
+    /// Splits long documents into chunks that are embedded separately, instead of truncating them.
+    ///
+    /// Each chunk produces an embedding of the document, and a document is as similar to the query
+    /// as its most similar chunk. Search results indicate the position of the best matching chunk.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`, `rest` and `onnx`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `null`, meaning that documents are not chunked
+    ///
+    /// # Note
+    ///
+    /// - When set, `documentTemplateMaxBytes` is not applied, as each chunk is bounded by `size`.
+    /// - A document is split in at most 255 chunks, the rest of the text is not embedded.
+    pub chunking: Setting<Chunking>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// Path to the model on the filesystem of the Meilisearch instance.
+    ///
+    /// The path must lead inside the models directory set with `--models-dir`, relative paths are resolved from it.
+    ///
+    /// - For source `onnx`, either the `.onnx` file of the model, or a directory containing a `model.onnx` file.
+    ///   The `tokenizer.json` file, and optionally the `1_Pooling/config.json` file, are read from the directory of the model.
+    /// - For source `huggingFace`, a directory laid out like a repository of the Hugging Face Hub, containing
+    ///   `config.json`, `tokenizer.json`, `model.safetensors` or `pytorch_model.bin`, and optionally `1_Pooling/config.json`.
+    ///   The model is then read from the directory instead of being fetched from the Hub.
+    ///
+    /// # Mandatory
+    ///
+    /// - This parameter is mandatory for source `onnx`
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `huggingFace` and `onnx`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Note
+    ///
+    /// - For source `huggingFace`, `model` and `revision` are ignored when this parameter is set.
+    pub model_path: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// The number of threads used to run a single inference of the model.
+    ///
+    /// Documents are embedded in parallel on the indexing threads, so this parameter mostly affects the
+    /// time it takes to embed a search query.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `onnx`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `1`
+    pub threads: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether the embeddings are sparse, mapping the indices of their nonzero dimensions to weights.
+    ///
+    /// Sparse embeddings, such as the ones produced by SPLADE models, are written as objects like
+    /// `{"1045": 0.8, "2003": 0.3}` and stored in an inverted index rather than in a vector store.
+    /// Documents are scored by the dot product of their embeddings with the query.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `userProvided` and `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - ❌ Changing the value of this parameter is not allowed, remove and re-add the embedder instead
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    ///
+    /// # Note
+    ///
+    /// - `dimensions` is the size of the vocabulary of the model, all dimensions of the embeddings must be lower.
+    /// - For source `rest`, `{{embedding}}` in `response` must be an object mapping dimensions to weights.
+    /// - Sparse embeddings are incompatible with `binaryQuantized`, `quantization`, `multiVector`, `chunking` and any `distance` other than `dotProduct`.
+    pub sparse: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<bool>)]
     /// A liquid template used to render documents to a text that can be embedded.
     ///
@@ -177,7 +358,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `rest` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -190,7 +371,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `rest` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -518,6 +699,28 @@ pub struct SubEmbeddingSettings {
     ///
     /// - 🌱 Changing the value of this parameter never regenerates embeddings
     pub headers: Setting<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// Directory containing the files of the model on the filesystem of the Meilisearch instance.
+    ///
+    /// The directory must be inside the models directory set with `--models-dir`, relative paths are resolved from it.
+    ///
+    /// The directory is laid out like a repository of the Hugging Face Hub, containing `config.json`, `tokenizer.json`,
+    /// `model.safetensors` or `pytorch_model.bin`, and optionally `1_Pooling/config.json`.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `huggingFace`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Note
+    ///
+    /// - `model` and `revision` are ignored when this parameter is set.
+    pub model_path: Setting<String>,
 
     // The following fields are provided for the sake of improving error handling
     // They should always be set to `NotSet`, otherwise an error will be returned
@@ -531,6 +734,36 @@ pub struct SubEmbeddingSettings {
     #[schema(ignore)]
     pub binary_quantized: Setting<bool>,
 
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub quantization: Setting<Quantization>,
+
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub distance: Setting<VectorDistance>,
+
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub multi_vector: Setting<bool>,
+
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub chunking: Setting<Chunking>,
+
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub threads: Setting<usize>,
+
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub sparse: Setting<bool>,
+
     #[serde(default, skip_serializing)]
     #[deserr(default)]
     #[schema(ignore)]
@@ -555,14 +788,24 @@ pub enum ReindexAction {
 
 pub enum SettingsDiff {
     Remove,
-    Reindex { action: ReindexAction, updated_settings: EmbeddingSettings, quantize: bool },
-    UpdateWithoutReindex { updated_settings: EmbeddingSettings, quantize: bool },
+    Reindex {
+        action: ReindexAction,
+        updated_settings: EmbeddingSettings,
+        quantize: bool,
+        change_distance: bool,
+    },
+    UpdateWithoutReindex {
+        updated_settings: EmbeddingSettings,
+        quantize: bool,
+        change_distance: bool,
+    },
 }
 
 #[derive(Default, Debug)]
 pub struct EmbedderAction {
     pub was_quantized: bool,
     pub is_being_quantized: bool,
+    pub is_changing_distance: bool,
     pub write_back: Option<WriteBackToDocuments>,
     pub reindex: Option<ReindexAction>,
 }
@@ -572,6 +815,10 @@ impl EmbedderAction {
         self.is_being_quantized
     }
 
+    pub fn is_changing_distance(&self) -> bool {
+        self.is_changing_distance
+    }
+
     pub fn write_back(&self) -> Option<&WriteBackToDocuments> {
         self.write_back.as_ref()
     }
@@ -585,17 +832,29 @@ impl EmbedderAction {
         self
     }
 
+    pub fn with_is_changing_distance(mut self, change_distance: bool) -> Self {
+        self.is_changing_distance = change_distance;
+        self
+    }
+
     pub fn with_write_back(write_back: WriteBackToDocuments, was_quantized: bool) -> Self {
         Self {
             was_quantized,
             is_being_quantized: false,
+            is_changing_distance: false,
             write_back: Some(write_back),
             reindex: None,
         }
     }
 
     pub fn with_reindex(reindex: ReindexAction, was_quantized: bool) -> Self {
-        Self { was_quantized, is_being_quantized: false, write_back: None, reindex: Some(reindex) }
+        Self {
+            was_quantized,
+            is_being_quantized: false,
+            is_changing_distance: false,
+            write_back: None,
+            reindex: Some(reindex),
+        }
     }
 }
 
@@ -630,6 +889,13 @@ impl SettingsDiff {
                     mut headers,
                     mut document_template_max_bytes,
                     binary_quantized: mut binary_quantize,
+                    mut quantization,
+                    mut distance,
+                    mut multi_vector,
+                    mut chunking,
+                    mut model_path,
+                    mut threads,
+                    mut sparse,
                 } = old;
 
                 let EmbeddingSettings {
@@ -649,6 +915,13 @@ impl SettingsDiff {
                     headers: new_headers,
                     document_template_max_bytes: new_document_template_max_bytes,
                     binary_quantized: new_binary_quantize,
+                    quantization: new_quantization,
+                    distance: new_distance,
+                    multi_vector: new_multi_vector,
+                    chunking: new_chunking,
+                    model_path: new_model_path,
+                    threads: new_threads,
+                    sparse: new_sparse,
                 } = new;
 
                 if matches!(binary_quantize, Setting::Set(true))
@@ -658,6 +931,12 @@ impl SettingsDiff {
                         embedder_name: embedder_name.to_string(),
                     });
                 }
+                if !new_binary_quantize.is_not_set() && !new_quantization.is_not_set() {
+                    return Err(UserError::InvalidSettingsQuantization {
+                        embedder_name: embedder_name.to_string(),
+                        reason: "`quantization` and `binaryQuantized` cannot be set at the same time.\n - Hint: Only use `quantization`.".to_string(),
+                    });
+                }
 
                 let mut reindex_action = None;
 
@@ -689,7 +968,35 @@ impl SettingsDiff {
                     new_headers,
                 );
 
-                let binary_quantize_changed = binary_quantize.apply(new_binary_quantize);
+                // the most recently set of the two settings decides of the quantization
+                let old_quantization = Self::quantization_of(&binary_quantize, &quantization);
+                let set_through_binary_quantized = !new_binary_quantize.is_not_set();
+                if set_through_binary_quantized {
+                    quantization = Setting::NotSet;
+                }
+                if !new_quantization.is_not_set() {
+                    binary_quantize = Setting::NotSet;
+                }
+                binary_quantize.apply(new_binary_quantize);
+                quantization.apply(new_quantization);
+                let new_quantization = Self::quantization_of(&binary_quantize, &quantization);
+                if !old_quantization.can_convert_to(new_quantization) {
+                    return Err(if set_through_binary_quantized {
+                        UserError::InvalidDisableBinaryQuantization {
+                            embedder_name: embedder_name.to_string(),
+                        }
+                    } else {
+                        UserError::InvalidSettingsQuantization {
+                            embedder_name: embedder_name.to_string(),
+                            reason: format!("Cannot change the quantization from `{old_quantization}` to `{new_quantization}`.\n - Note: Quantization is a lossy operation, the embeddings can only be quantized further.\n - Hint: Add a new embedder with the `{new_quantization}` quantization and regenerate the vectors."),
+                        }
+                    });
+                }
+                let quantization_changed = old_quantization != new_quantization;
+                // resetting the distance to its default value does not change the metric
+                let old_distance = distance.set().unwrap_or_default();
+                distance.apply(new_distance);
+                let distance_changed = distance.set().unwrap_or_default() != old_distance;
 
                 // changes to the *search* embedder never triggers any reindexing
                 search_embedder.apply(new_search_embedder);
@@ -700,6 +1007,58 @@ impl SettingsDiff {
                 )?;
 
                 distribution.apply(new_distribution);
+                // the parameter is specific to the `userProvided` source, drop it when the source changes
+                if !matches!(source, Setting::Set(EmbedderSource::UserProvided)) {
+                    multi_vector = Setting::NotSet;
+                }
+                // the stored embeddings are the same, only the way they are compared changes
+                multi_vector.apply(new_multi_vector);
+                // documents cannot be chunked when their embeddings are not generated
+                if matches!(
+                    source,
+                    Setting::Set(EmbedderSource::UserProvided | EmbedderSource::Composite)
+                ) {
+                    chunking = Setting::NotSet;
+                }
+                // the number of embeddings per document changes
+                if chunking.apply(new_chunking) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+                // the parameters are specific to local models, drop them when the source changes
+                if !matches!(
+                    source,
+                    Setting::Set(EmbedderSource::HuggingFace | EmbedderSource::Onnx)
+                ) {
+                    model_path = Setting::NotSet;
+                }
+                if !matches!(source, Setting::Set(EmbedderSource::Onnx)) {
+                    threads = Setting::NotSet;
+                }
+                if model_path.apply(new_model_path) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+                // the number of threads does not change the embeddings
+                threads.apply(new_threads);
+                // sparse and dense embeddings are not stored in the same database, so they cannot be converted
+                let was_sparse = sparse.set().unwrap_or_default();
+                if !matches!(
+                    source,
+                    Setting::Set(EmbedderSource::UserProvided | EmbedderSource::Rest)
+                ) {
+                    sparse = Setting::NotSet;
+                }
+                sparse.apply(new_sparse);
+                let is_sparse = sparse.set().unwrap_or_default();
+                if was_sparse != is_sparse {
+                    return Err(UserError::InvalidSettingsSparse {
+                        embedder_name: embedder_name.to_string(),
+                        reason: format!(
+                            "Cannot change the embeddings from {} to {}.\n  - Hint: Remove the embedder and add it again to change the kind of its embeddings.",
+                            if was_sparse { "sparse" } else { "dense" },
+                            if is_sparse { "sparse" } else { "dense" },
+                        ),
+                    });
+                }
 
                 let updated_settings = EmbeddingSettings {
                     source,
@@ -718,24 +1077,35 @@ impl SettingsDiff {
                     headers,
                     document_template_max_bytes,
                     binary_quantized: binary_quantize,
+                    quantization,
+                    distance,
+                    multi_vector,
+                    chunking,
+                    model_path,
+                    threads,
+                    sparse,
                 };
 
                 match reindex_action {
                     Some(action) => Self::Reindex {
                         action,
                         updated_settings,
-                        quantize: binary_quantize_changed,
+                        quantize: quantization_changed,
+                        change_distance: distance_changed,
                     },
                     None => Self::UpdateWithoutReindex {
                         updated_settings,
-                        quantize: binary_quantize_changed,
+                        quantize: quantization_changed,
+                        change_distance: distance_changed,
                     },
                 }
             }
             Setting::Reset => Self::Remove,
-            Setting::NotSet => {
-                Self::UpdateWithoutReindex { updated_settings: old, quantize: false }
-            }
+            Setting::NotSet => Self::UpdateWithoutReindex {
+                updated_settings: old,
+                quantize: false,
+                change_distance: false,
+            },
         };
         Ok(ret)
     }
@@ -763,6 +1133,13 @@ impl SettingsDiff {
                     // phony settings
                     mut distribution,
                     mut binary_quantized,
+                    mut quantization,
+                    mut distance,
+                    mut multi_vector,
+                    mut chunking,
+                    mut model_path,
+                    mut threads,
+                    mut sparse,
                     mut search_embedder,
                     mut indexing_embedder,
                 }) = sub_embedder
@@ -787,6 +1164,13 @@ impl SettingsDiff {
                     headers: new_headers,
                     distribution: new_distribution,
                     binary_quantized: new_binary_quantized,
+                    quantization: new_quantization,
+                    distance: new_distance,
+                    multi_vector: new_multi_vector,
+                    chunking: new_chunking,
+                    model_path: new_model_path,
+                    threads: new_threads,
+                    sparse: new_sparse,
                     search_embedder: new_search_embedder,
                     indexing_embedder: new_indexing_embedder,
                 } = new_sub_embedder;
@@ -819,9 +1203,19 @@ impl SettingsDiff {
                     new_headers,
                 );
 
+                if model_path.apply(new_model_path) {
+                    ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
+                }
+
                 // update phony settings, it is always an error to have them set.
                 distribution.apply(new_distribution);
                 binary_quantized.apply(new_binary_quantized);
+                quantization.apply(new_quantization);
+                distance.apply(new_distance);
+                multi_vector.apply(new_multi_vector);
+                chunking.apply(new_chunking);
+                threads.apply(new_threads);
+                sparse.apply(new_sparse);
                 search_embedder.apply(new_search_embedder);
                 indexing_embedder.apply(new_indexing_embedder);
 
@@ -840,6 +1234,13 @@ impl SettingsDiff {
                     document_template_max_bytes,
                     distribution,
                     binary_quantized,
+                    quantization,
+                    distance,
+                    multi_vector,
+                    chunking,
+                    model_path,
+                    threads,
+                    sparse,
                     search_embedder,
                     indexing_embedder,
                 };
@@ -851,6 +1252,18 @@ impl SettingsDiff {
         Ok(ret)
     }
 
+    /// The quantization resulting from the `binaryQuantized` and `quantization` settings of an embedder.
+    fn quantization_of(
+        binary_quantized: &Setting<bool>,
+        quantization: &Setting<Quantization>,
+    ) -> Quantization {
+        match (quantization, binary_quantized) {
+            (Setting::Set(quantization), _) => *quantization,
+            (_, Setting::Set(true)) => Quantization::Binary,
+            _ => Quantization::None,
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn apply_and_diff(
         reindex_action: &mut Option<ReindexAction>,
@@ -1047,6 +1460,18 @@ fn apply_default_for_source(
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
+        Setting::Set(EmbedderSource::Onnx) => {
+            *model = Setting::NotSet;
+            *revision = Setting::NotSet;
+            *pooling = Setting::Reset;
+            *dimensions = Setting::NotSet;
+            *url = Setting::NotSet;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *headers = Setting::NotSet;
+            *search_embedder = Setting::NotSet;
+            *indexing_embedder = Setting::NotSet;
+        }
         Setting::Set(EmbedderSource::Composite) => {
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
@@ -1126,6 +1551,13 @@ pub enum MetaEmbeddingSetting {
     IndexingEmbedder,
     Distribution,
     BinaryQuantized,
+    Quantization,
+    Distance,
+    MultiVector,
+    Chunking,
+    ModelPath,
+    Threads,
+    Sparse,
 }
 
 impl MetaEmbeddingSetting {
@@ -1148,6 +1580,13 @@ impl MetaEmbeddingSetting {
             IndexingEmbedder => "indexingEmbedder",
             Distribution => "distribution",
             BinaryQuantized => "binaryQuantized",
+            Quantization => "quantization",
+            Distance => "distance",
+            MultiVector => "multiVector",
+            Chunking => "chunking",
+            ModelPath => "modelPath",
+            Threads => "threads",
+            Sparse => "sparse",
         }
     }
 }
@@ -1172,6 +1611,13 @@ impl EmbeddingSettings {
         search_embedder: &Setting<SubEmbeddingSettings>,
         indexing_embedder: &Setting<SubEmbeddingSettings>,
         binary_quantized: &Setting<bool>,
+        quantization: &Setting<Quantization>,
+        distance: &Setting<VectorDistance>,
+        multi_vector: &Setting<bool>,
+        chunking: &Setting<Chunking>,
+        model_path: &Setting<String>,
+        threads: &Setting<usize>,
+        sparse: &Setting<bool>,
         distribution: &Setting<DistributionShift>,
     ) -> Result<(), UserError> {
         Self::check_setting(embedder_name, source, MetaEmbeddingSetting::Model, context, model)?;
@@ -1254,6 +1700,49 @@ impl EmbeddingSettings {
             context,
             binary_quantized,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Quantization,
+            context,
+            quantization,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Distance,
+            context,
+            distance,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::MultiVector,
+            context,
+            multi_vector,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Chunking,
+            context,
+            chunking,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::ModelPath,
+            context,
+            model_path,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Threads,
+            context,
+            threads,
+        )?;
+        Self::check_setting(embedder_name, source, MetaEmbeddingSetting::Sparse, context, sparse)?;
         Self::check_setting(
             embedder_name,
             source,
@@ -1320,8 +1809,23 @@ impl EmbeddingSettings {
         use MetaEmbeddingSetting::*;
         use NestingContext::*;
         match (source, field, context) {
-            (_, Distribution | BinaryQuantized, NotNested) => FieldStatus::Allowed,
-            (_, Distribution | BinaryQuantized, _) => FieldStatus::Disallowed,
+            (_, Distribution | BinaryQuantized | Quantization | Distance, NotNested) => {
+                FieldStatus::Allowed
+            }
+            (_, Distribution | BinaryQuantized | Quantization | Distance, _) => {
+                FieldStatus::Disallowed
+            }
+            (UserProvided, MultiVector, NotNested) => FieldStatus::Allowed,
+            (_, MultiVector, _) => FieldStatus::Disallowed,
+            (OpenAi | HuggingFace | Ollama | Rest | Onnx, Chunking, NotNested) => {
+                FieldStatus::Allowed
+            }
+            (_, Chunking, _) => FieldStatus::Disallowed,
+            (Onnx, ModelPath, _) => FieldStatus::Mandatory,
+            (HuggingFace, ModelPath, _) | (Onnx, Threads, _) => FieldStatus::Allowed,
+            (_, ModelPath | Threads, _) => FieldStatus::Disallowed,
+            (UserProvided | Rest, Sparse, NotNested) => FieldStatus::Allowed,
+            (_, Sparse, _) => FieldStatus::Disallowed,
             (_, DocumentTemplate | DocumentTemplateMaxBytes, Search) => FieldStatus::Disallowed,
             (
                 OpenAi,
@@ -1395,6 +1899,15 @@ impl EmbeddingSettings {
             (Rest, Model | Revision | Pooling | SearchEmbedder | IndexingEmbedder, _) => {
                 FieldStatus::Disallowed
             }
+            (Onnx, Source | Pooling | DocumentTemplate | DocumentTemplateMaxBytes, _) => {
+                FieldStatus::Allowed
+            }
+            (
+                Onnx,
+                Model | Revision | ApiKey | Dimensions | Url | Request | Response | Headers
+                | SearchEmbedder | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
             (Composite, SearchEmbedder | IndexingEmbedder, _) => FieldStatus::Mandatory,
             (Composite, Source, _) => FieldStatus::Allowed,
             (
@@ -1445,7 +1958,7 @@ impl EmbeddingSettings {
             (NestingContext::NotNested, _) => Ok(()),
             (
                 NestingContext::Search | NestingContext::Indexing,
-                EmbedderSource::Composite | EmbedderSource::UserProvided,
+                EmbedderSource::Composite | EmbedderSource::UserProvided | EmbedderSource::Onnx,
             ) => Err(UserError::InvalidSourceForNested {
                 embedder_name: context.embedder_name_with_context(embedder_name),
                 source_: source,
@@ -1484,6 +1997,7 @@ pub enum EmbedderSource {
     UserProvided,
     Rest,
     Composite,
+    Onnx,
 }
 
 impl std::fmt::Display for EmbedderSource {
@@ -1495,6 +2009,7 @@ impl std::fmt::Display for EmbedderSource {
             EmbedderSource::Ollama => "ollama",
             EmbedderSource::Rest => "rest",
             EmbedderSource::Composite => "composite",
+            EmbedderSource::Onnx => "onnx",
         };
         f.write_str(s)
     }
@@ -1507,15 +2022,21 @@ impl EmbeddingSettings {
         revision,
         distribution,
         pooling,
+        model_path,
     }: super::hf::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
         quantized: Option<bool>,
     ) -> Self {
+        // `model` and `revision` are ignored when the model is read from a directory
+        let (model, revision) = match model_path {
+            Some(_) => (Setting::NotSet, Setting::NotSet),
+            None => (Setting::Set(model), Setting::some_or_not_set(revision)),
+        };
         Self {
             source: Setting::Set(EmbedderSource::HuggingFace),
-            model: Setting::Set(model),
-            revision: Setting::some_or_not_set(revision),
+            model,
+            revision,
             pooling: Setting::Set(pooling),
             api_key: Setting::NotSet,
             dimensions: Setting::NotSet,
@@ -1529,6 +2050,13 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            distance: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            chunking: Setting::NotSet,
+            model_path: Setting::some_or_not_set(model_path),
+            threads: Setting::NotSet,
+            sparse: Setting::NotSet,
         }
     }
 
@@ -1561,6 +2089,13 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            distance: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            chunking: Setting::NotSet,
+            model_path: Setting::NotSet,
+            threads: Setting::NotSet,
+            sparse: Setting::NotSet,
         }
     }
 
@@ -1593,11 +2128,18 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            distance: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            chunking: Setting::NotSet,
+            model_path: Setting::NotSet,
+            threads: Setting::NotSet,
+            sparse: Setting::NotSet,
         }
     }
 
     fn from_user_provided(
-        super::manual::EmbedderOptions { dimensions, distribution }: super::manual::EmbedderOptions,
+        super::manual::EmbedderOptions { dimensions, distribution, multi_vector, sparse }: super::manual::EmbedderOptions,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1617,6 +2159,13 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            distance: Setting::NotSet,
+            multi_vector: Setting::some_or_not_set(multi_vector),
+            chunking: Setting::NotSet,
+            model_path: Setting::NotSet,
+            threads: Setting::NotSet,
+            sparse: Setting::some_or_not_set(sparse),
         }
     }
 
@@ -1629,6 +2178,7 @@ impl EmbeddingSettings {
             response,
             distribution,
             headers,
+            sparse,
         }: super::rest::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
@@ -1651,16 +2201,57 @@ impl EmbeddingSettings {
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            distance: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            chunking: Setting::NotSet,
+            model_path: Setting::NotSet,
+            threads: Setting::NotSet,
+            sparse: Setting::some_or_not_set(sparse),
+        }
+    }
+
+    fn from_onnx(
+        super::onnx::EmbedderOptions { model_path, threads, distribution, pooling }: super::onnx::EmbedderOptions,
+        document_template: Setting<String>,
+        document_template_max_bytes: Setting<usize>,
+        quantized: Option<bool>,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::Onnx),
+            model: Setting::NotSet,
+            revision: Setting::NotSet,
+            pooling: Setting::Set(pooling),
+            api_key: Setting::NotSet,
+            dimensions: Setting::NotSet,
+            document_template,
+            document_template_max_bytes,
+            url: Setting::NotSet,
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            distance: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            chunking: Setting::NotSet,
+            model_path: Setting::Set(model_path),
+            threads: Setting::some_or_not_set(threads),
+            sparse: Setting::NotSet,
         }
     }
 }
 
 impl From<EmbeddingConfig> for EmbeddingSettings {
     fn from(value: EmbeddingConfig) -> Self {
-        let EmbeddingConfig { embedder_options, prompt, quantized } = value;
+        let EmbeddingConfig { embedder_options, prompt, quantized, quantization, distance } = value;
         let document_template_max_bytes =
             Setting::Set(prompt.max_bytes.unwrap_or(default_max_bytes()).get());
-        match embedder_options {
+        let chunking = Setting::some_or_not_set(prompt.chunking);
+        let mut this = match embedder_options {
             super::EmbedderOptions::HuggingFace(options) => Self::from_hugging_face(
                 options,
                 Setting::Set(prompt.template),
@@ -1688,6 +2279,12 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 document_template_max_bytes,
                 quantized,
             ),
+            super::EmbedderOptions::Onnx(options) => Self::from_onnx(
+                options,
+                Setting::Set(prompt.template),
+                document_template_max_bytes,
+                quantized,
+            ),
             super::EmbedderOptions::Composite(super::composite::EmbedderOptions {
                 search,
                 index,
@@ -1699,6 +2296,13 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 api_key: Setting::NotSet,
                 dimensions: Setting::NotSet,
                 binary_quantized: Setting::some_or_not_set(quantized),
+                quantization: Setting::NotSet,
+                distance: Setting::NotSet,
+                multi_vector: Setting::NotSet,
+                chunking: Setting::NotSet,
+                model_path: Setting::NotSet,
+                threads: Setting::NotSet,
+                sparse: Setting::NotSet,
                 document_template: Setting::NotSet,
                 document_template_max_bytes: Setting::NotSet,
                 url: Setting::NotSet,
@@ -1717,7 +2321,11 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                     document_template_max_bytes,
                 )),
             },
-        }
+        };
+        this.quantization = Setting::some_or_not_set(quantization);
+        this.distance = Setting::some_or_not_set(distance);
+        this.chunking = chunking;
+        this
     }
 }
 
@@ -1778,6 +2386,13 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             response,
             headers,
             binary_quantized: _,
+            quantization: _,
+            distance: _,
+            multi_vector: _,
+            chunking: _,
+            model_path,
+            threads: _,
+            sparse: _,
             search_embedder: _,
             indexing_embedder: _,
             distribution: _,
@@ -1795,8 +2410,15 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             request,
             response,
             headers,
+            model_path,
             distribution: Setting::NotSet,
             binary_quantized: Setting::NotSet,
+            quantization: Setting::NotSet,
+            distance: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            chunking: Setting::NotSet,
+            threads: Setting::NotSet,
+            sparse: Setting::NotSet,
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
         }
@@ -1821,11 +2443,20 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             distribution,
             headers,
             binary_quantized,
+            quantization,
+            distance,
+            multi_vector,
+            chunking,
+            model_path,
+            threads,
+            sparse,
             search_embedder,
             mut indexing_embedder,
         } = value;
 
         this.quantized = binary_quantized.set();
+        this.quantization = quantization.set();
+        this.distance = distance.set();
         if let Some((template, document_template_max_bytes)) =
             match (document_template, &mut indexing_embedder) {
                 (Setting::Set(template), _) => Some((template, document_template_max_bytes)),
@@ -1846,8 +2477,9 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 .and_then(NonZeroUsize::new)
                 .unwrap_or(default_max_bytes());
 
-            this.prompt = PromptData { template, max_bytes: Some(max_bytes) }
+            this.prompt = PromptData { template, max_bytes: Some(max_bytes), chunking: None }
         }
+        this.prompt.chunking = chunking.set();
 
         if let Some(source) = source.set() {
             this.embedder_options = match source {
@@ -1857,13 +2489,21 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 EmbedderSource::Ollama => {
                     SubEmbedderOptions::ollama(model, url, api_key, dimensions, distribution).into()
                 }
-                EmbedderSource::HuggingFace => {
-                    SubEmbedderOptions::hugging_face(model, revision, pooling, distribution).into()
-                }
-                EmbedderSource::UserProvided => {
-                    SubEmbedderOptions::user_provided(dimensions.set().unwrap(), distribution)
-                        .into()
-                }
+                EmbedderSource::HuggingFace => SubEmbedderOptions::hugging_face(
+                    model,
+                    revision,
+                    pooling,
+                    model_path,
+                    distribution,
+                )
+                .into(),
+                EmbedderSource::UserProvided => SubEmbedderOptions::user_provided(
+                    dimensions.set().unwrap(),
+                    distribution,
+                    multi_vector,
+                    sparse,
+                )
+                .into(),
                 EmbedderSource::Rest => SubEmbedderOptions::rest(
                     url.set().unwrap(),
                     api_key,
@@ -1872,8 +2512,17 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                     headers,
                     dimensions,
                     distribution,
+                    sparse,
                 )
                 .into(),
+                EmbedderSource::Onnx => {
+                    super::EmbedderOptions::Onnx(super::onnx::EmbedderOptions {
+                        model_path: model_path.set().unwrap(),
+                        threads: threads.set(),
+                        distribution: distribution.set(),
+                        pooling: pooling.set().unwrap_or(OverridePooling::UseModel),
+                    })
+                }
                 EmbedderSource::Composite => {
                     super::EmbedderOptions::Composite(super::composite::EmbedderOptions {
                         // it is important to give the distribution to the search here, as this is from where we'll retrieve it
@@ -1913,9 +2562,16 @@ impl SubEmbedderOptions {
             request,
             response,
             headers,
+            model_path,
             // phony parameters
             distribution: _,
             binary_quantized: _,
+            quantization: _,
+            distance: _,
+            multi_vector: _,
+            chunking: _,
+            threads: _,
+            sparse: _,
             search_embedder: _,
             indexing_embedder: _,
         } = settings;
@@ -1923,12 +2579,15 @@ impl SubEmbedderOptions {
         match source.set().unwrap() {
             EmbedderSource::OpenAi => Self::openai(model, url, api_key, dimensions, distribution),
             EmbedderSource::HuggingFace => {
-                Self::hugging_face(model, revision, pooling, distribution)
+                Self::hugging_face(model, revision, pooling, model_path, distribution)
             }
             EmbedderSource::Ollama => Self::ollama(model, url, api_key, dimensions, distribution),
-            EmbedderSource::UserProvided => {
-                Self::user_provided(dimensions.set().unwrap(), distribution)
-            }
+            EmbedderSource::UserProvided => Self::user_provided(
+                dimensions.set().unwrap(),
+                distribution,
+                Setting::NotSet,
+                Setting::NotSet,
+            ),
             EmbedderSource::Rest => Self::rest(
                 url.set().unwrap(),
                 api_key,
@@ -1937,8 +2596,10 @@ impl SubEmbedderOptions {
                 headers,
                 dimensions,
                 distribution,
+                Setting::NotSet,
             ),
             EmbedderSource::Composite => panic!("nested composite embedders"),
+            EmbedderSource::Onnx => panic!("nested onnx embedders"),
         }
     }
 
@@ -1971,6 +2632,7 @@ impl SubEmbedderOptions {
         model: Setting<String>,
         revision: Setting<String>,
         pooling: Setting<OverridePooling>,
+        model_path: Setting<String>,
         distribution: Setting<DistributionShift>,
     ) -> Self {
         let mut options = super::hf::EmbedderOptions::default();
@@ -1989,15 +2651,24 @@ impl SubEmbedderOptions {
         if let Some(pooling) = pooling.set() {
             options.pooling = pooling;
         }
+        options.model_path = model_path.set();
         options.distribution = distribution.set();
         SubEmbedderOptions::HuggingFace(options)
     }
-    fn user_provided(dimensions: usize, distribution: Setting<DistributionShift>) -> Self {
+    fn user_provided(
+        dimensions: usize,
+        distribution: Setting<DistributionShift>,
+        multi_vector: Setting<bool>,
+        sparse: Setting<bool>,
+    ) -> Self {
         Self::UserProvided(super::manual::EmbedderOptions {
             dimensions,
             distribution: distribution.set(),
+            multi_vector: multi_vector.set(),
+            sparse: sparse.set(),
         })
     }
+    #[allow(clippy::too_many_arguments)]
     fn rest(
         url: String,
         api_key: Setting<String>,
@@ -2006,6 +2677,7 @@ impl SubEmbedderOptions {
         headers: Setting<BTreeMap<String, String>>,
         dimensions: Setting<usize>,
         distribution: Setting<DistributionShift>,
+        sparse: Setting<bool>,
     ) -> Self {
         Self::Rest(super::rest::EmbedderOptions {
             api_key: api_key.set(),
@@ -2015,6 +2687,7 @@ impl SubEmbedderOptions {
             response,
             distribution: distribution.set(),
             headers: headers.set().unwrap_or_default(),
+            sparse: sparse.set(),
         })
     }
     fn ollama(
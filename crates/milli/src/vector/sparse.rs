@@ -0,0 +1,427 @@
+//! Sparse embeddings, such as the term weights produced by learned sparse models like SPLADE.
+//!
+//! A sparse embedding only holds the weights of its non-zero dimensions.
+//! In the indexing pipelines, it travels as a regular [`Embedding`] starting with a NaN marker,
+//! followed by pairs of dimension and weight, sorted by dimension.
+//! As the marker is never a valid dimension or weight, concatenated sparse embeddings can be split again.
+//!
+//! The sparse embeddings of an embedder are stored in an inverted index, and compared with the dot product.
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+
+use arroy::ItemId;
+use deserr::{take_cf_content, DeserializeError, Deserr, IntoValue};
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{RoTxn, RwTxn};
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+use serde::Serialize;
+
+use super::{ArroyStats, Embedding};
+
+/// The maximum number of dimensions of a sparse embedder.
+///
+/// Dimensions are stored as `f32` in the flat representation, which represents exactly all the integers below this value.
+pub const MAX_DIMENSIONS: usize = 1 << 24;
+
+/// Encodes the weights of a sparse embedding, sorted by dimension, to its flat representation.
+///
+/// Zero weights are skipped.
+pub fn encode(weights: impl IntoIterator<Item = (u32, f32)>) -> Embedding {
+    let mut embedding = vec![f32::NAN];
+    for (dimension, weight) in weights {
+        if weight != 0.0 {
+            embedding.push(dimension as f32);
+            embedding.push(weight);
+        }
+    }
+    embedding
+}
+
+/// Whether the embedding is in the flat representation of sparse embeddings.
+pub fn is_sparse(embedding: &[f32]) -> bool {
+    embedding.first().is_some_and(|marker| marker.is_nan())
+}
+
+/// The non-zero weights of a sparse embedding, by increasing dimension.
+pub fn weights(embedding: &[f32]) -> impl Iterator<Item = (u32, f32)> + '_ {
+    embedding.get(1..).unwrap_or_default().chunks_exact(2).map(|pair| (pair[0] as u32, pair[1]))
+}
+
+/// Splits concatenated sparse embeddings.
+pub fn split(embeddings: &[f32]) -> impl Iterator<Item = &[f32]> + '_ {
+    let mut remaining = embeddings;
+    std::iter::from_fn(move || {
+        if remaining.is_empty() {
+            return None;
+        }
+        let end = remaining[1..]
+            .iter()
+            .position(|value| value.is_nan())
+            .map_or(remaining.len(), |position| position + 1);
+        let (embedding, rest) = remaining.split_at(end);
+        remaining = rest;
+        Some(embedding)
+    })
+}
+
+/// Merges sparse embeddings into one, keeping the highest weight of each dimension.
+pub fn max_pool<'a>(embeddings: impl IntoIterator<Item = &'a [f32]>) -> Embedding {
+    let mut pooled: BTreeMap<u32, f32> = BTreeMap::new();
+    for (dimension, weight) in embeddings.into_iter().flat_map(weights) {
+        pooled.entry(dimension).and_modify(|pooled| *pooled = pooled.max(weight)).or_insert(weight);
+    }
+    encode(pooled)
+}
+
+/// Checks that an embedding can be used with an embedder, returning why it can't otherwise.
+///
+/// The number of dimensions of dense embeddings is not checked.
+pub fn check(embedding: &[f32], sparse_embedder: bool, dimensions: usize) -> Result<(), String> {
+    match (sparse_embedder, is_sparse(embedding)) {
+        (true, false) => Err("is an array of numbers, but the embedder is sparse and expects an object mapping dimensions to weights".to_string()),
+        (false, true) => Err("is an object, but the embedder is not sparse and expects an array of numbers".to_string()),
+        (false, false) => Ok(()),
+        (true, true) => {
+            match weights(embedding).find(|(dimension, _)| *dimension as usize >= dimensions) {
+                Some((dimension, _)) => Err(format!(
+                    "has dimension `{dimension}`, but the embedder has {dimensions} dimensions"
+                )),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// Deserializes a sparse embedding from an object mapping dimensions to weights.
+pub fn deserialize_from_value<V: IntoValue, E: DeserializeError>(
+    value: deserr::Value<V>,
+    location: deserr::ValuePointerRef<'_>,
+) -> Result<Embedding, E> {
+    let weights = BTreeMap::<String, f32>::deserialize_from_value(value, location)?;
+    let mut parsed = BTreeMap::new();
+    for (key, weight) in weights {
+        match key.parse::<u32>() {
+            Ok(dimension) if (dimension as usize) < MAX_DIMENSIONS => {
+                parsed.insert(dimension, weight);
+            }
+            _ => {
+                return Err(take_cf_content(E::error::<Infallible>(
+                    None,
+                    deserr::ErrorKind::Unexpected {
+                        msg: format!(
+                            "expected a dimension between `0` and `{}`, found `{key}`",
+                            MAX_DIMENSIONS - 1
+                        ),
+                    },
+                    location.push_key(&key),
+                )))
+            }
+        }
+    }
+    Ok(encode(parsed))
+}
+
+/// A query vector, either dense as an array of numbers, or sparse as an object mapping dimensions to weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryVector(pub Embedding);
+
+impl QueryVector {
+    pub fn into_embedding(vector: Option<Self>) -> Option<Embedding> {
+        vector.map(|QueryVector(embedding)| embedding)
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for QueryVector {
+    fn deserialize_from_value<V: IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef<'_>,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::Map(_) => Ok(Self(deserialize_from_value(value, location)?)),
+            value => Ok(Self(Embedding::deserialize_from_value(value, location)?)),
+        }
+    }
+}
+
+/// Serializes an embedding as an array of numbers, or as an object mapping dimensions to weights when it is sparse.
+pub struct SerializeEmbedding<'a>(pub &'a [f32]);
+
+impl Serialize for SerializeEmbedding<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if is_sparse(self.0) {
+            serializer.collect_map(weights(self.0))
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+/// Serializes embeddings with [`SerializeEmbedding`].
+pub fn serialize_embeddings<S: serde::Serializer>(
+    embeddings: &[Embedding],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(embeddings.iter().map(|embedding| SerializeEmbedding(embedding)))
+}
+
+const DOCUMENT_TAG: u8 = 0;
+const POSTING_TAG: u8 = 1;
+
+/// The inverted index of the sparse embeddings of an embedder.
+///
+/// The embeddings of a document are stored under `[embedder, 0, docid]`, and the weight of each of
+/// their dimensions under `[embedder, 1, dimension, docid, embedding index]`.
+#[derive(Debug, Clone, Copy)]
+pub struct SparseVectors {
+    database: heed::Database<Bytes, Bytes>,
+    embedder_index: u8,
+}
+
+impl SparseVectors {
+    pub fn new(database: heed::Database<Bytes, Bytes>, embedder_index: u8) -> Self {
+        Self { database, embedder_index }
+    }
+
+    fn document_key(&self, item: ItemId) -> [u8; 6] {
+        let [a, b, c, d] = item.to_be_bytes();
+        [self.embedder_index, DOCUMENT_TAG, a, b, c, d]
+    }
+
+    fn posting_prefix(&self, dimension: u32) -> [u8; 6] {
+        let [a, b, c, d] = dimension.to_be_bytes();
+        [self.embedder_index, POSTING_TAG, a, b, c, d]
+    }
+
+    fn posting_key(&self, dimension: u32, item: ItemId, embedding_index: u8) -> [u8; 11] {
+        let mut key = [0; 11];
+        key[..6].copy_from_slice(&self.posting_prefix(dimension));
+        key[6..10].copy_from_slice(&item.to_be_bytes());
+        key[10] = embedding_index;
+        key
+    }
+
+    pub fn item_vectors(&self, rtxn: &RoTxn, item: ItemId) -> heed::Result<Vec<Embedding>> {
+        Ok(match self.database.get(rtxn, &self.document_key(item))? {
+            Some(bytes) => split(&decode_floats(bytes)).map(<[f32]>::to_vec).collect(),
+            None => Vec::new(),
+        })
+    }
+
+    pub fn contains_item(&self, rtxn: &RoTxn, item: ItemId) -> heed::Result<bool> {
+        let database = self.database.remap_data_type::<DecodeIgnore>();
+        Ok(database.get(rtxn, &self.document_key(item))?.is_some())
+    }
+
+    /// Replaces all the embeddings of the item.
+    ///
+    /// Like in arroy, an item holds at most 256 embeddings, the remaining ones are silently ignored.
+    pub fn set_item_vectors<'e>(
+        &self,
+        wtxn: &mut RwTxn,
+        item: ItemId,
+        embeddings: impl IntoIterator<Item = &'e [f32]>,
+    ) -> heed::Result<()> {
+        self.del_items(wtxn, item)?;
+
+        let mut all_embeddings = Vec::new();
+        for (embedding_index, embedding) in
+            embeddings.into_iter().take(u8::MAX as usize + 1).enumerate()
+        {
+            for (dimension, weight) in weights(embedding) {
+                let key = self.posting_key(dimension, item, embedding_index as u8);
+                self.database.put(wtxn, &key, &weight.to_le_bytes())?;
+            }
+            all_embeddings.extend_from_slice(embedding);
+        }
+        if !all_embeddings.is_empty() {
+            self.database.put(wtxn, &self.document_key(item), &encode_floats(&all_embeddings))?;
+        }
+        Ok(())
+    }
+
+    /// Adds an embedding after the existing embeddings of the item.
+    pub fn add_item(&self, wtxn: &mut RwTxn, item: ItemId, vector: &[f32]) -> heed::Result<()> {
+        let mut embeddings = self.item_vectors(wtxn, item)?;
+        embeddings.push(vector.to_vec());
+        self.set_item_vectors(wtxn, item, embeddings.iter().map(Vec::as_slice))
+    }
+
+    /// Deletes all the embeddings of the item.
+    pub fn del_items(&self, wtxn: &mut RwTxn, item: ItemId) -> heed::Result<()> {
+        for (embedding_index, embedding) in self.item_vectors(wtxn, item)?.iter().enumerate() {
+            for (dimension, _) in weights(embedding) {
+                let key = self.posting_key(dimension, item, embedding_index as u8);
+                self.database.delete(wtxn, &key)?;
+            }
+        }
+        self.database.delete(wtxn, &self.document_key(item))?;
+        Ok(())
+    }
+
+    /// Deletes one embedding of the item, returning whether it was found.
+    pub fn del_item(&self, wtxn: &mut RwTxn, item: ItemId, vector: &[f32]) -> heed::Result<bool> {
+        let mut embeddings = self.item_vectors(wtxn, item)?;
+        // the markers are NaN, and never equal to each other
+        let Some(position) =
+            embeddings.iter().position(|embedding| embedding.get(1..) == vector.get(1..))
+        else {
+            return Ok(false);
+        };
+        embeddings.remove(position);
+        self.set_item_vectors(wtxn, item, embeddings.iter().map(Vec::as_slice))?;
+        Ok(true)
+    }
+
+    pub fn clear(&self, wtxn: &mut RwTxn) -> heed::Result<()> {
+        let mut iter = self.database.prefix_iter_mut(wtxn, &[self.embedder_index])?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference on database entries.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+
+    /// Retrieves the embeddings that have the highest dot product with the query vector.
+    ///
+    /// Like arroy, returns the opposite of the dot product as the distance, along with the index of the matching embedding of the item.
+    /// Items that share no dimension with the query vector are never returned.
+    pub fn nns_by_vector(
+        &self,
+        rtxn: &RoTxn,
+        vector: &[f32],
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> heed::Result<Vec<(ItemId, u8, f32)>> {
+        let mut scores: HashMap<(ItemId, u8), f32> = HashMap::new();
+        for (dimension, weight) in weights(vector) {
+            for result in self.database.prefix_iter(rtxn, &self.posting_prefix(dimension))? {
+                let (key, value) = result?;
+                let item = ItemId::from_be_bytes(key[6..10].try_into().unwrap());
+                if filter.is_some_and(|filter| !filter.contains(item)) {
+                    continue;
+                }
+                let document_weight = f32::from_le_bytes(value.try_into().unwrap());
+                *scores.entry((item, key[10])).or_default() += weight * document_weight;
+            }
+        }
+
+        let mut results: Vec<_> =
+            scores.into_iter().map(|((item, index), score)| (item, index, -score)).collect();
+        results.sort_unstable_by_key(|(item, index, distance)| {
+            (OrderedFloat(*distance), *item, *index)
+        });
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    /// Retrieves the items closest to the item, using the highest weight of each dimension among its embeddings as the query.
+    pub fn nns_by_item(
+        &self,
+        rtxn: &RoTxn,
+        item: ItemId,
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> heed::Result<Vec<(ItemId, f32)>> {
+        let embeddings = self.item_vectors(rtxn, item)?;
+        if embeddings.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = max_pool(embeddings.iter().map(Vec::as_slice));
+        let results = self.nns_by_vector(rtxn, &query, limit, filter)?;
+        Ok(results.into_iter().map(|(item, _, distance)| (item, distance)).collect())
+    }
+
+    pub fn aggregate_stats(&self, rtxn: &RoTxn, stats: &mut ArroyStats) -> heed::Result<()> {
+        for result in self.database.prefix_iter(rtxn, &[self.embedder_index, DOCUMENT_TAG])? {
+            let (key, value) = result?;
+            stats.documents.insert(ItemId::from_be_bytes(key[2..6].try_into().unwrap()));
+            stats.number_of_embeddings += split(&decode_floats(value)).count() as u64;
+        }
+        Ok(())
+    }
+}
+
+fn encode_floats(floats: &[f32]) -> Vec<u8> {
+    floats.iter().flat_map(|float| float.to_le_bytes()).collect()
+}
+
+fn decode_floats(bytes: &[u8]) -> Vec<f32> {
+    bytes.chunks_exact(4).map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encoding() {
+        let embedding = encode([(3, 0.5), (7, 0.0), (12, 1.5)]);
+        assert!(is_sparse(&embedding));
+        assert!(!is_sparse(&[0.5, 1.5]));
+        assert!(!is_sparse(&[]));
+        assert_eq!(weights(&embedding).collect::<Vec<_>>(), [(3, 0.5), (12, 1.5)]);
+
+        let empty = encode([]);
+        assert!(is_sparse(&empty));
+        assert_eq!(weights(&empty).count(), 0);
+    }
+
+    #[test]
+    fn split_concatenated() {
+        let mut all = encode([(1, 1.0)]);
+        all.extend(encode([]));
+        all.extend(encode([(2, 2.0), (3, 3.0)]));
+        let embeddings: Vec<Vec<_>> =
+            split(&all).map(|embedding| weights(embedding).collect()).collect();
+        assert_eq!(embeddings, [vec![(1, 1.0)], vec![], vec![(2, 2.0), (3, 3.0)]]);
+        assert_eq!(split(&[]).count(), 0);
+    }
+
+    #[test]
+    fn pooling() {
+        let left = encode([(1, 1.0), (2, 0.5)]);
+        let right = encode([(2, 2.0), (3, 0.25)]);
+        let pooled = max_pool([left.as_slice(), right.as_slice()]);
+        assert_eq!(weights(&pooled).collect::<Vec<_>>(), [(1, 1.0), (2, 2.0), (3, 0.25)]);
+    }
+
+    #[test]
+    fn checking() {
+        let sparse = encode([(3, 0.5), (12, 1.5)]);
+        assert!(check(&sparse, true, 13).is_ok());
+        assert!(check(&sparse, true, 12).is_err());
+        assert!(check(&sparse, false, 13).is_err());
+        assert!(check(&[0.5, 1.5], true, 2).is_err());
+        assert!(check(&[0.5, 1.5], false, 2).is_ok());
+    }
+
+    #[test]
+    fn serialization() {
+        let sparse = encode([(3, 0.5), (12, 1.5)]);
+        let dense = vec![0.5, 1.5];
+        let json = serde_json::to_string(&SerializeEmbedding(&sparse)).unwrap();
+        assert_eq!(json, r#"{"3":0.5,"12":1.5}"#);
+        let json = serde_json::to_string(&SerializeEmbedding(&dense)).unwrap();
+        assert_eq!(json, "[0.5,1.5]");
+    }
+
+    #[test]
+    fn deserialization() {
+        let value = serde_json::json!({ "12": 1.5, "3": 0.5 });
+        let embedding: QueryVector =
+            deserr::deserialize::<_, _, deserr::errors::JsonError>(value).unwrap();
+        assert_eq!(weights(&embedding.0).collect::<Vec<_>>(), [(3, 0.5), (12, 1.5)]);
+
+        let value = serde_json::json!([0.5, 1.5]);
+        let embedding: QueryVector =
+            deserr::deserialize::<_, _, deserr::errors::JsonError>(value).unwrap();
+        assert_eq!(embedding.0, [0.5, 1.5]);
+
+        let value = serde_json::json!({ "kefir": 1.5 });
+        let error =
+            deserr::deserialize::<QueryVector, _, deserr::errors::JsonError>(value).unwrap_err();
+        assert!(error.to_string().contains("found `kefir`"), "{error}");
+    }
+}